@@ -15,7 +15,7 @@ use super::output::algorithm::validate_permutation::{
 };
 use super::output::{Algorithm, OutputStatus};
 use super::system::{DevicePollType, System};
-use crate::ImageDimensions;
+use crate::{BoundaryMode, ImageDimensions};
 use async_trait::async_trait;
 use std::error::Error;
 use std::fmt;
@@ -23,6 +23,7 @@ use std::fmt;
 #[derive(Debug, PartialEq, Eq)]
 pub struct Config {
     pub image_dimensions: ImageDimensions,
+    pub boundary: BoundaryMode,
 }
 
 pub async fn create_dispatcher(config: &Config) -> Result<Box<dyn Dispatcher>, Box<dyn Error>> {
@@ -144,7 +145,7 @@ struct DispatcherImplementation {
 impl DispatcherImplementation {
     async fn new(config: &Config) -> Result<Self, Box<dyn Error>> {
         Ok(Self {
-            system: System::new(&config.image_dimensions).await?,
+            system: System::new(&config.image_dimensions, config.boundary).await?,
             algorithm: AlgorithmChoice::None,
         })
     }
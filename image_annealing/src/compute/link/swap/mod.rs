@@ -1,5 +1,5 @@
 use super::super::operation::WorkgroupGridDimensions;
-use crate::ImageDimensions;
+use crate::{BoundaryMode, ImageDimensions};
 use bytemuck::{Pod, Zeroable};
 use image_annealing_shader::constant;
 use image_annealing_shader::WorkgroupDimensions;
@@ -56,33 +56,117 @@ impl SwapPass {
         }
     }
 
-    pub fn total_swaps(&self, image_dimensions: &ImageDimensions) -> usize {
-        match self {
-            Self::Horizontal => image_dimensions
-                .width()
-                .checked_div_euclid(Self::STRIDE)
-                .unwrap()
-                .checked_mul(image_dimensions.height())
-                .unwrap(),
-            Self::Vertical => image_dimensions
-                .height()
-                .checked_div_euclid(Self::STRIDE)
-                .unwrap()
-                .checked_mul(image_dimensions.width())
-                .unwrap(),
-            Self::OffsetHorizontal => (image_dimensions.width() - Self::OFFSET)
-                .checked_div_euclid(Self::STRIDE)
+    pub fn total_swaps(&self, image_dimensions: &ImageDimensions, boundary: BoundaryMode) -> usize {
+        let (length, breadth) = match self {
+            Self::Horizontal | Self::OffsetHorizontal => {
+                (image_dimensions.width(), image_dimensions.height())
+            }
+            Self::Vertical | Self::OffsetVertical => {
+                (image_dimensions.height(), image_dimensions.width())
+            }
+        };
+        let offset = match self {
+            Self::Horizontal | Self::Vertical => 0,
+            Self::OffsetHorizontal | Self::OffsetVertical => {
+                if self.wraps(image_dimensions, boundary) {
+                    0
+                } else {
+                    Self::OFFSET
+                }
+            }
+        };
+        (length - offset)
+            .checked_div_euclid(Self::STRIDE)
+            .unwrap()
+            .checked_mul(breadth)
+            .unwrap()
+    }
+
+    // Pairs can only cross the image boundary without overlapping other pairs
+    // if the image has an even number of texels along the direction of the swap
+    fn wraps(&self, image_dimensions: &ImageDimensions, boundary: BoundaryMode) -> bool {
+        let length = match self {
+            Self::Horizontal | Self::OffsetHorizontal => image_dimensions.width(),
+            Self::Vertical | Self::OffsetVertical => image_dimensions.height(),
+        };
+        boundary.is_toroidal() && length.checked_rem_euclid(Self::STRIDE).unwrap() == 0
+    }
+
+    // Pairs are indexed by the coordinates of their second texel, with the coordinate
+    // along the direction of the swap divided by the stride. This index is also the
+    // index of the shader invocation that processes the pair.
+    pub(in super::super) fn pair_grid_dimensions(
+        &self,
+        image_dimensions: &ImageDimensions,
+    ) -> (usize, usize) {
+        let strided_length = |length: usize| {
+            length
+                .checked_add(Self::STRIDE - 1)
                 .unwrap()
-                .checked_mul(image_dimensions.height())
-                .unwrap(),
-            Self::OffsetVertical => (image_dimensions.height() - Self::OFFSET)
                 .checked_div_euclid(Self::STRIDE)
                 .unwrap()
-                .checked_mul(image_dimensions.width())
-                .unwrap(),
+        };
+        match self {
+            Self::Horizontal | Self::OffsetHorizontal => (
+                strided_length(image_dimensions.width()),
+                image_dimensions.height(),
+            ),
+            Self::Vertical | Self::OffsetVertical => (
+                image_dimensions.width(),
+                strided_length(image_dimensions.height()),
+            ),
+        }
+    }
+
+    // Returns the coordinates of the two texels in the pair with the given index,
+    // or `None` if the pair is not entirely within the image.
+    // This function must mirror the swap shader.
+    pub(in super::super) fn pair_coordinates(
+        &self,
+        image_dimensions: &ImageDimensions,
+        boundary: BoundaryMode,
+        x: usize,
+        y: usize,
+    ) -> Option<((i64, i64), (i64, i64))> {
+        let [displacement_x, displacement_y] = self.displacement_vector().map(i64::from);
+        let [offset_x, offset_y] = self.offset_vector().map(i64::from);
+        let x = <i64 as TryFrom<usize>>::try_from(x).unwrap();
+        let y = <i64 as TryFrom<usize>>::try_from(y).unwrap();
+        let mut coords1 = (
+            x * (displacement_x + 1) + offset_x,
+            y * (displacement_y + 1) + offset_y,
+        );
+        let coords2 = (coords1.0 + displacement_x, coords1.1 + displacement_y);
+        let in_bounds = |coords: (i64, i64)| {
+            image_dimensions
+                .make_linear_index(coords.0, coords.1)
+                .is_ok()
+        };
+        if !in_bounds(coords2) {
+            return None;
+        }
+        if self.wraps(image_dimensions, boundary) {
+            coords1 = boundary.resolve_coordinates(image_dimensions, coords1.0, coords1.1);
+        }
+        if in_bounds(coords1) {
+            Some((coords1, coords2))
+        } else {
+            None
         }
     }
 
+    pub(in super::super) fn total_accepted_swaps_words(
+        image_dimensions: &ImageDimensions,
+    ) -> usize {
+        Self::total_workgroups(image_dimensions)
+            .checked_mul(
+                constant::swap::accepted_swaps_words_per_workgroup(WorkgroupDimensions::swap())
+                    .try_into()
+                    .unwrap(),
+            )
+            .unwrap()
+    }
+
     pub(in super::super) fn total_workgroups(image_dimensions: &ImageDimensions) -> usize {
         Self::PASSES
             .iter()
@@ -338,6 +422,10 @@ impl CountSwapInputLayout {
         }
     }
 
+    pub fn segment_start(&self, pass: SwapPass) -> usize {
+        self.segment_start[pass as usize].try_into().unwrap()
+    }
+
     pub fn get_set(&self) -> SwapPassSet {
         self.do_segment.iter().zip(SwapPass::PASSES.iter()).fold(
             SwapPassSet::empty(),
@@ -362,6 +450,8 @@ impl CountSwapInputLayout {
     }
 }
 
+pub type AcceptedSwapsWord = u32;
+
 pub type CountSwapOutputDataElement = f32;
 type CountSwapOutputData = [CountSwapOutputDataElement; constant::count_swap::N_CHANNEL];
 
@@ -400,7 +490,10 @@ pub struct SwapShaderParameters {
     offset: [i32; 2],
     count_output_offset: u32,
     acceptance_threshold: f32,
-    _padding: [u32; 2],
+    // Only read by the shader, as a boolean flag for toroidal boundaries
+    #[allow(dead_code)]
+    boundary: u32,
+    _padding: u32,
 }
 
 impl SwapShaderParameters {
@@ -408,6 +501,10 @@ impl SwapShaderParameters {
         Default::default()
     }
 
+    pub fn set_boundary(&mut self, boundary: BoundaryMode) {
+        self.boundary = u32::from(boundary.is_toroidal());
+    }
+
     pub fn set_acceptance_threshold(&mut self, threshold: f32) {
         self.acceptance_threshold = threshold;
     }
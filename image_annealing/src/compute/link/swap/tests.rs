@@ -122,13 +122,14 @@ mod swap_pass {
 
     mod total_swaps {
         use super::super::super::SwapPass;
-        use crate::ImageDimensions;
+        use crate::{BoundaryMode, ImageDimensions};
         use std::error::Error;
 
         #[test]
         fn horizontal() -> Result<(), Box<dyn Error>> {
             assert_eq!(
-                SwapPass::Horizontal.total_swaps(&ImageDimensions::try_new(33, 16)?),
+                SwapPass::Horizontal
+                    .total_swaps(&ImageDimensions::try_new(33, 16)?, BoundaryMode::Bounded),
                 256
             );
             Ok(())
@@ -137,7 +138,8 @@ mod swap_pass {
         #[test]
         fn vertical() -> Result<(), Box<dyn Error>> {
             assert_eq!(
-                SwapPass::Vertical.total_swaps(&ImageDimensions::try_new(16, 33)?),
+                SwapPass::Vertical
+                    .total_swaps(&ImageDimensions::try_new(16, 33)?, BoundaryMode::Bounded),
                 256
             );
             Ok(())
@@ -146,7 +148,8 @@ mod swap_pass {
         #[test]
         fn offset_horizontal() -> Result<(), Box<dyn Error>> {
             assert_eq!(
-                SwapPass::OffsetHorizontal.total_swaps(&ImageDimensions::try_new(33, 16)?),
+                SwapPass::OffsetHorizontal
+                    .total_swaps(&ImageDimensions::try_new(33, 16)?, BoundaryMode::Bounded),
                 256
             );
             Ok(())
@@ -155,11 +158,82 @@ mod swap_pass {
         #[test]
         fn offset_vertical() -> Result<(), Box<dyn Error>> {
             assert_eq!(
-                SwapPass::OffsetVertical.total_swaps(&ImageDimensions::try_new(16, 33)?),
+                SwapPass::OffsetVertical
+                    .total_swaps(&ImageDimensions::try_new(16, 33)?, BoundaryMode::Bounded),
                 256
             );
             Ok(())
         }
+
+        mod toroidal {
+            use super::super::super::super::SwapPass;
+            use crate::{BoundaryMode, ImageDimensions};
+            use std::error::Error;
+
+            #[test]
+            fn horizontal() -> Result<(), Box<dyn Error>> {
+                let pass = SwapPass::Horizontal;
+                assert_eq!(
+                    pass.total_swaps(&ImageDimensions::try_new(32, 16)?, BoundaryMode::Toroidal),
+                    256
+                );
+                assert_eq!(
+                    pass.total_swaps(&ImageDimensions::try_new(33, 16)?, BoundaryMode::Toroidal),
+                    256
+                );
+                Ok(())
+            }
+
+            #[test]
+            fn vertical() -> Result<(), Box<dyn Error>> {
+                let pass = SwapPass::Vertical;
+                assert_eq!(
+                    pass.total_swaps(&ImageDimensions::try_new(16, 32)?, BoundaryMode::Toroidal),
+                    256
+                );
+                assert_eq!(
+                    pass.total_swaps(&ImageDimensions::try_new(16, 33)?, BoundaryMode::Toroidal),
+                    256
+                );
+                Ok(())
+            }
+
+            #[test]
+            fn offset_horizontal() -> Result<(), Box<dyn Error>> {
+                let pass = SwapPass::OffsetHorizontal;
+                assert_eq!(
+                    pass.total_swaps(&ImageDimensions::try_new(32, 16)?, BoundaryMode::Bounded),
+                    240
+                );
+                assert_eq!(
+                    pass.total_swaps(&ImageDimensions::try_new(32, 16)?, BoundaryMode::Toroidal),
+                    256
+                );
+                assert_eq!(
+                    pass.total_swaps(&ImageDimensions::try_new(33, 16)?, BoundaryMode::Toroidal),
+                    256
+                );
+                Ok(())
+            }
+
+            #[test]
+            fn offset_vertical() -> Result<(), Box<dyn Error>> {
+                let pass = SwapPass::OffsetVertical;
+                assert_eq!(
+                    pass.total_swaps(&ImageDimensions::try_new(16, 32)?, BoundaryMode::Bounded),
+                    240
+                );
+                assert_eq!(
+                    pass.total_swaps(&ImageDimensions::try_new(16, 32)?, BoundaryMode::Toroidal),
+                    256
+                );
+                assert_eq!(
+                    pass.total_swaps(&ImageDimensions::try_new(16, 33)?, BoundaryMode::Toroidal),
+                    256
+                );
+                Ok(())
+            }
+        }
     }
 
     #[test]
@@ -1027,7 +1101,7 @@ mod count_swap_output {
 
 mod swap_shader_parameters {
     use super::super::{CountSwapInputLayout, SwapPass, SwapShaderParameters};
-    use crate::ImageDimensions;
+    use crate::{BoundaryMode, ImageDimensions};
     use std::error::Error;
 
     #[test]
@@ -1039,9 +1113,19 @@ mod swap_shader_parameters {
         assert_eq!(parameters.offset, [0, 0]);
         assert_eq!(parameters.count_output_offset, layout.segment_start[0]);
         assert_eq!(parameters.acceptance_threshold, Default::default());
+        assert_eq!(parameters.boundary, 0);
         Ok(())
     }
 
+    #[test]
+    fn set_boundary() {
+        let mut parameters = SwapShaderParameters::new();
+        parameters.set_boundary(BoundaryMode::Toroidal);
+        assert_eq!(parameters.boundary, 1);
+        parameters.set_boundary(BoundaryMode::Bounded);
+        assert_eq!(parameters.boundary, 0);
+    }
+
     #[test]
     fn set_acceptance_threshold() {
         let mut parameters = SwapShaderParameters::new();
@@ -19,9 +19,9 @@ pub use output::algorithm::create_permutation::{
 };
 pub use output::algorithm::permute::{PermuteInput, PermuteOutput, PermuteParameters};
 pub use output::algorithm::swap::{
-    InvalidSwapPassSelectionError, SwapFullOutput, SwapInput, SwapParameters, SwapPartialOutput,
-    SwapPass, SwapPassSequence, SwapPassSequenceSwapRatio, SwapPassSet, SwapPassSwapRatio,
-    SwapRatio, SwapReturnedInput,
+    AcceptedSwaps, InvalidAcceptedSwapsLengthError, InvalidSwapPassSelectionError, SwapFullOutput,
+    SwapInput, SwapParameters, SwapPartialOutput, SwapPass, SwapPassSequence,
+    SwapPassSequenceSwapRatio, SwapPassSet, SwapPassSwapRatio, SwapRatio, SwapReturnedInput,
 };
 pub use output::algorithm::validate_permutation::{
     ValidatePermutationInput, ValidatePermutationParameters,
@@ -21,6 +21,7 @@ impl SwapBinding {
         let permutation_input_texture = resources.permutation_input_texture();
         let permutation_output_texture = resources.permutation_output_texture();
         let count_swap_input_buffer = resources.count_swap_input_buffer();
+        let accepted_swaps_storage_buffer = resources.accepted_swaps_storage_buffer();
 
         let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("swap_bind_group_layout"),
@@ -55,6 +56,12 @@ impl SwapBinding {
                     ty: count_swap_input_buffer.output_binding_description(),
                     count: None,
                 },
+                wgpu::BindGroupLayoutEntry {
+                    binding: binding_constants::OUTPUT_ACCEPTED_SWAPS_BUFFER_INDEX,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: accepted_swaps_storage_buffer.output_binding_description(),
+                    count: None,
+                },
             ],
         });
 
@@ -84,6 +91,10 @@ impl SwapBinding {
                     binding: binding_constants::OUTPUT_COUNT_BUFFER_INDEX,
                     resource: count_swap_input_buffer.binding_resource(),
                 },
+                wgpu::BindGroupEntry {
+                    binding: binding_constants::OUTPUT_ACCEPTED_SWAPS_BUFFER_INDEX,
+                    resource: accepted_swaps_storage_buffer.binding_resource(),
+                },
             ],
         });
 
@@ -1,10 +1,11 @@
 use super::super::device::{DeviceManager, DevicePollType};
 use super::super::format::{ImageFormat, LosslessImage, VectorFieldImageBuffer};
 use super::super::link::swap::SwapPassSequence;
+use super::super::output::algorithm::swap::AcceptedSwaps;
 use super::super::resource::manager::ResourceManager;
 use super::pipeline::manager::PipelineManager;
 use crate::image_utils::validation::{self};
-use crate::{DisplacementGoal, ImageDimensions, ValidatedPermutation};
+use crate::{BoundaryMode, DisplacementGoal, ImageDimensions, ValidatedPermutation};
 use std::error::Error;
 
 mod input;
@@ -20,17 +21,23 @@ pub struct OperationManager {
     state: ResourceStateManager,
     pipelines: PipelineManager,
     image_dimensions: ImageDimensions,
+    boundary: BoundaryMode,
 }
 
 impl OperationManager {
-    pub fn new(device: &wgpu::Device, image_dimensions: &ImageDimensions) -> Self {
+    pub fn new(
+        device: &wgpu::Device,
+        image_dimensions: &ImageDimensions,
+        boundary: BoundaryMode,
+    ) -> Self {
         let resources = ResourceManager::new(device, image_dimensions);
         let pipelines = PipelineManager::new(device, &resources);
         OperationManager {
             resources,
-            state: ResourceStateManager::new(image_dimensions),
+            state: ResourceStateManager::new(image_dimensions, boundary),
             pipelines,
             image_dimensions: *image_dimensions,
+            boundary,
         }
     }
 
@@ -146,6 +153,36 @@ impl OperationManager {
             &result[0],
             sequence,
             &self.image_dimensions,
+            self.boundary,
+        ))
+    }
+
+    pub async fn output_accepted_swaps(
+        &mut self,
+        device: &DeviceManager,
+        poll_type: DevicePollType,
+        sequence: &SwapPassSequence,
+    ) -> Result<Vec<AcceptedSwaps>, Box<dyn Error>> {
+        let mut encoder = device
+            .device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("output_accepted_swaps_command_encoder"),
+            });
+        self.state
+            .output_accepted_swaps(&self.resources, &mut encoder, sequence)?;
+        device.queue().submit(Some(encoder.finish()));
+
+        let result = self
+            .resources
+            .accepted_swaps_output_buffer()
+            .collect(device, poll_type)
+            .await;
+
+        Ok(output::accepted_swaps_from_words(
+            &result,
+            sequence,
+            &self.image_dimensions,
+            self.boundary,
         ))
     }
 
@@ -231,4 +268,45 @@ impl OperationManager {
             result,
         ))
     }
+
+    pub fn copy_permuted_image_slice(
+        &mut self,
+        device: &DeviceManager,
+        slice_index: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        self.resources.create_lossless_image_slice_output_buffers(
+            device.device(),
+            &self.image_dimensions,
+            slice_index + 1,
+        );
+        let mut encoder = device
+            .device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("copy_permuted_image_slice_command_encoder"),
+            });
+        self.state
+            .output_permuted_image_slice(&self.resources, &mut encoder, slice_index)?;
+        device.queue().submit(Some(encoder.finish()));
+        Ok(())
+    }
+
+    pub async fn output_permuted_image_slice(
+        &self,
+        device: &DeviceManager,
+        poll_type: DevicePollType,
+        slice_index: usize,
+        format: ImageFormat,
+    ) -> Result<LosslessImage, Box<dyn Error>> {
+        let buffer = self
+            .resources
+            .lossless_image_slice_output_buffer(slice_index);
+        let result = buffer.collect(device, poll_type).await;
+
+        Ok(LosslessImage::from_texture_data(
+            format,
+            buffer.width().try_into().unwrap(),
+            buffer.height().try_into().unwrap(),
+            result,
+        ))
+    }
 }
@@ -1,10 +1,13 @@
 use super::super::super::link::swap::{
-    CountSwapOutput, CountSwapOutputDataElement, SwapPass, SwapPassSequence,
+    AcceptedSwapsWord, CountSwapInputLayout, CountSwapOutput, CountSwapOutputDataElement, SwapPass,
+    SwapPassSequence,
 };
 use super::super::super::output::algorithm::swap::{
-    SwapPassSequenceSwapRatio, SwapPassSwapRatio, SwapRatio,
+    AcceptedSwaps, SwapPassSequenceSwapRatio, SwapPassSwapRatio, SwapRatio,
 };
-use crate::ImageDimensions;
+use crate::{BoundaryMode, ImageDimensions};
+use image_annealing_shader::constant;
+use image_annealing_shader::WorkgroupDimensions;
 use std::fmt;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -127,6 +130,7 @@ impl CountSwapOperationOutput {
         count_swap_output: &CountSwapOutput,
         sequence: &SwapPassSequence,
         image_dimensions: &ImageDimensions,
+        boundary: BoundaryMode,
     ) -> Self {
         let (passes, total, accepted) = sequence.iter().fold(
             (
@@ -136,7 +140,7 @@ impl CountSwapOperationOutput {
             ),
             |mut acc, &pass| {
                 let accepted_i = count_swap_output.at_pass(pass);
-                let total_i = pass.total_swaps(image_dimensions);
+                let total_i = pass.total_swaps(image_dimensions, boundary);
                 acc.0.push(CountSwapOperationOutputPass {
                     pass,
                     swap_ratio: SwapRatioImplementation::new(total_i, accepted_i),
@@ -193,5 +197,49 @@ impl fmt::Display for CountSwapOperationOutput {
     }
 }
 
+pub(super) fn accepted_swaps_from_words(
+    words: &[AcceptedSwapsWord],
+    sequence: &SwapPassSequence,
+    image_dimensions: &ImageDimensions,
+    boundary: BoundaryMode,
+) -> Vec<AcceptedSwaps> {
+    let workgroup_dimensions = WorkgroupDimensions::swap();
+    let workgroup_width: usize = workgroup_dimensions.x().try_into().unwrap();
+    let workgroup_height: usize = workgroup_dimensions.y().try_into().unwrap();
+    let words_per_workgroup: usize =
+        constant::swap::accepted_swaps_words_per_workgroup(workgroup_dimensions)
+            .try_into()
+            .unwrap();
+    let word_bits: usize = constant::swap::ACCEPTED_SWAPS_WORD_BITS.try_into().unwrap();
+    let layout = CountSwapInputLayout::new(image_dimensions);
+
+    sequence
+        .iter()
+        .map(|&pass| {
+            let mut accepted_swaps = AcceptedSwaps::new(pass, image_dimensions, boundary);
+            let segment_start = layout.segment_start(pass);
+            let grid_width: usize = pass
+                .swap_workgroup_grid_dimensions(image_dimensions)
+                .x()
+                .try_into()
+                .unwrap();
+            let (width, height) = pass.pair_grid_dimensions(image_dimensions);
+            for y in 0..height {
+                for x in 0..width {
+                    let workgroup_index = x / workgroup_width + (y / workgroup_height) * grid_width;
+                    let local_index =
+                        x % workgroup_width + (y % workgroup_height) * workgroup_width;
+                    let word = words[(segment_start + workgroup_index) * words_per_workgroup
+                        + local_index / word_bits];
+                    if word & (1 << (local_index % word_bits)) != 0 {
+                        accepted_swaps.set_accepted(x, y);
+                    }
+                }
+            }
+            accepted_swaps
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests;
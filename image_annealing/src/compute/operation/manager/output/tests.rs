@@ -112,7 +112,7 @@ mod count_swap_operation_output {
         CountSwapOperationOutput, CountSwapOperationOutputPass, SwapRatioImplementation,
     };
     use crate::compute::{SwapPassSequenceSwapRatio, SwapPassSwapRatio, SwapRatio};
-    use crate::ImageDimensions;
+    use crate::{BoundaryMode, ImageDimensions};
     use image_annealing_shader::constant;
     use std::error::Error;
 
@@ -129,6 +129,7 @@ mod count_swap_operation_output {
             &count_swap_output,
             &sequence,
             &ImageDimensions::try_new(1, 1)?,
+            BoundaryMode::Bounded,
         );
         assert_eq!(output.passes().count(), constant::count_swap::N_CHANNEL);
         output
@@ -169,6 +170,7 @@ mod count_swap_operation_output {
             &count_swap_output,
             &sequence,
             &ImageDimensions::try_new(2, 9)?,
+            BoundaryMode::Bounded,
         );
         assert_eq!(output.passes().count(), 2);
         output
@@ -221,6 +223,7 @@ mod count_swap_operation_output {
             &count_swap_output,
             &sequence,
             &ImageDimensions::try_new(1, 1).unwrap(),
+            BoundaryMode::Bounded,
         );
     }
 
@@ -241,6 +244,68 @@ mod count_swap_operation_output {
             &count_swap_output,
             &sequence,
             &ImageDimensions::try_new(2, 2).unwrap(),
+            BoundaryMode::Bounded,
         );
     }
+
+    #[test]
+    fn toroidal_total() -> Result<(), Box<dyn Error>> {
+        let counts = [1.0_f32, 2.0_f32, 4.0_f32, 5.0_f32];
+        let bytes: Vec<u8> = counts
+            .iter()
+            .flat_map(|&count| count.to_ne_bytes())
+            .collect();
+        let count_swap_output = CountSwapOutput::from_ne_bytes(bytes.as_slice().try_into()?);
+        let sequence = SwapPassSequence::from_passes([SwapPass::OffsetVertical])?;
+        let dimensions = ImageDimensions::try_new(2, 8)?;
+        let bounded_output = CountSwapOperationOutput::new(
+            &count_swap_output,
+            &sequence,
+            &dimensions,
+            BoundaryMode::Bounded,
+        );
+        assert_eq!(bounded_output.total(), 6);
+        let toroidal_output = CountSwapOperationOutput::new(
+            &count_swap_output,
+            &sequence,
+            &dimensions,
+            BoundaryMode::Toroidal,
+        );
+        assert_eq!(toroidal_output.total(), 8);
+        assert_eq!(toroidal_output.accepted(), 5);
+        Ok(())
+    }
+}
+
+mod accepted_swaps_from_words {
+    use super::super::super::super::super::link::swap::{
+        AcceptedSwapsWord, SwapPass, SwapPassSequence,
+    };
+    use super::super::accepted_swaps_from_words;
+    use crate::{BoundaryMode, ImageDimensions};
+    use std::error::Error;
+
+    #[test]
+    fn decode_passes() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(20, 25)?;
+        let mut words: Vec<AcceptedSwapsWord> =
+            vec![0; SwapPass::total_accepted_swaps_words(&dimensions)];
+        words[8] = 1 << 19;
+        words[30] = 1 << 1;
+        let sequence = SwapPassSequence::from_passes([SwapPass::Vertical, SwapPass::Horizontal])?;
+        let accepted_swaps =
+            accepted_swaps_from_words(&words, &sequence, &dimensions, BoundaryMode::Bounded);
+        assert_eq!(accepted_swaps.len(), 2);
+
+        let vertical = &accepted_swaps[0];
+        assert_eq!(vertical.pass(), SwapPass::Vertical);
+        assert_eq!(vertical.accepted(), 1);
+        assert!(vertical.is_accepted(17, 12));
+
+        let horizontal = &accepted_swaps[1];
+        assert_eq!(horizontal.pass(), SwapPass::Horizontal);
+        assert_eq!(horizontal.accepted(), 1);
+        assert!(horizontal.is_accepted(3, 17));
+        Ok(())
+    }
 }
@@ -7,6 +7,7 @@ use resource::ResourceStateMachineWrapper;
 #[must_use]
 #[derive(Clone)]
 pub struct AllResourcesState {
+    accepted_swaps_pass_set: SwapPassSet,
     count_swap_pass_set: SwapPassSet,
     count_swap_output_storage_buffer: ResourceStateMachineWrapper,
     count_swap_output_buffer: ResourceStateMachineWrapper,
@@ -24,6 +25,7 @@ pub struct AllResourcesState {
 impl AllResourcesState {
     pub fn new() -> Self {
         Self {
+            accepted_swaps_pass_set: Default::default(),
             count_swap_pass_set: Default::default(),
             count_swap_output_storage_buffer: ResourceStateMachineWrapper::new(),
             count_swap_output_buffer: ResourceStateMachineWrapper::new(),
@@ -39,6 +41,10 @@ impl AllResourcesState {
         }
     }
 
+    pub fn check_accepted_swaps_pass_set(&self) -> SwapPassSet {
+        self.accepted_swaps_pass_set
+    }
+
     pub fn check_count_swap_pass_set(&self) -> SwapPassSet {
         self.count_swap_pass_set
     }
@@ -136,6 +142,7 @@ impl AllResourcesState {
 
     pub fn input_permutation(self) -> Self {
         let mut next = self.clear_output_permutation().clear_count_swap_pass_set();
+        next.accepted_swaps_pass_set = Default::default();
         next.permutation_input_texture = next.permutation_input_texture.write();
         next
     }
@@ -163,6 +170,7 @@ impl AllResourcesState {
         let mut next = self
             .clear_output_lossless_image()
             .clear_count_swap_pass_set();
+        next.accepted_swaps_pass_set = Default::default();
         if next.permutation_output_texture.is_written() {
             next.permutation_output_texture = next.permutation_output_texture.clear();
         }
@@ -195,6 +203,7 @@ impl AllResourcesState {
         next.permutation_input_texture = next.permutation_input_texture.clear();
         next.permutation_output_texture = next.permutation_output_texture.write();
         next.count_swap_pass_set = next.count_swap_pass_set.add_pass(pass);
+        next.accepted_swaps_pass_set = next.accepted_swaps_pass_set.add_pass(pass);
         next
     }
 
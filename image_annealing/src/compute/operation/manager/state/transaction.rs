@@ -7,7 +7,7 @@ use super::super::{
     CreateDisplacementGoalOperationInput, PermuteOperationInput, SwapOperationInput,
 };
 use super::data::AllResourcesState;
-use crate::{DisplacementGoal, ImageDimensions, ValidatedPermutation};
+use crate::{BoundaryMode, DisplacementGoal, ImageDimensions, ValidatedPermutation};
 use std::error::Error;
 use std::fmt;
 
@@ -36,6 +36,7 @@ impl Error for InsufficientInputError {}
 
 #[derive(Debug, Clone)]
 pub enum InsufficientOutputError {
+    AcceptedSwaps,
     CountSwap,
     SwapPass,
     DisplacementGoal,
@@ -46,6 +47,10 @@ pub enum InsufficientOutputError {
 impl fmt::Display for InsufficientOutputError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Self::AcceptedSwaps => write!(
+                f,
+                "not all selected swap passes have occurred since the last input permutation"
+            ),
             Self::CountSwap => write!(
                 f,
                 "not all selected swap passes were counted during the last count swap operation, if one was performed"
@@ -78,11 +83,13 @@ pub struct ResourceStateManager {
 }
 
 impl ResourceStateManager {
-    pub fn new(image_dimensions: &ImageDimensions) -> Self {
+    pub fn new(image_dimensions: &ImageDimensions, boundary: BoundaryMode) -> Self {
+        let mut swap_parameters = SwapShaderParameters::new();
+        swap_parameters.set_boundary(boundary);
         Self {
             flags: AllResourcesState::new(),
             count_swap_parameters: CountSwapInputLayout::new(image_dimensions),
-            swap_parameters: SwapShaderParameters::new(),
+            swap_parameters,
         }
     }
 
@@ -284,6 +291,26 @@ impl ResourceStateManager {
         Ok(())
     }
 
+    pub fn output_accepted_swaps(
+        &mut self,
+        resources: &ResourceManager,
+        encoder: &mut wgpu::CommandEncoder,
+        sequence: &SwapPassSequence,
+    ) -> Result<(), Box<dyn Error>> {
+        if self
+            .flags
+            .check_accepted_swaps_pass_set()
+            .contains_set(sequence)
+        {
+            resources
+                .accepted_swaps_output_buffer()
+                .load(encoder, resources.accepted_swaps_storage_buffer());
+            Ok(())
+        } else {
+            Err(Box::new(InsufficientOutputError::AcceptedSwaps))
+        }
+    }
+
     pub fn output_count_swap(
         &mut self,
         resources: &ResourceManager,
@@ -360,4 +387,20 @@ impl ResourceStateManager {
             Err(Box::new(InsufficientOutputError::PermutedImage))
         }
     }
+
+    pub fn output_permuted_image_slice(
+        &self,
+        resources: &ResourceManager,
+        encoder: &mut wgpu::CommandEncoder,
+        slice_index: usize,
+    ) -> Result<(), Box<dyn Error>> {
+        if self.flags.check_lossless_image_output_texture() {
+            resources
+                .lossless_image_slice_output_buffer(slice_index)
+                .load(encoder, resources.lossless_image_output_texture());
+            Ok(())
+        } else {
+            Err(Box::new(InsufficientOutputError::PermutedImage))
+        }
+    }
 }
@@ -1,5 +1,5 @@
 use super::super::super::system::{CreateDisplacementGoalOperationInput, DevicePollType, System};
-use super::super::format::LosslessImage;
+use super::super::format::{ImageFormatError, LosslessImage};
 use super::super::OutputStatus;
 use super::validate_permutation::{ValidatePermutation, ValidatePermutationInput};
 use super::{CompletionStatus, CompletionStatusHolder, FinalOutputHolder};
@@ -101,6 +101,13 @@ impl CompletionStatusHolder for CreateDisplacementGoal {
 
                 if let Some(ref image) = self.input.image {
                     check_dimensions_match2(system, image)?;
+                    let format = image.format();
+                    if format.texel_slice_count() > 1 {
+                        return Err(Box::new(ImageFormatError::Unsliced {
+                            image_name: String::from("input"),
+                            format,
+                        }));
+                    }
                 }
 
                 system.operation_create_displacement_goal(
@@ -17,6 +17,8 @@ pub struct PermuteParameters {
 #[derive(Default)]
 pub struct PermuteInput {
     pub candidate_permutation: Option<CandidatePermutation>,
+    /// If `None`, the image from the previous operation is reused, which is not possible
+    /// when that image did not fit in a single texture
     pub original_image: Option<LosslessImage>,
 }
 
@@ -32,6 +34,10 @@ pub struct Permute {
     validator: Option<ValidatePermutation>,
     permutation: Option<ValidatedPermutation>,
     permuted_image_format: Option<ImageFormat>,
+    last_texel_slice_format: Option<ImageFormat>,
+    /// The formats of the permuted texel slices, other than the last slice,
+    /// that are waiting to be read back
+    texel_slice_formats: Vec<ImageFormat>,
     has_given_output: bool,
 }
 
@@ -51,6 +57,8 @@ impl Permute {
             validator,
             permutation: None,
             permuted_image_format: parameters.permuted_image_format,
+            last_texel_slice_format: None,
+            texel_slice_formats: Vec::new(),
             has_given_output: false,
         }
     }
@@ -118,13 +126,48 @@ impl CompletionStatusHolder for Permute {
                                 image_name: String::from("permuted output"),
                             }));
                         }
+                        if system.lossless_image_is_slice() {
+                            return Err(Box::new(ImageFormatError::ResidentSlice {
+                                image_name: String::from("original"),
+                            }));
+                        }
                     }
                 }
 
-                system.operation_permute(&PermuteOperationInput {
-                    permutation: self.permutation.as_ref(),
-                    image: image_option,
-                })?;
+                let format = self.permuted_image_format.unwrap();
+                if format.texel_slice_count() > 1 {
+                    // Images that do not fit in a single texture are permuted one texture-sized
+                    // slice at a time, copying each permuted slice to a buffer before the next
+                    // slice replaces it. The copies are read back with the permuted last slice.
+                    let mut slices = image_option
+                        .ok_or_else(|| ImageFormatError::Unsliced {
+                            image_name: String::from("permuted output"),
+                            format,
+                        })?
+                        .clone()
+                        .into_texel_slices();
+                    let last_slice = slices.pop().unwrap();
+                    for (index, slice) in slices.iter().enumerate() {
+                        system.operation_permute(&PermuteOperationInput {
+                            permutation: self.permutation.as_ref().filter(|_| index == 0),
+                            image: Some(slice),
+                        })?;
+                        system.copy_permuted_image_slice(index)?;
+                        self.texel_slice_formats.push(slice.format());
+                    }
+                    system.operation_permute(&PermuteOperationInput {
+                        permutation: None,
+                        image: Some(&last_slice),
+                    })?;
+                    system.set_lossless_image_is_slice();
+                    self.last_texel_slice_format = Some(last_slice.format());
+                } else {
+                    system.operation_permute(&PermuteOperationInput {
+                        permutation: self.permutation.as_ref(),
+                        image: image_option,
+                    })?;
+                    self.last_texel_slice_format = Some(format);
+                }
                 self.completion_status = CompletionStatus::Finished;
                 Ok(OutputStatus::FinalFullOutput)
             }
@@ -146,14 +189,28 @@ impl FinalOutputHolder<PermuteOutput> for Permute {
         system: &mut System,
         poll_type: DevicePollType,
     ) -> Option<PermuteOutput> {
-        system
-            .output_permuted_image(poll_type, self.permuted_image_format.unwrap())
-            .await
-            .ok()
-            .map(|permuted_image| PermuteOutput {
-                permutation: self.permutation.take(),
-                original_image: self.input.original_image.take(),
-                permuted_image,
-            })
+        let mut permuted_slices = Vec::with_capacity(self.texel_slice_formats.len() + 1);
+        for (index, format) in std::mem::take(&mut self.texel_slice_formats)
+            .into_iter()
+            .enumerate()
+        {
+            permuted_slices.push(
+                system
+                    .output_permuted_image_slice(poll_type, index, format)
+                    .await
+                    .ok()?,
+            );
+        }
+        permuted_slices.push(
+            system
+                .output_permuted_image(poll_type, self.last_texel_slice_format.unwrap())
+                .await
+                .ok()?,
+        );
+        Some(PermuteOutput {
+            permutation: self.permutation.take(),
+            original_image: self.input.original_image.take(),
+            permuted_image: LosslessImage::from_texel_slices(permuted_slices),
+        })
     }
 }
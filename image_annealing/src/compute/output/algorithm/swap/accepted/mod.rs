@@ -0,0 +1,191 @@
+use super::super::super::super::link::swap::SwapPass;
+use crate::compute::conversion::VectorFieldEntry;
+use crate::image_utils::{check_dimensions_match2, validation};
+use crate::{BoundaryMode, ImageDimensions, ImageDimensionsHolder, ValidatedPermutation};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub struct InvalidAcceptedSwapsLengthError {
+    expected: usize,
+    actual: usize,
+}
+
+impl fmt::Display for InvalidAcceptedSwapsLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "expected {} bytes of accepted swap flags, but found {} bytes",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl Error for InvalidAcceptedSwapsLengthError {}
+
+/// The set of pairs of texels that were exchanged during a swap pass
+///
+/// Each pair is identified by the coordinates of its second texel,
+/// with the coordinate along the direction of the swap divided by two.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AcceptedSwaps {
+    pass: SwapPass,
+    image_dimensions: ImageDimensions,
+    boundary: BoundaryMode,
+    flags: Vec<u8>,
+}
+
+impl AcceptedSwaps {
+    pub fn new(pass: SwapPass, image_dimensions: &ImageDimensions, boundary: BoundaryMode) -> Self {
+        Self {
+            pass,
+            image_dimensions: *image_dimensions,
+            boundary,
+            flags: vec![0; Self::byte_length(pass, image_dimensions)],
+        }
+    }
+
+    pub fn from_bytes(
+        pass: SwapPass,
+        image_dimensions: &ImageDimensions,
+        boundary: BoundaryMode,
+        flags: Vec<u8>,
+    ) -> Result<Self, InvalidAcceptedSwapsLengthError> {
+        let expected = Self::byte_length(pass, image_dimensions);
+        if flags.len() == expected {
+            Ok(Self {
+                pass,
+                image_dimensions: *image_dimensions,
+                boundary,
+                flags,
+            })
+        } else {
+            Err(InvalidAcceptedSwapsLengthError {
+                expected,
+                actual: flags.len(),
+            })
+        }
+    }
+
+    pub fn byte_length(pass: SwapPass, image_dimensions: &ImageDimensions) -> usize {
+        let (width, height) = pass.pair_grid_dimensions(image_dimensions);
+        width
+            .checked_mul(height)
+            .unwrap()
+            .checked_add(u8::BITS as usize - 1)
+            .unwrap()
+            / u8::BITS as usize
+    }
+
+    pub fn pass(&self) -> SwapPass {
+        self.pass
+    }
+
+    pub fn boundary(&self) -> BoundaryMode {
+        self.boundary
+    }
+
+    pub fn pair_grid_dimensions(&self) -> (usize, usize) {
+        self.pass.pair_grid_dimensions(&self.image_dimensions)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.flags
+    }
+
+    fn bit_position(&self, x: usize, y: usize) -> (usize, u8) {
+        let (width, height) = self.pair_grid_dimensions();
+        assert!(
+            x < width && y < height,
+            "pair index ({}, {}) is outside the grid of pairs of dimensions ({}, {})",
+            x,
+            y,
+            width,
+            height
+        );
+        let index = y * width + x;
+        let bits = u8::BITS as usize;
+        (index / bits, 1u8 << (index % bits))
+    }
+
+    pub fn is_accepted(&self, x: usize, y: usize) -> bool {
+        let (byte, mask) = self.bit_position(x, y);
+        self.flags[byte] & mask != 0
+    }
+
+    pub fn set_accepted(&mut self, x: usize, y: usize) {
+        let (byte, mask) = self.bit_position(x, y);
+        self.flags[byte] |= mask;
+    }
+
+    pub fn accepted(&self) -> usize {
+        self.flags
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum()
+    }
+
+    /// Perform the recorded swaps on the given permutation,
+    /// which should be the input permutation of the swap pass
+    pub fn apply(
+        &self,
+        permutation: &ValidatedPermutation,
+    ) -> Result<ValidatedPermutation, Box<dyn Error>> {
+        check_dimensions_match2(self, permutation)?;
+        let mut vector_field = permutation.as_ref().clone();
+        let (width, height) = self.pair_grid_dimensions();
+        for y in 0..height {
+            for x in 0..width {
+                if !self.is_accepted(x, y) {
+                    continue;
+                }
+                if let Some((coords1, coords2)) =
+                    self.pass
+                        .pair_coordinates(&self.image_dimensions, self.boundary, x, y)
+                {
+                    let pixel_coords1: (u32, u32) =
+                        (coords1.0.try_into().unwrap(), coords1.1.try_into().unwrap());
+                    let pixel_coords2: (u32, u32) =
+                        (coords2.0.try_into().unwrap(), coords2.1.try_into().unwrap());
+                    let vector1 = VectorFieldEntry::from_pixel(
+                        vector_field.get_pixel(pixel_coords1.0, pixel_coords1.1),
+                    );
+                    let vector2 = VectorFieldEntry::from_pixel(
+                        vector_field.get_pixel(pixel_coords2.0, pixel_coords2.1),
+                    );
+                    let pair_displacement = (coords2.0 - coords1.0, coords2.1 - coords1.1);
+                    let new_vector1 = VectorFieldEntry(
+                        (i64::from(vector2.0) + pair_displacement.0).try_into()?,
+                        (i64::from(vector2.1) + pair_displacement.1).try_into()?,
+                    );
+                    let new_vector2 = VectorFieldEntry(
+                        (i64::from(vector1.0) - pair_displacement.0).try_into()?,
+                        (i64::from(vector1.1) - pair_displacement.1).try_into()?,
+                    );
+                    vector_field.put_pixel(
+                        pixel_coords1.0,
+                        pixel_coords1.1,
+                        new_vector1.to_pixel(),
+                    );
+                    vector_field.put_pixel(
+                        pixel_coords2.0,
+                        pixel_coords2.1,
+                        new_vector2.to_pixel(),
+                    );
+                }
+            }
+        }
+        // Safety: Exchanging the vectors of pairs of texels, and adjusting the vectors
+        // for the change in position, preserves permutation constraints.
+        Ok(unsafe { validation::vector_field_into_validated_permutation_unchecked(vector_field) })
+    }
+}
+
+impl ImageDimensionsHolder for AcceptedSwaps {
+    fn dimensions(&self) -> &ImageDimensions {
+        &self.image_dimensions
+    }
+}
+
+#[cfg(test)]
+mod tests;
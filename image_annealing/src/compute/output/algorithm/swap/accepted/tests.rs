@@ -0,0 +1,188 @@
+mod from_bytes {
+    use super::super::AcceptedSwaps;
+    use crate::compute::SwapPass;
+    use crate::{BoundaryMode, ImageDimensions};
+    use std::error::Error;
+
+    #[test]
+    fn valid_length() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(5, 3)?;
+        let accepted = AcceptedSwaps::from_bytes(
+            SwapPass::Horizontal,
+            &dimensions,
+            BoundaryMode::Bounded,
+            vec![0b101, 0],
+        )?;
+        assert_eq!(accepted.pair_grid_dimensions(), (3, 3));
+        assert!(accepted.is_accepted(0, 0));
+        assert!(!accepted.is_accepted(1, 0));
+        assert!(accepted.is_accepted(2, 0));
+        assert_eq!(accepted.accepted(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_length() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(5, 3)?;
+        test_util::assert_error_contains(
+            AcceptedSwaps::from_bytes(
+                SwapPass::Vertical,
+                &dimensions,
+                BoundaryMode::Bounded,
+                vec![0],
+            ),
+            "expected 2 bytes of accepted swap flags, but found 1 bytes",
+        );
+        Ok(())
+    }
+}
+
+mod set_accepted {
+    use super::super::AcceptedSwaps;
+    use crate::compute::SwapPass;
+    use crate::{BoundaryMode, ImageDimensions};
+    use std::error::Error;
+
+    #[test]
+    fn round_trip() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(3, 4)?;
+        let mut accepted =
+            AcceptedSwaps::new(SwapPass::OffsetVertical, &dimensions, BoundaryMode::Bounded);
+        assert_eq!(accepted.pair_grid_dimensions(), (3, 2));
+        assert_eq!(accepted.accepted(), 0);
+        accepted.set_accepted(2, 1);
+        assert!(accepted.is_accepted(2, 1));
+        assert_eq!(accepted.as_bytes(), &[0b100000]);
+        assert_eq!(accepted.accepted(), 1);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "pair index (3, 0) is outside the grid of pairs")]
+    fn out_of_bounds() {
+        let dimensions = ImageDimensions::try_new(3, 4).unwrap();
+        let mut accepted =
+            AcceptedSwaps::new(SwapPass::Vertical, &dimensions, BoundaryMode::Bounded);
+        accepted.set_accepted(3, 0);
+    }
+}
+
+mod apply {
+    use super::super::AcceptedSwaps;
+    use crate::compute::conversion::{self, VectorFieldEntry};
+    use crate::compute::SwapPass;
+    use crate::image_utils::validation;
+    use crate::{BoundaryMode, ImageDimensions, ValidatedPermutation, VectorField};
+    use std::error::Error;
+
+    fn make_permutation(
+        dimensions: &ImageDimensions,
+        entries: &[VectorFieldEntry],
+    ) -> ValidatedPermutation {
+        let vector_field = conversion::to_image(dimensions, entries);
+        validation::validate_permutation(vector_field).unwrap()
+    }
+
+    #[test]
+    fn horizontal() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(3, 2)?;
+        let mut accepted =
+            AcceptedSwaps::new(SwapPass::Horizontal, &dimensions, BoundaryMode::Bounded);
+        accepted.set_accepted(0, 1);
+        accepted.set_accepted(1, 1);
+        let output = accepted.apply(&ValidatedPermutation::identity(&dimensions))?;
+        let expected = make_permutation(
+            &dimensions,
+            &[
+                VectorFieldEntry(0, 0),
+                VectorFieldEntry(0, 0),
+                VectorFieldEntry(0, 0),
+                VectorFieldEntry(1, 0),
+                VectorFieldEntry(-1, 0),
+                VectorFieldEntry(0, 0),
+            ],
+        );
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn composes_with_input_permutation() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(2, 2)?;
+        let input = make_permutation(
+            &dimensions,
+            &[
+                VectorFieldEntry(0, 1),
+                VectorFieldEntry(0, 0),
+                VectorFieldEntry(0, -1),
+                VectorFieldEntry(0, 0),
+            ],
+        );
+        let mut accepted =
+            AcceptedSwaps::new(SwapPass::Horizontal, &dimensions, BoundaryMode::Bounded);
+        accepted.set_accepted(0, 0);
+        let output = accepted.apply(&input)?;
+        let expected = make_permutation(
+            &dimensions,
+            &[
+                VectorFieldEntry(1, 0),
+                VectorFieldEntry(-1, 1),
+                VectorFieldEntry(0, -1),
+                VectorFieldEntry(0, 0),
+            ],
+        );
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn offset_horizontal_bounded() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(4, 1)?;
+        let mut accepted = AcceptedSwaps::new(
+            SwapPass::OffsetHorizontal,
+            &dimensions,
+            BoundaryMode::Bounded,
+        );
+        accepted.set_accepted(0, 0);
+        let identity = ValidatedPermutation::identity(&dimensions);
+        let output = accepted.apply(&identity)?;
+        assert_eq!(output, identity);
+        Ok(())
+    }
+
+    #[test]
+    fn offset_horizontal_toroidal() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(4, 1)?;
+        let mut accepted = AcceptedSwaps::new(
+            SwapPass::OffsetHorizontal,
+            &dimensions,
+            BoundaryMode::Toroidal,
+        );
+        accepted.set_accepted(0, 0);
+        let output = accepted.apply(&ValidatedPermutation::identity(&dimensions))?;
+        let expected = conversion::to_image(
+            &dimensions,
+            &[
+                VectorFieldEntry(3, 0),
+                VectorFieldEntry(0, 0),
+                VectorFieldEntry(0, 0),
+                VectorFieldEntry(-3, 0),
+            ],
+        );
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn dimensions_mismatch() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(4, 1)?;
+        let accepted = AcceptedSwaps::new(SwapPass::Vertical, &dimensions, BoundaryMode::Bounded);
+        test_util::assert_error_contains(
+            accepted.apply(&ValidatedPermutation::identity(&ImageDimensions::try_new(
+                1, 4,
+            )?)),
+            "mismatch in image dimensions, (width, height) = (4, 1) and (width, height) = (1, 4)",
+        );
+        Ok(())
+    }
+}
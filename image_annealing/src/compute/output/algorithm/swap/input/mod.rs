@@ -1,3 +1,4 @@
+use super::super::super::format::ImageFormat;
 use crate::{CandidatePermutation, DisplacementGoal};
 use std::default::Default;
 
@@ -10,6 +11,10 @@ pub struct SwapParameters {
     pub sequence: SwapPassSequence,
     pub swap_acceptance_threshold: f32,
     pub count_swap: bool,
+    pub record_accepted_swaps: bool,
+    /// If set, each full output also includes the image input to the last permute operation,
+    /// permuted by the output permutation and read back in this format
+    pub permuted_image_format: Option<ImageFormat>,
 }
 
 impl SwapParameters {
@@ -25,6 +30,8 @@ impl SwapParameters {
             sequence,
             swap_acceptance_threshold,
             count_swap: false,
+            record_accepted_swaps: false,
+            permuted_image_format: None,
         }
     }
 }
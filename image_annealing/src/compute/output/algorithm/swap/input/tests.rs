@@ -12,7 +12,9 @@ mod swap_parameters {
             SwapParameters {
                 sequence,
                 swap_acceptance_threshold: Default::default(),
-                count_swap: false
+                count_swap: false,
+                record_accepted_swaps: false,
+                permuted_image_format: None,
             }
         );
         Ok(())
@@ -30,7 +32,9 @@ mod swap_parameters {
             SwapParameters {
                 sequence,
                 swap_acceptance_threshold,
-                count_swap: false
+                count_swap: false,
+                record_accepted_swaps: false,
+                permuted_image_format: None,
             }
         );
         Ok(())
@@ -44,6 +48,8 @@ mod swap_parameters {
                 sequence: SwapPassSequence::all(),
                 swap_acceptance_threshold: Default::default(),
                 count_swap: Default::default(),
+                record_accepted_swaps: Default::default(),
+                permuted_image_format: Default::default(),
             }
         );
     }
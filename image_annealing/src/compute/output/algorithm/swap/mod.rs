@@ -1,4 +1,7 @@
-use super::super::super::system::{DevicePollType, SwapOperationInput, System};
+use super::super::super::system::{
+    DevicePollType, PermuteOperationInput, SwapOperationInput, System,
+};
+use super::super::format::{ImageFormat, ImageFormatError, LosslessImage};
 use super::super::OutputStatus;
 use super::validate_permutation::{ValidatePermutation, ValidatePermutationInput};
 use super::{CompletionStatus, CompletionStatusHolder, FinalOutputHolder};
@@ -7,9 +10,11 @@ use crate::{DisplacementGoal, ValidatedPermutation};
 use async_trait::async_trait;
 use std::error::Error;
 
+mod accepted;
 mod input;
 mod output;
 
+pub use accepted::{AcceptedSwaps, InvalidAcceptedSwapsLengthError};
 pub use input::{
     InvalidSwapPassSelectionError, SwapInput, SwapParameters, SwapPass, SwapPassSequence,
     SwapPassSet,
@@ -29,6 +34,8 @@ pub struct Swap {
     previous_pass: Option<SwapPass>,
     swap_acceptance_threshold: f32,
     do_count_swap: bool,
+    do_record_accepted_swaps: bool,
+    permuted_image_format: Option<ImageFormat>,
     has_given_partial_output: bool,
     has_given_full_output: bool,
 }
@@ -52,7 +59,9 @@ impl Swap {
             remaining_passes: None,
             previous_pass: None,
             swap_acceptance_threshold: parameters.swap_acceptance_threshold,
-            do_count_swap: parameters.count_swap,
+            do_count_swap: parameters.count_swap || parameters.record_accepted_swaps,
+            do_record_accepted_swaps: parameters.record_accepted_swaps,
+            permuted_image_format: parameters.permuted_image_format,
             has_given_partial_output: false,
             has_given_full_output: false,
         }
@@ -80,37 +89,54 @@ impl Swap {
         } else {
             match self.completion_status {
                 CompletionStatus::Failed => None,
-                _ => {
-                    match self.previous_pass {
-                        Some(pass) => {
-                            self.has_given_full_output = true;
-                            system.output_permutation(poll_type).await.ok().map(
-                                |output_permutation| SwapFullOutput {
-                                    input: {
-                                        let permutation = self.input_permutation.take();
-                                        let displacement_goal = self.input_displacement_goal.take();
-                                        if permutation.is_some() || displacement_goal.is_some() {
-                                            Some(SwapReturnedInput {
-                                                permutation,
-                                                displacement_goal,
-                                            })
-                                        } else {
-                                            None
-                                        }
-                                    },
-                                    output_permutation,
-                                    pass,
-                                },
-                            )
-                        }
-                        None => None,
+                _ => match self.previous_pass {
+                    Some(pass) => {
+                        self.has_given_full_output = true;
+                        let output_permutation = system.output_permutation(poll_type).await.ok()?;
+                        let permuted_image = match self.permuted_image_format {
+                            Some(format) => {
+                                Some(permute_image(system, poll_type, format).await.ok()?)
+                            }
+                            None => None,
+                        };
+                        let permutation = self.input_permutation.take();
+                        let displacement_goal = self.input_displacement_goal.take();
+                        Some(SwapFullOutput {
+                            input: if permutation.is_some() || displacement_goal.is_some() {
+                                Some(SwapReturnedInput {
+                                    permutation,
+                                    displacement_goal,
+                                })
+                            } else {
+                                None
+                            },
+                            output_permutation,
+                            pass,
+                            permuted_image,
+                        })
                     }
-                }
+                    None => None,
+                },
             }
         }
     }
 }
 
+/// Permutes the image input to the last permute operation by the current output permutation
+async fn permute_image(
+    system: &mut System,
+    poll_type: DevicePollType,
+    format: ImageFormat,
+) -> Result<LosslessImage, Box<dyn Error>> {
+    if system.lossless_image_is_slice() {
+        return Err(Box::new(ImageFormatError::ResidentSlice {
+            image_name: String::from("original"),
+        }));
+    }
+    system.operation_permute(&PermuteOperationInput::default())?;
+    system.output_permuted_image(poll_type, format).await
+}
+
 impl CompletionStatusHolder for Swap {
     fn get_status(&self) -> &CompletionStatus {
         &self.completion_status
@@ -204,12 +230,23 @@ impl FinalOutputHolder<SwapPartialOutput> for Swap {
         system: &mut System,
         poll_type: DevicePollType,
     ) -> Option<SwapPartialOutput> {
-        system
+        let counts = system
             .output_count_swap(poll_type, &self.sequence)
             .await
-            .ok()
-            .map(|counts| SwapPartialOutput {
-                counts: Box::new(counts),
-            })
+            .ok()?;
+        let accepted_swaps = if self.do_record_accepted_swaps {
+            Some(
+                system
+                    .output_accepted_swaps(poll_type, &self.sequence)
+                    .await
+                    .ok()?,
+            )
+        } else {
+            None
+        };
+        Some(SwapPartialOutput {
+            counts: Box::new(counts),
+            accepted_swaps,
+        })
     }
 }
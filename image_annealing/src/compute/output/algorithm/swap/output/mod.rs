@@ -1,4 +1,6 @@
 use super::super::super::super::link::swap::SwapPass;
+use super::super::super::format::LosslessImage;
+use super::AcceptedSwaps;
 use crate::{DisplacementGoal, ValidatedPermutation};
 use std::fmt;
 
@@ -33,6 +35,7 @@ pub trait SwapPassSequenceSwapRatio: SwapRatio + fmt::Display {
 
 pub struct SwapPartialOutput {
     pub counts: Box<dyn SwapPassSequenceSwapRatio + Send>,
+    pub accepted_swaps: Option<Vec<AcceptedSwaps>>,
 }
 
 pub struct SwapReturnedInput {
@@ -44,6 +47,8 @@ pub struct SwapFullOutput {
     pub input: Option<SwapReturnedInput>,
     pub output_permutation: ValidatedPermutation,
     pub pass: SwapPass,
+    /// The permuted image, if requested by `SwapParameters::permuted_image_format`
+    pub permuted_image: Option<LosslessImage>,
 }
 
 #[cfg(test)]
@@ -68,8 +68,9 @@ impl CompletionStatusHolder for ValidatePermutation {
             candidate_permutation,
         } = self.input.take().unwrap();
         check_dimensions_match2(system, &candidate_permutation)?;
-        self.full_output = Some(validation::validate_permutation(
+        self.full_output = Some(validation::validate_permutation_with_boundary(
             candidate_permutation.into_inner(),
+            system.boundary(),
         )?);
         self.completion_status = CompletionStatus::Finished;
         Ok(OutputStatus::FinalFullOutput)
@@ -0,0 +1,262 @@
+use super::{ImageFileData, ImageFileWriterSaveError, ImageFileWriterSaveResult, Rgba8Image};
+use crate::ImageDimensionsHolder;
+use image::error::{EncodingError, ImageFormatHint, LimitError, LimitErrorKind};
+use image::{ImageError, ImageFormat};
+use std::error::Error;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// A file format for animations
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AnimationFormat {
+    /// Animated PNG, which stores frames losslessly
+    #[default]
+    Apng,
+    /// Animated GIF, which reduces each frame to a palette of at most 256 colors
+    Gif,
+}
+
+impl AnimationFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Apng => "png",
+            Self::Gif => "gif",
+        }
+    }
+
+    fn encoding_error<E>(&self, err: E) -> ImageFileWriterSaveError
+    where
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let format = match self {
+            Self::Apng => ImageFormat::Png,
+            Self::Gif => ImageFormat::Gif,
+        };
+        ImageError::Encoding(EncodingError::new(ImageFormatHint::Exact(format), err))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AnimationParameters {
+    pub frame_delay_milliseconds: u16,
+    /// The number of times that the animation is played, where zero means that it loops forever
+    pub loop_count: u16,
+}
+
+impl Default for AnimationParameters {
+    fn default() -> Self {
+        Self {
+            frame_delay_milliseconds: 100,
+            loop_count: 0,
+        }
+    }
+}
+
+// Speed of the GIF color quantizer, chosen to give up some quality for speed
+// because animations can have many frames
+const GIF_QUANTIZATION_SPEED: i32 = 10;
+
+const EMPTY_ANIMATION_MESSAGE: &str = "an animation must have at least one frame";
+
+fn create_gif_encoder(
+    width: u16,
+    height: u16,
+    parameters: &AnimationParameters,
+) -> Result<gif::Encoder<ImageFileData>, gif::EncodingError> {
+    let mut encoder = gif::Encoder::new(Vec::new(), width, height, &[])?;
+    // GIF files store the number of times that the animation is repeated after the first play
+    match parameters.loop_count {
+        0 => encoder.set_repeat(gif::Repeat::Infinite)?,
+        1 => (),
+        count => encoder.set_repeat(gif::Repeat::Finite(count - 1))?,
+    }
+    Ok(encoder)
+}
+
+/// The data of an animated PNG, which remains accessible while a PNG writer is writing to it
+#[derive(Clone, Default)]
+struct SharedImageFileData(Arc<Mutex<ImageFileData>>);
+
+impl Write for SharedImageFileData {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn create_apng_writer(
+    data: SharedImageFileData,
+    width: u32,
+    height: u32,
+    parameters: &AnimationParameters,
+) -> Result<png::Writer<SharedImageFileData>, png::EncodingError> {
+    let mut encoder = png::Encoder::new(data, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    // The number of frames is written before the frames themselves,
+    // so it is replaced once all frames have been written
+    encoder.set_animated(u32::MAX, parameters.loop_count.into())?;
+    encoder.set_frame_delay(parameters.frame_delay_milliseconds, 1000)?;
+    encoder.write_header()
+}
+
+/// Replaces the animation control chunk of an animated PNG
+fn set_apng_frame_count(
+    data: &mut [u8],
+    frame_count: u32,
+    parameters: &AnimationParameters,
+) -> Result<(), png::EncodingError> {
+    let mut chunk = Vec::new();
+    png::AnimationControl {
+        num_frames: frame_count,
+        num_plays: parameters.loop_count.into(),
+    }
+    .encode(&mut chunk)?;
+    // Chunks follow the 8-byte PNG signature, and each has a 4-byte length,
+    // a 4-byte type, data, and a 4-byte checksum.
+    let mut offset = 8;
+    while data[offset + 4..offset + 8] != *b"acTL" {
+        offset += 12 + u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+    }
+    data[offset..offset + chunk.len()].copy_from_slice(&chunk);
+    Ok(())
+}
+
+enum AnimationState {
+    Apng(Option<(png::Writer<SharedImageFileData>, SharedImageFileData)>),
+    Gif(Option<gif::Encoder<ImageFileData>>),
+}
+
+/// Encodes a sequence of 8-bit RGBA images, which must all have the same dimensions,
+/// as an animation
+///
+/// Each frame is compressed when it is added, so that the frames are not kept in memory.
+pub struct AnimationEncoder {
+    format: AnimationFormat,
+    parameters: AnimationParameters,
+    state: AnimationState,
+    frame_count: usize,
+}
+
+impl AnimationEncoder {
+    pub fn new(format: AnimationFormat, parameters: &AnimationParameters) -> Self {
+        Self {
+            format,
+            parameters: *parameters,
+            state: match format {
+                AnimationFormat::Apng => AnimationState::Apng(None),
+                AnimationFormat::Gif => AnimationState::Gif(None),
+            },
+            frame_count: 0,
+        }
+    }
+
+    pub fn format(&self) -> AnimationFormat {
+        self.format
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    pub fn add_frame(&mut self, frame: &Rgba8Image) -> Result<(), ImageFileWriterSaveError> {
+        match self.state {
+            AnimationState::Apng(ref mut writer_option) => {
+                if writer_option.is_none() {
+                    let data = SharedImageFileData::default();
+                    let dimensions = frame.dimensions();
+                    let writer = create_apng_writer(
+                        data.clone(),
+                        dimensions.width().try_into().unwrap(),
+                        dimensions.height().try_into().unwrap(),
+                        &self.parameters,
+                    )
+                    .map_err(|err| self.format.encoding_error(err))?;
+                    *writer_option = Some((writer, data));
+                }
+                writer_option
+                    .as_mut()
+                    .unwrap()
+                    .0
+                    .write_image_data(frame.as_ref().as_raw())
+                    .map_err(|err| self.format.encoding_error(err))?;
+            }
+            AnimationState::Gif(ref mut encoder_option) => {
+                let dimensions = frame.dimensions();
+                let (width, height) = match (
+                    u16::try_from(dimensions.width()),
+                    u16::try_from(dimensions.height()),
+                ) {
+                    (Ok(width), Ok(height)) => (width, height),
+                    _ => {
+                        return Err(ImageError::Limits(LimitError::from_kind(
+                            LimitErrorKind::DimensionError,
+                        )))
+                    }
+                };
+                if encoder_option.is_none() {
+                    *encoder_option = Some(
+                        create_gif_encoder(width, height, &self.parameters)
+                            .map_err(|err| self.format.encoding_error(err))?,
+                    );
+                }
+                let mut pixels = frame.as_ref().as_raw().clone();
+                let mut gif_frame =
+                    gif::Frame::from_rgba_speed(width, height, &mut pixels, GIF_QUANTIZATION_SPEED);
+                // GIF frame delays are in units of 10 milliseconds
+                gif_frame.delay = self.parameters.frame_delay_milliseconds.saturating_add(5) / 10;
+                encoder_option
+                    .as_mut()
+                    .unwrap()
+                    .write_frame(&gif_frame)
+                    .map_err(|err| self.format.encoding_error(err))?;
+            }
+        }
+        self.frame_count += 1;
+        Ok(())
+    }
+
+    pub fn encode(self) -> Result<ImageFileData, ImageFileWriterSaveError> {
+        let format = self.format;
+        match self.state {
+            AnimationState::Apng(Some((writer, data))) => {
+                writer.finish().map_err(|err| format.encoding_error(err))?;
+                let mut data = std::mem::take(&mut *data.0.lock().unwrap());
+                set_apng_frame_count(
+                    &mut data,
+                    self.frame_count.try_into().unwrap(),
+                    &self.parameters,
+                )
+                .map_err(|err| format.encoding_error(err))?;
+                Ok(data)
+            }
+            AnimationState::Apng(None) => Err(format.encoding_error(EMPTY_ANIMATION_MESSAGE)),
+            AnimationState::Gif(Some(encoder)) => encoder
+                .into_inner()
+                .map_err(|err| format.encoding_error(err)),
+            AnimationState::Gif(None) => Err(format.encoding_error(EMPTY_ANIMATION_MESSAGE)),
+        }
+    }
+
+    pub fn make_filename<P: AsRef<Path>>(&self, path_no_extension: P) -> PathBuf {
+        path_no_extension
+            .as_ref()
+            .with_extension(self.format.extension())
+    }
+
+    pub fn save_add_extension<P: AsRef<Path>>(
+        self,
+        path_no_extension: P,
+    ) -> ImageFileWriterSaveResult {
+        let output_path = self.make_filename(path_no_extension);
+        std::fs::write(&output_path, self.encode()?)?;
+        Ok(output_path)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,97 @@
+use super::super::Rgba8Image;
+use super::{AnimationEncoder, AnimationFormat, AnimationParameters};
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::{AnimationDecoder, Frame};
+use std::error::Error;
+use std::io::Cursor;
+
+fn make_frames() -> Vec<Rgba8Image> {
+    [[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255]]
+        .into_iter()
+        .map(|color| {
+            Rgba8Image::new(image::RgbaImage::from_pixel(3, 2, image::Rgba(color))).unwrap()
+        })
+        .collect()
+}
+
+fn encode(format: AnimationFormat, frames: &[Rgba8Image]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut encoder = AnimationEncoder::new(
+        format,
+        &AnimationParameters {
+            frame_delay_milliseconds: 40,
+            loop_count: 0,
+        },
+    );
+    for frame in frames {
+        encoder.add_frame(frame)?;
+    }
+    assert_eq!(encoder.frame_count(), frames.len());
+    Ok(encoder.encode()?)
+}
+
+fn assert_frames_match(decoded: Vec<Frame>, frames: &[Rgba8Image]) {
+    assert_eq!(decoded.len(), frames.len());
+    for (decoded_frame, frame) in decoded.iter().zip(frames) {
+        assert_eq!(decoded_frame.buffer(), frame.as_ref());
+        assert_eq!(
+            std::time::Duration::from(decoded_frame.delay()),
+            std::time::Duration::from_millis(40)
+        );
+    }
+}
+
+#[test]
+fn extension() {
+    assert_eq!(AnimationFormat::Apng.extension(), "png");
+    assert_eq!(AnimationFormat::Gif.extension(), "gif");
+    assert_eq!(
+        AnimationEncoder::new(AnimationFormat::Gif, &Default::default()).make_filename("a/b"),
+        std::path::PathBuf::from("a/b.gif")
+    );
+}
+
+#[test]
+fn apng() -> Result<(), Box<dyn Error>> {
+    let frames = make_frames();
+    let data = encode(AnimationFormat::Apng, &frames)?;
+    let decoder = PngDecoder::new(Cursor::new(data))?;
+    assert!(decoder.is_apng());
+    assert_frames_match(decoder.apng().into_frames().collect_frames()?, &frames);
+    Ok(())
+}
+
+#[test]
+fn gif() -> Result<(), Box<dyn Error>> {
+    let frames = make_frames();
+    let data = encode(AnimationFormat::Gif, &frames)?;
+    let decoder = GifDecoder::new(Cursor::new(data))?;
+    assert_frames_match(decoder.into_frames().collect_frames()?, &frames);
+    Ok(())
+}
+
+#[test]
+fn empty() {
+    for format in [AnimationFormat::Apng, AnimationFormat::Gif] {
+        test_util::assert_error_contains(
+            AnimationEncoder::new(format, &Default::default()).encode(),
+            "an animation must have at least one frame",
+        );
+    }
+}
+
+#[test]
+fn save() -> Result<(), Box<dyn Error>> {
+    let mut encoder = AnimationEncoder::new(AnimationFormat::Gif, &Default::default());
+    encoder.add_frame(&make_frames()[0])?;
+    let path = encoder.save_add_extension(test_util::make_test_output_path(["animation_save"]))?;
+    assert_eq!(
+        GifDecoder::new(std::fs::File::open(&path)?)?
+            .into_frames()
+            .collect_frames()?
+            .len(),
+        1
+    );
+    std::fs::remove_file(path)?;
+    Ok(())
+}
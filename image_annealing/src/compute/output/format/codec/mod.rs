@@ -0,0 +1,76 @@
+use super::{
+    ImageFileData, ImageFileReader, ImageFileWriter, ImageFileWriterEncodeResult,
+    ImageFileWriterSaveError, ImageFormat, LosslessImage,
+};
+use futures::executor::ThreadPool;
+use futures::future::RemoteHandle;
+use futures::task::SpawnExt;
+use futures::{Future, FutureExt};
+use std::error::Error;
+use std::io;
+
+/// A thread pool for encoding and decoding images without blocking the calling thread
+///
+/// Errors from decoding are converted to strings on the worker threads,
+/// because `Box<dyn Error>` cannot be sent between threads.
+#[derive(Clone, Debug)]
+pub struct ImageCodecPool(ThreadPool);
+
+impl ImageCodecPool {
+    pub fn new() -> Result<Self, io::Error> {
+        Ok(Self(ThreadPool::new()?))
+    }
+
+    pub fn with_thread_count(thread_count: usize) -> Result<Self, io::Error> {
+        Ok(Self(
+            ThreadPool::builder().pool_size(thread_count).create()?,
+        ))
+    }
+
+    /// Runs a task on the thread pool, returning a future that resolves to its output
+    ///
+    /// The task is cancelled if the returned future is dropped before the task runs.
+    pub fn spawn<F, T>(&self, f: F) -> RemoteHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.0
+            .spawn_with_handle(futures::future::lazy(|_| f()))
+            .expect("the thread pool should accept new tasks while it is alive")
+    }
+
+    pub fn encode<T>(&self, image: T) -> RemoteHandle<ImageFileWriterEncodeResult>
+    where
+        T: ImageFileWriter + Send + 'static,
+    {
+        self.spawn(move || image.encode())
+    }
+
+    pub fn decode<T>(&self, data: ImageFileData) -> impl Future<Output = Result<T, Box<dyn Error>>>
+    where
+        T: ImageFileReader + Send + 'static,
+    {
+        self.spawn(move || T::decode(&data).map_err(|err| err.to_string()))
+            .map(|result| result.map_err(Box::from))
+    }
+
+    pub fn encode_lossless(
+        &self,
+        image: LosslessImage,
+    ) -> RemoteHandle<Result<Vec<ImageFileData>, ImageFileWriterSaveError>> {
+        self.spawn(move || image.encode())
+    }
+
+    pub fn decode_lossless(
+        &self,
+        format: ImageFormat,
+        data: Vec<ImageFileData>,
+    ) -> impl Future<Output = Result<LosslessImage, Box<dyn Error>>> {
+        self.spawn(move || LosslessImage::decode(format, &data).map_err(|err| err.to_string()))
+            .map(|result| result.map_err(Box::from))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,54 @@
+use super::super::{ImageFormat, LosslessImage, Rgba8Image, VectorFieldImageBuffer};
+use super::ImageCodecPool;
+use futures::executor::block_on;
+use std::error::Error;
+
+fn make_image() -> VectorFieldImageBuffer {
+    VectorFieldImageBuffer::from_fn(3, 2, |x, y| {
+        image::Rgba([x.try_into().unwrap(), y.try_into().unwrap(), 1, 2])
+    })
+}
+
+#[test]
+fn encode_decode() -> Result<(), Box<dyn Error>> {
+    let pool = ImageCodecPool::with_thread_count(2)?;
+    let image = make_image();
+    let data = block_on(pool.encode(image.clone()))?;
+    assert_eq!(
+        block_on(pool.decode::<VectorFieldImageBuffer>(data))?,
+        image
+    );
+    Ok(())
+}
+
+#[test]
+fn many_in_flight() -> Result<(), Box<dyn Error>> {
+    let pool = ImageCodecPool::new()?;
+    let image = make_image();
+    let handles: Vec<_> = (0..8).map(|_| pool.encode(image.clone())).collect();
+    for handle in handles {
+        assert_eq!(
+            block_on(pool.decode::<VectorFieldImageBuffer>(block_on(handle)?))?,
+            image
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn encode_decode_lossless() -> Result<(), Box<dyn Error>> {
+    let pool = ImageCodecPool::new()?;
+    let image = LosslessImage::Rgba8(Rgba8Image::new(make_image())?);
+    let data = block_on(pool.encode_lossless(image.clone()))?;
+    assert_eq!(
+        block_on(pool.decode_lossless(ImageFormat::Rgba8, data))?,
+        image
+    );
+    Ok(())
+}
+
+#[test]
+fn decode_error() {
+    let pool = ImageCodecPool::new().unwrap();
+    assert!(block_on(pool.decode::<VectorFieldImageBuffer>(vec![0u8; 4])).is_err());
+}
@@ -1,7 +1,8 @@
 use super::super::super::resource::texture::{LosslessImageTexture, TextureDatatype};
 use super::{
-    ImageFileReader, ImageFileWriter, ImageFileWriterSaveError, Rgba16Image, Rgba16ImageBuffer,
-    Rgba16ImageBufferComponent, Rgba8Image, VectorFieldImageBuffer,
+    ImageConversionPolicy, ImageFileData, ImageFileReader, ImageFileWriter,
+    ImageFileWriterSaveError, Rgba16Image, Rgba16ImageBuffer, Rgba16ImageBufferComponent,
+    Rgba8Image, VectorFieldImageBuffer,
 };
 use crate::image_utils::{
     check_dimensions_match2, check_dimensions_match3, check_dimensions_match4,
@@ -25,12 +26,31 @@ impl Rgba8x2Image {
         path1: P1,
         path2: P2,
     ) -> Result<Self, Box<dyn Error>> {
-        let image1 = Rgba8Image::load(path1)?;
-        let image2 = Rgba8Image::load(path2)?;
+        Self::load_with_conversion(path1, path2, ImageConversionPolicy::Strict)
+    }
+
+    pub fn load_with_conversion<P1: AsRef<Path>, P2: AsRef<Path>>(
+        path1: P1,
+        path2: P2,
+        policy: ImageConversionPolicy,
+    ) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba8Image::load_with_conversion(path1, policy)?;
+        let image2 = Rgba8Image::load_with_conversion(path2, policy)?;
         check_dimensions_match2(&image1, &image2)?;
         Ok(Self(image1, image2))
     }
 
+    pub fn decode(data1: &[u8], data2: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba8Image::decode(data1)?;
+        let image2 = Rgba8Image::decode(data2)?;
+        check_dimensions_match2(&image1, &image2)?;
+        Ok(Self(image1, image2))
+    }
+
+    pub fn encode(&self) -> Result<(ImageFileData, ImageFileData), ImageFileWriterSaveError> {
+        Ok((self.0.encode()?, self.1.encode()?))
+    }
+
     pub fn save_add_extension<P1: AsRef<Path>, P2: AsRef<Path>>(
         &self,
         path1_no_extension: P1,
@@ -106,13 +126,36 @@ impl Rgba8x3Image {
         path2: P2,
         path3: P3,
     ) -> Result<Self, Box<dyn Error>> {
-        let image1 = Rgba8Image::load(path1)?;
-        let image2 = Rgba8Image::load(path2)?;
-        let image3 = Rgba8Image::load(path3)?;
+        Self::load_with_conversion(path1, path2, path3, ImageConversionPolicy::Strict)
+    }
+
+    pub fn load_with_conversion<P1: AsRef<Path>, P2: AsRef<Path>, P3: AsRef<Path>>(
+        path1: P1,
+        path2: P2,
+        path3: P3,
+        policy: ImageConversionPolicy,
+    ) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba8Image::load_with_conversion(path1, policy)?;
+        let image2 = Rgba8Image::load_with_conversion(path2, policy)?;
+        let image3 = Rgba8Image::load_with_conversion(path3, policy)?;
+        check_dimensions_match3(&image1, &image2, &image3)?;
+        Ok(Self(image1, image2, image3))
+    }
+
+    pub fn decode(data1: &[u8], data2: &[u8], data3: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba8Image::decode(data1)?;
+        let image2 = Rgba8Image::decode(data2)?;
+        let image3 = Rgba8Image::decode(data3)?;
         check_dimensions_match3(&image1, &image2, &image3)?;
         Ok(Self(image1, image2, image3))
     }
 
+    pub fn encode(
+        &self,
+    ) -> Result<(ImageFileData, ImageFileData, ImageFileData), ImageFileWriterSaveError> {
+        Ok((self.0.encode()?, self.1.encode()?, self.2.encode()?))
+    }
+
     pub fn save_add_extension<P1: AsRef<Path>, P2: AsRef<Path>, P3: AsRef<Path>>(
         &self,
         path1_no_extension: P1,
@@ -217,14 +260,57 @@ impl Rgba8x4Image {
         path3: P3,
         path4: P4,
     ) -> Result<Self, Box<dyn Error>> {
-        let image1 = Rgba8Image::load(path1)?;
-        let image2 = Rgba8Image::load(path2)?;
-        let image3 = Rgba8Image::load(path3)?;
-        let image4 = Rgba8Image::load(path4)?;
+        Self::load_with_conversion(path1, path2, path3, path4, ImageConversionPolicy::Strict)
+    }
+
+    pub fn load_with_conversion<
+        P1: AsRef<Path>,
+        P2: AsRef<Path>,
+        P3: AsRef<Path>,
+        P4: AsRef<Path>,
+    >(
+        path1: P1,
+        path2: P2,
+        path3: P3,
+        path4: P4,
+        policy: ImageConversionPolicy,
+    ) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba8Image::load_with_conversion(path1, policy)?;
+        let image2 = Rgba8Image::load_with_conversion(path2, policy)?;
+        let image3 = Rgba8Image::load_with_conversion(path3, policy)?;
+        let image4 = Rgba8Image::load_with_conversion(path4, policy)?;
+        check_dimensions_match4(&image1, &image2, &image3, &image4)?;
+        Ok(Self(image1, image2, image3, image4))
+    }
+
+    pub fn decode(
+        data1: &[u8],
+        data2: &[u8],
+        data3: &[u8],
+        data4: &[u8],
+    ) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba8Image::decode(data1)?;
+        let image2 = Rgba8Image::decode(data2)?;
+        let image3 = Rgba8Image::decode(data3)?;
+        let image4 = Rgba8Image::decode(data4)?;
         check_dimensions_match4(&image1, &image2, &image3, &image4)?;
         Ok(Self(image1, image2, image3, image4))
     }
 
+    pub fn encode(
+        &self,
+    ) -> Result<
+        (ImageFileData, ImageFileData, ImageFileData, ImageFileData),
+        ImageFileWriterSaveError,
+    > {
+        Ok((
+            self.0.encode()?,
+            self.1.encode()?,
+            self.2.encode()?,
+            self.3.encode()?,
+        ))
+    }
+
     pub fn save_add_extension<
         P1: AsRef<Path>,
         P2: AsRef<Path>,
@@ -334,12 +420,31 @@ impl Rgba16x2Image {
         path1: P1,
         path2: P2,
     ) -> Result<Self, Box<dyn Error>> {
-        let image1 = Rgba16Image::load(path1)?;
-        let image2 = Rgba16Image::load(path2)?;
+        Self::load_with_conversion(path1, path2, ImageConversionPolicy::Strict)
+    }
+
+    pub fn load_with_conversion<P1: AsRef<Path>, P2: AsRef<Path>>(
+        path1: P1,
+        path2: P2,
+        policy: ImageConversionPolicy,
+    ) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba16Image::load_with_conversion(path1, policy)?;
+        let image2 = Rgba16Image::load_with_conversion(path2, policy)?;
         check_dimensions_match2(&image1, &image2)?;
         Ok(Self(image1, image2))
     }
 
+    pub fn decode(data1: &[u8], data2: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba16Image::decode(data1)?;
+        let image2 = Rgba16Image::decode(data2)?;
+        check_dimensions_match2(&image1, &image2)?;
+        Ok(Self(image1, image2))
+    }
+
+    pub fn encode(&self) -> Result<(ImageFileData, ImageFileData), ImageFileWriterSaveError> {
+        Ok((self.0.encode()?, self.1.encode()?))
+    }
+
     pub fn save_add_extension<P1: AsRef<Path>, P2: AsRef<Path>>(
         &self,
         path1_no_extension: P1,
@@ -424,12 +529,31 @@ impl Rgba16Rgba8Image {
         path1: P1,
         path2: P2,
     ) -> Result<Self, Box<dyn Error>> {
-        let image1 = Rgba16Image::load(path1)?;
-        let image2 = Rgba8Image::load(path2)?;
+        Self::load_with_conversion(path1, path2, ImageConversionPolicy::Strict)
+    }
+
+    pub fn load_with_conversion<P1: AsRef<Path>, P2: AsRef<Path>>(
+        path1: P1,
+        path2: P2,
+        policy: ImageConversionPolicy,
+    ) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba16Image::load_with_conversion(path1, policy)?;
+        let image2 = Rgba8Image::load_with_conversion(path2, policy)?;
+        check_dimensions_match2(&image1, &image2)?;
+        Ok(Self(image1, image2))
+    }
+
+    pub fn decode(data1: &[u8], data2: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba16Image::decode(data1)?;
+        let image2 = Rgba8Image::decode(data2)?;
         check_dimensions_match2(&image1, &image2)?;
         Ok(Self(image1, image2))
     }
 
+    pub fn encode(&self) -> Result<(ImageFileData, ImageFileData), ImageFileWriterSaveError> {
+        Ok((self.0.encode()?, self.1.encode()?))
+    }
+
     pub fn save_add_extension<P1: AsRef<Path>, P2: AsRef<Path>>(
         &self,
         path1_no_extension: P1,
@@ -513,13 +637,36 @@ impl Rgba16Rgba8x2Image {
         path2: P2,
         path3: P3,
     ) -> Result<Self, Box<dyn Error>> {
-        let image1 = Rgba16Image::load(path1)?;
-        let image2 = Rgba8Image::load(path2)?;
-        let image3 = Rgba8Image::load(path3)?;
+        Self::load_with_conversion(path1, path2, path3, ImageConversionPolicy::Strict)
+    }
+
+    pub fn load_with_conversion<P1: AsRef<Path>, P2: AsRef<Path>, P3: AsRef<Path>>(
+        path1: P1,
+        path2: P2,
+        path3: P3,
+        policy: ImageConversionPolicy,
+    ) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba16Image::load_with_conversion(path1, policy)?;
+        let image2 = Rgba8Image::load_with_conversion(path2, policy)?;
+        let image3 = Rgba8Image::load_with_conversion(path3, policy)?;
+        check_dimensions_match3(&image1, &image2, &image3)?;
+        Ok(Self(image1, image2, image3))
+    }
+
+    pub fn decode(data1: &[u8], data2: &[u8], data3: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let image1 = Rgba16Image::decode(data1)?;
+        let image2 = Rgba8Image::decode(data2)?;
+        let image3 = Rgba8Image::decode(data3)?;
         check_dimensions_match3(&image1, &image2, &image3)?;
         Ok(Self(image1, image2, image3))
     }
 
+    pub fn encode(
+        &self,
+    ) -> Result<(ImageFileData, ImageFileData, ImageFileData), ImageFileWriterSaveError> {
+        Ok((self.0.encode()?, self.1.encode()?, self.2.encode()?))
+    }
+
     pub fn save_add_extension<P1: AsRef<Path>, P2: AsRef<Path>, P3: AsRef<Path>>(
         &self,
         path1_no_extension: P1,
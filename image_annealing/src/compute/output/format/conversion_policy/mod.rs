@@ -0,0 +1,95 @@
+use super::{ImageFormat, ImageFormatError, Rgba16ImageBuffer, IN_MEMORY_IMAGE_NAME};
+use image::io::Reader as ImageReader;
+use image::{ColorType, DynamicImage};
+use std::error::Error;
+use std::path::Path;
+
+/// How images that are not already in the expected RGBA format are treated when loading
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ImageConversionPolicy {
+    /// Images must already be in the expected format
+    #[default]
+    Strict,
+    /// Images can be given an opaque alpha channel, be expanded from grayscale to RGB,
+    /// and have their channels widened from 8 to 16 bits
+    Lossless,
+    /// In addition to the conversions allowed by `Lossless`, image channels can be
+    /// narrowed, which discards the least significant bits of each channel
+    AllowNarrowing,
+}
+
+fn bytes_per_channel(color: ColorType) -> u8 {
+    color.bytes_per_pixel() / color.channel_count()
+}
+
+impl ImageConversionPolicy {
+    fn check(
+        &self,
+        image: &DynamicImage,
+        image_name: String,
+        expected_format: ImageFormat,
+        expected_color: ColorType,
+    ) -> Result<(), ImageFormatError> {
+        let color = image.color();
+        if color == expected_color {
+            return Ok(());
+        }
+        match self {
+            Self::Strict => Err(ImageFormatError::Unexpected {
+                image_name,
+                expected_format,
+            }),
+            Self::Lossless if bytes_per_channel(color) > bytes_per_channel(expected_color) => {
+                Err(ImageFormatError::Narrowing {
+                    image_name,
+                    expected_format,
+                })
+            }
+            Self::Lossless | Self::AllowNarrowing => Ok(()),
+        }
+    }
+
+    fn convert_rgba8(
+        &self,
+        image: DynamicImage,
+        image_name: String,
+    ) -> Result<image::RgbaImage, ImageFormatError> {
+        self.check(&image, image_name, ImageFormat::Rgba8, ColorType::Rgba8)?;
+        Ok(image.into_rgba8())
+    }
+
+    fn convert_rgba16(
+        &self,
+        image: DynamicImage,
+        image_name: String,
+    ) -> Result<Rgba16ImageBuffer, ImageFormatError> {
+        self.check(&image, image_name, ImageFormat::Rgba16, ColorType::Rgba16)?;
+        Ok(image.into_rgba16())
+    }
+
+    pub fn load_rgba8<P: AsRef<Path>>(&self, path: P) -> Result<image::RgbaImage, Box<dyn Error>> {
+        let image = ImageReader::open(&path)?.decode()?;
+        Ok(self.convert_rgba8(image, format!("{}", path.as_ref().display()))?)
+    }
+
+    pub fn load_rgba16<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Rgba16ImageBuffer, Box<dyn Error>> {
+        let image = ImageReader::open(&path)?.decode()?;
+        Ok(self.convert_rgba16(image, format!("{}", path.as_ref().display()))?)
+    }
+
+    pub fn decode_rgba8(&self, data: &[u8]) -> Result<image::RgbaImage, Box<dyn Error>> {
+        let image = image::load_from_memory(data)?;
+        Ok(self.convert_rgba8(image, String::from(IN_MEMORY_IMAGE_NAME))?)
+    }
+
+    pub fn decode_rgba16(&self, data: &[u8]) -> Result<Rgba16ImageBuffer, Box<dyn Error>> {
+        let image = image::load_from_memory(data)?;
+        Ok(self.convert_rgba16(image, String::from(IN_MEMORY_IMAGE_NAME))?)
+    }
+}
+
+#[cfg(test)]
+mod tests;
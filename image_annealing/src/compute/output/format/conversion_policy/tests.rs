@@ -0,0 +1,80 @@
+use super::super::ImageFormat;
+use super::ImageConversionPolicy;
+use image::{GrayImage, Luma, Rgb, RgbImage, Rgba};
+use std::error::Error;
+
+#[test]
+fn default() {
+    assert_eq!(
+        ImageConversionPolicy::default(),
+        ImageConversionPolicy::Strict
+    );
+}
+
+#[test]
+fn strict() {
+    let path = test_util::make_test_data_path(["image", "image", "red.png"]);
+    test_util::assert_error_contains(
+        ImageConversionPolicy::Strict.load_rgba8(path),
+        &format!("not the expected format of {}", ImageFormat::Rgba8),
+    );
+}
+
+#[test]
+fn expand_grayscale() -> Result<(), Box<dyn Error>> {
+    let path = test_util::make_test_output_path(["conversion_policy_expand_grayscale.png"]);
+    GrayImage::from_fn(2, 3, |x, y| Luma([(x + 2 * y).try_into().unwrap()])).save(&path)?;
+    let image = ImageConversionPolicy::Lossless.load_rgba8(&path)?;
+    assert_eq!(*image.get_pixel(1, 2), Rgba([5, 5, 5, u8::MAX]));
+    let image = ImageConversionPolicy::Lossless.load_rgba16(&path)?;
+    assert_eq!(
+        *image.get_pixel(1, 2),
+        Rgba([5 * 257, 5 * 257, 5 * 257, u16::MAX])
+    );
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn add_alpha() -> Result<(), Box<dyn Error>> {
+    let path = test_util::make_test_output_path(["conversion_policy_add_alpha.png"]);
+    RgbImage::from_pixel(2, 3, Rgb([1, 2, 3])).save(&path)?;
+    test_util::assert_error_contains(
+        ImageConversionPolicy::Strict.load_rgba8(&path),
+        &format!("not the expected format of {}", ImageFormat::Rgba8),
+    );
+    let image = ImageConversionPolicy::Lossless.load_rgba8(&path)?;
+    assert!(image
+        .pixels()
+        .all(|pixel| *pixel == Rgba([1, 2, 3, u8::MAX])));
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn widen() -> Result<(), Box<dyn Error>> {
+    let path = test_util::make_test_data_path(["image", "image", "stripes.png"]);
+    let expected = ImageConversionPolicy::Strict.load_rgba8(&path)?;
+    let image = ImageConversionPolicy::Lossless.load_rgba16(&path)?;
+    assert!(image
+        .pixels()
+        .zip(expected.pixels())
+        .all(|(pixel, expected_pixel)| pixel.0 == expected_pixel.0.map(|c| u16::from(c) * 257)));
+    Ok(())
+}
+
+#[test]
+fn narrow() -> Result<(), Box<dyn Error>> {
+    let path = test_util::make_test_data_path(["image", "image", "red.png"]);
+    test_util::assert_error_contains(
+        ImageConversionPolicy::Lossless.load_rgba8(&path),
+        "conversion by narrowing channels is not allowed",
+    );
+    let expected = ImageConversionPolicy::Strict.load_rgba16(&path)?;
+    let image = ImageConversionPolicy::AllowNarrowing.load_rgba8(&path)?;
+    assert!(image
+        .pixels()
+        .zip(expected.pixels())
+        .all(|(pixel, expected_pixel)| pixel.0 == expected_pixel.0.map(|c| (c >> 8) as u8)));
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use super::{
-    ImageFileReader, ImageFileWriter, ImageFileWriterSaveError, Rgba16Image, Rgba16Rgba8Image,
-    Rgba16Rgba8x2Image, Rgba16x2Image, Rgba8Image, Rgba8x2Image, Rgba8x3Image, Rgba8x4Image,
+    ImageConversionPolicy, ImageFileData, ImageFileReader, ImageFileWriter,
+    ImageFileWriterSaveError, ImageLayerFormat, LayeredImage, LayeredImageFormat,
+    LayeredImageFormatError, Rgba16Image, Rgba16Rgba8Image, Rgba16Rgba8x2Image, Rgba16x2Image,
+    Rgba8Image, Rgba8x2Image, Rgba8x3Image, Rgba8x4Image, RunMetadata,
 };
 use crate::{ImageDimensions, ImageDimensionsHolder};
 use std::error::Error;
@@ -17,6 +19,58 @@ pub enum ImageFormat {
     Rgba16x2,
     Rgba16Rgba8,
     Rgba16Rgba8x2,
+    Layered(LayeredImageFormat),
+}
+
+impl ImageFormat {
+    /// Returns the fixed format with the given layers, if there is one,
+    /// or a layered format otherwise
+    pub fn from_layers(layers: &[ImageLayerFormat]) -> Result<Self, LayeredImageFormatError> {
+        use ImageLayerFormat::{Rgba16, Rgba8};
+        Ok(match layers {
+            [Rgba8] => Self::Rgba8,
+            [Rgba8, Rgba8] => Self::Rgba8x2,
+            [Rgba8, Rgba8, Rgba8] => Self::Rgba8x3,
+            [Rgba8, Rgba8, Rgba8, Rgba8] => Self::Rgba8x4,
+            [Rgba16] => Self::Rgba16,
+            [Rgba16, Rgba16] => Self::Rgba16x2,
+            [Rgba16, Rgba8] => Self::Rgba16Rgba8,
+            [Rgba16, Rgba8, Rgba8] => Self::Rgba16Rgba8x2,
+            _ => Self::Layered(LayeredImageFormat::new(layers)?),
+        })
+    }
+
+    /// Returns the format of the images at the given paths, one image per layer,
+    /// based on the number of bits per channel of each image
+    pub fn detect<P: AsRef<Path>>(paths: &[P]) -> Result<Self, Box<dyn Error>> {
+        let layers = paths
+            .iter()
+            .map(ImageLayerFormat::detect)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_layers(&layers)?)
+    }
+
+    pub fn layers(&self) -> Vec<ImageLayerFormat> {
+        use ImageLayerFormat::{Rgba16, Rgba8};
+        match self {
+            Self::Rgba8 => vec![Rgba8],
+            Self::Rgba8x2 => vec![Rgba8; 2],
+            Self::Rgba8x3 => vec![Rgba8; 3],
+            Self::Rgba8x4 => vec![Rgba8; 4],
+            Self::Rgba16 => vec![Rgba16],
+            Self::Rgba16x2 => vec![Rgba16; 2],
+            Self::Rgba16Rgba8 => vec![Rgba16, Rgba8],
+            Self::Rgba16Rgba8x2 => vec![Rgba16, Rgba8, Rgba8],
+            Self::Layered(format) => format.layers().collect(),
+        }
+    }
+
+    pub fn texel_slice_count(&self) -> usize {
+        match self {
+            Self::Layered(format) => format.texel_slice_count(),
+            _ => 1,
+        }
+    }
 }
 
 impl fmt::Display for ImageFormat {
@@ -30,6 +84,7 @@ impl fmt::Display for ImageFormat {
             Self::Rgba16x2 => write!(f, "2 x 16-bit RGBA"),
             Self::Rgba16Rgba8 => write!(f, "16-bit RGBA + 8-bit RGBA"),
             Self::Rgba16Rgba8x2 => write!(f, "16-bit RGBA + 2 x 8-bit RGBA"),
+            Self::Layered(format) => write!(f, "{}", format),
         }
     }
 }
@@ -44,10 +99,21 @@ pub enum ImageFormatError {
     Missing {
         image_name: String,
     },
+    Narrowing {
+        image_name: String,
+        expected_format: ImageFormat,
+    },
     Unexpected {
         image_name: String,
         expected_format: ImageFormat,
     },
+    Unsliced {
+        image_name: String,
+        format: ImageFormat,
+    },
+    ResidentSlice {
+        image_name: String,
+    },
 }
 
 impl fmt::Display for ImageFormatError {
@@ -67,6 +133,14 @@ impl fmt::Display for ImageFormatError {
                 "no image format was provided for the {} image",
                 image_name
             ),
+            ImageFormatError::Narrowing {
+                image_name,
+                expected_format,
+            } => write!(
+                f,
+                "image {} has more bits per channel than the expected format of {}, and conversion by narrowing channels is not allowed",
+                image_name, expected_format
+            ),
             ImageFormatError::Unexpected {
                 image_name,
                 expected_format,
@@ -75,6 +149,16 @@ impl fmt::Display for ImageFormatError {
                 "actual format of image {} is not the expected format of {}",
                 image_name, expected_format
             ),
+            ImageFormatError::Unsliced { image_name, format } => write!(
+                f,
+                "the {} image has format {}, which does not fit in a single texture",
+                image_name, format
+            ),
+            ImageFormatError::ResidentSlice { image_name } => write!(
+                f,
+                "the {} image must be provided again, as only one texture-sized slice of the previous image remains after it was permuted one slice at a time",
+                image_name
+            ),
         }
     }
 }
@@ -91,27 +175,66 @@ pub enum LosslessImage {
     Rgba16x2(Rgba16x2Image),
     Rgba16Rgba8(Rgba16Rgba8Image),
     Rgba16Rgba8x2(Rgba16Rgba8x2Image),
+    Layered(LayeredImage),
 }
 
 impl LosslessImage {
     pub fn load<P: AsRef<Path>>(format: ImageFormat, paths: &[P]) -> Result<Self, Box<dyn Error>> {
+        Self::load_with_conversion(format, paths, ImageConversionPolicy::Strict)
+    }
+
+    pub fn load_with_conversion<P: AsRef<Path>>(
+        format: ImageFormat,
+        paths: &[P],
+        policy: ImageConversionPolicy,
+    ) -> Result<Self, Box<dyn Error>> {
         Ok(match format {
-            ImageFormat::Rgba8 => Self::Rgba8(Rgba8Image::load(&paths[0])?),
-            ImageFormat::Rgba8x2 => Self::Rgba8x2(Rgba8x2Image::load(&paths[0], &paths[1])?),
-            ImageFormat::Rgba8x3 => {
-                Self::Rgba8x3(Rgba8x3Image::load(&paths[0], &paths[1], &paths[2])?)
+            ImageFormat::Rgba8 => Self::Rgba8(Rgba8Image::load_with_conversion(&paths[0], policy)?),
+            ImageFormat::Rgba8x2 => Self::Rgba8x2(Rgba8x2Image::load_with_conversion(
+                &paths[0], &paths[1], policy,
+            )?),
+            ImageFormat::Rgba8x3 => Self::Rgba8x3(Rgba8x3Image::load_with_conversion(
+                &paths[0], &paths[1], &paths[2], policy,
+            )?),
+            ImageFormat::Rgba8x4 => Self::Rgba8x4(Rgba8x4Image::load_with_conversion(
+                &paths[0], &paths[1], &paths[2], &paths[3], policy,
+            )?),
+            ImageFormat::Rgba16 => {
+                Self::Rgba16(Rgba16Image::load_with_conversion(&paths[0], policy)?)
             }
-            ImageFormat::Rgba8x4 => Self::Rgba8x4(Rgba8x4Image::load(
-                &paths[0], &paths[1], &paths[2], &paths[3],
+            ImageFormat::Rgba16x2 => Self::Rgba16x2(Rgba16x2Image::load_with_conversion(
+                &paths[0], &paths[1], policy,
             )?),
-            ImageFormat::Rgba16 => Self::Rgba16(Rgba16Image::load(&paths[0])?),
-            ImageFormat::Rgba16x2 => Self::Rgba16x2(Rgba16x2Image::load(&paths[0], &paths[1])?),
+            ImageFormat::Rgba16Rgba8 => Self::Rgba16Rgba8(Rgba16Rgba8Image::load_with_conversion(
+                &paths[0], &paths[1], policy,
+            )?),
+            ImageFormat::Rgba16Rgba8x2 => Self::Rgba16Rgba8x2(
+                Rgba16Rgba8x2Image::load_with_conversion(&paths[0], &paths[1], &paths[2], policy)?,
+            ),
+            ImageFormat::Layered(format) => {
+                Self::Layered(LayeredImage::load_with_conversion(&format, paths, policy)?)
+            }
+        })
+    }
+
+    pub fn decode<D: AsRef<[u8]>>(format: ImageFormat, data: &[D]) -> Result<Self, Box<dyn Error>> {
+        let data: Vec<&[u8]> = data.iter().map(AsRef::as_ref).collect();
+        Ok(match format {
+            ImageFormat::Rgba8 => Self::Rgba8(Rgba8Image::decode(data[0])?),
+            ImageFormat::Rgba8x2 => Self::Rgba8x2(Rgba8x2Image::decode(data[0], data[1])?),
+            ImageFormat::Rgba8x3 => Self::Rgba8x3(Rgba8x3Image::decode(data[0], data[1], data[2])?),
+            ImageFormat::Rgba8x4 => {
+                Self::Rgba8x4(Rgba8x4Image::decode(data[0], data[1], data[2], data[3])?)
+            }
+            ImageFormat::Rgba16 => Self::Rgba16(Rgba16Image::decode(data[0])?),
+            ImageFormat::Rgba16x2 => Self::Rgba16x2(Rgba16x2Image::decode(data[0], data[1])?),
             ImageFormat::Rgba16Rgba8 => {
-                Self::Rgba16Rgba8(Rgba16Rgba8Image::load(&paths[0], &paths[1])?)
+                Self::Rgba16Rgba8(Rgba16Rgba8Image::decode(data[0], data[1])?)
             }
             ImageFormat::Rgba16Rgba8x2 => {
-                Self::Rgba16Rgba8x2(Rgba16Rgba8x2Image::load(&paths[0], &paths[1], &paths[2])?)
+                Self::Rgba16Rgba8x2(Rgba16Rgba8x2Image::decode(data[0], data[1], data[2])?)
             }
+            ImageFormat::Layered(format) => Self::Layered(LayeredImage::decode(&format, &data)?),
         })
     }
 
@@ -147,6 +270,64 @@ impl LosslessImage {
                 let result = image.save_add_extension(&paths[0], &paths[1], &paths[2])?;
                 vec![result.0, result.1, result.2]
             }
+            Self::Layered(image) => image.save_add_extension(paths)?,
+        })
+    }
+
+    /// Saves the image as for `save_add_extension`, with the metadata embedded in each file
+    pub fn save_add_extension_with_metadata<P: AsRef<Path>>(
+        &self,
+        paths: &[P],
+        metadata: &RunMetadata,
+    ) -> Result<Vec<PathBuf>, ImageFileWriterSaveError> {
+        let data = self.encode()?;
+        assert_eq!(paths.len(), data.len());
+        let mut output_paths = Vec::with_capacity(data.len());
+        for (layer_data, path_no_extension) in data.into_iter().zip(paths) {
+            // Each layer is saved as a PNG image, regardless of its bit depth
+            let path = Rgba8Image::make_filename(path_no_extension);
+            match metadata.write_embedded(layer_data, &path) {
+                Ok(()) => output_paths.push(path),
+                Err(err) => {
+                    for path in output_paths {
+                        std::fs::remove_file(path).unwrap();
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        Ok(output_paths)
+    }
+
+    pub fn encode(&self) -> Result<Vec<ImageFileData>, ImageFileWriterSaveError> {
+        Ok(match self {
+            Self::Rgba8(image) => vec![image.encode()?],
+            Self::Rgba8x2(image) => {
+                let result = image.encode()?;
+                vec![result.0, result.1]
+            }
+            Self::Rgba8x3(image) => {
+                let result = image.encode()?;
+                vec![result.0, result.1, result.2]
+            }
+            Self::Rgba8x4(image) => {
+                let result = image.encode()?;
+                vec![result.0, result.1, result.2, result.3]
+            }
+            Self::Rgba16(image) => vec![image.encode()?],
+            Self::Rgba16x2(image) => {
+                let result = image.encode()?;
+                vec![result.0, result.1]
+            }
+            Self::Rgba16Rgba8(image) => {
+                let result = image.encode()?;
+                vec![result.0, result.1]
+            }
+            Self::Rgba16Rgba8x2(image) => {
+                let result = image.encode()?;
+                vec![result.0, result.1, result.2]
+            }
+            Self::Layered(image) => image.encode()?,
         })
     }
 
@@ -160,6 +341,34 @@ impl LosslessImage {
             Self::Rgba16x2(_) => ImageFormat::Rgba16x2,
             Self::Rgba16Rgba8(_) => ImageFormat::Rgba16Rgba8,
             Self::Rgba16Rgba8x2(_) => ImageFormat::Rgba16Rgba8x2,
+            Self::Layered(image) => ImageFormat::Layered(image.format()),
+        }
+    }
+
+    pub(crate) fn into_texel_slices(self) -> Vec<Self> {
+        match self {
+            Self::Layered(image) => image
+                .into_texel_slices()
+                .into_iter()
+                .map(Self::Layered)
+                .collect(),
+            _ => vec![self],
+        }
+    }
+
+    pub(crate) fn from_texel_slices(mut slices: Vec<Self>) -> Self {
+        if slices.len() == 1 {
+            slices.pop().unwrap()
+        } else {
+            Self::Layered(LayeredImage::from_texel_slices(
+                slices
+                    .into_iter()
+                    .map(|slice| match slice {
+                        Self::Layered(image) => image,
+                        _ => unreachable!("only layered images can be split into texel slices"),
+                    })
+                    .collect(),
+            ))
         }
     }
 
@@ -173,6 +382,7 @@ impl LosslessImage {
             Self::Rgba16x2(image) => image.to_texture_data(),
             Self::Rgba16Rgba8(image) => image.to_texture_data(),
             Self::Rgba16Rgba8x2(image) => image.to_texture_data(),
+            Self::Layered(image) => image.to_texture_data(),
         }
     }
 
@@ -205,6 +415,9 @@ impl LosslessImage {
             ImageFormat::Rgba16Rgba8x2 => {
                 Self::Rgba16Rgba8x2(Rgba16Rgba8x2Image::from_texture_data(width, height, data))
             }
+            ImageFormat::Layered(format) => {
+                Self::Layered(LayeredImage::from_texture_data(format, width, height, data))
+            }
         }
     }
 }
@@ -220,6 +433,7 @@ impl ImageDimensionsHolder for LosslessImage {
             Self::Rgba16x2(image) => image.dimensions(),
             Self::Rgba16Rgba8(image) => image.dimensions(),
             Self::Rgba16Rgba8x2(image) => image.dimensions(),
+            Self::Layered(image) => image.dimensions(),
         }
     }
 }
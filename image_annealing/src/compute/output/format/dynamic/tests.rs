@@ -1,5 +1,6 @@
 mod image_format {
     mod display {
+        use super::super::super::super::{ImageLayerFormat, LayeredImageFormat};
         use super::super::super::ImageFormat;
 
         #[test]
@@ -49,6 +50,106 @@ mod image_format {
             let str = ImageFormat::Rgba16Rgba8x2.to_string();
             assert_eq!(str, "16-bit RGBA + 2 x 8-bit RGBA");
         }
+
+        #[test]
+        fn layered() {
+            let str = ImageFormat::Layered(
+                LayeredImageFormat::new(&[ImageLayerFormat::Rgba8, ImageLayerFormat::Rgba16])
+                    .unwrap(),
+            )
+            .to_string();
+            assert_eq!(str, "layered 8-bit RGBA + 16-bit RGBA");
+        }
+    }
+
+    mod from_layers {
+        use super::super::super::super::{ImageLayerFormat, LayeredImageFormat};
+        use super::super::super::ImageFormat;
+
+        #[test]
+        fn fixed() {
+            for format in [
+                ImageFormat::Rgba8,
+                ImageFormat::Rgba8x2,
+                ImageFormat::Rgba8x3,
+                ImageFormat::Rgba8x4,
+                ImageFormat::Rgba16,
+                ImageFormat::Rgba16x2,
+                ImageFormat::Rgba16Rgba8,
+                ImageFormat::Rgba16Rgba8x2,
+            ] {
+                assert_eq!(ImageFormat::from_layers(&format.layers()), Ok(format));
+            }
+        }
+
+        #[test]
+        fn layered() {
+            let layers = [ImageLayerFormat::Rgba8, ImageLayerFormat::Rgba16];
+            assert_eq!(
+                ImageFormat::from_layers(&layers),
+                Ok(ImageFormat::Layered(
+                    LayeredImageFormat::new(&layers).unwrap()
+                ))
+            );
+        }
+
+        #[test]
+        fn empty() {
+            test_util::assert_error_contains(
+                ImageFormat::from_layers(&[]),
+                "a layered image must have at least one layer",
+            );
+        }
+    }
+
+    mod detect {
+        use super::super::super::ImageFormat;
+        use std::error::Error;
+
+        #[test]
+        fn rgba16_rgba8() -> Result<(), Box<dyn Error>> {
+            assert_eq!(
+                ImageFormat::detect(&[
+                    test_util::make_test_data_path(["image", "image", "red.png"]),
+                    test_util::make_test_data_path(["image", "image", "stripes.png"]),
+                ])?,
+                ImageFormat::Rgba16Rgba8
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn missing_image() {
+            test_util::assert_error_contains(
+                ImageFormat::detect(&[test_util::make_test_data_path([
+                    "image",
+                    "image",
+                    "not_found.png",
+                ])]),
+                "No such file or directory",
+            );
+        }
+    }
+
+    mod texel_slice_count {
+        use super::super::super::super::{ImageLayerFormat, LayeredImageFormat};
+        use super::super::super::ImageFormat;
+
+        #[test]
+        fn fixed() {
+            assert_eq!(ImageFormat::Rgba16Rgba8x2.texel_slice_count(), 1);
+        }
+
+        #[test]
+        fn layered() {
+            assert_eq!(
+                ImageFormat::Layered(
+                    LayeredImageFormat::new(&[ImageLayerFormat::Rgba16; 3]).unwrap()
+                )
+                .texel_slice_count(),
+                2
+            );
+        }
     }
 }
 
@@ -168,7 +269,8 @@ mod lossless_image {
 
         mod success {
             use super::super::super::super::super::{
-                ImageFileReader, ImageFileWriter, Rgba16ImageBuffer, VectorFieldImageBuffer,
+                ImageFileReader, ImageFileWriter, Rgba16ImageBuffer, RunMetadata,
+                VectorFieldImageBuffer,
             };
             use super::super::super::super::{
                 ImageFormat, LosslessImage, Rgba16Image, Rgba16Rgba8Image, Rgba16Rgba8x2Image,
@@ -657,6 +759,50 @@ mod lossless_image {
                     .iter()
                     .try_for_each(std::fs::remove_file)?)
             }
+
+            #[test]
+            fn with_metadata() -> Result<(), Box<dyn Error>> {
+                let DimensionsAndRgba16Buffer { image: image1, .. } =
+                    test_util::image::linear_indices_with_bias_to_colors(0);
+                let DimensionsAndRgba8Buffer { image: image2, .. } =
+                    test_util::image::linear_indices_with_bias_to_colors(
+                        *image1.last().unwrap() + 1,
+                    );
+                let output_image = LosslessImage::Rgba16Rgba8(Rgba16Rgba8Image::new(
+                    image1.clone(),
+                    image2.clone(),
+                )?);
+                let metadata = RunMetadata::new("Permute");
+
+                let paths = [
+                    test_util::make_test_output_path_string([
+                        "compute_output_format_dynamic_io_with_metadata_1",
+                    ]),
+                    test_util::make_test_output_path_string([
+                        "compute_output_format_dynamic_io_with_metadata_2",
+                    ]),
+                ];
+                let expected_output_paths = [
+                    Rgba16ImageBuffer::make_filename(&paths[0]),
+                    VectorFieldImageBuffer::make_filename(&paths[1]),
+                ];
+                super::assert_not_files(&expected_output_paths);
+
+                let full_output_paths =
+                    output_image.save_add_extension_with_metadata(&paths, &metadata)?;
+                assert_eq!(full_output_paths, expected_output_paths);
+
+                for path in full_output_paths.iter() {
+                    assert_eq!(RunMetadata::read(path)?, Some(metadata.clone()));
+                }
+                let input_image =
+                    LosslessImage::load(ImageFormat::Rgba16Rgba8, &full_output_paths)?;
+                assert_eq!(input_image, output_image);
+
+                Ok(expected_output_paths
+                    .iter()
+                    .try_for_each(std::fs::remove_file)?)
+            }
         }
 
         mod first_image_missing {
@@ -1064,6 +1210,54 @@ mod lossless_image {
             }
         }
 
+        mod conversion {
+            use super::super::super::super::super::ImageConversionPolicy;
+            use super::super::super::super::{ImageFormat, LosslessImage};
+            use std::error::Error;
+
+            #[test]
+            fn strict() {
+                test_util::assert_error_contains(
+                    LosslessImage::load_with_conversion(
+                        ImageFormat::Rgba16Rgba8,
+                        &[super::existing_rgba8_path(), super::existing_rgba8_path()],
+                        ImageConversionPolicy::Strict,
+                    ),
+                    "is not the expected format of 16-bit RGBA",
+                );
+            }
+
+            #[test]
+            fn lossless() -> Result<(), Box<dyn Error>> {
+                let image = LosslessImage::load_with_conversion(
+                    ImageFormat::Rgba16Rgba8,
+                    &[super::existing_rgba8_path(), super::existing_rgba8_path()],
+                    ImageConversionPolicy::Lossless,
+                )?;
+                assert_eq!(image.format(), ImageFormat::Rgba16Rgba8);
+                test_util::assert_error_contains(
+                    LosslessImage::load_with_conversion(
+                        ImageFormat::Rgba16Rgba8,
+                        &[super::existing_rgba16_path(), super::existing_rgba16_path()],
+                        ImageConversionPolicy::Lossless,
+                    ),
+                    "has more bits per channel than the expected format of 8-bit RGBA",
+                );
+                Ok(())
+            }
+
+            #[test]
+            fn allow_narrowing() -> Result<(), Box<dyn Error>> {
+                let image = LosslessImage::load_with_conversion(
+                    ImageFormat::Rgba16Rgba8,
+                    &[super::existing_rgba16_path(), super::existing_rgba16_path()],
+                    ImageConversionPolicy::AllowNarrowing,
+                )?;
+                assert_eq!(image.format(), ImageFormat::Rgba16Rgba8);
+                Ok(())
+            }
+        }
+
         mod first_image_save_error {
             #[test]
             fn rgba8() {
@@ -1451,6 +1645,48 @@ mod lossless_image {
         }
     }
 
+    mod encode_decode {
+        use super::super::super::super::{
+            ImageFormat, ImageLayerFormat, LayeredImageFormat, LayeredImageFormatError,
+        };
+        use super::super::super::LosslessImage;
+        use std::error::Error;
+
+        #[test]
+        fn round_trip() -> Result<(), Box<dyn Error>> {
+            for image in [
+                super::valid_rgba8(),
+                super::valid_rgba8x2(),
+                super::valid_rgba8x3(),
+                super::valid_rgba8x4(),
+                super::valid_rgba16(),
+                super::valid_rgba16x2(),
+                super::valid_rgba16_rgba8(),
+                super::valid_rgba16_rgba8x2(),
+            ] {
+                let data = image.encode()?;
+                assert_eq!(LosslessImage::decode(image.format(), &data)?, image);
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn layered_data_count_mismatch() -> Result<(), Box<dyn Error>> {
+            let image = super::valid_rgba8x2();
+            let format =
+                ImageFormat::Layered(LayeredImageFormat::new(&[ImageLayerFormat::Rgba8; 3])?);
+            test_util::assert_error_contains(
+                LosslessImage::decode(format, &image.encode()?),
+                &LayeredImageFormatError::DataCountMismatch {
+                    layer_count: 3,
+                    data_count: 2,
+                }
+                .to_string(),
+            );
+            Ok(())
+        }
+    }
+
     mod format {
         use super::super::super::ImageFormat;
 
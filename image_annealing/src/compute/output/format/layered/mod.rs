@@ -0,0 +1,485 @@
+use super::super::super::resource::texture::{LosslessImageTexture, TextureDatatype};
+use super::{
+    ImageConversionPolicy, ImageFileData, ImageFileReader, ImageFileWriter,
+    ImageFileWriterEncodeResult, ImageFileWriterSaveError, ImageFileWriterSaveResult, Rgba16Image,
+    Rgba16ImageBuffer, Rgba16ImageBufferComponent, Rgba8Image,
+};
+use crate::image_utils::check_dimensions_match2;
+use crate::{ImageDimensions, ImageDimensionsHolder};
+use image::codecs::png::PngDecoder;
+use image::io::Reader as ImageReader;
+use image::{ColorType, ImageDecoder};
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ImageLayerFormat {
+    Rgba8,
+    Rgba16,
+}
+
+impl ImageLayerFormat {
+    pub fn component_size(&self) -> usize {
+        match self {
+            Self::Rgba8 => 1,
+            Self::Rgba16 => 2,
+        }
+    }
+
+    /// Returns the format with the same number of bits per channel as the image at the given path
+    ///
+    /// The image is not required to have an alpha channel or three color channels,
+    /// as such images can still be loaded under a lenient [`ImageConversionPolicy`].
+    pub fn detect<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let reader = ImageReader::open(&path)?.with_guessed_format()?;
+        // Only the header of a PNG image is read. Images in other formats are decoded in full,
+        // because `ImageReader` does not expose its decoders.
+        let color = if reader.format() == Some(image::ImageFormat::Png) {
+            PngDecoder::new(reader.into_inner())?.color_type()
+        } else {
+            reader.decode()?.color()
+        };
+        match color.bytes_per_pixel() / color.channel_count() {
+            1 => Ok(Self::Rgba8),
+            2 => Ok(Self::Rgba16),
+            _ => Err(Box::new(UnsupportedColorTypeError {
+                image_name: format!("{}", path.as_ref().display()),
+                color,
+            })),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnsupportedColorTypeError {
+    image_name: String,
+    color: ColorType,
+}
+
+impl fmt::Display for UnsupportedColorTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "image {} has color type {:?}, which has neither 8 nor 16 bits per channel, so its format cannot be detected",
+            self.image_name, self.color
+        )
+    }
+}
+
+impl Error for UnsupportedColorTypeError {}
+
+impl fmt::Display for ImageLayerFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Rgba8 => write!(f, "8-bit RGBA"),
+            Self::Rgba16 => write!(f, "16-bit RGBA"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LayeredImageFormatError {
+    Empty,
+    TooManyLayers(usize),
+    PathCountMismatch {
+        layer_count: usize,
+        path_count: usize,
+    },
+    DataCountMismatch {
+        layer_count: usize,
+        data_count: usize,
+    },
+}
+
+impl fmt::Display for LayeredImageFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "a layered image must have at least one layer"),
+            Self::TooManyLayers(layer_count) => write!(
+                f,
+                "a layered image can have at most {} layers, but {} layers were provided",
+                LayeredImageFormat::MAX_LAYERS,
+                layer_count
+            ),
+            Self::PathCountMismatch {
+                layer_count,
+                path_count,
+            } => write!(
+                f,
+                "a layered image with {} layers cannot be loaded from {} paths",
+                layer_count, path_count
+            ),
+            Self::DataCountMismatch {
+                layer_count,
+                data_count,
+            } => write!(
+                f,
+                "a layered image with {} layers cannot be decoded from {} buffers",
+                layer_count, data_count
+            ),
+        }
+    }
+}
+
+impl Error for LayeredImageFormatError {}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct LayeredImageFormat {
+    layer_count: u8,
+    rgba16_layers: u64,
+}
+
+impl LayeredImageFormat {
+    pub const MAX_LAYERS: usize = u64::BITS as usize;
+
+    pub fn new(layers: &[ImageLayerFormat]) -> Result<Self, LayeredImageFormatError> {
+        if layers.is_empty() {
+            Err(LayeredImageFormatError::Empty)
+        } else if layers.len() > Self::MAX_LAYERS {
+            Err(LayeredImageFormatError::TooManyLayers(layers.len()))
+        } else {
+            Ok(Self {
+                layer_count: layers.len().try_into().unwrap(),
+                rgba16_layers: layers
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, layer)| **layer == ImageLayerFormat::Rgba16)
+                    .fold(0u64, |mask, (i, _)| mask | (1 << i)),
+            })
+        }
+    }
+
+    pub fn layer_count(&self) -> usize {
+        self.layer_count.into()
+    }
+
+    pub fn layer(&self, index: usize) -> Option<ImageLayerFormat> {
+        if index < self.layer_count() {
+            Some(if (self.rgba16_layers & (1 << index)) == 0 {
+                ImageLayerFormat::Rgba8
+            } else {
+                ImageLayerFormat::Rgba16
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn layers(&self) -> impl Iterator<Item = ImageLayerFormat> {
+        let format = *self;
+        (0..format.layer_count()).map(move |i| format.layer(i).unwrap())
+    }
+
+    pub fn texel_slice_count(&self) -> usize {
+        self.texel_slices().len()
+    }
+
+    // Groups consecutive layers into slices that each fit in one texel of a lossless image texture
+    pub(crate) fn texel_slices(&self) -> Vec<Range<usize>> {
+        let mut slices = Vec::new();
+        let mut start = 0;
+        let mut size = 0;
+        for (i, layer) in self.layers().enumerate() {
+            if size + layer.component_size()
+                > <LosslessImageTexture as TextureDatatype>::COMPONENT_SIZE
+            {
+                slices.push(start..i);
+                start = i;
+                size = 0;
+            }
+            size += layer.component_size();
+        }
+        slices.push(start..self.layer_count());
+        slices
+    }
+}
+
+impl fmt::Display for LayeredImageFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "layered ")?;
+        for (i, layer) in self.layers().enumerate() {
+            if i > 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "{}", layer)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ImageLayer {
+    Rgba8(Rgba8Image),
+    Rgba16(Rgba16Image),
+}
+
+impl ImageLayer {
+    pub fn load<P: AsRef<Path>>(format: ImageLayerFormat, path: P) -> Result<Self, Box<dyn Error>> {
+        Self::load_with_conversion(format, path, ImageConversionPolicy::Strict)
+    }
+
+    pub fn load_with_conversion<P: AsRef<Path>>(
+        format: ImageLayerFormat,
+        path: P,
+        policy: ImageConversionPolicy,
+    ) -> Result<Self, Box<dyn Error>> {
+        Ok(match format {
+            ImageLayerFormat::Rgba8 => Self::Rgba8(Rgba8Image::load_with_conversion(path, policy)?),
+            ImageLayerFormat::Rgba16 => {
+                Self::Rgba16(Rgba16Image::load_with_conversion(path, policy)?)
+            }
+        })
+    }
+
+    pub fn decode(format: ImageLayerFormat, data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Ok(match format {
+            ImageLayerFormat::Rgba8 => Self::Rgba8(Rgba8Image::decode(data)?),
+            ImageLayerFormat::Rgba16 => Self::Rgba16(Rgba16Image::decode(data)?),
+        })
+    }
+
+    pub fn save_add_extension<P: AsRef<Path>>(
+        &self,
+        path_no_extension: P,
+    ) -> ImageFileWriterSaveResult {
+        match self {
+            Self::Rgba8(image) => image.save_add_extension(path_no_extension),
+            Self::Rgba16(image) => image.save_add_extension(path_no_extension),
+        }
+    }
+
+    pub fn encode(&self) -> ImageFileWriterEncodeResult {
+        match self {
+            Self::Rgba8(image) => image.encode(),
+            Self::Rgba16(image) => image.encode(),
+        }
+    }
+
+    pub fn format(&self) -> ImageLayerFormat {
+        match self {
+            Self::Rgba8(_) => ImageLayerFormat::Rgba8,
+            Self::Rgba16(_) => ImageLayerFormat::Rgba16,
+        }
+    }
+}
+
+impl ImageDimensionsHolder for ImageLayer {
+    fn dimensions(&self) -> &ImageDimensions {
+        match self {
+            Self::Rgba8(image) => image.dimensions(),
+            Self::Rgba16(image) => image.dimensions(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LayeredImage {
+    format: LayeredImageFormat,
+    layers: Vec<ImageLayer>,
+}
+
+impl LayeredImage {
+    pub fn new(layers: Vec<ImageLayer>) -> Result<Self, Box<dyn Error>> {
+        let format = LayeredImageFormat::new(
+            layers
+                .iter()
+                .map(ImageLayer::format)
+                .collect::<Vec<_>>()
+                .as_slice(),
+        )?;
+        for layer in layers.iter().skip(1) {
+            check_dimensions_match2(&layers[0], layer)?;
+        }
+        Ok(Self { format, layers })
+    }
+
+    pub fn load<P: AsRef<Path>>(
+        format: &LayeredImageFormat,
+        paths: &[P],
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::load_with_conversion(format, paths, ImageConversionPolicy::Strict)
+    }
+
+    pub fn load_with_conversion<P: AsRef<Path>>(
+        format: &LayeredImageFormat,
+        paths: &[P],
+        policy: ImageConversionPolicy,
+    ) -> Result<Self, Box<dyn Error>> {
+        if paths.len() != format.layer_count() {
+            return Err(Box::new(LayeredImageFormatError::PathCountMismatch {
+                layer_count: format.layer_count(),
+                path_count: paths.len(),
+            }));
+        }
+        Self::new(
+            format
+                .layers()
+                .zip(paths)
+                .map(|(layer_format, path)| {
+                    ImageLayer::load_with_conversion(layer_format, path, policy)
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+    }
+
+    pub fn decode<D: AsRef<[u8]>>(
+        format: &LayeredImageFormat,
+        data: &[D],
+    ) -> Result<Self, Box<dyn Error>> {
+        if data.len() != format.layer_count() {
+            return Err(Box::new(LayeredImageFormatError::DataCountMismatch {
+                layer_count: format.layer_count(),
+                data_count: data.len(),
+            }));
+        }
+        Self::new(
+            format
+                .layers()
+                .zip(data)
+                .map(|(layer_format, layer_data)| {
+                    ImageLayer::decode(layer_format, layer_data.as_ref())
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+    }
+
+    pub fn save_add_extension<P: AsRef<Path>>(
+        &self,
+        paths_no_extension: &[P],
+    ) -> Result<Vec<PathBuf>, ImageFileWriterSaveError> {
+        assert_eq!(paths_no_extension.len(), self.layers.len());
+        let mut output_paths = Vec::with_capacity(self.layers.len());
+        for (layer, path_no_extension) in self.layers.iter().zip(paths_no_extension) {
+            match layer.save_add_extension(path_no_extension) {
+                Ok(path) => output_paths.push(path),
+                Err(err) => {
+                    for path in output_paths {
+                        std::fs::remove_file(path).unwrap();
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        Ok(output_paths)
+    }
+
+    pub fn encode(&self) -> Result<Vec<ImageFileData>, ImageFileWriterSaveError> {
+        self.layers.iter().map(ImageLayer::encode).collect()
+    }
+
+    pub fn format(&self) -> LayeredImageFormat {
+        self.format
+    }
+
+    pub fn layers(&self) -> &[ImageLayer] {
+        &self.layers
+    }
+
+    pub fn into_layers(self) -> Vec<ImageLayer> {
+        self.layers
+    }
+
+    pub(crate) fn to_texture_data(&self) -> Vec<u8> {
+        let component_size = <LosslessImageTexture as TextureDatatype>::COMPONENT_SIZE;
+        debug_assert_eq!(self.format.texel_slice_count(), 1);
+        let dimensions = self.dimensions();
+        let mut data = vec![
+            0u8;
+            dimensions.count()
+                * <LosslessImageTexture as TextureDatatype>::N_COMPONENTS
+                * component_size
+        ];
+        let mut offset = 0;
+        for layer in self.layers.iter() {
+            let texels = data.chunks_exact_mut(component_size);
+            match layer {
+                ImageLayer::Rgba8(image) => {
+                    for (texel, &component) in texels.zip(image.as_raw_iter()) {
+                        texel[offset] = component;
+                    }
+                }
+                ImageLayer::Rgba16(image) => {
+                    for (texel, &component) in texels.zip(image.as_raw_iter()) {
+                        texel[offset..offset + 2].copy_from_slice(&component.to_ne_bytes());
+                    }
+                }
+            }
+            offset += layer.format().component_size();
+        }
+        data
+    }
+
+    pub(crate) fn from_texture_data(
+        format: LayeredImageFormat,
+        width: u32,
+        height: u32,
+        data: Vec<u8>,
+    ) -> Self {
+        let component_size = <LosslessImageTexture as TextureDatatype>::COMPONENT_SIZE;
+        let mut offset = 0;
+        let layers = format
+            .layers()
+            .map(|layer_format| {
+                let texels = data.as_slice().chunks_exact(component_size);
+                let layer = match layer_format {
+                    ImageLayerFormat::Rgba8 => ImageLayer::Rgba8(
+                        Rgba8Image::new(
+                            image::RgbaImage::from_vec(
+                                width,
+                                height,
+                                texels.map(|texel| texel[offset]).collect(),
+                            )
+                            .unwrap(),
+                        )
+                        .unwrap(),
+                    ),
+                    ImageLayerFormat::Rgba16 => ImageLayer::Rgba16(
+                        Rgba16Image::new(
+                            Rgba16ImageBuffer::from_vec(
+                                width,
+                                height,
+                                texels
+                                    .map(|texel| {
+                                        Rgba16ImageBufferComponent::from_ne_bytes([
+                                            texel[offset],
+                                            texel[offset + 1],
+                                        ])
+                                    })
+                                    .collect(),
+                            )
+                            .unwrap(),
+                        )
+                        .unwrap(),
+                    ),
+                };
+                offset += layer_format.component_size();
+                layer
+            })
+            .collect();
+        Self::new(layers).unwrap()
+    }
+
+    pub(crate) fn into_texel_slices(self) -> Vec<Self> {
+        let mut layers = self.layers.into_iter();
+        self.format
+            .texel_slices()
+            .into_iter()
+            .map(|slice| Self::new(layers.by_ref().take(slice.len()).collect()).unwrap())
+            .collect()
+    }
+
+    pub(crate) fn from_texel_slices(slices: Vec<Self>) -> Self {
+        Self::new(slices.into_iter().flat_map(Self::into_layers).collect()).unwrap()
+    }
+}
+
+impl ImageDimensionsHolder for LayeredImage {
+    fn dimensions(&self) -> &ImageDimensions {
+        self.layers[0].dimensions()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,246 @@
+mod image_layer_format {
+    use super::super::ImageLayerFormat;
+    use image::{GrayImage, Luma};
+    use std::error::Error;
+
+    #[test]
+    fn detect_rgba8() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_data_path(["image", "image", "stripes.png"]);
+        assert_eq!(ImageLayerFormat::detect(path)?, ImageLayerFormat::Rgba8);
+        Ok(())
+    }
+
+    #[test]
+    fn detect_rgba16() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_data_path(["image", "image", "red.png"]);
+        assert_eq!(ImageLayerFormat::detect(path)?, ImageLayerFormat::Rgba16);
+        Ok(())
+    }
+
+    #[test]
+    fn detect_grayscale() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_output_path(["layered_detect_grayscale.png"]);
+        GrayImage::from_pixel(2, 3, Luma([1])).save(&path)?;
+        let result = ImageLayerFormat::detect(&path);
+        std::fs::remove_file(path)?;
+        assert_eq!(result?, ImageLayerFormat::Rgba8);
+        Ok(())
+    }
+}
+
+mod layered_image_format {
+    use super::super::{ImageLayerFormat, LayeredImageFormat, LayeredImageFormatError};
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            LayeredImageFormat::new(&[]),
+            Err(LayeredImageFormatError::Empty)
+        );
+    }
+
+    #[test]
+    fn too_many_layers() {
+        let layers = vec![ImageLayerFormat::Rgba8; LayeredImageFormat::MAX_LAYERS + 1];
+        test_util::assert_error_contains(
+            LayeredImageFormat::new(&layers),
+            "a layered image can have at most 64 layers, but 65 layers were provided",
+        );
+    }
+
+    #[test]
+    fn max_layers() {
+        let mut layers = vec![ImageLayerFormat::Rgba8; LayeredImageFormat::MAX_LAYERS];
+        layers[LayeredImageFormat::MAX_LAYERS - 1] = ImageLayerFormat::Rgba16;
+        let format = LayeredImageFormat::new(&layers).unwrap();
+        assert_eq!(format.layer_count(), LayeredImageFormat::MAX_LAYERS);
+        assert!(format.layers().eq(layers.into_iter()));
+    }
+
+    #[test]
+    fn layer() {
+        let format =
+            LayeredImageFormat::new(&[ImageLayerFormat::Rgba16, ImageLayerFormat::Rgba8]).unwrap();
+        assert_eq!(format.layer_count(), 2);
+        assert_eq!(format.layer(0), Some(ImageLayerFormat::Rgba16));
+        assert_eq!(format.layer(1), Some(ImageLayerFormat::Rgba8));
+        assert_eq!(format.layer(2), None);
+    }
+
+    #[test]
+    fn display() {
+        let format = LayeredImageFormat::new(&[
+            ImageLayerFormat::Rgba16,
+            ImageLayerFormat::Rgba8,
+            ImageLayerFormat::Rgba8,
+        ])
+        .unwrap();
+        assert_eq!(
+            format.to_string(),
+            "layered 16-bit RGBA + 8-bit RGBA + 8-bit RGBA"
+        );
+    }
+
+    mod texel_slices {
+        use super::super::super::{ImageLayerFormat, LayeredImageFormat};
+
+        #[test]
+        fn single_slice() {
+            let format = LayeredImageFormat::new(&[
+                ImageLayerFormat::Rgba8,
+                ImageLayerFormat::Rgba16,
+                ImageLayerFormat::Rgba8,
+            ])
+            .unwrap();
+            assert_eq!(format.texel_slices(), vec![0..3]);
+            assert_eq!(format.texel_slice_count(), 1);
+        }
+
+        #[test]
+        fn multiple_slices() {
+            let format = LayeredImageFormat::new(&[
+                ImageLayerFormat::Rgba8,
+                ImageLayerFormat::Rgba8,
+                ImageLayerFormat::Rgba16,
+                ImageLayerFormat::Rgba8,
+                ImageLayerFormat::Rgba16,
+                ImageLayerFormat::Rgba16,
+                ImageLayerFormat::Rgba8,
+            ])
+            .unwrap();
+            assert_eq!(format.texel_slices(), vec![0..3, 3..5, 5..7]);
+            assert_eq!(format.texel_slice_count(), 3);
+        }
+    }
+}
+
+mod layered_image {
+    use super::super::super::{Rgba16Image, Rgba16ImageBuffer, Rgba8Image};
+    use super::super::{ImageLayer, ImageLayerFormat, LayeredImage, LayeredImageFormat};
+    use image::Rgba;
+    use std::error::Error;
+    use test_util::image::{DimensionsAndRgba16Buffer, DimensionsAndRgba8Buffer};
+
+    fn make_layers() -> Vec<ImageLayer> {
+        let DimensionsAndRgba16Buffer { image: image1, .. } =
+            test_util::image::linear_indices_with_bias_to_colors(0);
+        let DimensionsAndRgba8Buffer { image: image2, .. } =
+            test_util::image::linear_indices_with_bias_to_colors(*image1.last().unwrap() + 1);
+        let DimensionsAndRgba16Buffer { image: image3, .. } =
+            test_util::image::linear_indices_with_bias_to_colors(*image2.last().unwrap() + 1);
+        let DimensionsAndRgba8Buffer { image: image4, .. } =
+            test_util::image::linear_indices_with_bias_to_colors(*image3.last().unwrap() + 1);
+        vec![
+            ImageLayer::Rgba16(Rgba16Image::new(image1).unwrap()),
+            ImageLayer::Rgba8(Rgba8Image::new(image2).unwrap()),
+            ImageLayer::Rgba16(Rgba16Image::new(image3).unwrap()),
+            ImageLayer::Rgba8(Rgba8Image::new(image4).unwrap()),
+        ]
+    }
+
+    #[test]
+    fn empty() {
+        test_util::assert_error_contains(
+            LayeredImage::new(Vec::new()),
+            "a layered image must have at least one layer",
+        );
+    }
+
+    #[test]
+    fn dimensions_mismatch() {
+        test_util::assert_error_contains(
+            LayeredImage::new(vec![
+                ImageLayer::Rgba8(
+                    Rgba8Image::new(image::RgbaImage::from_pixel(2, 1, Rgba([0, 0, 0, 0])))
+                        .unwrap(),
+                ),
+                ImageLayer::Rgba16(
+                    Rgba16Image::new(Rgba16ImageBuffer::from_pixel(2, 3, Rgba([0, 0, 0, 0])))
+                        .unwrap(),
+                ),
+            ]),
+            "mismatch in image dimensions, (width, height) = (2, 1) and (width, height) = (2, 3)",
+        );
+    }
+
+    #[test]
+    fn format() -> Result<(), Box<dyn Error>> {
+        let image = LayeredImage::new(make_layers())?;
+        assert_eq!(
+            image.format(),
+            LayeredImageFormat::new(&[
+                ImageLayerFormat::Rgba16,
+                ImageLayerFormat::Rgba8,
+                ImageLayerFormat::Rgba16,
+                ImageLayerFormat::Rgba8,
+            ])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn texture_data_round_trip() -> Result<(), Box<dyn Error>> {
+        let mut layers = make_layers();
+        layers.truncate(2);
+        let image = LayeredImage::new(layers)?;
+        let data = image.to_texture_data();
+        assert_eq!(data.len(), 2 * 3 * 4 * 4);
+        assert_eq!(
+            LayeredImage::from_texture_data(image.format(), 2, 3, data),
+            image
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn texel_slices_round_trip() -> Result<(), Box<dyn Error>> {
+        let image = LayeredImage::new(make_layers())?;
+        let slices = image.clone().into_texel_slices();
+        assert_eq!(slices.len(), 2);
+        assert!(slices
+            .iter()
+            .all(|slice| slice.format().texel_slice_count() == 1));
+        assert_eq!(LayeredImage::from_texel_slices(slices), image);
+        Ok(())
+    }
+
+    #[test]
+    fn io() -> Result<(), Box<dyn Error>> {
+        let image = LayeredImage::new(make_layers())?;
+        let paths: Vec<String> = (1..=4)
+            .map(|i| {
+                test_util::make_test_output_path_string([format!(
+                    "compute_output_format_layered_io_{}",
+                    i
+                )])
+            })
+            .collect();
+        let output_paths = image.save_add_extension(&paths)?;
+        assert!(output_paths.iter().all(|path| path.is_file()));
+
+        let read_image = LayeredImage::load(&image.format(), &output_paths)?;
+        assert_eq!(read_image, image);
+
+        for path in output_paths {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn load_path_count_mismatch() {
+        let format =
+            LayeredImageFormat::new(&[ImageLayerFormat::Rgba8, ImageLayerFormat::Rgba8]).unwrap();
+        test_util::assert_error_contains(
+            LayeredImage::load(
+                &format,
+                &[test_util::make_test_data_path([
+                    "image",
+                    "image",
+                    "stripes.png",
+                ])],
+            ),
+            "a layered image with 2 layers cannot be loaded from 1 paths",
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use super::{ImageFileData, ImageFileWriterSaveError};
+use image::error::{EncodingError, ImageFormatHint};
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+const CHUNK_TYPE: [u8; 4] = *b"iTXt";
+/// The length of the PNG signature plus the IHDR chunk, which must be the first chunk
+const HEADER_LENGTH: usize = 8 + 4 + 4 + 13 + 4;
+const KEYWORD_PREFIX: &str = "image_annealing:";
+
+const OPERATION: &str = "operation";
+const VERSION: &str = "version";
+const ROUND_INDEX: &str = "round_index";
+const PASS_INDEX: &str = "pass_index";
+const PASS: &str = "pass";
+const THRESHOLD: &str = "threshold";
+const SEED: &str = "seed";
+const INPUT_HASH: &str = "input_hash";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RunMetadataError {
+    NotPng,
+    Truncated,
+    InvalidField { keyword: String, text: String },
+    MissingField(&'static str),
+}
+
+impl fmt::Display for RunMetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotPng => write!(f, "data is not a PNG image"),
+            Self::Truncated => write!(f, "PNG data is truncated"),
+            Self::InvalidField { keyword, text } => {
+                write!(
+                    f,
+                    "invalid value '{}' for metadata field '{}'",
+                    text, keyword
+                )
+            }
+            Self::MissingField(keyword) => {
+                write!(f, "metadata field '{}' is missing", keyword)
+            }
+        }
+    }
+}
+
+impl Error for RunMetadataError {}
+
+/// A digest of an input file, identifying the file by its contents
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InputFileHash {
+    pub path: String,
+    /// Hexadecimal SHA-256 digest
+    pub sha256: String,
+}
+
+/// A description of how an output image was created, for reconstructing its provenance
+/// after the image has been renamed or moved
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunMetadata {
+    pub operation: String,
+    pub version: String,
+    pub round_index: Option<usize>,
+    pub pass_index: Option<usize>,
+    pub pass: Option<String>,
+    pub threshold: Option<f32>,
+    /// The seed of the random number generator, for operations that use one,
+    /// such as an experiment that samples its runs randomly
+    pub seed: Option<u64>,
+    pub input_hashes: Vec<InputFileHash>,
+}
+
+fn append_chunk(data: &mut ImageFileData, keyword: &str, text: &str) {
+    let mut body = Vec::with_capacity(KEYWORD_PREFIX.len() + keyword.len() + text.len() + 5);
+    body.extend_from_slice(KEYWORD_PREFIX.as_bytes());
+    body.extend_from_slice(keyword.as_bytes());
+    // Null separator, uncompressed, compression method, empty language tag,
+    // and empty translated keyword
+    body.extend_from_slice(&[0, 0, 0, 0, 0]);
+    body.extend_from_slice(text.as_bytes());
+
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(&CHUNK_TYPE);
+    hasher.update(&body);
+    data.extend_from_slice(&u32::try_from(body.len()).unwrap().to_be_bytes());
+    data.extend_from_slice(&CHUNK_TYPE);
+    data.extend_from_slice(&body);
+    data.extend_from_slice(&hasher.finalize().to_be_bytes());
+}
+
+/// Returns the keywords and text of uncompressed iTXt chunks with the metadata keyword prefix
+fn read_chunks(data: &[u8]) -> Result<Vec<(String, String)>, RunMetadataError> {
+    if !data.starts_with(&PNG_SIGNATURE) {
+        return Err(RunMetadataError::NotPng);
+    }
+    let mut chunks = Vec::new();
+    let mut offset = PNG_SIGNATURE.len();
+    while offset < data.len() {
+        let header = data
+            .get(offset..offset + 8)
+            .ok_or(RunMetadataError::Truncated)?;
+        let length = u32::from_be_bytes(header[0..4].try_into().unwrap()) as usize;
+        let chunk_type = &header[4..8];
+        let body = data
+            .get(offset + 8..offset + 8 + length)
+            .ok_or(RunMetadataError::Truncated)?;
+        if chunk_type == b"IEND" {
+            break;
+        }
+        if chunk_type == CHUNK_TYPE {
+            let mut fields = body.splitn(2, |&byte| byte == 0);
+            let keyword = fields.next().unwrap();
+            if let (Some(keyword), Some(rest)) = (
+                std::str::from_utf8(keyword)
+                    .ok()
+                    .and_then(|keyword| keyword.strip_prefix(KEYWORD_PREFIX)),
+                fields.next(),
+            ) {
+                // Skip the compression flag and method, then the language tag
+                // and translated keyword
+                if rest.len() >= 2 && rest[0] == 0 {
+                    let text = rest[2..].splitn(3, |&byte| byte == 0).nth(2);
+                    if let Some(text) = text {
+                        chunks.push((
+                            keyword.to_string(),
+                            String::from_utf8_lossy(text).into_owned(),
+                        ));
+                    }
+                }
+            }
+        }
+        offset += 8 + length + 4;
+    }
+    Ok(chunks)
+}
+
+fn encoding_error(err: RunMetadataError) -> ImageFileWriterSaveError {
+    ImageFileWriterSaveError::Encoding(EncodingError::new(
+        ImageFormatHint::Exact(image::ImageFormat::Png),
+        err,
+    ))
+}
+
+fn parse_field<T: FromStr>(keyword: &str, text: String) -> Result<T, RunMetadataError> {
+    text.parse().map_err(|_| RunMetadataError::InvalidField {
+        keyword: keyword.to_string(),
+        text,
+    })
+}
+
+impl RunMetadata {
+    /// Creates metadata for an operation, recording the version of this crate
+    pub fn new<T: Into<String>>(operation: T) -> Self {
+        Self {
+            operation: operation.into(),
+            version: String::from(env!("CARGO_PKG_VERSION")),
+            round_index: None,
+            pass_index: None,
+            pass: None,
+            threshold: None,
+            seed: None,
+            input_hashes: Vec::new(),
+        }
+    }
+
+    /// Adds the metadata to PNG data as text chunks. Data in other formats is returned unchanged,
+    /// because other formats cannot store metadata.
+    pub fn embed(&self, data: ImageFileData) -> Result<ImageFileData, ImageFileWriterSaveError> {
+        if !data.starts_with(&PNG_SIGNATURE) {
+            return Ok(data);
+        }
+        if data.len() < HEADER_LENGTH {
+            return Err(encoding_error(RunMetadataError::Truncated));
+        }
+        let mut output = Vec::with_capacity(data.len() + 512);
+        output.extend_from_slice(&data[..HEADER_LENGTH]);
+        append_chunk(&mut output, OPERATION, &self.operation);
+        append_chunk(&mut output, VERSION, &self.version);
+        if let Some(round_index) = self.round_index {
+            append_chunk(&mut output, ROUND_INDEX, &round_index.to_string());
+        }
+        if let Some(pass_index) = self.pass_index {
+            append_chunk(&mut output, PASS_INDEX, &pass_index.to_string());
+        }
+        if let Some(pass) = self.pass.as_ref() {
+            append_chunk(&mut output, PASS, pass);
+        }
+        if let Some(threshold) = self.threshold {
+            append_chunk(&mut output, THRESHOLD, &threshold.to_string());
+        }
+        if let Some(seed) = self.seed {
+            append_chunk(&mut output, SEED, &seed.to_string());
+        }
+        for hash in self.input_hashes.iter() {
+            append_chunk(
+                &mut output,
+                INPUT_HASH,
+                &format!("{} {}", hash.sha256, hash.path),
+            );
+        }
+        output.extend_from_slice(&data[HEADER_LENGTH..]);
+        Ok(output)
+    }
+
+    /// Adds the metadata to image data, as for `embed`, and writes the result to a file
+    pub fn write_embedded<P: AsRef<Path>>(
+        &self,
+        data: ImageFileData,
+        path: P,
+    ) -> Result<(), ImageFileWriterSaveError> {
+        std::fs::write(path, self.embed(data)?)?;
+        Ok(())
+    }
+
+    /// Extracts metadata from PNG data, returning `None` if the data does not contain metadata
+    pub fn extract(data: &[u8]) -> Result<Option<Self>, RunMetadataError> {
+        let chunks = read_chunks(data)?;
+        if chunks.is_empty() {
+            return Ok(None);
+        }
+        let mut metadata = Self::new("");
+        let mut operation = None;
+        let mut version = None;
+        for (keyword, text) in chunks {
+            match keyword.as_str() {
+                OPERATION => operation = Some(text),
+                VERSION => version = Some(text),
+                ROUND_INDEX => metadata.round_index = Some(parse_field(&keyword, text)?),
+                PASS_INDEX => metadata.pass_index = Some(parse_field(&keyword, text)?),
+                PASS => metadata.pass = Some(text),
+                THRESHOLD => metadata.threshold = Some(parse_field(&keyword, text)?),
+                SEED => metadata.seed = Some(parse_field(&keyword, text)?),
+                INPUT_HASH => match text.split_once(' ') {
+                    Some((sha256, path)) => metadata.input_hashes.push(InputFileHash {
+                        path: path.to_string(),
+                        sha256: sha256.to_string(),
+                    }),
+                    None => return Err(RunMetadataError::InvalidField { keyword, text }),
+                },
+                // Fields added by later versions are ignored
+                _ => (),
+            }
+        }
+        metadata.operation = operation.ok_or(RunMetadataError::MissingField(OPERATION))?;
+        metadata.version = version.ok_or(RunMetadataError::MissingField(VERSION))?;
+        Ok(Some(metadata))
+    }
+
+    /// Reads metadata from a PNG file, returning `None` if the file does not contain metadata
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Option<Self>, Box<dyn Error>> {
+        Ok(Self::extract(&std::fs::read(path)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests;
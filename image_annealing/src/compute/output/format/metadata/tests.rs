@@ -0,0 +1,107 @@
+use super::{InputFileHash, RunMetadata, RunMetadataError};
+use crate::compute::format::{ImageFileReader, ImageFileWriter, Rgba8Image};
+use crate::ImageDimensions;
+use std::error::Error;
+
+fn make_metadata() -> RunMetadata {
+    RunMetadata {
+        round_index: Some(3),
+        pass_index: Some(1),
+        pass: Some(String::from("offset_vertical")),
+        threshold: Some(0.5),
+        seed: Some(42),
+        input_hashes: vec![
+            InputFileHash {
+                path: String::from("permutation.png"),
+                sha256: String::from("ab12"),
+            },
+            InputFileHash {
+                path: String::from("goal with spaces.png"),
+                sha256: String::from("cd34"),
+            },
+        ],
+        ..RunMetadata::new("Swap")
+    }
+}
+
+fn make_png() -> Result<Vec<u8>, Box<dyn Error>> {
+    let dimensions = ImageDimensions::try_new(3, 2)?;
+    Ok(Rgba8Image::new(image::RgbaImage::from_fn(
+        dimensions.width().try_into()?,
+        dimensions.height().try_into()?,
+        |x, y| image::Rgba([x as u8, y as u8, 7, 255]),
+    ))?
+    .encode()?)
+}
+
+#[test]
+fn new() {
+    let metadata = RunMetadata::new("Permute");
+    assert_eq!(metadata.operation, "Permute");
+    assert_eq!(metadata.version, env!("CARGO_PKG_VERSION"));
+    assert!(metadata.input_hashes.is_empty());
+}
+
+#[test]
+fn round_trip() -> Result<(), Box<dyn Error>> {
+    let png = make_png()?;
+    let metadata = make_metadata();
+    let data = metadata.embed(png.clone())?;
+    assert_eq!(RunMetadata::extract(&data)?, Some(metadata));
+    // The image is unchanged
+    assert_eq!(Rgba8Image::decode(&data)?, Rgba8Image::decode(&png)?);
+    Ok(())
+}
+
+#[test]
+fn minimal_round_trip() -> Result<(), Box<dyn Error>> {
+    let metadata = RunMetadata::new("CreatePermutation");
+    let data = metadata.embed(make_png()?)?;
+    assert_eq!(RunMetadata::extract(&data)?, Some(metadata));
+    Ok(())
+}
+
+#[test]
+fn standard_text_chunks() -> Result<(), Box<dyn Error>> {
+    let data = make_metadata().embed(make_png()?)?;
+    let reader = png::Decoder::new(data.as_slice()).read_info()?;
+    let chunks = &reader.info().utf8_text;
+    assert_eq!(chunks[0].keyword, "image_annealing:operation");
+    assert_eq!(chunks[0].get_text()?, "Swap");
+    Ok(())
+}
+
+#[test]
+fn no_metadata() -> Result<(), Box<dyn Error>> {
+    assert_eq!(RunMetadata::extract(&make_png()?)?, None);
+    Ok(())
+}
+
+#[test]
+fn not_png() -> Result<(), Box<dyn Error>> {
+    let data = vec![1, 2, 3];
+    assert_eq!(make_metadata().embed(data.clone())?, data);
+    assert_eq!(RunMetadata::extract(&data), Err(RunMetadataError::NotPng));
+    Ok(())
+}
+
+#[test]
+fn truncated() -> Result<(), Box<dyn Error>> {
+    let mut data = make_metadata().embed(make_png()?)?;
+    data.truncate(40);
+    assert_eq!(
+        RunMetadata::extract(&data),
+        Err(RunMetadataError::Truncated)
+    );
+    Ok(())
+}
+
+#[test]
+fn file_round_trip() -> Result<(), Box<dyn Error>> {
+    let path = test_util::make_test_output_path(["compute_format_run_metadata.png"]);
+    let metadata = make_metadata();
+    metadata.write_embedded(make_png()?, &path)?;
+    assert_eq!(RunMetadata::read(&path)?, Some(metadata));
+    std::fs::remove_file(path)?;
+    Ok(())
+}
@@ -1,29 +1,62 @@
 use std::error::Error;
 use std::path::{Path, PathBuf};
 
+mod animation;
+mod codec;
 mod compound;
+mod conversion_policy;
 mod dynamic;
+mod layered;
+mod metadata;
 mod primitive;
+mod swap_history;
+mod vector_field;
 
+pub use animation::{AnimationEncoder, AnimationFormat, AnimationParameters};
+pub use codec::ImageCodecPool;
 pub use compound::{
     Rgba16Rgba8Image, Rgba16Rgba8x2Image, Rgba16x2Image, Rgba8x2Image, Rgba8x3Image, Rgba8x4Image,
 };
+pub use conversion_policy::ImageConversionPolicy;
 pub use dynamic::{ImageFormat, ImageFormatError, LosslessImage};
+pub use layered::{
+    ImageLayer, ImageLayerFormat, LayeredImage, LayeredImageFormat, LayeredImageFormatError,
+    UnsupportedColorTypeError,
+};
+pub use metadata::{InputFileHash, RunMetadata, RunMetadataError};
 pub use primitive::{
     identity, is_identity, Rgba16Image, Rgba16ImageBuffer, Rgba16ImageBufferComponent, Rgba8Image,
     VectorFieldImageBuffer, VectorFieldImageBufferComponent, VectorFieldImageBufferPixel,
 };
+pub use swap_history::{SwapHistoryError, SwapHistoryReader, SwapHistoryWriter};
+pub use vector_field::{
+    NpyVectorField, RawVectorField, Rg16VectorField, VectorFieldFileError, VectorFieldFormat,
+};
+
+/// The name used in error messages for images that are decoded from memory
+/// instead of being loaded from files
+pub const IN_MEMORY_IMAGE_NAME: &str = "(in memory)";
 
 pub trait ImageFileReader {
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>>
     where
         Self: Sized;
+
+    /// Decodes an image from data in the same format as the file read by `load`
+    fn decode(data: &[u8]) -> Result<Self, Box<dyn Error>>
+    where
+        Self: Sized;
 }
 
 pub type ImageFileWriterSaveError = image::error::ImageError;
 
 pub type ImageFileWriterSaveResult = Result<PathBuf, ImageFileWriterSaveError>;
 
+/// The contents of an image file, held in memory
+pub type ImageFileData = Vec<u8>;
+
+pub type ImageFileWriterEncodeResult = Result<ImageFileData, ImageFileWriterSaveError>;
+
 pub trait ImageFileWriter {
     const EXTENSION: &'static str;
 
@@ -33,4 +66,7 @@ pub trait ImageFileWriter {
 
     fn save_add_extension<P: AsRef<Path>>(&self, path_no_extension: P)
         -> ImageFileWriterSaveResult;
+
+    /// Encodes an image into data in the same format as the file written by `save_add_extension`
+    fn encode(&self) -> ImageFileWriterEncodeResult;
 }
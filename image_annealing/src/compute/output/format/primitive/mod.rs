@@ -1,11 +1,15 @@
 use super::super::super::resource::texture::{LosslessImageTexture, TextureDatatype};
 use super::{
-    ImageFileReader, ImageFileWriter, ImageFileWriterSaveResult, ImageFormat, ImageFormatError,
+    ImageConversionPolicy, ImageFileReader, ImageFileWriter, ImageFileWriterEncodeResult,
+    ImageFileWriterSaveResult,
 };
 use crate::compute::conversion::VectorFieldEntry;
 use crate::{ImageDimensions, ImageDimensionsHolder};
-use image::{io::Reader as ImageReader, GenericImageView, ImageBuffer};
+use image::{
+    EncodableLayout, GenericImageView, ImageBuffer, ImageOutputFormat, PixelWithColorType,
+};
 use std::error::Error;
+use std::io::Cursor;
 use std::path::Path;
 
 pub type VectorFieldImageBufferComponent = u8;
@@ -17,14 +21,23 @@ pub type Rgba16ImageBufferComponent = u16;
 pub type Rgba16ImageBuffer =
     ImageBuffer<image::Rgba<Rgba16ImageBufferComponent>, Vec<Rgba16ImageBufferComponent>>;
 
+pub(super) fn encode_png<P>(image: &ImageBuffer<P, Vec<P::Subpixel>>) -> ImageFileWriterEncodeResult
+where
+    P: PixelWithColorType,
+    [P::Subpixel]: EncodableLayout,
+{
+    let mut data = Vec::new();
+    image.write_to(&mut Cursor::new(&mut data), ImageOutputFormat::Png)?;
+    Ok(data)
+}
+
 impl ImageFileReader for ImageBuffer<image::Rgba<u8>, Vec<u8>> {
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
-        let image = ImageReader::open(&path)?.decode()?;
-        image.as_rgba8().ok_or(ImageFormatError::Unexpected {
-            image_name: format!("{}", path.as_ref().display()),
-            expected_format: ImageFormat::Rgba8,
-        })?;
-        Ok(image.into_rgba8())
+        ImageConversionPolicy::Strict.load_rgba8(path)
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        ImageConversionPolicy::Strict.decode_rgba8(data)
     }
 }
 
@@ -32,12 +45,11 @@ impl ImageFileReader
     for ImageBuffer<image::Rgba<Rgba16ImageBufferComponent>, Vec<Rgba16ImageBufferComponent>>
 {
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
-        let image = ImageReader::open(&path)?.decode()?;
-        image.as_rgba16().ok_or(ImageFormatError::Unexpected {
-            image_name: format!("{}", path.as_ref().display()),
-            expected_format: ImageFormat::Rgba16,
-        })?;
-        Ok(image.into_rgba16())
+        ImageConversionPolicy::Strict.load_rgba16(path)
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        ImageConversionPolicy::Strict.decode_rgba16(data)
     }
 }
 
@@ -51,6 +63,10 @@ impl ImageFileWriter for ImageBuffer<image::Rgba<u8>, Vec<u8>> {
         self.save(&output_path)?;
         Ok(output_path)
     }
+
+    fn encode(&self) -> ImageFileWriterEncodeResult {
+        encode_png(self)
+    }
 }
 
 impl ImageFileWriter
@@ -65,6 +81,10 @@ impl ImageFileWriter
         self.save(&output_path)?;
         Ok(output_path)
     }
+
+    fn encode(&self) -> ImageFileWriterEncodeResult {
+        encode_png(self)
+    }
 }
 
 pub fn identity(dimensions: &ImageDimensions) -> VectorFieldImageBuffer {
@@ -92,6 +112,13 @@ impl Rgba8Image {
         Ok(Self { dimensions, image })
     }
 
+    pub fn load_with_conversion<P: AsRef<Path>>(
+        path: P,
+        policy: ImageConversionPolicy,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::new(policy.load_rgba8(path)?)
+    }
+
     pub fn into_inner(self) -> VectorFieldImageBuffer {
         self.image
     }
@@ -140,7 +167,11 @@ impl ImageDimensionsHolder for Rgba8Image {
 
 impl ImageFileReader for Rgba8Image {
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
-        Self::new(<image::RgbaImage as ImageFileReader>::load(path)?)
+        Self::load_with_conversion(path, ImageConversionPolicy::Strict)
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::new(<image::RgbaImage as ImageFileReader>::decode(data)?)
     }
 }
 
@@ -153,6 +184,10 @@ impl ImageFileWriter for Rgba8Image {
     ) -> ImageFileWriterSaveResult {
         self.image.save_add_extension(path_no_extension)
     }
+
+    fn encode(&self) -> ImageFileWriterEncodeResult {
+        self.image.encode()
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -167,6 +202,13 @@ impl Rgba16Image {
         Ok(Self { dimensions, image })
     }
 
+    pub fn load_with_conversion<P: AsRef<Path>>(
+        path: P,
+        policy: ImageConversionPolicy,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::new(policy.load_rgba16(path)?)
+    }
+
     pub fn into_inner(self) -> Rgba16ImageBuffer {
         self.image
     }
@@ -218,7 +260,11 @@ impl ImageDimensionsHolder for Rgba16Image {
 
 impl ImageFileReader for Rgba16Image {
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
-        Self::new(<Rgba16ImageBuffer as ImageFileReader>::load(path)?)
+        Self::load_with_conversion(path, ImageConversionPolicy::Strict)
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::new(<Rgba16ImageBuffer as ImageFileReader>::decode(data)?)
     }
 }
 
@@ -231,6 +277,10 @@ impl ImageFileWriter for Rgba16Image {
     ) -> ImageFileWriterSaveResult {
         self.image.save_add_extension(path_no_extension)
     }
+
+    fn encode(&self) -> ImageFileWriterEncodeResult {
+        self.image.encode()
+    }
 }
 
 #[cfg(test)]
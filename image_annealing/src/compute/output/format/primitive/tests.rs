@@ -29,6 +29,22 @@ mod vector_field_image_buffer {
             &format!("not the expected format of {}", ImageFormat::Rgba8),
         );
     }
+
+    #[test]
+    fn decode_unexpected_format() -> Result<(), Box<dyn std::error::Error>> {
+        let data = std::fs::read(test_util::make_test_data_path([
+            "image", "image", "red.png",
+        ]))?;
+
+        test_util::assert_error_contains(
+            VectorFieldImageBuffer::decode(&data),
+            &format!(
+                "actual format of image (in memory) is not the expected format of {}",
+                ImageFormat::Rgba8
+            ),
+        );
+        Ok(())
+    }
 }
 
 mod rgba16_image_buffer {
@@ -44,6 +60,16 @@ mod rgba16_image_buffer {
             &format!("not the expected format of {}", ImageFormat::Rgba16),
         );
     }
+
+    #[test]
+    fn encode_decode() -> Result<(), Box<dyn std::error::Error>> {
+        use super::super::super::ImageFileWriter;
+
+        let image: Rgba16ImageBuffer = super::make_image_buffer();
+        let data = image.encode()?;
+        assert_eq!(Rgba16ImageBuffer::decode(&data)?, image);
+        Ok(())
+    }
 }
 
 mod identity {
@@ -0,0 +1,334 @@
+use super::super::algorithm::swap::{AcceptedSwaps, SwapPass};
+use crate::image_utils::check_dimensions_match2;
+use crate::{BoundaryMode, ImageDimensions, ImageDimensionsHolder, ValidatedPermutation};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use image_annealing_shader::constant;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+const SIGNATURE: [u8; 8] = *b"IASWPHST";
+const VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SwapHistoryError {
+    InvalidSignature,
+    UnsupportedVersion(u32),
+    InvalidBoundary(u8),
+    InvalidPass(u8),
+    InvalidPassCount(usize),
+    BoundaryMismatch {
+        history: BoundaryMode,
+        accepted_swaps: BoundaryMode,
+    },
+    RoundNotFound {
+        round_index: usize,
+        round_count: usize,
+    },
+    PassNotFound {
+        round_index: usize,
+        pass_index: usize,
+        pass_count: usize,
+    },
+}
+
+impl fmt::Display for SwapHistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidSignature => write!(f, "data is not a swap history log"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported swap history log version {}", version)
+            }
+            Self::InvalidBoundary(value) => {
+                write!(f, "invalid boundary mode identifier {}", value)
+            }
+            Self::InvalidPass(value) => write!(f, "invalid swap pass identifier {}", value),
+            Self::InvalidPassCount(count) => write!(
+                f,
+                "a swap round must contain between 1 and {} passes, not {} passes",
+                constant::count_swap::N_CHANNEL,
+                count
+            ),
+            Self::BoundaryMismatch {
+                history,
+                accepted_swaps,
+            } => write!(
+                f,
+                "the swap history log uses {:?} boundary conditions, but the accepted swaps use {:?} boundary conditions",
+                history, accepted_swaps
+            ),
+            Self::RoundNotFound {
+                round_index,
+                round_count,
+            } => write!(
+                f,
+                "round index {} is out of range, as the swap history log contains {} rounds",
+                round_index, round_count
+            ),
+            Self::PassNotFound {
+                round_index,
+                pass_index,
+                pass_count,
+            } => write!(
+                f,
+                "pass index {} is out of range, as round {} of the swap history log contains {} passes",
+                pass_index, round_index, pass_count
+            ),
+        }
+    }
+}
+
+impl Error for SwapHistoryError {}
+
+fn boundary_to_byte(boundary: BoundaryMode) -> u8 {
+    match boundary {
+        BoundaryMode::Bounded => 0,
+        BoundaryMode::Toroidal => 1,
+    }
+}
+
+fn boundary_from_byte(value: u8) -> Result<BoundaryMode, SwapHistoryError> {
+    match value {
+        0 => Ok(BoundaryMode::Bounded),
+        1 => Ok(BoundaryMode::Toroidal),
+        _ => Err(SwapHistoryError::InvalidBoundary(value)),
+    }
+}
+
+fn pass_to_byte(pass: SwapPass) -> u8 {
+    match pass {
+        SwapPass::Horizontal => 0,
+        SwapPass::Vertical => 1,
+        SwapPass::OffsetHorizontal => 2,
+        SwapPass::OffsetVertical => 3,
+    }
+}
+
+fn pass_from_byte(value: u8) -> Result<SwapPass, SwapHistoryError> {
+    match value {
+        0 => Ok(SwapPass::Horizontal),
+        1 => Ok(SwapPass::Vertical),
+        2 => Ok(SwapPass::OffsetHorizontal),
+        3 => Ok(SwapPass::OffsetVertical),
+        _ => Err(SwapHistoryError::InvalidPass(value)),
+    }
+}
+
+fn read_u8<R: Read>(reader: &mut R) -> std::io::Result<u8> {
+    let mut buffer = [0; 1];
+    reader.read_exact(&mut buffer)?;
+    Ok(buffer[0])
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> std::io::Result<u32> {
+    let mut buffer = [0; 4];
+    reader.read_exact(&mut buffer)?;
+    Ok(u32::from_le_bytes(buffer))
+}
+
+/// Writes the swaps accepted during each round of swap passes to a compressed log
+///
+/// The log is a signature and version number followed by a zlib stream.
+/// The stream starts with the image dimensions and boundary conditions,
+/// and then contains, for each round, the number of passes in the round and,
+/// for each pass, the pass identifier followed by the bitset of accepted swaps.
+pub struct SwapHistoryWriter<W: Write> {
+    encoder: ZlibEncoder<W>,
+    image_dimensions: ImageDimensions,
+    boundary: BoundaryMode,
+}
+
+impl SwapHistoryWriter<BufWriter<File>> {
+    pub const EXTENSION: &'static str = "swaphist";
+
+    pub fn make_filename<P: AsRef<Path>>(path_no_extension: P) -> PathBuf {
+        path_no_extension.as_ref().with_extension(Self::EXTENSION)
+    }
+
+    pub fn create<P: AsRef<Path>>(
+        path_no_extension: P,
+        image_dimensions: &ImageDimensions,
+        boundary: BoundaryMode,
+    ) -> Result<(Self, PathBuf), Box<dyn Error>> {
+        let path = Self::make_filename(path_no_extension);
+        let file = BufWriter::new(File::create(&path)?);
+        Ok((Self::new(file, image_dimensions, boundary)?, path))
+    }
+}
+
+impl<W: Write> SwapHistoryWriter<W> {
+    pub fn new(
+        mut writer: W,
+        image_dimensions: &ImageDimensions,
+        boundary: BoundaryMode,
+    ) -> Result<Self, Box<dyn Error>> {
+        writer.write_all(&SIGNATURE)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        let mut encoder = ZlibEncoder::new(writer, Compression::default());
+        encoder.write_all(&u32::try_from(image_dimensions.width())?.to_le_bytes())?;
+        encoder.write_all(&u32::try_from(image_dimensions.height())?.to_le_bytes())?;
+        encoder.write_all(&[boundary_to_byte(boundary)])?;
+        Ok(Self {
+            encoder,
+            image_dimensions: *image_dimensions,
+            boundary,
+        })
+    }
+
+    pub fn boundary(&self) -> BoundaryMode {
+        self.boundary
+    }
+
+    pub fn write_round(&mut self, round: &[AcceptedSwaps]) -> Result<(), Box<dyn Error>> {
+        if round.is_empty() || round.len() > constant::count_swap::N_CHANNEL {
+            return Err(Box::new(SwapHistoryError::InvalidPassCount(round.len())));
+        }
+        for accepted_swaps in round {
+            check_dimensions_match2(self, accepted_swaps)?;
+            if accepted_swaps.boundary() != self.boundary {
+                return Err(Box::new(SwapHistoryError::BoundaryMismatch {
+                    history: self.boundary,
+                    accepted_swaps: accepted_swaps.boundary(),
+                }));
+            }
+        }
+        self.encoder.write_all(&[round.len().try_into().unwrap()])?;
+        for accepted_swaps in round {
+            self.encoder
+                .write_all(&[pass_to_byte(accepted_swaps.pass())])?;
+            self.encoder.write_all(accepted_swaps.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> std::io::Result<W> {
+        self.encoder.finish()
+    }
+}
+
+impl<W: Write> ImageDimensionsHolder for SwapHistoryWriter<W> {
+    fn dimensions(&self) -> &ImageDimensions {
+        &self.image_dimensions
+    }
+}
+
+/// Reads logs produced by [`SwapHistoryWriter`]
+pub struct SwapHistoryReader<R: Read> {
+    decoder: ZlibDecoder<R>,
+    image_dimensions: ImageDimensions,
+    boundary: BoundaryMode,
+}
+
+impl SwapHistoryReader<BufReader<File>> {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> SwapHistoryReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, Box<dyn Error>> {
+        let mut signature = [0; SIGNATURE.len()];
+        reader.read_exact(&mut signature)?;
+        if signature != SIGNATURE {
+            return Err(Box::new(SwapHistoryError::InvalidSignature));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != VERSION {
+            return Err(Box::new(SwapHistoryError::UnsupportedVersion(version)));
+        }
+        let mut decoder = ZlibDecoder::new(reader);
+        let width = read_u32(&mut decoder)?;
+        let height = read_u32(&mut decoder)?;
+        let image_dimensions = ImageDimensions::try_new(width, height)?;
+        let boundary = boundary_from_byte(read_u8(&mut decoder)?)?;
+        Ok(Self {
+            decoder,
+            image_dimensions,
+            boundary,
+        })
+    }
+
+    pub fn boundary(&self) -> BoundaryMode {
+        self.boundary
+    }
+
+    /// Returns the swaps accepted during the next round, or `None` at the end of the log
+    pub fn next_round(&mut self) -> Result<Option<Vec<AcceptedSwaps>>, Box<dyn Error>> {
+        let mut pass_count = [0; 1];
+        if self.decoder.read(&mut pass_count)? == 0 {
+            return Ok(None);
+        }
+        let pass_count = pass_count[0];
+        if pass_count == 0 || usize::from(pass_count) > constant::count_swap::N_CHANNEL {
+            return Err(Box::new(SwapHistoryError::InvalidPassCount(
+                pass_count.into(),
+            )));
+        }
+        let mut round = Vec::with_capacity(pass_count.into());
+        for _ in 0..pass_count {
+            let pass = pass_from_byte(read_u8(&mut self.decoder)?)?;
+            let mut flags = vec![0; AcceptedSwaps::byte_length(pass, &self.image_dimensions)];
+            self.decoder.read_exact(&mut flags)?;
+            round.push(AcceptedSwaps::from_bytes(
+                pass,
+                &self.image_dimensions,
+                self.boundary,
+                flags,
+            )?);
+        }
+        Ok(Some(round))
+    }
+
+    /// Reconstructs the permutation output by the pass with the given index
+    /// in the round with the given index, starting from the permutation
+    /// that was input to the first round
+    pub fn replay(
+        mut self,
+        initial_permutation: ValidatedPermutation,
+        round_index: usize,
+        pass_index: usize,
+    ) -> Result<ValidatedPermutation, Box<dyn Error>> {
+        check_dimensions_match2(&self, &initial_permutation)?;
+        let mut permutation = initial_permutation;
+        let mut current_round_index = 0;
+        loop {
+            let round = self.next_round()?.ok_or(SwapHistoryError::RoundNotFound {
+                round_index,
+                round_count: current_round_index,
+            })?;
+            if current_round_index == round_index {
+                if pass_index >= round.len() {
+                    return Err(Box::new(SwapHistoryError::PassNotFound {
+                        round_index,
+                        pass_index,
+                        pass_count: round.len(),
+                    }));
+                }
+                return round[..=pass_index]
+                    .iter()
+                    .try_fold(permutation, |permutation, accepted_swaps| {
+                        accepted_swaps.apply(&permutation)
+                    });
+            }
+            permutation = round
+                .iter()
+                .try_fold(permutation, |permutation, accepted_swaps| {
+                    accepted_swaps.apply(&permutation)
+                })?;
+            current_round_index += 1;
+        }
+    }
+}
+
+impl<R: Read> ImageDimensionsHolder for SwapHistoryReader<R> {
+    fn dimensions(&self) -> &ImageDimensions {
+        &self.image_dimensions
+    }
+}
+
+#[cfg(test)]
+mod tests;
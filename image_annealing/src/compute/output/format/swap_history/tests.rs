@@ -0,0 +1,224 @@
+use super::super::super::algorithm::swap::{AcceptedSwaps, SwapPass};
+use super::SwapHistoryWriter;
+use crate::{BoundaryMode, ImageDimensions};
+use std::error::Error;
+
+fn make_round(
+    dimensions: &ImageDimensions,
+    boundary: BoundaryMode,
+    accepted: &[(SwapPass, &[(usize, usize)])],
+) -> Vec<AcceptedSwaps> {
+    accepted
+        .iter()
+        .map(|(pass, pairs)| {
+            let mut accepted_swaps = AcceptedSwaps::new(*pass, dimensions, boundary);
+            pairs
+                .iter()
+                .for_each(|&(x, y)| accepted_swaps.set_accepted(x, y));
+            accepted_swaps
+        })
+        .collect()
+}
+
+fn make_history(
+    dimensions: &ImageDimensions,
+    boundary: BoundaryMode,
+    rounds: &[Vec<AcceptedSwaps>],
+) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut writer = SwapHistoryWriter::new(Vec::new(), dimensions, boundary)?;
+    for round in rounds {
+        writer.write_round(round)?;
+    }
+    Ok(writer.finish()?)
+}
+
+mod writer {
+    use super::super::{SwapHistoryReader, SwapHistoryWriter};
+    use super::SwapPass;
+    use crate::{BoundaryMode, ImageDimensions, ImageDimensionsHolder};
+    use std::error::Error;
+
+    #[test]
+    fn round_trip() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(5, 4)?;
+        let boundary = BoundaryMode::Toroidal;
+        let rounds = [
+            super::make_round(
+                &dimensions,
+                boundary,
+                &[
+                    (SwapPass::Horizontal, &[(0, 0), (2, 3)]),
+                    (SwapPass::OffsetVertical, &[(4, 1)]),
+                ],
+            ),
+            super::make_round(&dimensions, boundary, &[(SwapPass::Vertical, &[])]),
+        ];
+        let history = super::make_history(&dimensions, boundary, &rounds)?;
+
+        let mut reader = SwapHistoryReader::new(history.as_slice())?;
+        assert_eq!(reader.dimensions(), &dimensions);
+        assert_eq!(reader.boundary(), boundary);
+        assert_eq!(reader.next_round()?.as_ref(), Some(&rounds[0]));
+        assert_eq!(reader.next_round()?.as_ref(), Some(&rounds[1]));
+        assert!(reader.next_round()?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn empty_round() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(5, 4)?;
+        let mut writer = SwapHistoryWriter::new(Vec::new(), &dimensions, BoundaryMode::Bounded)?;
+        test_util::assert_error_contains(
+            writer.write_round(&[]),
+            "a swap round must contain between 1 and 4 passes, not 0 passes",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn dimensions_mismatch() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(5, 4)?;
+        let mut writer = SwapHistoryWriter::new(Vec::new(), &dimensions, BoundaryMode::Bounded)?;
+        let round = super::make_round(
+            &ImageDimensions::try_new(4, 5)?,
+            BoundaryMode::Bounded,
+            &[(SwapPass::Horizontal, &[])],
+        );
+        test_util::assert_error_contains(
+            writer.write_round(&round),
+            "mismatch in image dimensions, (width, height) = (5, 4) and (width, height) = (4, 5)",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn boundary_mismatch() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(5, 4)?;
+        let mut writer = SwapHistoryWriter::new(Vec::new(), &dimensions, BoundaryMode::Bounded)?;
+        let round = super::make_round(
+            &dimensions,
+            BoundaryMode::Toroidal,
+            &[(SwapPass::Horizontal, &[])],
+        );
+        test_util::assert_error_contains(
+            writer.write_round(&round),
+            "the swap history log uses Bounded boundary conditions, but the accepted swaps use Toroidal boundary conditions",
+        );
+        Ok(())
+    }
+}
+
+mod reader {
+    use super::super::SwapHistoryReader;
+    use super::SwapPass;
+    use crate::compute::conversion::{self, VectorFieldEntry};
+    use crate::{BoundaryMode, ImageDimensions, ValidatedPermutation, VectorField};
+    use std::error::Error;
+
+    #[test]
+    fn invalid_signature() {
+        test_util::assert_error_contains(
+            SwapHistoryReader::new([0u8; 16].as_slice()).map(|_| ()),
+            "data is not a swap history log",
+        );
+    }
+
+    fn make_two_rounds() -> Result<(ImageDimensions, Vec<u8>), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(3, 1)?;
+        let boundary = BoundaryMode::Bounded;
+        let rounds = [
+            super::make_round(
+                &dimensions,
+                boundary,
+                &[
+                    (SwapPass::Horizontal, &[(0, 0)]),
+                    (SwapPass::OffsetHorizontal, &[(1, 0)]),
+                ],
+            ),
+            super::make_round(
+                &dimensions,
+                boundary,
+                &[
+                    (SwapPass::Horizontal, &[]),
+                    (SwapPass::OffsetHorizontal, &[(1, 0)]),
+                ],
+            ),
+        ];
+        Ok((
+            dimensions,
+            super::make_history(&dimensions, boundary, &rounds)?,
+        ))
+    }
+
+    #[test]
+    fn replay() -> Result<(), Box<dyn Error>> {
+        let (dimensions, history) = make_two_rounds()?;
+        let identity = ValidatedPermutation::identity(&dimensions);
+        let expected = [
+            (
+                0,
+                0,
+                [
+                    VectorFieldEntry(1, 0),
+                    VectorFieldEntry(-1, 0),
+                    VectorFieldEntry(0, 0),
+                ],
+            ),
+            (
+                0,
+                1,
+                [
+                    VectorFieldEntry(1, 0),
+                    VectorFieldEntry(1, 0),
+                    VectorFieldEntry(-2, 0),
+                ],
+            ),
+            (
+                1,
+                0,
+                [
+                    VectorFieldEntry(1, 0),
+                    VectorFieldEntry(1, 0),
+                    VectorFieldEntry(-2, 0),
+                ],
+            ),
+            (
+                1,
+                1,
+                [
+                    VectorFieldEntry(1, 0),
+                    VectorFieldEntry(-1, 0),
+                    VectorFieldEntry(0, 0),
+                ],
+            ),
+        ];
+        for (round_index, pass_index, entries) in expected {
+            let reader = SwapHistoryReader::new(history.as_slice())?;
+            let permutation = reader.replay(identity.clone(), round_index, pass_index)?;
+            assert_eq!(permutation, conversion::to_image(&dimensions, &entries));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn round_not_found() -> Result<(), Box<dyn Error>> {
+        let (dimensions, history) = make_two_rounds()?;
+        let reader = SwapHistoryReader::new(history.as_slice())?;
+        test_util::assert_error_contains(
+            reader.replay(ValidatedPermutation::identity(&dimensions), 2, 0),
+            "round index 2 is out of range, as the swap history log contains 2 rounds",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn pass_not_found() -> Result<(), Box<dyn Error>> {
+        let (dimensions, history) = make_two_rounds()?;
+        let reader = SwapHistoryReader::new(history.as_slice())?;
+        test_util::assert_error_contains(
+            reader.replay(ValidatedPermutation::identity(&dimensions), 1, 2),
+            "pass index 2 is out of range, as round 1 of the swap history log contains 2 passes",
+        );
+        Ok(())
+    }
+}
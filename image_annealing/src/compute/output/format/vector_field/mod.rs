@@ -0,0 +1,610 @@
+use super::super::conversion::{self, VectorFieldEntry, VectorFieldEntryComponent};
+use super::primitive::encode_png;
+use super::{
+    ImageFileReader, ImageFileWriter, ImageFileWriterEncodeResult, ImageFileWriterSaveResult,
+    VectorFieldImageBuffer, IN_MEMORY_IMAGE_NAME,
+};
+use crate::ImageDimensions;
+use image::io::Reader as ImageReader;
+use image::{DynamicImage, ImageBuffer, LumaA};
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+const NPY_MAGIC: [u8; 6] = *b"\x93NUMPY";
+const NPY_HEADER_ALIGNMENT: usize = 64;
+const RAW_SIGNATURE: [u8; 8] = *b"IAVFIELD";
+const RAW_VERSION: u32 = 1;
+const RG16_OFFSET: i32 = 1 << 15;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VectorFieldFileError {
+    UnexpectedPngFormat(String),
+    InvalidNpySignature,
+    InvalidNpyHeader(String),
+    UnsupportedNpyDataType(String),
+    FortranOrder,
+    InvalidShape(Vec<usize>),
+    OutOfRange(i64),
+    InvalidRawSignature,
+    UnsupportedRawVersion(u32),
+}
+
+impl fmt::Display for VectorFieldFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnexpectedPngFormat(image_name) => write!(
+                f,
+                "image {} is not a 16-bit two-channel PNG image",
+                image_name
+            ),
+            Self::InvalidNpySignature => write!(f, "data is not a NumPy array file"),
+            Self::InvalidNpyHeader(header) => {
+                write!(f, "failed to parse NumPy array header \"{}\"", header)
+            }
+            Self::UnsupportedNpyDataType(descr) => write!(
+                f,
+                "unsupported NumPy array data type '{}', as only '<i2' and '<i4' are supported",
+                descr
+            ),
+            Self::FortranOrder => write!(
+                f,
+                "NumPy arrays stored in Fortran (column-major) order are not supported"
+            ),
+            Self::InvalidShape(shape) => write!(
+                f,
+                "array of shape {:?} is not a vector field of shape (height, width, 2)",
+                shape
+            ),
+            Self::OutOfRange(value) => write!(
+                f,
+                "vector field component {} is outside the range of 16-bit signed integers",
+                value
+            ),
+            Self::InvalidRawSignature => write!(f, "data is not a raw vector field file"),
+            Self::UnsupportedRawVersion(version) => {
+                write!(f, "unsupported raw vector field file version {}", version)
+            }
+        }
+    }
+}
+
+impl Error for VectorFieldFileError {}
+
+/// A file format for vector fields, identified by file extension
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum VectorFieldFormat {
+    /// 8-bit RGBA PNG, with big-endian byte pairs split across channels
+    #[default]
+    Rgba8Png,
+    /// 16-bit two-channel (gray and alpha) PNG, with components offset by 32768
+    Rg16Png,
+    /// NumPy array of 16-bit or 32-bit signed integers, with shape (height, width, 2)
+    Npy,
+    /// Raw little-endian binary with a header (see [`RawVectorField`])
+    Raw,
+}
+
+impl VectorFieldFormat {
+    // Ordered such that longer extensions are matched first
+    const ALL: [Self; 4] = [Self::Rg16Png, Self::Rgba8Png, Self::Npy, Self::Raw];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Rgba8Png => <VectorFieldImageBuffer as ImageFileWriter>::EXTENSION,
+            Self::Rg16Png => <Rg16VectorField as ImageFileWriter>::EXTENSION,
+            Self::Npy => <NpyVectorField as ImageFileWriter>::EXTENSION,
+            Self::Raw => <RawVectorField as ImageFileWriter>::EXTENSION,
+        }
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let file_name = path.as_ref().file_name()?.to_str()?.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|format| file_name.ends_with(&format!(".{}", format.extension())))
+    }
+
+    pub fn from_path_or_default<P: AsRef<Path>>(path: P) -> Self {
+        Self::from_path(path).unwrap_or_default()
+    }
+
+    pub fn strip_extension<'a>(&self, path: &'a str) -> &'a str {
+        let extension_length = self.extension().len() + 1;
+        match path.len().checked_sub(extension_length) {
+            Some(stem_length)
+                if path.is_char_boundary(stem_length)
+                    && path[stem_length..]
+                        .eq_ignore_ascii_case(&format!(".{}", self.extension())) =>
+            {
+                &path[..stem_length]
+            }
+            _ => path,
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(&self, path: P) -> Result<VectorFieldImageBuffer, Box<dyn Error>> {
+        Ok(match self {
+            Self::Rgba8Png => VectorFieldImageBuffer::load(path)?,
+            Self::Rg16Png => Rg16VectorField::load(path)?.into_inner(),
+            Self::Npy => NpyVectorField::load(path)?.into_inner(),
+            Self::Raw => RawVectorField::load(path)?.into_inner(),
+        })
+    }
+
+    pub fn decode(&self, data: &[u8]) -> Result<VectorFieldImageBuffer, Box<dyn Error>> {
+        Ok(match self {
+            Self::Rgba8Png => VectorFieldImageBuffer::decode(data)?,
+            Self::Rg16Png => Rg16VectorField::decode(data)?.into_inner(),
+            Self::Npy => NpyVectorField::decode(data)?.into_inner(),
+            Self::Raw => RawVectorField::decode(data)?.into_inner(),
+        })
+    }
+
+    pub fn dimensions<P: AsRef<Path>>(&self, path: P) -> Result<ImageDimensions, Box<dyn Error>> {
+        match self {
+            Self::Rgba8Png | Self::Rg16Png => ImageDimensions::from_image_path(path),
+            Self::Npy => {
+                let mut reader = BufReader::new(File::open(path)?);
+                let header = read_npy_header(&mut reader)?;
+                Ok(ImageDimensions::try_new(header.width, header.height)?)
+            }
+            Self::Raw => {
+                let mut reader = BufReader::new(File::open(path)?);
+                read_raw_header(&mut reader)
+            }
+        }
+    }
+
+    pub fn save_add_extension<P: AsRef<Path>>(
+        &self,
+        vector_field: &VectorFieldImageBuffer,
+        path_no_extension: P,
+    ) -> ImageFileWriterSaveResult {
+        match self {
+            Self::Rgba8Png => vector_field.save_add_extension(path_no_extension),
+            Self::Rg16Png => save_rg16(
+                vector_field,
+                Rg16VectorField::make_filename(path_no_extension),
+            ),
+            Self::Npy => save_npy(
+                vector_field,
+                NpyVectorField::make_filename(path_no_extension),
+            ),
+            Self::Raw => save_raw(
+                vector_field,
+                RawVectorField::make_filename(path_no_extension),
+            ),
+        }
+    }
+
+    pub fn make_filename<P: AsRef<Path>>(&self, path_no_extension: P) -> PathBuf {
+        match self {
+            Self::Rgba8Png => VectorFieldImageBuffer::make_filename(path_no_extension),
+            Self::Rg16Png => Rg16VectorField::make_filename(path_no_extension),
+            Self::Npy => NpyVectorField::make_filename(path_no_extension),
+            Self::Raw => RawVectorField::make_filename(path_no_extension),
+        }
+    }
+
+    pub fn encode(&self, vector_field: &VectorFieldImageBuffer) -> ImageFileWriterEncodeResult {
+        match self {
+            Self::Rgba8Png => vector_field.encode(),
+            Self::Rg16Png => encode_rg16(vector_field),
+            Self::Npy => {
+                let mut data = Vec::new();
+                write_npy(vector_field, &mut data)?;
+                Ok(data)
+            }
+            Self::Raw => {
+                let mut data = Vec::new();
+                write_raw(vector_field, &mut data)?;
+                Ok(data)
+            }
+        }
+    }
+}
+
+type Rg16ImageBuffer = ImageBuffer<LumaA<u16>, Vec<u16>>;
+
+fn component_to_rg16(component: VectorFieldEntryComponent) -> u16 {
+    (i32::from(component) + RG16_OFFSET).try_into().unwrap()
+}
+
+fn component_from_rg16(value: u16) -> VectorFieldEntryComponent {
+    (i32::from(value) - RG16_OFFSET).try_into().unwrap()
+}
+
+fn to_rg16(vector_field: &VectorFieldImageBuffer) -> Rg16ImageBuffer {
+    let data: Vec<u16> = conversion::to_vec(vector_field)
+        .into_iter()
+        .flat_map(|VectorFieldEntry(delta_x, delta_y)| {
+            [component_to_rg16(delta_x), component_to_rg16(delta_y)]
+        })
+        .collect();
+    Rg16ImageBuffer::from_vec(vector_field.width(), vector_field.height(), data).unwrap()
+}
+
+fn from_rg16(
+    image: DynamicImage,
+    image_name: String,
+) -> Result<VectorFieldImageBuffer, Box<dyn Error>> {
+    let image = image
+        .as_luma_alpha16()
+        .ok_or(VectorFieldFileError::UnexpectedPngFormat(image_name))?;
+    let dimensions = ImageDimensions::from_image(image)?;
+    let entries: Vec<VectorFieldEntry> = image
+        .pixels()
+        .map(|px| VectorFieldEntry(component_from_rg16(px[0]), component_from_rg16(px[1])))
+        .collect();
+    Ok(conversion::to_image(&dimensions, &entries))
+}
+
+fn save_rg16(
+    vector_field: &VectorFieldImageBuffer,
+    output_path: PathBuf,
+) -> ImageFileWriterSaveResult {
+    to_rg16(vector_field).save(&output_path)?;
+    Ok(output_path)
+}
+
+fn encode_rg16(vector_field: &VectorFieldImageBuffer) -> ImageFileWriterEncodeResult {
+    encode_png(&to_rg16(vector_field))
+}
+
+/// A vector field stored as a 16-bit two-channel PNG image
+///
+/// The first channel holds the x-components and the second channel holds the y-components,
+/// each offset by 32768 so that a zero vector is stored as mid-gray.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rg16VectorField(VectorFieldImageBuffer);
+
+impl Rg16VectorField {
+    pub fn new(vector_field: VectorFieldImageBuffer) -> Self {
+        Self(vector_field)
+    }
+
+    pub fn into_inner(self) -> VectorFieldImageBuffer {
+        self.0
+    }
+}
+
+impl AsRef<VectorFieldImageBuffer> for Rg16VectorField {
+    fn as_ref(&self) -> &VectorFieldImageBuffer {
+        &self.0
+    }
+}
+
+impl ImageFileReader for Rg16VectorField {
+    fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let image = ImageReader::open(&path)?.decode()?;
+        Ok(Self(from_rg16(
+            image,
+            format!("{}", path.as_ref().display()),
+        )?))
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let image = image::load_from_memory(data)?;
+        Ok(Self(from_rg16(image, String::from(IN_MEMORY_IMAGE_NAME))?))
+    }
+}
+
+impl ImageFileWriter for Rg16VectorField {
+    const EXTENSION: &'static str = "rg16.png";
+
+    fn save_add_extension<P: AsRef<Path>>(
+        &self,
+        path_no_extension: P,
+    ) -> ImageFileWriterSaveResult {
+        save_rg16(&self.0, Self::make_filename(path_no_extension))
+    }
+
+    fn encode(&self) -> ImageFileWriterEncodeResult {
+        encode_rg16(&self.0)
+    }
+}
+
+struct NpyHeader {
+    width: u32,
+    height: u32,
+    component_size: usize,
+}
+
+fn npy_header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let start = header.find(&format!("'{}':", key))? + key.len() + 3;
+    let value = header[start..].trim_start();
+    let end = match value.chars().next()? {
+        '(' => value.find(')')? + 1,
+        quote @ ('\'' | '"') => value[1..].find(quote)? + 2,
+        _ => value.find([',', '}'])?,
+    };
+    Some(value[..end].trim())
+}
+
+fn parse_npy_header(header: &str) -> Result<NpyHeader, Box<dyn Error>> {
+    let invalid = || VectorFieldFileError::InvalidNpyHeader(String::from(header.trim()));
+    let descr = npy_header_value(header, "descr").ok_or_else(invalid)?;
+    let descr = descr.trim_matches(|c| c == '\'' || c == '"');
+    let component_size = match descr {
+        "<i2" => 2,
+        "<i4" => 4,
+        _ => {
+            return Err(Box::new(VectorFieldFileError::UnsupportedNpyDataType(
+                String::from(descr),
+            )))
+        }
+    };
+    match npy_header_value(header, "fortran_order").ok_or_else(invalid)? {
+        "False" => (),
+        "True" => return Err(Box::new(VectorFieldFileError::FortranOrder)),
+        _ => return Err(Box::new(invalid())),
+    }
+    let shape = npy_header_value(header, "shape")
+        .ok_or_else(invalid)?
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(str::trim)
+        .filter(|dimension| !dimension.is_empty())
+        .map(|dimension| dimension.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid())?;
+    match shape[..] {
+        [height, width, 2] => Ok(NpyHeader {
+            width: width.try_into()?,
+            height: height.try_into()?,
+            component_size,
+        }),
+        _ => Err(Box::new(VectorFieldFileError::InvalidShape(shape))),
+    }
+}
+
+fn read_npy_header<R: Read>(reader: &mut R) -> Result<NpyHeader, Box<dyn Error>> {
+    let mut magic = [0u8; NPY_MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    if magic != NPY_MAGIC {
+        return Err(Box::new(VectorFieldFileError::InvalidNpySignature));
+    }
+    let mut version = [0u8; 2];
+    reader.read_exact(&mut version)?;
+    let header_length = if version[0] == 1 {
+        let mut bytes = [0u8; 2];
+        reader.read_exact(&mut bytes)?;
+        usize::from(u16::from_le_bytes(bytes))
+    } else {
+        let mut bytes = [0u8; 4];
+        reader.read_exact(&mut bytes)?;
+        u32::from_le_bytes(bytes).try_into()?
+    };
+    let mut header = vec![0u8; header_length];
+    reader.read_exact(&mut header)?;
+    parse_npy_header(&String::from_utf8_lossy(&header))
+}
+
+fn read_npy<R: Read>(reader: &mut R) -> Result<VectorFieldImageBuffer, Box<dyn Error>> {
+    let header = read_npy_header(reader)?;
+    let dimensions = ImageDimensions::try_new(header.width, header.height)?;
+    let mut data = vec![0u8; dimensions.count() * 2 * header.component_size];
+    reader.read_exact(&mut data)?;
+    let components = data
+        .chunks_exact(header.component_size)
+        .map(|bytes| {
+            if header.component_size == 2 {
+                Ok(VectorFieldEntryComponent::from_le_bytes([
+                    bytes[0], bytes[1],
+                ]))
+            } else {
+                let value = i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                value
+                    .try_into()
+                    .map_err(|_| VectorFieldFileError::OutOfRange(value.into()))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let entries: Vec<VectorFieldEntry> = components
+        .chunks_exact(2)
+        .map(|pair| VectorFieldEntry(pair[0], pair[1]))
+        .collect();
+    Ok(conversion::to_image(&dimensions, &entries))
+}
+
+fn write_npy<W: Write>(vector_field: &VectorFieldImageBuffer, writer: &mut W) -> io::Result<()> {
+    let mut header = format!(
+        "{{'descr': '<i2', 'fortran_order': False, 'shape': ({}, {}, 2), }}",
+        vector_field.height(),
+        vector_field.width()
+    );
+    // The header is padded with spaces and terminated with a newline
+    // so that the array data is aligned
+    let unpadded_length = NPY_MAGIC.len() + 4 + header.len() + 1;
+    let padding_length =
+        (NPY_HEADER_ALIGNMENT - unpadded_length % NPY_HEADER_ALIGNMENT) % NPY_HEADER_ALIGNMENT;
+    header.push_str(&" ".repeat(padding_length));
+    header.push('\n');
+
+    writer.write_all(&NPY_MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&u16::try_from(header.len()).unwrap().to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    for VectorFieldEntry(delta_x, delta_y) in conversion::to_vec(vector_field) {
+        writer.write_all(&delta_x.to_le_bytes())?;
+        writer.write_all(&delta_y.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn save_npy(
+    vector_field: &VectorFieldImageBuffer,
+    output_path: PathBuf,
+) -> ImageFileWriterSaveResult {
+    let mut writer = BufWriter::new(File::create(&output_path)?);
+    write_npy(vector_field, &mut writer)?;
+    writer.flush()?;
+    Ok(output_path)
+}
+
+/// A vector field stored as a NumPy array file
+///
+/// The array has shape (height, width, 2), with the x-component of each vector
+/// before its y-component. Arrays of little-endian 16-bit or 32-bit signed integers
+/// can be loaded, and arrays are saved as 16-bit signed integers.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NpyVectorField(VectorFieldImageBuffer);
+
+impl NpyVectorField {
+    pub fn new(vector_field: VectorFieldImageBuffer) -> Self {
+        Self(vector_field)
+    }
+
+    pub fn into_inner(self) -> VectorFieldImageBuffer {
+        self.0
+    }
+}
+
+impl AsRef<VectorFieldImageBuffer> for NpyVectorField {
+    fn as_ref(&self) -> &VectorFieldImageBuffer {
+        &self.0
+    }
+}
+
+impl ImageFileReader for NpyVectorField {
+    fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let mut reader = BufReader::new(File::open(path)?);
+        Ok(Self(read_npy(&mut reader)?))
+    }
+
+    fn decode(mut data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Ok(Self(read_npy(&mut data)?))
+    }
+}
+
+impl ImageFileWriter for NpyVectorField {
+    const EXTENSION: &'static str = "npy";
+
+    fn save_add_extension<P: AsRef<Path>>(
+        &self,
+        path_no_extension: P,
+    ) -> ImageFileWriterSaveResult {
+        save_npy(&self.0, Self::make_filename(path_no_extension))
+    }
+
+    fn encode(&self) -> ImageFileWriterEncodeResult {
+        VectorFieldFormat::Npy.encode(&self.0)
+    }
+}
+
+fn read_raw_header<R: Read>(reader: &mut R) -> Result<ImageDimensions, Box<dyn Error>> {
+    let mut signature = [0u8; RAW_SIGNATURE.len()];
+    reader.read_exact(&mut signature)?;
+    if signature != RAW_SIGNATURE {
+        return Err(Box::new(VectorFieldFileError::InvalidRawSignature));
+    }
+    let mut fields = [[0u8; 4]; 3];
+    for field in fields.iter_mut() {
+        reader.read_exact(field)?;
+    }
+    let [version, width, height] = fields.map(u32::from_le_bytes);
+    if version != RAW_VERSION {
+        return Err(Box::new(VectorFieldFileError::UnsupportedRawVersion(
+            version,
+        )));
+    }
+    Ok(ImageDimensions::try_new(width, height)?)
+}
+
+fn read_raw<R: Read>(reader: &mut R) -> Result<VectorFieldImageBuffer, Box<dyn Error>> {
+    let dimensions = read_raw_header(reader)?;
+    let mut data = vec![0u8; dimensions.count() * 4];
+    reader.read_exact(&mut data)?;
+    let entries: Vec<VectorFieldEntry> = data
+        .chunks_exact(4)
+        .map(|bytes| {
+            VectorFieldEntry(
+                VectorFieldEntryComponent::from_le_bytes([bytes[0], bytes[1]]),
+                VectorFieldEntryComponent::from_le_bytes([bytes[2], bytes[3]]),
+            )
+        })
+        .collect();
+    Ok(conversion::to_image(&dimensions, &entries))
+}
+
+fn write_raw<W: Write>(vector_field: &VectorFieldImageBuffer, writer: &mut W) -> io::Result<()> {
+    writer.write_all(&RAW_SIGNATURE)?;
+    for field in [RAW_VERSION, vector_field.width(), vector_field.height()] {
+        writer.write_all(&field.to_le_bytes())?;
+    }
+    for VectorFieldEntry(delta_x, delta_y) in conversion::to_vec(vector_field) {
+        writer.write_all(&delta_x.to_le_bytes())?;
+        writer.write_all(&delta_y.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn save_raw(
+    vector_field: &VectorFieldImageBuffer,
+    output_path: PathBuf,
+) -> ImageFileWriterSaveResult {
+    let mut writer = BufWriter::new(File::create(&output_path)?);
+    write_raw(vector_field, &mut writer)?;
+    writer.flush()?;
+    Ok(output_path)
+}
+
+/// A vector field stored as raw little-endian binary data
+///
+/// The file consists of the following fields, where all integers are little-endian:
+/// 1. The 8-byte signature `IAVFIELD`
+/// 2. The format version, 1, as a 32-bit unsigned integer
+/// 3. The width and then the height of the vector field, as 32-bit unsigned integers
+/// 4. The vectors in row-major order, each stored as a 16-bit signed x-component
+///    followed by a 16-bit signed y-component
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RawVectorField(VectorFieldImageBuffer);
+
+impl RawVectorField {
+    pub fn new(vector_field: VectorFieldImageBuffer) -> Self {
+        Self(vector_field)
+    }
+
+    pub fn into_inner(self) -> VectorFieldImageBuffer {
+        self.0
+    }
+}
+
+impl AsRef<VectorFieldImageBuffer> for RawVectorField {
+    fn as_ref(&self) -> &VectorFieldImageBuffer {
+        &self.0
+    }
+}
+
+impl ImageFileReader for RawVectorField {
+    fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let mut reader = BufReader::new(File::open(path)?);
+        Ok(Self(read_raw(&mut reader)?))
+    }
+
+    fn decode(mut data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Ok(Self(read_raw(&mut data)?))
+    }
+}
+
+impl ImageFileWriter for RawVectorField {
+    const EXTENSION: &'static str = "raw";
+
+    fn save_add_extension<P: AsRef<Path>>(
+        &self,
+        path_no_extension: P,
+    ) -> ImageFileWriterSaveResult {
+        save_raw(&self.0, Self::make_filename(path_no_extension))
+    }
+
+    fn encode(&self) -> ImageFileWriterEncodeResult {
+        VectorFieldFormat::Raw.encode(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,339 @@
+use super::super::super::conversion::{self, VectorFieldEntry};
+use super::super::VectorFieldImageBuffer;
+use crate::ImageDimensions;
+use std::error::Error;
+use std::path::PathBuf;
+
+fn make_vector_field() -> Result<VectorFieldImageBuffer, Box<dyn Error>> {
+    Ok(conversion::to_image(
+        &ImageDimensions::try_new(3, 2)?,
+        &[
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(-1, 1),
+            VectorFieldEntry(i16::MIN, i16::MAX),
+            VectorFieldEntry(2, -300),
+            VectorFieldEntry(1000, 0),
+            VectorFieldEntry(-2, -1),
+        ],
+    ))
+}
+
+fn write_npy_file(name: &str, header: &str, data: &[u8]) -> Result<PathBuf, Box<dyn Error>> {
+    let path = test_util::make_test_output_path([name]);
+    let mut bytes = Vec::from(*b"\x93NUMPY\x01\x00");
+    bytes.extend_from_slice(&u16::try_from(header.len())?.to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(data);
+    std::fs::write(&path, bytes)?;
+    Ok(path)
+}
+
+mod vector_field_format {
+    use super::super::VectorFieldFormat;
+    use std::error::Error;
+
+    #[test]
+    fn from_path() {
+        assert_eq!(
+            VectorFieldFormat::from_path("a/b.png"),
+            Some(VectorFieldFormat::Rgba8Png)
+        );
+        assert_eq!(
+            VectorFieldFormat::from_path("a/b.rg16.png"),
+            Some(VectorFieldFormat::Rg16Png)
+        );
+        assert_eq!(
+            VectorFieldFormat::from_path("a/b.NPY"),
+            Some(VectorFieldFormat::Npy)
+        );
+        assert_eq!(
+            VectorFieldFormat::from_path("a/b.raw"),
+            Some(VectorFieldFormat::Raw)
+        );
+        assert_eq!(VectorFieldFormat::from_path("a/b.txt"), None);
+        assert_eq!(VectorFieldFormat::from_path("a/b"), None);
+        assert_eq!(
+            VectorFieldFormat::from_path_or_default("a/b"),
+            VectorFieldFormat::Rgba8Png
+        );
+    }
+
+    #[test]
+    fn strip_extension() {
+        assert_eq!(
+            VectorFieldFormat::Rg16Png.strip_extension("a/b.rg16.png"),
+            "a/b"
+        );
+        assert_eq!(
+            VectorFieldFormat::Rgba8Png.strip_extension("a/b.rg16.png"),
+            "a/b.rg16"
+        );
+        assert_eq!(VectorFieldFormat::Npy.strip_extension("a/b.NPY"), "a/b");
+        assert_eq!(VectorFieldFormat::Raw.strip_extension("a/b"), "a/b");
+        assert_eq!(VectorFieldFormat::Raw.strip_extension("raw"), "raw");
+    }
+
+    #[test]
+    fn round_trip() -> Result<(), Box<dyn Error>> {
+        let vector_field = super::make_vector_field()?;
+        for format in [
+            VectorFieldFormat::Rgba8Png,
+            VectorFieldFormat::Rg16Png,
+            VectorFieldFormat::Npy,
+            VectorFieldFormat::Raw,
+        ] {
+            let path = format.save_add_extension(
+                &vector_field,
+                test_util::make_test_output_path(["vector_field_format_round_trip"]),
+            )?;
+            assert_eq!(VectorFieldFormat::from_path(&path), Some(format));
+            assert_eq!(format.load(&path)?, vector_field);
+            let dimensions = format.dimensions(&path)?;
+            assert_eq!((dimensions.width(), dimensions.height()), (3, 2));
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn encode_decode() -> Result<(), Box<dyn Error>> {
+        let vector_field = super::make_vector_field()?;
+        for format in [
+            VectorFieldFormat::Rgba8Png,
+            VectorFieldFormat::Rg16Png,
+            VectorFieldFormat::Npy,
+            VectorFieldFormat::Raw,
+        ] {
+            let path = format.save_add_extension(
+                &vector_field,
+                test_util::make_test_output_path(["vector_field_format_encode_decode"]),
+            )?;
+            let data = format.encode(&vector_field)?;
+            assert_eq!(data, std::fs::read(&path)?);
+            assert_eq!(format.decode(&data)?, vector_field);
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn make_filename() {
+        assert_eq!(
+            VectorFieldFormat::Rg16Png.make_filename("a/b"),
+            std::path::PathBuf::from("a/b.rg16.png")
+        );
+        assert_eq!(
+            VectorFieldFormat::Npy.make_filename("a/b"),
+            std::path::PathBuf::from("a/b.npy")
+        );
+    }
+}
+
+mod rg16_vector_field {
+    use super::super::super::{ImageFileReader, ImageFileWriter};
+    use super::super::Rg16VectorField;
+    use std::error::Error;
+
+    #[test]
+    fn zero_is_mid_gray() -> Result<(), Box<dyn Error>> {
+        let path = Rg16VectorField::new(super::make_vector_field()?)
+            .save_add_extension(test_util::make_test_output_path(["rg16_vector_field_zero"]))?;
+        let image = image::open(&path)?.into_luma_alpha16();
+        assert_eq!(image.get_pixel(0, 0).0, [32768, 32768]);
+        assert_eq!(image.get_pixel(2, 0).0, [0, u16::MAX]);
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn decode_unexpected_format() -> Result<(), Box<dyn Error>> {
+        let data = std::fs::read(test_util::make_test_data_path([
+            "image",
+            "permutation",
+            "identity_permutation.png",
+        ]))?;
+        test_util::assert_error_contains(
+            Rg16VectorField::decode(&data),
+            "image (in memory) is not a 16-bit two-channel PNG image",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn load_unexpected_format() {
+        test_util::assert_error_contains(
+            Rg16VectorField::load(test_util::make_test_data_path([
+                "image",
+                "permutation",
+                "identity_permutation.png",
+            ])),
+            "is not a 16-bit two-channel PNG image",
+        );
+    }
+}
+
+mod npy_vector_field {
+    use super::super::super::super::conversion::{self, VectorFieldEntry};
+    use super::super::super::{ImageFileReader, ImageFileWriter};
+    use super::super::NpyVectorField;
+    use std::error::Error;
+
+    #[test]
+    fn save_aligned_header() -> Result<(), Box<dyn Error>> {
+        let path = NpyVectorField::new(super::make_vector_field()?)
+            .save_add_extension(test_util::make_test_output_path(["npy_vector_field_save"]))?;
+        let bytes = std::fs::read(&path)?;
+        let header_length = usize::from(u16::from_le_bytes([bytes[8], bytes[9]]));
+        assert_eq!((10 + header_length) % 64, 0);
+        assert_eq!(
+            std::str::from_utf8(&bytes[10..10 + header_length])?.trim_end(),
+            "{'descr': '<i2', 'fortran_order': False, 'shape': (2, 3, 2), }"
+        );
+        assert_eq!(bytes.len(), 10 + header_length + 3 * 2 * 2 * 2);
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn load_int32() -> Result<(), Box<dyn Error>> {
+        let data: Vec<u8> = [1i32, -2, 0, 5]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let path = super::write_npy_file(
+            "npy_vector_field_int32.npy",
+            "{'descr': '<i4', 'fortran_order': False, 'shape': (1, 2, 2), }\n",
+            &data,
+        )?;
+        assert_eq!(
+            conversion::to_vec(&NpyVectorField::load(&path)?.into_inner()),
+            [VectorFieldEntry(1, -2), VectorFieldEntry(0, 5)]
+        );
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn load_int32_out_of_range() -> Result<(), Box<dyn Error>> {
+        let data: Vec<u8> = [1i32, 40000]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let path = super::write_npy_file(
+            "npy_vector_field_out_of_range.npy",
+            "{'descr': '<i4', 'fortran_order': False, 'shape': (1, 1, 2), }\n",
+            &data,
+        )?;
+        test_util::assert_error_contains(
+            NpyVectorField::load(&path),
+            "vector field component 40000 is outside the range of 16-bit signed integers",
+        );
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_data_type() -> Result<(), Box<dyn Error>> {
+        let path = super::write_npy_file(
+            "npy_vector_field_unsupported_data_type.npy",
+            "{'descr': '<f4', 'fortran_order': False, 'shape': (1, 1, 2), }\n",
+            &[0; 8],
+        )?;
+        test_util::assert_error_contains(
+            NpyVectorField::load(&path),
+            "unsupported NumPy array data type '<f4'",
+        );
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn fortran_order() -> Result<(), Box<dyn Error>> {
+        let path = super::write_npy_file(
+            "npy_vector_field_fortran_order.npy",
+            "{'descr': '<i2', 'fortran_order': True, 'shape': (1, 1, 2), }\n",
+            &[0; 4],
+        )?;
+        test_util::assert_error_contains(
+            NpyVectorField::load(&path),
+            "NumPy arrays stored in Fortran (column-major) order are not supported",
+        );
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_shape() -> Result<(), Box<dyn Error>> {
+        let path = super::write_npy_file(
+            "npy_vector_field_invalid_shape.npy",
+            "{'descr': '<i2', 'fortran_order': False, 'shape': (2, 2), }\n",
+            &[0; 8],
+        )?;
+        test_util::assert_error_contains(
+            NpyVectorField::load(&path),
+            "array of shape [2, 2] is not a vector field of shape (height, width, 2)",
+        );
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_signature() {
+        test_util::assert_error_contains(
+            NpyVectorField::load(test_util::make_test_data_path([
+                "image",
+                "permutation",
+                "identity_permutation.png",
+            ])),
+            "data is not a NumPy array file",
+        );
+    }
+}
+
+mod raw_vector_field {
+    use super::super::super::{ImageFileReader, ImageFileWriter};
+    use super::super::RawVectorField;
+    use std::error::Error;
+
+    #[test]
+    fn save_layout() -> Result<(), Box<dyn Error>> {
+        let path = RawVectorField::new(super::make_vector_field()?)
+            .save_add_extension(test_util::make_test_output_path(["raw_vector_field_save"]))?;
+        let bytes = std::fs::read(&path)?;
+        assert_eq!(&bytes[..8], b"IAVFIELD");
+        assert_eq!(bytes[8..20], [1, 0, 0, 0, 3, 0, 0, 0, 2, 0, 0, 0]);
+        assert_eq!(bytes[24..28], [0xff, 0xff, 1, 0]);
+        assert_eq!(bytes.len(), 20 + 3 * 2 * 4);
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_version() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_output_path(["raw_vector_field_unsupported_version.raw"]);
+        let mut bytes = Vec::from(*b"IAVFIELD");
+        for field in [2u32, 1, 1] {
+            bytes.extend_from_slice(&field.to_le_bytes());
+        }
+        bytes.extend_from_slice(&[0; 4]);
+        std::fs::write(&path, bytes)?;
+        test_util::assert_error_contains(
+            RawVectorField::load(&path),
+            "unsupported raw vector field file version 2",
+        );
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_signature() {
+        test_util::assert_error_contains(
+            RawVectorField::load(test_util::make_test_data_path([
+                "image",
+                "permutation",
+                "identity_permutation.png",
+            ])),
+            "data is not a raw vector field file",
+        );
+    }
+}
@@ -6,8 +6,8 @@ mod storage;
 mod texture_copy;
 mod uniform;
 
-pub use staging::CountSwapOutputBuffer;
-pub use storage::{CountSwapInputBuffer, CountSwapOutputStorageBuffer};
+pub use staging::{AcceptedSwapsOutputBuffer, CountSwapOutputBuffer};
+pub use storage::{AcceptedSwapsStorageBuffer, CountSwapInputBuffer, CountSwapOutputStorageBuffer};
 pub use texture_copy::{
     DisplacementGoalOutputBuffer, LosslessImageOutputBuffer, PermutationOutputBuffer,
 };
@@ -0,0 +1,59 @@
+use super::super::data::BufferData;
+use super::super::dimensions::BufferDimensions;
+use super::super::storage::AcceptedSwapsStorageBuffer;
+use crate::compute::device::{DeviceManager, DevicePollType};
+use crate::compute::link::swap::{AcceptedSwapsWord, SwapPass};
+use crate::ImageDimensions;
+
+type BufferElement = AcceptedSwapsWord;
+
+pub struct AcceptedSwapsOutputBuffer(BufferData);
+
+impl AcceptedSwapsOutputBuffer {
+    pub fn new(device: &wgpu::Device, image_dimensions: &ImageDimensions) -> Self {
+        let buffer_dimensions = BufferDimensions::new_buffer(
+            SwapPass::total_accepted_swaps_words(image_dimensions),
+            std::mem::size_of::<BufferElement>(),
+        );
+        Self(BufferData::create_output_buffer(
+            device,
+            &buffer_dimensions,
+            Some("accepted_swaps_output_buffer"),
+        ))
+    }
+
+    pub fn load(&self, encoder: &mut wgpu::CommandEncoder, buffer: &AcceptedSwapsStorageBuffer) {
+        super::assert_same_dimensions(&self.0, buffer.dimensions());
+
+        encoder.copy_buffer_to_buffer(
+            buffer.buffer(),
+            0,
+            self.0.buffer(),
+            0,
+            self.dimensions().byte_size().try_into().unwrap(),
+        );
+    }
+
+    fn output_chunk_mapper(chunk: &[u8]) -> BufferElement {
+        BufferElement::from_ne_bytes(chunk.try_into().unwrap())
+    }
+
+    pub async fn collect(
+        &self,
+        device_manager: &DeviceManager,
+        poll_type: DevicePollType,
+    ) -> Vec<BufferElement> {
+        self.0
+            .collect_elements(
+                std::mem::size_of::<BufferElement>(),
+                Self::output_chunk_mapper,
+                device_manager,
+                poll_type,
+            )
+            .await
+    }
+
+    pub(in super::super) fn dimensions(&self) -> &BufferDimensions {
+        self.0.dimensions()
+    }
+}
@@ -1,8 +1,10 @@
 use super::data::BufferData;
 use super::dimensions::BufferDimensions;
 
+mod accepted_swaps;
 mod count_swap;
 
+pub use accepted_swaps::AcceptedSwapsOutputBuffer;
 pub use count_swap::CountSwapOutputBuffer;
 
 fn assert_same_dimensions(buffer: &BufferData, dimensions: &BufferDimensions) {
@@ -0,0 +1,36 @@
+use super::super::data::BufferData;
+use super::super::dimensions::BufferDimensions;
+use super::super::AcceptedSwapsOutputBuffer;
+use super::super::{BindableBuffer, OutputBuffer};
+
+pub struct AcceptedSwapsStorageBuffer(BufferData);
+
+impl AcceptedSwapsStorageBuffer {
+    pub fn new(device: &wgpu::Device, size_reference: &AcceptedSwapsOutputBuffer) -> Self {
+        Self(BufferData::create_output_storage_buffer(
+            device,
+            size_reference.dimensions(),
+            Some("accepted_swaps_storage_buffer"),
+        ))
+    }
+
+    pub(in super::super) fn dimensions(&self) -> &BufferDimensions {
+        self.0.dimensions()
+    }
+
+    pub fn buffer(&self) -> &wgpu::Buffer {
+        self.0.buffer()
+    }
+}
+
+impl BindableBuffer for AcceptedSwapsStorageBuffer {
+    fn binding_resource(&self) -> wgpu::BindingResource {
+        self.0.buffer().as_entire_binding()
+    }
+}
+
+impl OutputBuffer for AcceptedSwapsStorageBuffer {
+    fn output_binding_description(&self) -> wgpu::BindingType {
+        super::make_storage_buffer_binding_description(false, self.0.dimensions())
+    }
+}
@@ -1,7 +1,9 @@
 use super::dimensions::BufferDimensions;
 
+mod accepted_swaps;
 mod count_swap;
 
+pub use accepted_swaps::AcceptedSwapsStorageBuffer;
 pub use count_swap::CountSwapInputBuffer;
 pub use count_swap::CountSwapOutputStorageBuffer;
 
@@ -1,7 +1,8 @@
 use super::buffer::{
-    CountSwapInputBuffer, CountSwapInputLayoutBuffer, CountSwapOutputBuffer,
-    CountSwapOutputStorageBuffer, DisplacementGoalOutputBuffer, LosslessImageOutputBuffer,
-    PermutationOutputBuffer, SwapParametersBuffer,
+    AcceptedSwapsOutputBuffer, AcceptedSwapsStorageBuffer, CountSwapInputBuffer,
+    CountSwapInputLayoutBuffer, CountSwapOutputBuffer, CountSwapOutputStorageBuffer,
+    DisplacementGoalOutputBuffer, LosslessImageOutputBuffer, PermutationOutputBuffer,
+    SwapParametersBuffer,
 };
 use super::texture::{
     DisplacementGoalInputTexture, DisplacementGoalOutputTexture, LosslessImageInputTexture,
@@ -10,6 +11,8 @@ use super::texture::{
 use crate::ImageDimensions;
 
 pub struct ResourceManager {
+    accepted_swaps_output_buffer: AcceptedSwapsOutputBuffer,
+    accepted_swaps_storage_buffer: AcceptedSwapsStorageBuffer,
     count_swap_input_buffer: CountSwapInputBuffer,
     count_swap_input_layout_buffer: CountSwapInputLayoutBuffer,
     count_swap_output_buffer: CountSwapOutputBuffer,
@@ -23,6 +26,9 @@ pub struct ResourceManager {
     lossless_image_input_texture: LosslessImageInputTexture,
     lossless_image_output_texture: LosslessImageOutputTexture,
     lossless_image_output_buffer: LosslessImageOutputBuffer,
+    /// Buffers holding the permuted texel slices of an image, other than the last slice,
+    /// until they are read back
+    lossless_image_slice_output_buffers: Vec<LosslessImageOutputBuffer>,
     swap_parameters_buffer: SwapParametersBuffer,
 }
 
@@ -31,7 +37,12 @@ impl ResourceManager {
         let count_swap_output_buffer = CountSwapOutputBuffer::new(device);
         let count_swap_output_storage_buffer =
             CountSwapOutputStorageBuffer::new(device, &count_swap_output_buffer);
+        let accepted_swaps_output_buffer = AcceptedSwapsOutputBuffer::new(device, image_dimensions);
+        let accepted_swaps_storage_buffer =
+            AcceptedSwapsStorageBuffer::new(device, &accepted_swaps_output_buffer);
         Self {
+            accepted_swaps_output_buffer,
+            accepted_swaps_storage_buffer,
             count_swap_input_buffer: CountSwapInputBuffer::new(device, image_dimensions),
             count_swap_input_layout_buffer: CountSwapInputLayoutBuffer::new(device),
             count_swap_output_buffer,
@@ -57,10 +68,19 @@ impl ResourceManager {
                 image_dimensions,
             ),
             lossless_image_output_buffer: LosslessImageOutputBuffer::new(device, image_dimensions),
+            lossless_image_slice_output_buffers: Vec::new(),
             swap_parameters_buffer: SwapParametersBuffer::new(device),
         }
     }
 
+    pub fn accepted_swaps_output_buffer(&self) -> &AcceptedSwapsOutputBuffer {
+        &self.accepted_swaps_output_buffer
+    }
+
+    pub fn accepted_swaps_storage_buffer(&self) -> &AcceptedSwapsStorageBuffer {
+        &self.accepted_swaps_storage_buffer
+    }
+
     pub fn count_swap_input_buffer(&self) -> &CountSwapInputBuffer {
         &self.count_swap_input_buffer
     }
@@ -113,6 +133,22 @@ impl ResourceManager {
         &self.lossless_image_output_buffer
     }
 
+    pub fn create_lossless_image_slice_output_buffers(
+        &mut self,
+        device: &wgpu::Device,
+        image_dimensions: &ImageDimensions,
+        count: usize,
+    ) {
+        while self.lossless_image_slice_output_buffers.len() < count {
+            self.lossless_image_slice_output_buffers
+                .push(LosslessImageOutputBuffer::new(device, image_dimensions));
+        }
+    }
+
+    pub fn lossless_image_slice_output_buffer(&self, index: usize) -> &LosslessImageOutputBuffer {
+        &self.lossless_image_slice_output_buffers[index]
+    }
+
     pub fn swap_parameters_buffer(&self) -> &SwapParametersBuffer {
         &self.swap_parameters_buffer
     }
@@ -2,8 +2,11 @@ use super::device::DeviceManager;
 use super::link::swap::SwapPassSequence;
 use super::operation::manager::CountSwapOperationOutput;
 use super::operation::manager::OperationManager;
+use super::output::algorithm::swap::AcceptedSwaps;
 use super::output::format::{ImageFormat, LosslessImage};
-use crate::{DisplacementGoal, ImageDimensions, ImageDimensionsHolder, ValidatedPermutation};
+use crate::{
+    BoundaryMode, DisplacementGoal, ImageDimensions, ImageDimensionsHolder, ValidatedPermutation,
+};
 use std::error::Error;
 
 pub use super::device::DevicePollType;
@@ -15,19 +18,40 @@ pub struct System {
     device: DeviceManager,
     operations: OperationManager,
     image_dimensions: ImageDimensions,
+    boundary: BoundaryMode,
+    /// Whether the image input texture holds only the last texel slice of an image
+    /// that was permuted one slice at a time
+    lossless_image_is_slice: bool,
 }
 
 impl System {
-    pub async fn new(image_dimensions: &ImageDimensions) -> Result<Self, Box<dyn Error>> {
+    pub async fn new(
+        image_dimensions: &ImageDimensions,
+        boundary: BoundaryMode,
+    ) -> Result<Self, Box<dyn Error>> {
         let device = DeviceManager::new().await?;
-        let operations = OperationManager::new(device.device(), image_dimensions);
+        let operations = OperationManager::new(device.device(), image_dimensions, boundary);
         Ok(Self {
             device,
             operations,
             image_dimensions: *image_dimensions,
+            boundary,
+            lossless_image_is_slice: false,
         })
     }
 
+    pub fn boundary(&self) -> BoundaryMode {
+        self.boundary
+    }
+
+    pub fn lossless_image_is_slice(&self) -> bool {
+        self.lossless_image_is_slice
+    }
+
+    pub fn set_lossless_image_is_slice(&mut self) {
+        self.lossless_image_is_slice = true;
+    }
+
     pub fn operation_count_swap(
         &mut self,
         sequence: SwapPassSequence,
@@ -40,7 +64,11 @@ impl System {
         input: &CreateDisplacementGoalOperationInput,
     ) -> Result<(), Box<dyn Error>> {
         self.operations
-            .create_displacement_goal(&self.device, input)
+            .create_displacement_goal(&self.device, input)?;
+        if input.image.is_some() {
+            self.lossless_image_is_slice = false;
+        }
+        Ok(())
     }
 
     pub fn operation_create_permutation(&mut self) -> Result<(), Box<dyn Error>> {
@@ -51,7 +79,11 @@ impl System {
         &mut self,
         input: &PermuteOperationInput,
     ) -> Result<(), Box<dyn Error>> {
-        self.operations.permute(&self.device, input)
+        self.operations.permute(&self.device, input)?;
+        if input.image.is_some() {
+            self.lossless_image_is_slice = false;
+        }
+        Ok(())
     }
 
     pub fn operation_swap(&mut self, input: &SwapOperationInput) -> Result<(), Box<dyn Error>> {
@@ -68,6 +100,16 @@ impl System {
             .await
     }
 
+    pub async fn output_accepted_swaps(
+        &mut self,
+        poll_type: DevicePollType,
+        sequence: &SwapPassSequence,
+    ) -> Result<Vec<AcceptedSwaps>, Box<dyn Error>> {
+        self.operations
+            .output_accepted_swaps(&self.device, poll_type, sequence)
+            .await
+    }
+
     pub async fn output_displacement_goal(
         &mut self,
         poll_type: DevicePollType,
@@ -95,6 +137,25 @@ impl System {
             .output_permuted_image(&self.device, poll_type, format)
             .await
     }
+
+    /// Copies the permuted image to a buffer which holds it until it is read back by
+    /// [`System::output_permuted_image_slice`], so that the next texel slice of the image
+    /// can be permuted in the meantime
+    pub fn copy_permuted_image_slice(&mut self, slice_index: usize) -> Result<(), Box<dyn Error>> {
+        self.operations
+            .copy_permuted_image_slice(&self.device, slice_index)
+    }
+
+    pub async fn output_permuted_image_slice(
+        &self,
+        poll_type: DevicePollType,
+        slice_index: usize,
+        format: ImageFormat,
+    ) -> Result<LosslessImage, Box<dyn Error>> {
+        self.operations
+            .output_permuted_image_slice(&self.device, poll_type, slice_index, format)
+            .await
+    }
 }
 
 impl ImageDimensionsHolder for System {
@@ -2,7 +2,11 @@ use super::System;
 use crate::ImageDimensions;
 
 fn create_system_single_pixel() -> System {
-    futures::executor::block_on(System::new(&ImageDimensions::try_new(1, 1).unwrap())).unwrap()
+    futures::executor::block_on(System::new(
+        &ImageDimensions::try_new(1, 1).unwrap(),
+        Default::default(),
+    ))
+    .unwrap()
 }
 
 mod operation_count_swap {
@@ -141,10 +145,10 @@ mod output_permutation {
         let image = LosslessImage::Rgba16(Rgba16Image::new(
             test_util::image::coordinates_to_colors(&dimensions),
         )?);
-        let mut system = futures::executor::block_on(System::new(&ImageDimensions::try_new(
-            dimensions.width(),
-            dimensions.height(),
-        )?))?;
+        let mut system = futures::executor::block_on(System::new(
+            &ImageDimensions::try_new(dimensions.width(), dimensions.height())?,
+            Default::default(),
+        ))?;
         system.operation_permute(&PermuteOperationInput {
             image: Some(&image),
             permutation: Some(&unsafe {
@@ -174,3 +178,14 @@ mod output_permuted_image {
         );
     }
 }
+
+mod copy_permuted_image_slice {
+    #[test]
+    fn no_preceding_operations() {
+        let mut system = super::create_system_single_pixel();
+        test_util::assert_error_contains(
+            system.copy_permuted_image_slice(0),
+            "an output image does not exist or has been invalidated",
+        );
+    }
+}
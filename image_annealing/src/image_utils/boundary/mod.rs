@@ -0,0 +1,40 @@
+use super::ImageDimensions;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum BoundaryMode {
+    #[default]
+    Bounded,
+    Toroidal,
+}
+
+impl BoundaryMode {
+    pub fn is_toroidal(&self) -> bool {
+        match self {
+            Self::Bounded => false,
+            Self::Toroidal => true,
+        }
+    }
+
+    pub fn resolve_coordinates(&self, dimensions: &ImageDimensions, x: i64, y: i64) -> (i64, i64) {
+        match self {
+            Self::Bounded => (x, y),
+            Self::Toroidal => (
+                x.rem_euclid(dimensions.width().try_into().unwrap()),
+                y.rem_euclid(dimensions.height().try_into().unwrap()),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for BoundaryMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Bounded => write!(f, "bounded"),
+            Self::Toroidal => write!(f, "toroidal"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
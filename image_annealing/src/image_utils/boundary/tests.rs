@@ -0,0 +1,57 @@
+#[test]
+fn default() {
+    assert_eq!(
+        <super::BoundaryMode as Default>::default(),
+        super::BoundaryMode::Bounded
+    );
+}
+
+#[test]
+fn is_toroidal() {
+    assert!(!super::BoundaryMode::Bounded.is_toroidal());
+    assert!(super::BoundaryMode::Toroidal.is_toroidal());
+}
+
+mod resolve_coordinates {
+    use super::super::BoundaryMode;
+    use crate::ImageDimensions;
+    use std::error::Error;
+
+    #[test]
+    fn bounded() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(3, 4)?;
+        assert_eq!(
+            BoundaryMode::Bounded.resolve_coordinates(&dimensions, 1, 2),
+            (1, 2)
+        );
+        assert_eq!(
+            BoundaryMode::Bounded.resolve_coordinates(&dimensions, -1, 4),
+            (-1, 4)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn toroidal() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(3, 4)?;
+        assert_eq!(
+            BoundaryMode::Toroidal.resolve_coordinates(&dimensions, 1, 2),
+            (1, 2)
+        );
+        assert_eq!(
+            BoundaryMode::Toroidal.resolve_coordinates(&dimensions, -1, 4),
+            (2, 0)
+        );
+        assert_eq!(
+            BoundaryMode::Toroidal.resolve_coordinates(&dimensions, 7, -9),
+            (1, 3)
+        );
+        Ok(())
+    }
+}
+
+#[test]
+fn display() {
+    assert_eq!(super::BoundaryMode::Bounded.to_string(), "bounded");
+    assert_eq!(super::BoundaryMode::Toroidal.to_string(), "toroidal");
+}
@@ -1,8 +1,8 @@
 use super::validation::{self, CandidatePermutation, ValidatedPermutation};
 use super::{manipulation, VectorField};
 use crate::compute::format::{
-    self, ImageFileReader, ImageFileWriter, ImageFileWriterSaveResult, Rgba8Image,
-    VectorFieldImageBuffer, VectorFieldImageBufferComponent,
+    self, ImageFileReader, ImageFileWriter, ImageFileWriterEncodeResult, ImageFileWriterSaveResult,
+    Rgba8Image, VectorFieldFormat, VectorFieldImageBuffer, VectorFieldImageBufferComponent,
 };
 use crate::{ImageDimensions, ImageDimensionsHolder};
 use std::error::Error;
@@ -68,9 +68,11 @@ impl VectorField for DisplacementGoal {
 
 impl ImageFileReader for DisplacementGoal {
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
-        Ok(Self::from_vector_field_image(
-            <Rgba8Image as ImageFileReader>::load(path)?,
-        ))
+        Self::from_vector_field(VectorFieldFormat::from_path_or_default(&path).load(path)?)
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::from_vector_field(VectorFieldFormat::default().decode(data)?)
     }
 }
 
@@ -83,6 +85,10 @@ impl ImageFileWriter for DisplacementGoal {
     ) -> ImageFileWriterSaveResult {
         self.0.save_add_extension(path_no_extension)
     }
+
+    fn encode(&self) -> ImageFileWriterEncodeResult {
+        self.0.encode()
+    }
 }
 
 impl From<ValidatedPermutation> for DisplacementGoal {
@@ -0,0 +1,62 @@
+use super::{BoundaryMode, DimensionsMismatchError, ImageDimensions};
+use crate::compute::conversion::VectorFieldEntry;
+use crate::{DisplacementGoal, ValidatedPermutation};
+
+/// Returns the shortest distance between two positions, measured around the torus
+/// under toroidal boundary conditions
+fn separation(dimensions: &ImageDimensions, dx: i64, dy: i64, boundary: BoundaryMode) -> f64 {
+    let (dx, dy) = if boundary.is_toroidal() {
+        let (width, height) = (
+            i64::try_from(dimensions.width()).unwrap(),
+            i64::try_from(dimensions.height()).unwrap(),
+        );
+        let (wrapped_dx, wrapped_dy) = (dx.rem_euclid(width), dy.rem_euclid(height));
+        (
+            wrapped_dx.min(width - wrapped_dx),
+            wrapped_dy.min(height - wrapped_dy),
+        )
+    } else {
+        (dx, dy)
+    };
+    (dx as f64).hypot(dy as f64)
+}
+
+/// Returns the total potential energy of a permutation with respect to a displacement goal,
+/// which the swap algorithm decreases by accepting swaps
+///
+/// Each pixel contributes `d / (d + 1)`, where `d` is the distance from the pixel to the
+/// position that the displacement goal assigns to the pixel that the permutation moved there.
+pub fn potential_energy(
+    permutation: &ValidatedPermutation,
+    displacement_goal: &DisplacementGoal,
+    boundary: BoundaryMode,
+) -> Result<f64, DimensionsMismatchError> {
+    let dimensions = super::check_dimensions_match2(permutation, displacement_goal)?;
+    let goal = displacement_goal.as_ref();
+    Ok(permutation
+        .as_ref()
+        .enumerate_pixels()
+        .map(|(x, y, px)| {
+            let (x, y) = (i64::from(x), i64::from(y));
+            let VectorFieldEntry(permutation_x, permutation_y) = VectorFieldEntry::from_pixel(px);
+            let origin = boundary.resolve_coordinates(
+                dimensions,
+                x + i64::from(permutation_x),
+                y + i64::from(permutation_y),
+            );
+            let VectorFieldEntry(goal_x, goal_y) = VectorFieldEntry::from_pixel(
+                goal.get_pixel(origin.0.try_into().unwrap(), origin.1.try_into().unwrap()),
+            );
+            let distance = separation(
+                dimensions,
+                origin.0 + i64::from(goal_x) - x,
+                origin.1 + i64::from(goal_y) - y,
+                boundary,
+            );
+            distance / (distance + 1.0)
+        })
+        .sum())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,99 @@
+use crate::compute::conversion::{self, VectorFieldEntry};
+use crate::image_utils::validation;
+use crate::{BoundaryMode, DisplacementGoal, ImageDimensions, ValidatedPermutation};
+use std::error::Error;
+
+fn make_permutation(
+    dimensions: &ImageDimensions,
+    entries: &[VectorFieldEntry],
+) -> Result<ValidatedPermutation, Box<dyn Error>> {
+    validation::validate_permutation(conversion::to_image(dimensions, entries))
+}
+
+fn make_displacement_goal(
+    dimensions: &ImageDimensions,
+    entries: &[VectorFieldEntry],
+) -> Result<DisplacementGoal, Box<dyn Error>> {
+    DisplacementGoal::from_vector_field(conversion::to_image(dimensions, entries))
+}
+
+#[test]
+fn goal_reached() -> Result<(), Box<dyn Error>> {
+    let dimensions = ImageDimensions::try_new(2, 1)?;
+    let entries = [VectorFieldEntry(1, 0), VectorFieldEntry(-1, 0)];
+    assert_eq!(
+        super::potential_energy(
+            &make_permutation(&dimensions, &entries)?,
+            &make_displacement_goal(&dimensions, &entries)?,
+            BoundaryMode::Bounded
+        )?,
+        0.0
+    );
+    Ok(())
+}
+
+#[test]
+fn goal_not_reached() -> Result<(), Box<dyn Error>> {
+    let dimensions = ImageDimensions::try_new(2, 1)?;
+    assert_eq!(
+        super::potential_energy(
+            &make_permutation(
+                &dimensions,
+                &[VectorFieldEntry(0, 0), VectorFieldEntry(0, 0)]
+            )?,
+            &make_displacement_goal(
+                &dimensions,
+                &[VectorFieldEntry(1, 0), VectorFieldEntry(-1, 0)]
+            )?,
+            BoundaryMode::Bounded
+        )?,
+        1.0
+    );
+    Ok(())
+}
+
+#[test]
+fn toroidal() -> Result<(), Box<dyn Error>> {
+    let dimensions = ImageDimensions::try_new(3, 1)?;
+    let permutation = make_permutation(
+        &dimensions,
+        &[
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+        ],
+    )?;
+    let displacement_goal = make_displacement_goal(
+        &dimensions,
+        &[
+            VectorFieldEntry(2, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+        ],
+    )?;
+    assert_eq!(
+        super::potential_energy(&permutation, &displacement_goal, BoundaryMode::Bounded)?,
+        2.0 / 3.0
+    );
+    assert_eq!(
+        super::potential_energy(&permutation, &displacement_goal, BoundaryMode::Toroidal)?,
+        0.5
+    );
+    Ok(())
+}
+
+#[test]
+fn dimensions_mismatch() -> Result<(), Box<dyn Error>> {
+    let permutation = make_permutation(
+        &ImageDimensions::try_new(2, 1)?,
+        &[VectorFieldEntry(0, 0), VectorFieldEntry(0, 0)],
+    )?;
+    let displacement_goal = make_displacement_goal(
+        &ImageDimensions::try_new(1, 2)?,
+        &[VectorFieldEntry(0, 0), VectorFieldEntry(0, 0)],
+    )?;
+    assert!(
+        super::potential_energy(&permutation, &displacement_goal, BoundaryMode::Bounded).is_err()
+    );
+    Ok(())
+}
@@ -1,10 +1,14 @@
 use crate::compute::format::{self, VectorFieldImageBuffer, VectorFieldImageBufferComponent};
 
+mod boundary;
 mod dimension;
 pub mod displacement_goal;
+pub mod energy;
 mod manipulation;
 pub mod validation;
+pub mod visualization;
 
+pub use boundary::BoundaryMode;
 pub(crate) use dimension::{
     check_dimensions_match2, check_dimensions_match3, check_dimensions_match4,
 };
@@ -1,9 +1,9 @@
 use super::{manipulation, VectorField};
-use super::{ImageDimensions, ImageDimensionsHolder};
+use super::{BoundaryMode, ImageDimensions, ImageDimensionsHolder};
 use crate::compute::conversion::VectorFieldEntry;
 use crate::compute::format::{
-    self, ImageFileReader, ImageFileWriter, ImageFileWriterSaveResult, Rgba8Image,
-    VectorFieldImageBuffer, VectorFieldImageBufferComponent,
+    self, ImageFileReader, ImageFileWriter, ImageFileWriterEncodeResult, ImageFileWriterSaveResult,
+    Rgba8Image, VectorFieldFormat, VectorFieldImageBuffer, VectorFieldImageBufferComponent,
 };
 use std::error::Error;
 use std::fmt;
@@ -112,7 +112,11 @@ impl VectorField for CandidatePermutation {
 
 impl ImageFileReader for CandidatePermutation {
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
-        Ok(Self(<Rgba8Image as ImageFileReader>::load(path)?))
+        Self::from_vector_field(VectorFieldFormat::from_path_or_default(&path).load(path)?)
+    }
+
+    fn decode(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+        Self::from_vector_field(VectorFieldFormat::default().decode(data)?)
     }
 }
 
@@ -181,18 +185,39 @@ impl ImageFileWriter for ValidatedPermutation {
     ) -> ImageFileWriterSaveResult {
         self.data.save_add_extension(path_no_extension)
     }
+
+    fn encode(&self) -> ImageFileWriterEncodeResult {
+        self.data.encode()
+    }
 }
 
 pub(crate) fn validate_permutation(
     image: VectorFieldImageBuffer,
+) -> Result<ValidatedPermutation, Box<dyn Error>> {
+    validate_permutation_with_boundary(image, BoundaryMode::Bounded)
+}
+
+// Under toroidal boundary conditions, vectors are rewritten so that they point
+// to locations within the image, without changing the permutation they represent
+pub(crate) fn validate_permutation_with_boundary(
+    mut image: VectorFieldImageBuffer,
+    boundary: BoundaryMode,
 ) -> Result<ValidatedPermutation, Box<dyn Error>> {
     let dimensions = ImageDimensions::from_image(&image)?;
     let mut sources: Vec<Option<PermutationPixelData>> = vec![None; dimensions.count()];
-    for (x_in, y_in, px) in image.enumerate_pixels() {
+    for (x_in, y_in, px) in image.enumerate_pixels_mut() {
         let x = i64::from(x_in);
         let y = i64::from(y_in);
         let delta = VectorFieldEntry::from_pixel(px);
-        let target = (x + i64::from(delta.0), y + i64::from(delta.1));
+        let target = boundary.resolve_coordinates(
+            &dimensions,
+            x + i64::from(delta.0),
+            y + i64::from(delta.1),
+        );
+        if boundary.is_toroidal() {
+            *px =
+                VectorFieldEntry((target.0 - x).try_into()?, (target.1 - y).try_into()?).to_pixel();
+        }
         match dimensions.make_linear_index(target.0, target.1) {
             Err(_) => {
                 return Err(Box::new(PermutationFlaw::OutOfBounds {
@@ -337,3 +337,78 @@ mod validate_permutation {
         Ok(())
     }
 }
+
+mod validate_permutation_with_boundary {
+    use super::super::validate_permutation_with_boundary;
+    use crate::compute::conversion::{self, VectorFieldEntry};
+    use crate::{BoundaryMode, ImageDimensions};
+    use std::error::Error;
+    use test_util::permutation::{self, DimensionsAndPermutation};
+
+    #[test]
+    fn non_identity_toroidal() -> Result<(), Box<dyn Error>> {
+        let DimensionsAndPermutation { permutation, .. } = permutation::non_identity();
+        let expected = permutation.clone();
+        let permutation = validate_permutation_with_boundary(permutation, BoundaryMode::Toroidal)?;
+        assert_eq!(*permutation.as_ref(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn wrapped_bounded() -> Result<(), Box<dyn Error>> {
+        let permutation = conversion::to_image(
+            &ImageDimensions::try_new(1, 3)?,
+            &[
+                VectorFieldEntry(0, -1),
+                VectorFieldEntry(0, -1),
+                VectorFieldEntry(3, -1),
+            ],
+        );
+        test_util::assert_error_contains(
+            validate_permutation_with_boundary(permutation, BoundaryMode::Bounded),
+            "out of bounds mapping (x, y, delta_x, delta_y) = (0, 0, 0, -1) for an image of dimensions (width, height) = (1, 3)",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn wrapped_toroidal() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(1, 3)?;
+        let permutation = conversion::to_image(
+            &dimensions,
+            &[
+                VectorFieldEntry(0, -1),
+                VectorFieldEntry(0, -1),
+                VectorFieldEntry(3, -1),
+            ],
+        );
+        let expected = conversion::to_image(
+            &dimensions,
+            &[
+                VectorFieldEntry(0, 2),
+                VectorFieldEntry(0, -1),
+                VectorFieldEntry(0, -1),
+            ],
+        );
+        let permutation = validate_permutation_with_boundary(permutation, BoundaryMode::Toroidal)?;
+        assert_eq!(*permutation.as_ref(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_toroidal() -> Result<(), Box<dyn Error>> {
+        let permutation = conversion::to_image(
+            &ImageDimensions::try_new(1, 3)?,
+            &[
+                VectorFieldEntry(0, 0),
+                VectorFieldEntry(0, 2),
+                VectorFieldEntry(0, -1),
+            ],
+        );
+        test_util::assert_error_contains(
+            validate_permutation_with_boundary(permutation, BoundaryMode::Toroidal),
+            "entries (x, y, delta_x, delta_y) = (0, 0, 0, 0) and (x, y, delta_x, delta_y) = (0, 1, 0, 2) both map to location (x, y) = (0, 0)",
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use super::VectorField;
+use crate::compute::conversion::VectorFieldEntry;
+use crate::compute::format::Rgba8Image;
+use std::f64::consts::PI;
+use std::num::NonZeroU32;
+
+const ARROW_COLOR: image::Rgba<u8> = image::Rgba([255, 255, 255, 255]);
+const ARROW_HEAD_ANGLE: f64 = PI / 6.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VectorFieldVisualizationParameters {
+    /// The vector magnitude shown at full brightness, or the largest magnitude
+    /// in the vector field if `None`. Larger magnitudes are clamped.
+    pub max_magnitude: Option<f64>,
+    /// The spacing, in pixels, of a grid of arrows drawn over the image, or no arrows if `None`
+    pub arrow_spacing: Option<NonZeroU32>,
+}
+
+/// Returns the largest magnitude of any vector in the vector field
+pub fn max_magnitude<V: VectorField>(vector_field: &V) -> f64 {
+    vector_field
+        .as_ref()
+        .pixels()
+        .map(|px| magnitude(VectorFieldEntry::from_pixel(px)))
+        .fold(0.0, f64::max)
+}
+
+fn magnitude(entry: VectorFieldEntry) -> f64 {
+    f64::from(entry.0).hypot(f64::from(entry.1))
+}
+
+/// Converts a color from HSV to RGB, where `hue` is in degrees,
+/// and `saturation` and `value` are between zero and one
+fn hsv_to_rgb(hue: f64, saturation: f64, value: f64) -> [u8; 3] {
+    let chroma = value * saturation;
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    [r, g, b].map(|channel| ((channel + m) * 255.0).round() as u8)
+}
+
+/// Maps the direction of a vector to hue, and its magnitude relative to `max_magnitude` to value
+fn vector_to_color(x: f64, y: f64, max_magnitude: f64) -> image::Rgba<u8> {
+    let value = (x.hypot(y) / max_magnitude).min(1.0);
+    let [r, g, b] = hsv_to_rgb(y.atan2(x).to_degrees(), 1.0, value);
+    image::Rgba([r, g, b, u8::MAX])
+}
+
+fn draw_line(image: &mut image::RgbaImage, from: (f64, f64), to: (f64, f64)) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let steps = dx.abs().max(dy.abs()).ceil().max(1.0);
+    for step in 0..=(steps as u32) {
+        let t = f64::from(step) / steps;
+        let (x, y) = ((from.0 + dx * t).round(), (from.1 + dy * t).round());
+        if x >= 0.0 && y >= 0.0 && x < f64::from(image.width()) && y < f64::from(image.height()) {
+            image.put_pixel(x as u32, y as u32, ARROW_COLOR);
+        }
+    }
+}
+
+/// Draws an arrow for the vector at the center of each grid cell. The longest arrows,
+/// for vectors with magnitudes of at least `max_magnitude`, reach the edges of their cells.
+fn draw_arrows(
+    image: &mut image::RgbaImage,
+    vector_field: &image::RgbaImage,
+    spacing: u32,
+    max_magnitude: f64,
+) {
+    let max_length = f64::from(spacing) / 2.0;
+    for y in (spacing / 2..vector_field.height()).step_by(spacing as usize) {
+        for x in (spacing / 2..vector_field.width()).step_by(spacing as usize) {
+            let entry = VectorFieldEntry::from_pixel(vector_field.get_pixel(x, y));
+            let length = (magnitude(entry) / max_magnitude).min(1.0) * max_length;
+            if length < 1.0 {
+                continue;
+            }
+            let angle = f64::from(entry.1).atan2(f64::from(entry.0));
+            let start = (f64::from(x), f64::from(y));
+            let tip = (
+                start.0 + length * angle.cos(),
+                start.1 + length * angle.sin(),
+            );
+            draw_line(image, start, tip);
+            let head_length = (length / 3.0).max(1.0);
+            for head_angle in [angle + PI - ARROW_HEAD_ANGLE, angle + PI + ARROW_HEAD_ANGLE] {
+                draw_line(
+                    image,
+                    tip,
+                    (
+                        tip.0 + head_length * head_angle.cos(),
+                        tip.1 + head_length * head_angle.sin(),
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Renders a vector field as a color wheel image, where the hue of a pixel
+/// shows the direction of its vector, and the brightness shows the magnitude of its vector
+pub fn render<V: VectorField>(
+    vector_field: &V,
+    parameters: &VectorFieldVisualizationParameters,
+) -> Rgba8Image {
+    let max_magnitude = parameters
+        .max_magnitude
+        .unwrap_or_else(|| max_magnitude(vector_field));
+    // Avoid division by zero when all vectors are zero
+    let max_magnitude = if max_magnitude > 0.0 {
+        max_magnitude
+    } else {
+        1.0
+    };
+    let field = vector_field.as_ref();
+    let mut image = image::RgbaImage::from_fn(field.width(), field.height(), |x, y| {
+        let entry = VectorFieldEntry::from_pixel(field.get_pixel(x, y));
+        vector_to_color(f64::from(entry.0), f64::from(entry.1), max_magnitude)
+    });
+    if let Some(spacing) = parameters.arrow_spacing {
+        draw_arrows(&mut image, field, spacing.get(), max_magnitude);
+    }
+    Rgba8Image::new(image).unwrap()
+}
+
+/// Renders a legend for the images produced by [`render`]
+///
+/// The legend is a disc showing the colors of vectors from the center of the disc to each pixel.
+/// Vectors reaching the edge of the disc have the maximum magnitude. Pixels outside the disc
+/// are transparent.
+pub fn render_legend(size: NonZeroU32) -> Rgba8Image {
+    let radius = f64::from(size.get()) / 2.0;
+    let image = image::RgbaImage::from_fn(size.get(), size.get(), |x, y| {
+        let (offset_x, offset_y) = (f64::from(x) + 0.5 - radius, f64::from(y) + 0.5 - radius);
+        if offset_x.hypot(offset_y) <= radius {
+            vector_to_color(offset_x, offset_y, radius)
+        } else {
+            image::Rgba([0, 0, 0, 0])
+        }
+    });
+    Rgba8Image::new(image).unwrap()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,117 @@
+use super::{VectorFieldVisualizationParameters, ARROW_COLOR};
+use crate::compute::conversion::{self, VectorFieldEntry};
+use crate::{DisplacementGoal, ImageDimensions};
+use std::error::Error;
+use std::num::NonZeroU32;
+
+fn make_vector_field(
+    width: u32,
+    height: u32,
+    entries: &[VectorFieldEntry],
+) -> Result<DisplacementGoal, Box<dyn Error>> {
+    DisplacementGoal::from_vector_field(conversion::to_image(
+        &ImageDimensions::try_new(width, height)?,
+        entries,
+    ))
+}
+
+#[test]
+fn max_magnitude() -> Result<(), Box<dyn Error>> {
+    let vector_field = make_vector_field(
+        3,
+        1,
+        &[
+            VectorFieldEntry(1, 0),
+            VectorFieldEntry(-3, 4),
+            VectorFieldEntry(0, -2),
+        ],
+    )?;
+    assert_eq!(super::max_magnitude(&vector_field), 5.0);
+    Ok(())
+}
+
+#[test]
+fn hue_and_value() -> Result<(), Box<dyn Error>> {
+    let vector_field = make_vector_field(
+        6,
+        1,
+        &[
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(2, 0),
+            VectorFieldEntry(0, 2),
+            VectorFieldEntry(-2, 0),
+            VectorFieldEntry(0, -2),
+            VectorFieldEntry(1, 0),
+        ],
+    )?;
+    let image = super::render(&vector_field, &Default::default());
+    let pixels = image.as_ref().pixels().map(|px| px.0).collect::<Vec<_>>();
+    assert_eq!(
+        pixels,
+        [
+            [0, 0, 0, 255],
+            [255, 0, 0, 255],
+            [128, 255, 0, 255],
+            [0, 255, 255, 255],
+            [128, 0, 255, 255],
+            [128, 0, 0, 255],
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn clamped_max_magnitude() -> Result<(), Box<dyn Error>> {
+    let vector_field = make_vector_field(2, 1, &[VectorFieldEntry(4, 0), VectorFieldEntry(1, 0)])?;
+    let image = super::render(
+        &vector_field,
+        &VectorFieldVisualizationParameters {
+            max_magnitude: Some(2.0),
+            arrow_spacing: None,
+        },
+    );
+    let pixels = image.as_ref().pixels().map(|px| px.0).collect::<Vec<_>>();
+    assert_eq!(pixels, [[255, 0, 0, 255], [128, 0, 0, 255]]);
+    Ok(())
+}
+
+#[test]
+fn zero_vector_field() -> Result<(), Box<dyn Error>> {
+    let vector_field = make_vector_field(2, 1, &[VectorFieldEntry(0, 0); 2])?;
+    let image = super::render(&vector_field, &Default::default());
+    assert!(image.as_ref().pixels().all(|px| px.0 == [0, 0, 0, 255]));
+    Ok(())
+}
+
+#[test]
+fn arrows() -> Result<(), Box<dyn Error>> {
+    let vector_field = make_vector_field(5, 5, &[VectorFieldEntry(1, 0); 25])?;
+    let image = super::render(
+        &vector_field,
+        &VectorFieldVisualizationParameters {
+            max_magnitude: None,
+            arrow_spacing: NonZeroU32::new(5),
+        },
+    );
+    let image = image.as_ref();
+    // The arrow starts at the center of the grid cell and points to the right
+    for x in 2..5 {
+        assert_eq!(*image.get_pixel(x, 2), ARROW_COLOR);
+    }
+    assert_ne!(*image.get_pixel(1, 2), ARROW_COLOR);
+    assert_ne!(*image.get_pixel(2, 0), ARROW_COLOR);
+    Ok(())
+}
+
+#[test]
+fn legend() {
+    let legend = super::render_legend(NonZeroU32::new(16).unwrap());
+    let legend = legend.as_ref();
+    assert_eq!(legend.dimensions(), (16, 16));
+    assert_eq!(legend.get_pixel(0, 0).0, [0, 0, 0, 0]);
+    assert_eq!(legend.get_pixel(15, 7).0[3], 255);
+    let right = legend.get_pixel(15, 8).0;
+    assert!(right[0] > 200 && right[1] < 50 && right[2] < 50);
+    let center = legend.get_pixel(8, 8).0;
+    assert!(center[0] < 50 && center[1] < 50 && center[2] < 50);
+}
@@ -4,6 +4,6 @@ pub mod image_utils;
 pub use image_utils::displacement_goal::DisplacementGoal;
 pub use image_utils::validation::{CandidatePermutation, ValidatedPermutation};
 pub use image_utils::{
-    DimensionsMismatchError, ImageDimensions, ImageDimensionsHolder, InvalidDimensionError,
-    VectorField,
+    BoundaryMode, DimensionsMismatchError, ImageDimensions, ImageDimensionsHolder,
+    InvalidDimensionError, VectorField,
 };
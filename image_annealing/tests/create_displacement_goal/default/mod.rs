@@ -24,6 +24,7 @@ fn run_once_all_inputs() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.create_displacement_goal(
         CreateDisplacementGoalInput {
@@ -54,6 +55,7 @@ fn run_once_no_inputs() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm =
         dispatcher.create_displacement_goal(Default::default(), &Default::default());
@@ -74,6 +76,7 @@ async fn run_once_no_inputs_async_inner() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })
     .await?;
     let mut algorithm =
@@ -108,6 +111,7 @@ fn run_twice() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.create_displacement_goal(
         CreateDisplacementGoalInput {
@@ -18,6 +18,7 @@ fn invalid_displacement_goal_dimensions() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: other_dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.create_displacement_goal(
         CreateDisplacementGoalInput {
@@ -52,6 +53,7 @@ fn run_twice_invalid_permutation_valid() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.create_displacement_goal(
         CreateDisplacementGoalInput {
@@ -92,6 +94,7 @@ fn invalid_permutation_dimensions() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: other_dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.create_displacement_goal(
         CreateDisplacementGoalInput {
@@ -119,6 +122,7 @@ fn invalid_image_dimensions() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.create_displacement_goal(
         CreateDisplacementGoalInput {
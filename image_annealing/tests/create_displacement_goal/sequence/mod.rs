@@ -19,6 +19,7 @@ fn overwrite_swap_displacement_goal() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let swap_parameters = test_util::algorithm::default_swap_parameters();
     let mut algorithm = dispatcher.swap(
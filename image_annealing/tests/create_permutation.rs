@@ -17,6 +17,7 @@ fn run_once() -> Result<(), Box<dyn Error>> {
     let dim = ImageDimensions::try_new(3, 4)?;
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dim,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.create_permutation(Default::default(), &Default::default());
     assert_step_until_success(algorithm.as_mut(), OutputStatus::FinalFullOutput)?;
@@ -31,6 +32,7 @@ async fn run_once_async_inner() -> Result<(), Box<dyn Error>> {
     let dim = ImageDimensions::try_new(3, 4)?;
     let dispatcher = compute::create_dispatcher(&Config {
         image_dimensions: dim,
+        boundary: Default::default(),
     })
     .await?;
     let mut algorithm = dispatcher.create_permutation(Default::default(), &Default::default());
@@ -52,6 +54,7 @@ fn run_twice() -> Result<(), Box<dyn Error>> {
     let dim = ImageDimensions::try_new(35, 42)?;
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dim,
+        boundary: Default::default(),
     })?;
 
     let mut algorithm = dispatcher.create_permutation(Default::default(), &Default::default());
@@ -82,6 +85,7 @@ fn overwrite_permute() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
@@ -120,6 +124,7 @@ fn overwrite_swap() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let swap_parameters = test_util::algorithm::default_swap_parameters();
     let mut algorithm = dispatcher.swap(
@@ -1,9 +1,13 @@
-use image_annealing::compute::format::{ImageFormat, LosslessImage, Rgba16Image};
+use image_annealing::compute::format::{
+    ImageFormat, ImageLayer, ImageLayerFormat, LayeredImage, LayeredImageFormat, LosslessImage,
+    Rgba16Image, Rgba8Image,
+};
 use image_annealing::compute::{self, Config, OutputStatus, PermuteInput, PermuteParameters};
 use image_annealing::{CandidatePermutation, ImageDimensions};
 use std::default::Default;
 use std::error::Error;
 use test_util::algorithm::{assert_step_until_error, assert_step_until_success};
+use test_util::image::{DimensionsAndRgba16Buffer, DimensionsAndRgba8Buffer};
 use test_util::permutation::DimensionsAndPermutation;
 
 #[test]
@@ -27,6 +31,7 @@ fn run_twice_invalid_permutation_valid() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
@@ -69,6 +74,7 @@ fn invalid_image_dimensions() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
@@ -99,6 +105,7 @@ fn invalid_permutation_dimensions() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: other_dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
@@ -124,6 +131,7 @@ fn forget_image() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })
     .unwrap();
     let mut algorithm = dispatcher.permute(
@@ -143,6 +151,83 @@ fn forget_image() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn forget_layered_image() -> Result<(), Box<dyn Error>> {
+    let DimensionsAndPermutation {
+        permutation,
+        dimensions,
+    } = test_util::permutation::identity();
+
+    let dispatcher = compute::create_dispatcher_block(&Config {
+        image_dimensions: dimensions,
+        boundary: Default::default(),
+    })
+    .unwrap();
+    let mut algorithm = dispatcher.permute(
+        PermuteInput {
+            candidate_permutation: Some(CandidatePermutation::from_vector_field(permutation)?),
+            ..Default::default()
+        },
+        &PermuteParameters {
+            permuted_image_format: Some(ImageFormat::Layered(LayeredImageFormat::new(&[
+                ImageLayerFormat::Rgba16,
+                ImageLayerFormat::Rgba16,
+                ImageLayerFormat::Rgba8,
+            ])?)),
+        },
+    );
+    assert_step_until_error(
+        algorithm.as_mut(),
+        OutputStatus::FinalFullOutput,
+        "the permuted output image has format layered 16-bit RGBA + 16-bit RGBA + 8-bit RGBA, which does not fit in a single texture",
+    );
+    Ok(())
+}
+
+#[test]
+fn reuse_sliced_image() -> Result<(), Box<dyn Error>> {
+    let DimensionsAndRgba16Buffer {
+        image: image1,
+        dimensions,
+    } = test_util::image::linear_indices_with_bias_to_colors(0);
+    let DimensionsAndRgba16Buffer { image: image2, .. } =
+        test_util::image::linear_indices_with_bias_to_colors(0);
+    let DimensionsAndRgba8Buffer { image: image3, .. } =
+        test_util::image::linear_indices_with_bias_to_colors(0);
+    let original_image = LosslessImage::Layered(LayeredImage::new(vec![
+        ImageLayer::Rgba16(Rgba16Image::new(image1)?),
+        ImageLayer::Rgba16(Rgba16Image::new(image2)?),
+        ImageLayer::Rgba8(Rgba8Image::new(image3)?),
+    ])?);
+
+    let mut dispatcher = compute::create_dispatcher_block(&Config {
+        image_dimensions: dimensions,
+        boundary: Default::default(),
+    })?;
+    let mut algorithm = dispatcher.permute(
+        PermuteInput {
+            original_image: Some(original_image),
+            ..Default::default()
+        },
+        &Default::default(),
+    );
+    assert_step_until_success(algorithm.as_mut(), OutputStatus::FinalFullOutput)?;
+    dispatcher = algorithm.return_to_dispatcher();
+
+    algorithm = dispatcher.permute(
+        Default::default(),
+        &PermuteParameters {
+            permuted_image_format: Some(ImageFormat::Rgba8),
+        },
+    );
+    assert_step_until_error(
+        algorithm.as_mut(),
+        OutputStatus::FinalFullOutput,
+        "the original image must be provided again",
+    );
+    Ok(())
+}
+
 #[test]
 fn forgot_format() -> Result<(), Box<dyn Error>> {
     let DimensionsAndPermutation {
@@ -156,6 +241,7 @@ fn forgot_format() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
@@ -205,6 +291,7 @@ fn format_mismatch() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
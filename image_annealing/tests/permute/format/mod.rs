@@ -1,6 +1,6 @@
 use image_annealing::compute::format::{
-    LosslessImage, Rgba16Image, Rgba16Rgba8Image, Rgba16Rgba8x2Image, Rgba16x2Image, Rgba8Image,
-    Rgba8x2Image, Rgba8x3Image, Rgba8x4Image,
+    ImageLayer, LayeredImage, LosslessImage, Rgba16Image, Rgba16Rgba8Image, Rgba16Rgba8x2Image,
+    Rgba16x2Image, Rgba8Image, Rgba8x2Image, Rgba8x3Image, Rgba8x4Image,
 };
 use image_annealing::compute::{self, Config, OutputStatus, PermuteInput};
 use image_annealing::{CandidatePermutation, ImageDimensionsHolder};
@@ -54,10 +54,31 @@ fn permute_lossless_image(original_image: LosslessImage) -> Result<(), Box<dyn E
                 test_util::permutation::non_identity_forward_permute(image.third_inner()),
             )?)
         }
+        LosslessImage::Layered(ref image) => LosslessImage::Layered(LayeredImage::new(
+            image
+                .layers()
+                .iter()
+                .map(|layer| match layer {
+                    ImageLayer::Rgba8(layer) => ImageLayer::Rgba8(
+                        Rgba8Image::new(test_util::permutation::non_identity_forward_permute(
+                            layer.as_ref(),
+                        ))
+                        .unwrap(),
+                    ),
+                    ImageLayer::Rgba16(layer) => ImageLayer::Rgba16(
+                        Rgba16Image::new(test_util::permutation::non_identity_forward_permute(
+                            layer.as_ref(),
+                        ))
+                        .unwrap(),
+                    ),
+                })
+                .collect(),
+        )?),
     };
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
 
     let mut algorithm = dispatcher.permute(
@@ -160,3 +181,37 @@ fn permute_rgba16_rgba8x2() -> Result<(), Box<dyn Error>> {
         image1, image2, image3,
     )?))
 }
+
+fn make_layers(formats: &[bool]) -> Vec<ImageLayer> {
+    let mut bias = 0;
+    formats
+        .iter()
+        .map(|&is_rgba16| {
+            if is_rgba16 {
+                let DimensionsAndRgba16Buffer { image, .. } =
+                    test_util::image::linear_indices_with_bias_to_colors(bias);
+                bias = usize::from(*image.last().unwrap()) + 1;
+                ImageLayer::Rgba16(Rgba16Image::new(image).unwrap())
+            } else {
+                let DimensionsAndRgba8Buffer { image, .. } =
+                    test_util::image::linear_indices_with_bias_to_colors(bias);
+                bias = usize::from(*image.last().unwrap()) + 1;
+                ImageLayer::Rgba8(Rgba8Image::new(image).unwrap())
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn permute_layered_single_texture() -> Result<(), Box<dyn Error>> {
+    permute_lossless_image(LosslessImage::Layered(LayeredImage::new(make_layers(&[
+        false, true, false,
+    ]))?))
+}
+
+#[test]
+fn permute_layered_multiple_textures() -> Result<(), Box<dyn Error>> {
+    permute_lossless_image(LosslessImage::Layered(LayeredImage::new(make_layers(&[
+        true, false, false, true, true, false, false, false, false, false,
+    ]))?))
+}
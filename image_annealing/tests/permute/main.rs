@@ -2,3 +2,4 @@ mod error;
 mod format;
 mod pattern;
 mod sequence;
+mod toroidal;
@@ -21,6 +21,7 @@ fn run_once_identity() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
@@ -53,6 +54,7 @@ async fn run_once_identity_async_inner() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })
     .await?;
     let mut algorithm = dispatcher.permute(
@@ -91,6 +93,7 @@ fn bit_interpretation_cases() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
@@ -15,6 +15,7 @@ fn create_identity_permutation() -> Result<(), Box<dyn Error>> {
     let dimensions = ImageDimensions::try_new(3, 4)?;
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.create_permutation(Default::default(), &Default::default());
     assert_step_until_success(algorithm.as_mut(), OutputStatus::FinalFullOutput)?;
@@ -48,6 +49,7 @@ fn zero_initialized_permutation() -> Result<(), Box<dyn Error>> {
     let dimensions = ImageDimensions::try_new(3, 4)?;
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
 
     let original_image = test_util::image::coordinates_to_colors(&dimensions);
@@ -81,6 +83,7 @@ fn ignore_identity_displacement_goal() -> Result<(), Box<dyn Error>> {
     } = test_util::permutation::non_identity();
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
 
     let mut algorithm =
@@ -120,6 +123,7 @@ fn reuse_create_displacement_goal_inputs() -> Result<(), Box<dyn Error>> {
     } = test_util::permutation::non_identity();
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
 
     let original_image = test_util::image::coordinates_to_colors(&dimensions);
@@ -168,6 +172,7 @@ fn reuse_swap_permutation() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let swap_parameters = test_util::algorithm::default_swap_parameters();
     let mut algorithm = dispatcher.swap(
@@ -232,6 +237,7 @@ fn reuse_image() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
@@ -298,6 +304,7 @@ fn reuse_permutation() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
@@ -356,6 +363,7 @@ fn reuse_nothing() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
@@ -0,0 +1,82 @@
+use image_annealing::compute::conversion::{self, VectorFieldEntry};
+use image_annealing::compute::format::{LosslessImage, Rgba16Image, Rgba16ImageBuffer};
+use image_annealing::compute::{self, Config, OutputStatus, PermuteInput};
+use image_annealing::{BoundaryMode, CandidatePermutation, ImageDimensions};
+use std::default::Default;
+use std::error::Error;
+use test_util::algorithm::assert_step_until_success;
+
+fn cyclic_shift(dimensions: &ImageDimensions) -> CandidatePermutation {
+    CandidatePermutation::from_vector_field(conversion::to_image(
+        dimensions,
+        &vec![VectorFieldEntry(-1, 0); dimensions.count()],
+    ))
+    .unwrap()
+}
+
+#[test]
+fn bounded_rejects_wrapping_permutation() -> Result<(), Box<dyn Error>> {
+    let dimensions = ImageDimensions::try_new(4, 2)?;
+    let original_image = test_util::image::coordinates_to_colors(&dimensions);
+
+    let dispatcher = compute::create_dispatcher_block(&Config {
+        image_dimensions: dimensions,
+        boundary: BoundaryMode::Bounded,
+    })?;
+    let mut algorithm = dispatcher.permute(
+        PermuteInput {
+            candidate_permutation: Some(cyclic_shift(&dimensions)),
+            original_image: Some(LosslessImage::Rgba16(Rgba16Image::new(original_image)?)),
+        },
+        &Default::default(),
+    );
+    test_util::assert_error_contains(
+        algorithm.step(),
+        "out of bounds mapping (x, y, delta_x, delta_y) = (0, 0, -1, 0) for an image of dimensions (width, height) = (4, 2)",
+    );
+    Ok(())
+}
+
+#[test]
+fn toroidal_cyclic_shift() -> Result<(), Box<dyn Error>> {
+    let dimensions = ImageDimensions::try_new(4, 2)?;
+    let original_image = test_util::image::coordinates_to_colors(&dimensions);
+    let width: u32 = dimensions.width().try_into()?;
+    let permuted_image = Rgba16ImageBuffer::from_fn(width, original_image.height(), |x, y| {
+        *original_image.get_pixel((x + width - 1) % width, y)
+    });
+    let expected_permutation = conversion::to_image(
+        &dimensions,
+        &[
+            VectorFieldEntry(3, 0),
+            VectorFieldEntry(-1, 0),
+            VectorFieldEntry(-1, 0),
+            VectorFieldEntry(-1, 0),
+            VectorFieldEntry(3, 0),
+            VectorFieldEntry(-1, 0),
+            VectorFieldEntry(-1, 0),
+            VectorFieldEntry(-1, 0),
+        ],
+    );
+
+    let dispatcher = compute::create_dispatcher_block(&Config {
+        image_dimensions: dimensions,
+        boundary: BoundaryMode::Toroidal,
+    })?;
+    let mut algorithm = dispatcher.permute(
+        PermuteInput {
+            candidate_permutation: Some(cyclic_shift(&dimensions)),
+            original_image: Some(LosslessImage::Rgba16(Rgba16Image::new(original_image)?)),
+        },
+        &Default::default(),
+    );
+    assert_step_until_success(algorithm.as_mut(), OutputStatus::FinalFullOutput)?;
+
+    let output = algorithm.full_output_block().unwrap();
+    assert_eq!(*output.permutation.unwrap().as_ref(), expected_permutation);
+    assert_eq!(
+        output.permuted_image,
+        LosslessImage::Rgba16(Rgba16Image::new(permuted_image)?)
+    );
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use image_annealing::compute::conversion::{self, VectorFieldEntry};
+use image_annealing::compute::{
+    self, Config, OutputStatus, SwapInput, SwapParameters, SwapPass, SwapPassSequence,
+};
+use image_annealing::{
+    BoundaryMode, CandidatePermutation, DisplacementGoal, ImageDimensions, ValidatedPermutation,
+    VectorField,
+};
+use std::error::Error;
+use test_util::algorithm::assert_step_until_success;
+
+fn run_and_replay(
+    dimensions: &ImageDimensions,
+    boundary: BoundaryMode,
+    sequence: SwapPassSequence,
+    displacement_goal: &[VectorFieldEntry],
+) -> Result<usize, Box<dyn Error>> {
+    let permutation = ValidatedPermutation::identity(dimensions);
+    let displacement_goal =
+        DisplacementGoal::from_vector_field(conversion::to_image(dimensions, displacement_goal))?;
+
+    let dispatcher = compute::create_dispatcher_block(&Config {
+        image_dimensions: *dimensions,
+        boundary,
+    })?;
+    let swap_parameters = SwapParameters {
+        sequence,
+        swap_acceptance_threshold: Default::default(),
+        count_swap: false,
+        record_accepted_swaps: true,
+        permuted_image_format: None,
+    };
+    let mut algorithm = dispatcher.swap(
+        SwapInput {
+            candidate_permutation: Some(CandidatePermutation::from_vector_field(
+                permutation.as_ref().clone(),
+            )?),
+            displacement_goal: Some(displacement_goal),
+        },
+        &swap_parameters,
+    );
+    assert_step_until_success(algorithm.as_mut(), OutputStatus::FinalPartialOutput)?;
+
+    let output = algorithm.full_output_block().unwrap();
+    let partial_output = algorithm.partial_output_block().unwrap();
+    let accepted_swaps = partial_output.accepted_swaps.unwrap();
+    assert_eq!(
+        accepted_swaps
+            .iter()
+            .map(|accepted| accepted.pass())
+            .collect::<Vec<SwapPass>>(),
+        sequence.iter().copied().collect::<Vec<SwapPass>>()
+    );
+    let accepted_count = accepted_swaps
+        .iter()
+        .map(|accepted| accepted.accepted())
+        .sum::<usize>();
+    assert_eq!(accepted_count, partial_output.counts.accepted());
+
+    let replayed = accepted_swaps
+        .iter()
+        .try_fold(permutation, |permutation, accepted| {
+            accepted.apply(&permutation)
+        })?;
+    assert_eq!(replayed, output.output_permutation);
+    Ok(accepted_count)
+}
+
+#[test]
+fn offset_horizontal_toroidal() -> Result<(), Box<dyn Error>> {
+    let accepted_count = run_and_replay(
+        &ImageDimensions::try_new(4, 1)?,
+        BoundaryMode::Toroidal,
+        SwapPassSequence::from(SwapPass::OffsetHorizontal),
+        &[
+            VectorFieldEntry(-1, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(1, 0),
+        ],
+    )?;
+    assert_eq!(accepted_count, 1);
+    Ok(())
+}
+
+fn mirror_displacement_goal(dimensions: &ImageDimensions) -> Vec<VectorFieldEntry> {
+    let width = i16::try_from(dimensions.width()).unwrap();
+    let height = i16::try_from(dimensions.height()).unwrap();
+    (0..height)
+        .flat_map(|y| {
+            (0..width).map(move |x| VectorFieldEntry(width - 1 - 2 * x, height - 1 - 2 * y))
+        })
+        .collect()
+}
+
+#[test]
+fn all_passes_bounded() -> Result<(), Box<dyn Error>> {
+    let dimensions = ImageDimensions::try_new(35, 19)?;
+    run_and_replay(
+        &dimensions,
+        BoundaryMode::Bounded,
+        SwapPassSequence::all(),
+        &mirror_displacement_goal(&dimensions),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn all_passes_toroidal() -> Result<(), Box<dyn Error>> {
+    let dimensions = ImageDimensions::try_new(34, 20)?;
+    run_and_replay(
+        &dimensions,
+        BoundaryMode::Toroidal,
+        SwapPassSequence::all(),
+        &mirror_displacement_goal(&dimensions),
+    )?;
+    Ok(())
+}
@@ -75,6 +75,7 @@ fn skip_count_swap() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let swap_parameters = SwapParameters::from_sequence(SwapPass::Horizontal.into());
     let mut algorithm = dispatcher.swap(
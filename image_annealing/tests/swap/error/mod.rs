@@ -19,6 +19,7 @@ fn invalid_permutation_dimensions() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: other_dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.swap(
         SwapInput {
@@ -46,6 +47,7 @@ fn invalid_displacement_goal_dimensions() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.swap(
         SwapInput {
@@ -70,6 +72,7 @@ fn forget_displacement_goal() -> Result<(), Box<dyn Error>> {
     } = test_util::permutation::identity();
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
 
     let mut algorithm = dispatcher.swap(
@@ -106,6 +109,7 @@ fn run_twice_invalid_permutation_valid() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let swap_parameters = test_util::algorithm::default_swap_parameters();
     let mut algorithm = dispatcher.swap(
@@ -1,5 +1,7 @@
+mod accepted;
 mod border;
 mod count;
 mod error;
 mod pattern;
 mod sequence;
+mod toroidal;
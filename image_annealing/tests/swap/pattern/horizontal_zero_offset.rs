@@ -24,6 +24,7 @@ mod accept_all_swap {
 
         let dispatcher = compute::create_dispatcher_block(&Config {
             image_dimensions: dimensions,
+            boundary: Default::default(),
         })?;
         let swap_parameters = test_util::algorithm::default_swap_parameters();
         let mut algorithm = dispatcher.swap(
@@ -64,6 +65,7 @@ mod accept_all_swap {
 
         let dispatcher = compute::create_dispatcher(&Config {
             image_dimensions: dimensions,
+            boundary: Default::default(),
         })
         .await?;
         let swap_parameters = test_util::algorithm::default_swap_parameters();
@@ -114,6 +116,7 @@ mod accept_all_swap {
 
         let dispatcher = compute::create_dispatcher_block(&Config {
             image_dimensions: dimensions,
+            boundary: Default::default(),
         })?;
         let swap_parameters = test_util::algorithm::default_swap_parameters();
         let mut algorithm = dispatcher.swap(
@@ -174,6 +177,7 @@ mod select_swap {
 
         let dispatcher = compute::create_dispatcher_block(&Config {
             image_dimensions: dimensions,
+            boundary: Default::default(),
         })?;
         let swap_parameters = test_util::algorithm::default_swap_parameters();
         let mut algorithm = dispatcher.swap(
@@ -230,6 +234,7 @@ mod swap_acceptance_threshold {
 
         let mut dispatcher = compute::create_dispatcher_block(&Config {
             image_dimensions: dimensions,
+            boundary: Default::default(),
         })?;
         let mut swap_acceptance_threshold = -2.0;
         let pass = SwapPass::Horizontal;
@@ -237,6 +242,8 @@ mod swap_acceptance_threshold {
             sequence: pass.into(),
             swap_acceptance_threshold,
             count_swap: true,
+            record_accepted_swaps: false,
+            permuted_image_format: None,
         };
         let mut algorithm = dispatcher.swap(
             SwapInput {
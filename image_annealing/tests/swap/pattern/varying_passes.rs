@@ -31,6 +31,7 @@ where
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let swap_parameters = SwapParameters {
         sequence,
@@ -551,6 +552,7 @@ mod reject_out_of_bounds {
 
         let dispatcher = compute::create_dispatcher_block(&Config {
             image_dimensions: dimensions,
+            boundary: Default::default(),
         })?;
         let swap_parameters = test_util::algorithm::default_swap_parameters();
         let mut algorithm = dispatcher.swap(
@@ -24,6 +24,7 @@ fn create_identity_permutation() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.create_permutation(Default::default(), &Default::default());
     assert_step_until_success(algorithm.as_mut(), OutputStatus::FinalFullOutput)?;
@@ -76,6 +77,7 @@ fn zero_initialized_permutation() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
 
     let swap_parameters = test_util::algorithm::default_swap_parameters();
@@ -120,6 +122,7 @@ fn create_identity_displacement_goal() -> Result<(), Box<dyn Error>> {
     } = test_util::permutation::non_identity();
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
 
     let mut algorithm =
@@ -169,6 +172,7 @@ fn reuse_create_displacement_goal_inputs() -> Result<(), Box<dyn Error>> {
     } = test_util::permutation::non_identity();
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
 
     let mut algorithm = dispatcher.create_displacement_goal(
@@ -215,6 +219,7 @@ fn reuse_permutation() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.permute(
         PermuteInput {
@@ -286,6 +291,7 @@ fn reuse_nothing() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let swap_parameters = test_util::algorithm::default_swap_parameters();
     let mut algorithm = dispatcher.swap(
@@ -362,6 +368,7 @@ fn run_twice_reflect_around_center() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let swap_parameters = test_util::algorithm::default_swap_parameters();
     let mut algorithm = dispatcher.swap(
@@ -450,6 +457,7 @@ fn run_twice_previous_pass_not_counted() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let pass = SwapPass::OffsetVertical;
     let mut swap_parameters = SwapParameters::from_sequence(pass.into());
@@ -0,0 +1,119 @@
+use image_annealing::compute::conversion::{self, VectorFieldEntry};
+use image_annealing::compute::{
+    self, Config, OutputStatus, SwapInput, SwapParameters, SwapPass, SwapPassSequence,
+};
+use image_annealing::{BoundaryMode, CandidatePermutation, DisplacementGoal, ImageDimensions};
+use std::error::Error;
+use test_util::algorithm::assert_step_until_success;
+
+fn run_offset_horizontal(
+    boundary: BoundaryMode,
+    expected_permutation: &[VectorFieldEntry],
+    expected_total: usize,
+    expected_accepted: usize,
+) -> Result<(), Box<dyn Error>> {
+    let dimensions = ImageDimensions::try_new(4, 1)?;
+    let permutation = conversion::to_image(&dimensions, &[VectorFieldEntry(0, 0); 4]);
+    let displacement_goal = DisplacementGoal::from_vector_field(conversion::to_image(
+        &dimensions,
+        &[
+            VectorFieldEntry(-1, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(1, 0),
+        ],
+    ))?;
+
+    let dispatcher = compute::create_dispatcher_block(&Config {
+        image_dimensions: dimensions,
+        boundary,
+    })?;
+    let swap_parameters = SwapParameters {
+        sequence: SwapPassSequence::from(SwapPass::OffsetHorizontal),
+        swap_acceptance_threshold: Default::default(),
+        count_swap: true,
+        record_accepted_swaps: false,
+        permuted_image_format: None,
+    };
+    let mut algorithm = dispatcher.swap(
+        SwapInput {
+            candidate_permutation: Some(CandidatePermutation::from_vector_field(permutation)?),
+            displacement_goal: Some(displacement_goal),
+        },
+        &swap_parameters,
+    );
+    assert_step_until_success(algorithm.as_mut(), OutputStatus::FinalPartialOutput)?;
+
+    let output = algorithm.full_output_block().unwrap();
+    assert_eq!(
+        *output.output_permutation.as_ref(),
+        conversion::to_image(&dimensions, expected_permutation)
+    );
+    let counts = algorithm.partial_output_block().unwrap().counts;
+    assert_eq!(counts.total(), expected_total);
+    assert_eq!(counts.accepted(), expected_accepted);
+    Ok(())
+}
+
+#[test]
+fn offset_horizontal_bounded() -> Result<(), Box<dyn Error>> {
+    run_offset_horizontal(BoundaryMode::Bounded, &[VectorFieldEntry(0, 0); 4], 1, 0)
+}
+
+#[test]
+fn offset_horizontal_toroidal() -> Result<(), Box<dyn Error>> {
+    run_offset_horizontal(
+        BoundaryMode::Toroidal,
+        &[
+            VectorFieldEntry(3, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(-3, 0),
+        ],
+        2,
+        1,
+    )
+}
+
+#[test]
+fn odd_width_does_not_wrap() -> Result<(), Box<dyn Error>> {
+    let dimensions = ImageDimensions::try_new(3, 1)?;
+    let permutation = conversion::to_image(&dimensions, &[VectorFieldEntry(0, 0); 3]);
+    let displacement_goal = DisplacementGoal::from_vector_field(conversion::to_image(
+        &dimensions,
+        &[
+            VectorFieldEntry(-1, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(1, 0),
+        ],
+    ))?;
+
+    let dispatcher = compute::create_dispatcher_block(&Config {
+        image_dimensions: dimensions,
+        boundary: BoundaryMode::Toroidal,
+    })?;
+    let swap_parameters = SwapParameters {
+        sequence: SwapPassSequence::from(SwapPass::OffsetHorizontal),
+        swap_acceptance_threshold: Default::default(),
+        count_swap: true,
+        record_accepted_swaps: false,
+        permuted_image_format: None,
+    };
+    let mut algorithm = dispatcher.swap(
+        SwapInput {
+            candidate_permutation: Some(CandidatePermutation::from_vector_field(
+                permutation.clone(),
+            )?),
+            displacement_goal: Some(displacement_goal),
+        },
+        &swap_parameters,
+    );
+    assert_step_until_success(algorithm.as_mut(), OutputStatus::FinalPartialOutput)?;
+
+    let output = algorithm.full_output_block().unwrap();
+    assert_eq!(*output.output_permutation.as_ref(), permutation);
+    let counts = algorithm.partial_output_block().unwrap().counts;
+    assert_eq!(counts.total(), 1);
+    assert_eq!(counts.accepted(), 0);
+    Ok(())
+}
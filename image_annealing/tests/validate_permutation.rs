@@ -15,6 +15,7 @@ fn run_once_identity() -> Result<(), Box<dyn Error>> {
     let expected = permutation.clone();
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.validate_permutation(
         ValidatePermutationInput {
@@ -37,6 +38,7 @@ async fn run_once_identity_async_inner() -> Result<(), Box<dyn Error>> {
     let expected = permutation.clone();
     let dispatcher = compute::create_dispatcher(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })
     .await?;
     let mut algorithm = dispatcher.validate_permutation(
@@ -72,6 +74,7 @@ fn run_twice_invalid_valid() -> Result<(), Box<dyn Error>> {
 
     let mut dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.validate_permutation(
         ValidatePermutationInput {
@@ -105,6 +108,7 @@ fn invalid_dimensions() -> Result<(), Box<dyn Error>> {
 
     let dispatcher = compute::create_dispatcher_block(&Config {
         image_dimensions: invalid_dimensions,
+        boundary: Default::default(),
     })?;
     let mut algorithm = dispatcher.validate_permutation(
         ValidatePermutationInput {
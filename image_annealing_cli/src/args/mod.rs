@@ -1,17 +1,104 @@
 use crate::config::{self, Config};
 pub use bpaf::ParseFailure;
 use bpaf::{Bpaf, ShellComp};
+use std::error::Error;
+use std::path::PathBuf;
+
+mod operation;
+
+pub use operation::OperationOptionsError;
+use operation::{
+    create_displacement_goal_options, create_permutation_options, permute_options, swap_options,
+    validate_permutation_options, visualize_vector_field_options, CreateDisplacementGoalOptions,
+    CreatePermutationOptions, PermuteOptions, SwapOptions, ValidatePermutationOptions,
+    VisualizeVectorFieldOptions,
+};
 
 #[derive(Debug, Bpaf)]
 #[bpaf(generate(make_option_parser), options, version)]
 /// Run individual operations
-struct Options {
-    /// Path of the configuration file describing the operation to run
-    #[bpaf(long, short, argument::<String>("CONFIG_FILE"), complete_shell(ShellComp::File { mask: Some("*.json") }), parse(config::parse_config_file))]
-    config: Config,
+struct Arguments {
+    /// Verify the configuration and print a summary of the operation, without running it
+    #[bpaf(long)]
+    check: bool,
+    #[bpaf(external(options))]
+    options: Options,
+}
+
+#[derive(Debug, Bpaf)]
+#[bpaf(command("schema"))]
+/// Output the JSON Schema of configuration files
+struct SchemaOptions {
+    /// Path of the file to output (defaults to standard output)
+    #[bpaf(long, short('f'), argument("OUTPUT_FILE"), complete_shell(ShellComp::File { mask: Some("*.json") }))]
+    output_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Bpaf)]
+enum Options {
+    ConfigFile {
+        /// Path of the configuration file describing the operation to run
+        #[bpaf(long, short, argument::<String>("CONFIG_FILE"), complete_shell(ShellComp::File { mask: None }), parse(config::parse_config_file))]
+        config: Config,
+    },
+    CreateDisplacementGoal(
+        #[bpaf(external(create_displacement_goal_options))] CreateDisplacementGoalOptions,
+    ),
+    CreatePermutation(#[bpaf(external(create_permutation_options))] CreatePermutationOptions),
+    Permute(#[bpaf(external(permute_options))] PermuteOptions),
+    Swap(#[bpaf(external(swap_options))] SwapOptions),
+    ValidatePermutation(#[bpaf(external(validate_permutation_options))] ValidatePermutationOptions),
+    VisualizeVectorField(
+        #[bpaf(external(visualize_vector_field_options))] VisualizeVectorFieldOptions,
+    ),
+    Schema(#[bpaf(external(schema_options))] SchemaOptions),
+}
+
+impl Options {
+    /// Builds the configuration of an operation described by command-line options
+    /// in the same way as a configuration file is checked, unless the options
+    /// describe a task other than running an operation
+    fn into_parsed_args(self, check: bool) -> Result<ParsedArgs, Box<dyn Error>> {
+        let (unverified_config, output_policy) = match self {
+            Self::ConfigFile { config } => {
+                return Ok(ParsedArgs::Operation {
+                    config: Box::new(config),
+                    check,
+                })
+            }
+            Self::Schema(SchemaOptions { output_file }) => {
+                return Ok(ParsedArgs::Schema { output_file })
+            }
+            Self::CreateDisplacementGoal(options) => options.into_unverified_config()?,
+            Self::CreatePermutation(options) => options.into_unverified_config()?,
+            Self::Permute(options) => options.into_unverified_config()?,
+            Self::Swap(options) => options.into_unverified_config()?,
+            Self::ValidatePermutation(options) => options.into_unverified_config()?,
+            Self::VisualizeVectorField(options) => options.into_unverified_config()?,
+        };
+        Ok(ParsedArgs::Operation {
+            config: Box::new(Config::try_from_with_output_policy(
+                unverified_config,
+                output_policy,
+            )?),
+            check,
+        })
+    }
+}
+
+/// The task described by the command-line arguments
+#[derive(Debug, PartialEq)]
+pub enum ParsedArgs {
+    Operation {
+        config: Box<Config>,
+        /// Whether to only verify the configuration instead of running the operation
+        check: bool,
+    },
+    /// Output the JSON Schema of configuration files
+    Schema { output_file: Option<PathBuf> },
 }
 
-pub fn parse_args<T>(args: T) -> Result<Config, ParseFailure>
+pub fn parse_args<T>(args: T) -> Result<ParsedArgs, ParseFailure>
 where
     T: IntoIterator<Item = String>,
 {
@@ -29,7 +116,11 @@ where
                 .as_slice()
                 .into(),
         )
-        .map(|opts| opts.config)
+        .and_then(|Arguments { check, options }| {
+            options
+                .into_parsed_args(check)
+                .map_err(|e| ParseFailure::Stderr(e.to_string()))
+        })
 }
 
 #[cfg(test)]
@@ -0,0 +1,637 @@
+use crate::config::{
+    self, OutputPolicyConfig, UnverifiedConfig, UnverifiedCreateDisplacementGoalInputConfig,
+    UnverifiedCreateDisplacementGoalInputDataConfig, UnverifiedImageDimensionsConfig,
+    UnverifiedIterationCount, UnverifiedLosslessImagePath, UnverifiedPermuteImageInput,
+    UnverifiedPermuteImageOutput, UnverifiedSwapParametersConfig,
+    UnverifiedSwapStatisticsLogConfig, UnverifiedSwapStopConfig, UnverifiedSwapStopThreshold,
+    UnverifiedVectorFieldVisualizationParametersConfig,
+};
+use bpaf::{Bpaf, Parser, ShellComp};
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum OperationOptionsError {
+    OperationMismatch {
+        path: String,
+        operation: &'static str,
+    },
+    MissingOption(&'static str),
+    ConflictingOptions(&'static str, &'static str),
+    ConflictingDisplacementGoalInputs,
+}
+
+impl fmt::Display for OperationOptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OperationMismatch { path, operation } => write!(
+                f,
+                "configuration file '{}' does not describe a {} operation",
+                path, operation
+            ),
+            Self::MissingOption(name) => write!(
+                f,
+                "option --{} is required when it is not set by a configuration file",
+                name
+            ),
+            Self::ConflictingOptions(name1, name2) => write!(
+                f,
+                "options --{} and --{} cannot be used together",
+                name1, name2
+            ),
+            Self::ConflictingDisplacementGoalInputs => write!(
+                f,
+                "image dimensions (--width and --height) cannot be used together with input data \
+                (--displacement-goal, --permutation, --image, and --conversion)"
+            ),
+        }
+    }
+}
+
+impl Error for OperationOptionsError {}
+
+/// Loads the configuration file given by the `--config` option, if any, and its output policy
+fn load_config_file(
+    path: &Option<String>,
+) -> Result<(Option<UnverifiedConfig>, OutputPolicyConfig), Box<dyn Error>> {
+    Ok(match path {
+        Some(path) => {
+            let (config, output_policy) = config::parse_unverified_config_file(path)?;
+            (Some(config), output_policy)
+        }
+        None => (None, Default::default()),
+    })
+}
+
+fn mismatch(path: &Option<String>, operation: &'static str) -> Box<dyn Error> {
+    Box::new(OperationOptionsError::OperationMismatch {
+        path: path.clone().unwrap_or_default(),
+        operation,
+    })
+}
+
+fn required<T>(value: Option<T>, name: &'static str) -> Result<T, OperationOptionsError> {
+    value.ok_or(OperationOptionsError::MissingOption(name))
+}
+
+/// Parses the name of an enumeration variant in the same way as in configuration files
+fn parse_variant<T: DeserializeOwned>(name: String) -> Result<T, Box<dyn Error>> {
+    Ok(serde_json::from_value(serde_json::Value::String(name))?)
+}
+
+/// Makes an input image path whose format is detected from the file
+fn auto_image_path(path: String) -> UnverifiedLosslessImagePath {
+    UnverifiedLosslessImagePath::Auto(vec![path])
+}
+
+/// Parses the `--intermediate` and `--no-intermediate` flags, which override
+/// the `output_intermediate_permutations` swap parameter of a configuration file
+fn intermediate() -> impl Parser<bool> {
+    let output = bpaf::long("intermediate")
+        .help("Output the permutation produced by every swap pass, not only the final permutation")
+        .req_flag(true);
+    let no_output = bpaf::long("no-intermediate")
+        .help("Output only the final permutation")
+        .req_flag(false);
+    bpaf::construct!([output, no_output])
+}
+
+#[derive(Debug, Bpaf, PartialEq)]
+#[bpaf(command("create-displacement-goal"))]
+/// Create a displacement goal from image dimensions or from input data
+pub struct CreateDisplacementGoalOptions {
+    /// Path of a configuration file describing the operation, to which the other options are applied
+    #[bpaf(long, short, argument("CONFIG_FILE"), complete_shell(ShellComp::File { mask: None }))]
+    pub config: Option<String>,
+    /// Width of the displacement goal, when it is not created from input data
+    #[bpaf(long, argument("WIDTH"))]
+    pub width: Option<usize>,
+    /// Height of the displacement goal, when it is not created from input data
+    #[bpaf(long, argument("HEIGHT"))]
+    pub height: Option<usize>,
+    /// Path of an input displacement goal
+    #[bpaf(long, argument("DISPLACEMENT_GOAL_FILE"))]
+    pub displacement_goal: Option<String>,
+    /// Path of an input permutation
+    #[bpaf(long, argument("PERMUTATION_FILE"))]
+    pub permutation: Option<String>,
+    /// Path of an input image, whose format is detected from its number of bits per channel
+    #[bpaf(long, argument("IMAGE_FILE"))]
+    pub image: Option<String>,
+    /// Policy for converting the input image to RGBA (Strict, Lossless, or AllowNarrowing)
+    #[bpaf(long, argument("POLICY"))]
+    pub conversion: Option<String>,
+    /// Path of the displacement goal to output, with or without a file extension
+    #[bpaf(long, short, argument("OUTPUT_PATH"))]
+    pub output: Option<String>,
+}
+
+impl CreateDisplacementGoalOptions {
+    pub fn into_unverified_config(
+        self,
+    ) -> Result<(UnverifiedConfig, OutputPolicyConfig), Box<dyn Error>> {
+        let (file_config, output_policy) = load_config_file(&self.config)?;
+        let (input, output) = match file_config {
+            None => (None, None),
+            Some(UnverifiedConfig::CreateDisplacementGoal {
+                input,
+                displacement_goal_output_path_no_extension,
+            }) => (
+                Some(input),
+                Some(displacement_goal_output_path_no_extension),
+            ),
+            Some(_) => return Err(mismatch(&self.config, "create displacement goal")),
+        };
+        let use_dimensions = self.width.is_some() || self.height.is_some();
+        let use_data = self.displacement_goal.is_some()
+            || self.permutation.is_some()
+            || self.image.is_some()
+            || self.conversion.is_some();
+        if use_dimensions && use_data {
+            return Err(Box::new(
+                OperationOptionsError::ConflictingDisplacementGoalInputs,
+            ));
+        }
+        let input = if use_dimensions {
+            let (width, height) = match input {
+                Some(UnverifiedCreateDisplacementGoalInputConfig::ImageDimensions(
+                    UnverifiedImageDimensionsConfig { width, height },
+                )) => (Some(width), Some(height)),
+                _ => (None, None),
+            };
+            UnverifiedCreateDisplacementGoalInputConfig::ImageDimensions(
+                UnverifiedImageDimensionsConfig {
+                    width: required(self.width.or(width), "width")?,
+                    height: required(self.height.or(height), "height")?,
+                },
+            )
+        } else if use_data {
+            let data = match input {
+                Some(UnverifiedCreateDisplacementGoalInputConfig::Input(data)) => data,
+                _ => Default::default(),
+            };
+            UnverifiedCreateDisplacementGoalInputConfig::Input(
+                UnverifiedCreateDisplacementGoalInputDataConfig {
+                    displacement_goal: self.displacement_goal.or(data.displacement_goal),
+                    candidate_permutation: self.permutation.or(data.candidate_permutation),
+                    image: self.image.map(auto_image_path).or(data.image),
+                    image_conversion: self
+                        .conversion
+                        .map(parse_variant)
+                        .transpose()?
+                        .or(data.image_conversion),
+                },
+            )
+        } else {
+            required(input, "width")?
+        };
+        Ok((
+            UnverifiedConfig::CreateDisplacementGoal {
+                input,
+                displacement_goal_output_path_no_extension: required(
+                    self.output.or(output),
+                    "output",
+                )?,
+            },
+            output_policy,
+        ))
+    }
+}
+
+#[derive(Debug, Bpaf, PartialEq)]
+#[bpaf(command("create-permutation"))]
+/// Create an identity permutation
+pub struct CreatePermutationOptions {
+    /// Path of a configuration file describing the operation, to which the other options are applied
+    #[bpaf(long, short, argument("CONFIG_FILE"), complete_shell(ShellComp::File { mask: None }))]
+    pub config: Option<String>,
+    /// Width of the permutation
+    #[bpaf(long, argument("WIDTH"))]
+    pub width: Option<usize>,
+    /// Height of the permutation
+    #[bpaf(long, argument("HEIGHT"))]
+    pub height: Option<usize>,
+    /// Path of the permutation to output, with or without a file extension
+    #[bpaf(long, short, argument("OUTPUT_PATH"))]
+    pub output: Option<String>,
+}
+
+impl CreatePermutationOptions {
+    pub fn into_unverified_config(
+        self,
+    ) -> Result<(UnverifiedConfig, OutputPolicyConfig), Box<dyn Error>> {
+        let (file_config, output_policy) = load_config_file(&self.config)?;
+        let (width, height, output) = match file_config {
+            None => (None, None, None),
+            Some(UnverifiedConfig::CreatePermutation {
+                image_dimensions: UnverifiedImageDimensionsConfig { width, height },
+                permutation_output_path_no_extension,
+            }) => (
+                Some(width),
+                Some(height),
+                Some(permutation_output_path_no_extension),
+            ),
+            Some(_) => return Err(mismatch(&self.config, "create permutation")),
+        };
+        Ok((
+            UnverifiedConfig::CreatePermutation {
+                image_dimensions: UnverifiedImageDimensionsConfig {
+                    width: required(self.width.or(width), "width")?,
+                    height: required(self.height.or(height), "height")?,
+                },
+                permutation_output_path_no_extension: required(self.output.or(output), "output")?,
+            },
+            output_policy,
+        ))
+    }
+}
+
+#[derive(Debug, Bpaf, PartialEq)]
+#[bpaf(command("permute"))]
+/// Permute an image
+pub struct PermuteOptions {
+    /// Path of a configuration file describing the operation, to which the other options are applied
+    #[bpaf(long, short, argument("CONFIG_FILE"), complete_shell(ShellComp::File { mask: None }))]
+    pub config: Option<String>,
+    /// Path of the permutation to apply
+    #[bpaf(long, argument("PERMUTATION_FILE"))]
+    pub permutation: Option<String>,
+    /// Path of the image to permute, whose format is detected from its number of bits per channel
+    #[bpaf(long, argument("IMAGE_FILE"))]
+    pub image: Option<String>,
+    /// Policy for converting the input image to RGBA (Strict, Lossless, or AllowNarrowing)
+    #[bpaf(long, argument("POLICY"))]
+    pub conversion: Option<String>,
+    /// Path of the image to output, without a file extension, which has the format of the input image
+    #[bpaf(long, short, argument("OUTPUT_PATH"))]
+    pub output: Option<String>,
+    /// Boundary condition (Bounded or Toroidal)
+    #[bpaf(long, argument("MODE"))]
+    pub boundary: Option<String>,
+}
+
+impl PermuteOptions {
+    pub fn into_unverified_config(
+        self,
+    ) -> Result<(UnverifiedConfig, OutputPolicyConfig), Box<dyn Error>> {
+        let (file_config, output_policy) = load_config_file(&self.config)?;
+        let (permutation, image, conversion, output, boundary) = match file_config {
+            None => (None, None, None, None, None),
+            Some(UnverifiedConfig::Permute {
+                candidate_permutation,
+                original_image,
+                original_image_conversion,
+                permuted_image_output_path_no_extension,
+                boundary,
+            }) => (
+                Some(candidate_permutation),
+                Some(original_image),
+                original_image_conversion,
+                Some(permuted_image_output_path_no_extension),
+                boundary,
+            ),
+            Some(_) => return Err(mismatch(&self.config, "permute")),
+        };
+        Ok((
+            UnverifiedConfig::Permute {
+                candidate_permutation: required(self.permutation.or(permutation), "permutation")?,
+                original_image: required(
+                    self.image
+                        .map(|path| UnverifiedPermuteImageInput::Image(auto_image_path(path)))
+                        .or(image),
+                    "image",
+                )?,
+                original_image_conversion: self
+                    .conversion
+                    .map(parse_variant)
+                    .transpose()?
+                    .or(conversion),
+                // The output image takes the format of the input image
+                permuted_image_output_path_no_extension: required(
+                    self.output
+                        .map(|path| UnverifiedPermuteImageOutput::Image(auto_image_path(path)))
+                        .or(output),
+                    "output",
+                )?,
+                boundary: self.boundary.map(parse_variant).transpose()?.or(boundary),
+            },
+            output_policy,
+        ))
+    }
+}
+
+#[derive(Debug, Bpaf, PartialEq)]
+#[bpaf(command("swap"))]
+/// Optimize a permutation by swapping its elements
+pub struct SwapOptions {
+    /// Path of a configuration file describing the operation, to which the other options are applied
+    #[bpaf(long, short, argument("CONFIG_FILE"), complete_shell(ShellComp::File { mask: None }))]
+    pub config: Option<String>,
+    /// Path of the initial permutation
+    #[bpaf(long, argument("PERMUTATION_FILE"))]
+    pub permutation: Option<String>,
+    /// Path of a checkpoint file to resume from, instead of an initial permutation
+    #[bpaf(long, argument("CHECKPOINT_FILE"))]
+    pub resume: Option<String>,
+    /// Path of the displacement goal
+    #[bpaf(long, argument("DISPLACEMENT_GOAL_FILE"))]
+    pub displacement_goal: Option<String>,
+    /// Prefix of the paths of the permutations to output
+    #[bpaf(long, short, argument("OUTPUT_PATH_PREFIX"))]
+    pub output: Option<String>,
+    /// Path of the file of accepted swaps to output, without a file extension
+    #[bpaf(long, argument("HISTORY_PATH"))]
+    pub history: Option<String>,
+    /// Threshold on the change in cost below which swaps are accepted
+    #[bpaf(long, argument("THRESHOLD"))]
+    pub threshold: Option<f32>,
+    /// Maximum number of rounds of swap passes
+    #[bpaf(long, argument("COUNT"))]
+    pub iterations: Option<usize>,
+    /// Stop when a round of swap passes accepts at most this number of swaps
+    #[bpaf(long, argument("COUNT"))]
+    pub stop_swaps_accepted: Option<usize>,
+    /// Stop when a round of swap passes accepts at most this fraction of swaps
+    #[bpaf(long, argument("FRACTION"))]
+    pub stop_acceptance_fraction: Option<f64>,
+    /// Swap pass to include in each round (Horizontal, Vertical, OffsetHorizontal, or OffsetVertical),
+    /// which can be repeated to define the sequence of passes
+    #[bpaf(long, argument("PASS"))]
+    pub pass: Vec<String>,
+    /// Whether to output the permutation produced by every swap pass,
+    /// overriding the configuration file if given
+    #[bpaf(external(intermediate), optional)]
+    pub intermediate: Option<bool>,
+    /// Path of a log of the numbers of swaps accepted in every swap pass,
+    /// which is written as CSV if the path ends with ".csv", and as JSON Lines otherwise
+    #[bpaf(long, argument("STATISTICS_LOG_PATH"))]
+    pub statistics_log: Option<String>,
+    /// Boundary condition (Bounded or Toroidal)
+    #[bpaf(long, argument("MODE"))]
+    pub boundary: Option<String>,
+}
+
+impl SwapOptions {
+    fn stop(
+        &self,
+        stop: Option<UnverifiedSwapStopConfig>,
+    ) -> Result<UnverifiedSwapStopConfig, OperationOptionsError> {
+        let (iteration_count, threshold) = match stop {
+            Some(UnverifiedSwapStopConfig::Bounded {
+                iteration_count,
+                threshold,
+            }) => (Some(iteration_count.0), threshold),
+            Some(UnverifiedSwapStopConfig::Unbounded(threshold)) => (None, Some(threshold)),
+            // Stop criteria from the configuration file are replaced by any stop options
+            Some(criteria @ UnverifiedSwapStopConfig::Criteria(_)) => {
+                if self.iterations.is_none()
+                    && self.stop_swaps_accepted.is_none()
+                    && self.stop_acceptance_fraction.is_none()
+                {
+                    return Ok(criteria);
+                }
+                (None, None)
+            }
+            None => (None, None),
+        };
+        let threshold = match (self.stop_swaps_accepted, self.stop_acceptance_fraction) {
+            (Some(_), Some(_)) => {
+                return Err(OperationOptionsError::ConflictingOptions(
+                    "stop-swaps-accepted",
+                    "stop-acceptance-fraction",
+                ))
+            }
+            (Some(count), None) => Some(UnverifiedSwapStopThreshold::SwapsAccepted(count)),
+            (None, Some(fraction)) => Some(UnverifiedSwapStopThreshold::SwapAcceptanceFraction(
+                fraction,
+            )),
+            (None, None) => threshold,
+        };
+        match (self.iterations.or(iteration_count), threshold) {
+            (Some(count), threshold) => Ok(UnverifiedSwapStopConfig::Bounded {
+                iteration_count: UnverifiedIterationCount(count),
+                threshold,
+            }),
+            (None, Some(threshold)) => Ok(UnverifiedSwapStopConfig::Unbounded(threshold)),
+            (None, None) => Err(OperationOptionsError::MissingOption("iterations")),
+        }
+    }
+
+    pub fn into_unverified_config(
+        self,
+    ) -> Result<(UnverifiedConfig, OutputPolicyConfig), Box<dyn Error>> {
+        let (file_config, output_policy) = load_config_file(&self.config)?;
+        let (
+            permutation,
+            resume,
+            displacement_goal,
+            output,
+            history,
+            checkpoint,
+            parameters,
+            boundary,
+        ) = match file_config {
+            None => (None, None, None, None, None, None, None, None),
+            Some(UnverifiedConfig::Swap {
+                candidate_permutation,
+                resume,
+                displacement_goal,
+                permutation_output_path_prefix,
+                swap_history_output_path_no_extension,
+                checkpoint,
+                parameters,
+                boundary,
+            }) => (
+                candidate_permutation,
+                resume,
+                Some(displacement_goal),
+                Some(permutation_output_path_prefix),
+                swap_history_output_path_no_extension,
+                checkpoint,
+                Some(parameters),
+                boundary,
+            ),
+            Some(_) => return Err(mismatch(&self.config, "swap")),
+        };
+        let (
+            stop,
+            swap_acceptance_threshold,
+            swap_pass_sequence,
+            intermediate,
+            intermediate_permutations,
+            animation,
+            statistics_log,
+        ) = match parameters {
+            Some(UnverifiedSwapParametersConfig {
+                stop,
+                swap_acceptance_threshold,
+                swap_pass_sequence,
+                output_intermediate_permutations,
+                intermediate_permutations,
+                animation,
+                statistics_log,
+            }) => (
+                Some(stop),
+                Some(swap_acceptance_threshold),
+                Some(swap_pass_sequence),
+                output_intermediate_permutations,
+                intermediate_permutations,
+                animation,
+                statistics_log,
+            ),
+            None => (None, None, None, false, None, None, None),
+        };
+        // An initial permutation or a checkpoint given on the command line replaces both
+        // of the inputs from the configuration file, because only one of them can be used.
+        let (candidate_permutation, resume) =
+            match (self.permutation.as_ref(), self.resume.as_ref()) {
+                (None, None) => (permutation, resume),
+                (permutation, resume) => (permutation.cloned(), resume.cloned()),
+            };
+        let swap_pass_sequence = if self.pass.is_empty() {
+            required(swap_pass_sequence, "pass")?
+        } else {
+            self.pass
+                .iter()
+                .cloned()
+                .map(parse_variant)
+                .collect::<Result<_, _>>()?
+        };
+        Ok((
+            UnverifiedConfig::Swap {
+                candidate_permutation,
+                resume,
+                displacement_goal: required(
+                    self.displacement_goal.clone().or(displacement_goal),
+                    "displacement-goal",
+                )?,
+                permutation_output_path_prefix: required(self.output.clone().or(output), "output")?,
+                swap_history_output_path_no_extension: self.history.clone().or(history),
+                checkpoint,
+                parameters: UnverifiedSwapParametersConfig {
+                    stop: self.stop(stop)?,
+                    swap_acceptance_threshold: required(
+                        self.threshold.or(swap_acceptance_threshold),
+                        "threshold",
+                    )?,
+                    swap_pass_sequence,
+                    output_intermediate_permutations: self.intermediate.unwrap_or(intermediate),
+                    intermediate_permutations,
+                    animation,
+                    statistics_log: match self.statistics_log.as_ref() {
+                        // The format is inferred from the path given on the command line
+                        Some(path) => Some(UnverifiedSwapStatisticsLogConfig {
+                            path: path.clone(),
+                            format: None,
+                        }),
+                        None => statistics_log,
+                    },
+                },
+                boundary: self.boundary.map(parse_variant).transpose()?.or(boundary),
+            },
+            output_policy,
+        ))
+    }
+}
+
+#[derive(Debug, Bpaf, PartialEq)]
+#[bpaf(command("validate-permutation"))]
+/// Check that a permutation is valid
+pub struct ValidatePermutationOptions {
+    /// Path of a configuration file describing the operation, to which the other options are applied
+    #[bpaf(long, short, argument("CONFIG_FILE"), complete_shell(ShellComp::File { mask: None }))]
+    pub config: Option<String>,
+    /// Path of the permutation to validate
+    #[bpaf(long, argument("PERMUTATION_FILE"))]
+    pub permutation: Option<String>,
+    /// Boundary condition (Bounded or Toroidal)
+    #[bpaf(long, argument("MODE"))]
+    pub boundary: Option<String>,
+}
+
+impl ValidatePermutationOptions {
+    pub fn into_unverified_config(
+        self,
+    ) -> Result<(UnverifiedConfig, OutputPolicyConfig), Box<dyn Error>> {
+        let (file_config, output_policy) = load_config_file(&self.config)?;
+        let (permutation, boundary) = match file_config {
+            None => (None, None),
+            Some(UnverifiedConfig::ValidatePermutation {
+                candidate_permutation,
+                boundary,
+            }) => (Some(candidate_permutation), boundary),
+            Some(_) => return Err(mismatch(&self.config, "validate permutation")),
+        };
+        Ok((
+            UnverifiedConfig::ValidatePermutation {
+                candidate_permutation: required(self.permutation.or(permutation), "permutation")?,
+                boundary: self.boundary.map(parse_variant).transpose()?.or(boundary),
+            },
+            output_policy,
+        ))
+    }
+}
+
+#[derive(Debug, Bpaf, PartialEq)]
+#[bpaf(command("visualize-vector-field"))]
+/// Render a vector field as a color image
+pub struct VisualizeVectorFieldOptions {
+    /// Path of a configuration file describing the operation, to which the other options are applied
+    #[bpaf(long, short, argument("CONFIG_FILE"), complete_shell(ShellComp::File { mask: None }))]
+    pub config: Option<String>,
+    /// Path of the vector field to visualize
+    #[bpaf(long, argument("VECTOR_FIELD_FILE"))]
+    pub vector_field: Option<String>,
+    /// Path of the visualization to output, without a file extension
+    #[bpaf(long, short, argument("OUTPUT_PATH"))]
+    pub output: Option<String>,
+    /// Path of the legend to output, without a file extension
+    #[bpaf(long, argument("LEGEND_PATH"))]
+    pub legend: Option<String>,
+    /// Vector magnitude that is shown at full brightness
+    #[bpaf(long, argument("MAGNITUDE"))]
+    pub max_magnitude: Option<f64>,
+    /// Spacing between arrows, in pixels
+    #[bpaf(long, argument("SPACING"))]
+    pub arrow_spacing: Option<u32>,
+}
+
+impl VisualizeVectorFieldOptions {
+    pub fn into_unverified_config(
+        self,
+    ) -> Result<(UnverifiedConfig, OutputPolicyConfig), Box<dyn Error>> {
+        let (file_config, output_policy) = load_config_file(&self.config)?;
+        let (vector_field, output, legend, parameters) = match file_config {
+            None => (None, None, None, None),
+            Some(UnverifiedConfig::VisualizeVectorField {
+                vector_field,
+                visualization_output_path_no_extension,
+                legend_output_path_no_extension,
+                parameters,
+            }) => (
+                Some(vector_field),
+                Some(visualization_output_path_no_extension),
+                legend_output_path_no_extension,
+                parameters,
+            ),
+            Some(_) => return Err(mismatch(&self.config, "visualize vector field")),
+        };
+        let parameters = parameters.unwrap_or_default();
+        Ok((
+            UnverifiedConfig::VisualizeVectorField {
+                vector_field: required(self.vector_field.or(vector_field), "vector-field")?,
+                visualization_output_path_no_extension: required(self.output.or(output), "output")?,
+                legend_output_path_no_extension: self.legend.or(legend),
+                parameters: Some(UnverifiedVectorFieldVisualizationParametersConfig {
+                    max_magnitude: self.max_magnitude.or(parameters.max_magnitude),
+                    arrow_spacing: self.arrow_spacing.or(parameters.arrow_spacing),
+                }),
+            },
+            output_policy,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests;
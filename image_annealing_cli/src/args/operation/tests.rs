@@ -0,0 +1,379 @@
+mod create_permutation_options {
+    use super::super::CreatePermutationOptions;
+    use crate::config::{AlgorithmConfig, Config, ImagePath, PermutationPath};
+    use image_annealing::{compute, ImageDimensions};
+    use std::error::Error;
+
+    fn make_config_path() -> String {
+        test_util::make_test_data_path_string([
+            "config",
+            "operation",
+            "create_permutation",
+            "valid.json",
+        ])
+    }
+
+    #[test]
+    fn options_only() -> Result<(), Box<dyn Error>> {
+        let options = CreatePermutationOptions {
+            config: None,
+            width: Some(3),
+            height: Some(4),
+            output: Some(String::from("permutation_out")),
+        };
+        let r: Config = options.into_unverified_config()?.0.try_into()?;
+        assert_eq!(
+            r,
+            Config {
+                algorithm: AlgorithmConfig::CreatePermutation {
+                    permutation_output_path_no_extension: PermutationPath::from_raw_clone(
+                        "permutation_out"
+                    ),
+                },
+                dispatcher: compute::Config {
+                    image_dimensions: ImageDimensions::try_new(3, 4)?,
+                    boundary: Default::default(),
+                },
+                output_policy: Default::default(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn config_file_with_overrides() -> Result<(), Box<dyn Error>> {
+        let options = CreatePermutationOptions {
+            config: Some(make_config_path()),
+            width: None,
+            height: Some(30),
+            output: Some(String::from("other_out")),
+        };
+        let r: Config = options.into_unverified_config()?.0.try_into()?;
+        assert_eq!(
+            r,
+            Config {
+                algorithm: AlgorithmConfig::CreatePermutation {
+                    permutation_output_path_no_extension: PermutationPath::from_raw_clone(
+                        "other_out"
+                    ),
+                },
+                dispatcher: compute::Config {
+                    image_dimensions: ImageDimensions::try_new(20, 30)?,
+                    boundary: Default::default(),
+                },
+                output_policy: Default::default(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn missing_option() {
+        let options = CreatePermutationOptions {
+            config: None,
+            width: Some(3),
+            height: None,
+            output: Some(String::from("permutation_out")),
+        };
+        test_util::assert_error_contains(
+            options.into_unverified_config().map(|_| ()),
+            "option --height is required when it is not set by a configuration file",
+        );
+    }
+
+    #[test]
+    fn invalid_dimensions() {
+        let options = CreatePermutationOptions {
+            config: None,
+            width: Some(0),
+            height: Some(4),
+            output: Some(String::from("permutation_out")),
+        };
+        test_util::assert_error_contains(
+            options
+                .into_unverified_config()
+                .and_then(|(config, _)| Config::try_from(config)),
+            "width is zero",
+        );
+    }
+}
+
+mod swap_options {
+    use super::super::SwapOptions;
+    use crate::config::{
+        AlgorithmConfig, BoundaryMode, Config, DisplacementGoalPath, ImagePath, IterationCount,
+        PermutationPath, SwapParametersConfig, SwapStatisticsLogConfig, SwapStatisticsLogFormat,
+        SwapStopConfig, SwapStopThreshold,
+    };
+    use image_annealing::compute::{self, SwapPass, SwapPassSequence};
+    use std::error::Error;
+    use std::num::NonZeroUsize;
+
+    fn make_options() -> SwapOptions {
+        SwapOptions {
+            config: None,
+            permutation: Some(String::from(
+                "../test_data/image/permutation/identity_permutation.png",
+            )),
+            resume: None,
+            displacement_goal: Some(String::from(
+                "../test_data/image/displacement_goal/identity_displacement_goal.png",
+            )),
+            output: Some(String::from("permutation_out")),
+            history: None,
+            threshold: Some(2.0),
+            iterations: Some(3),
+            stop_swaps_accepted: None,
+            stop_acceptance_fraction: None,
+            pass: vec![String::from("Vertical"), String::from("OffsetVertical")],
+            intermediate: Some(true),
+            statistics_log: None,
+            boundary: Some(String::from("Toroidal")),
+        }
+    }
+
+    #[test]
+    fn options_only() -> Result<(), Box<dyn Error>> {
+        let r: Config = make_options().into_unverified_config()?.0.try_into()?;
+        let (candidate_permutation, image_dimensions) = PermutationPath::from_input_path(
+            "../test_data/image/permutation/identity_permutation.png",
+        )?;
+        let (displacement_goal, _) = DisplacementGoalPath::from_input_path(
+            "../test_data/image/displacement_goal/identity_displacement_goal.png",
+        )?;
+        assert_eq!(
+            r,
+            Config {
+                algorithm: AlgorithmConfig::Swap {
+                    candidate_permutation,
+                    resume: None,
+                    displacement_goal,
+                    permutation_output_path_prefix: PermutationPath::from_raw_clone(
+                        "permutation_out"
+                    ),
+                    swap_history_output_path_no_extension: None,
+                    checkpoint: None,
+                    parameters: SwapParametersConfig {
+                        stop: SwapStopConfig::Bounded {
+                            iteration_count: IterationCount(NonZeroUsize::new(3).unwrap()),
+                            threshold: None,
+                        },
+                        swap_acceptance_threshold: 2.0,
+                        swap_pass_sequence: SwapPassSequence::from_passes([
+                            SwapPass::Vertical,
+                            SwapPass::OffsetVertical,
+                        ])?,
+                        output_intermediate_permutations: true,
+                        intermediate_permutations: Default::default(),
+                        animation: None,
+                        statistics_log: None,
+                    },
+                },
+                dispatcher: compute::Config {
+                    image_dimensions,
+                    boundary: BoundaryMode::Toroidal.into(),
+                },
+                output_policy: Default::default(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unbounded() -> Result<(), Box<dyn Error>> {
+        let r: Config = SwapOptions {
+            iterations: None,
+            stop_acceptance_fraction: Some(0.5),
+            ..make_options()
+        }
+        .into_unverified_config()?
+        .0
+        .try_into()?;
+        match r.algorithm {
+            AlgorithmConfig::Swap { parameters, .. } => assert_eq!(
+                parameters.stop,
+                SwapStopConfig::Unbounded(SwapStopThreshold::SwapAcceptanceFraction(
+                    0.5.try_into()?
+                ))
+            ),
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn statistics_log() -> Result<(), Box<dyn Error>> {
+        let r: Config = SwapOptions {
+            statistics_log: Some(String::from("statistics.csv")),
+            ..make_options()
+        }
+        .into_unverified_config()?
+        .0
+        .try_into()?;
+        match r.algorithm {
+            AlgorithmConfig::Swap { parameters, .. } => assert_eq!(
+                parameters.statistics_log,
+                Some(SwapStatisticsLogConfig {
+                    path: String::from("statistics.csv"),
+                    format: SwapStatisticsLogFormat::Csv,
+                })
+            ),
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn intermediate_override() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_data_path_string([
+            "config",
+            "operation",
+            "swap",
+            "intermediate.json",
+        ]);
+        for (intermediate, expected) in [(None, true), (Some(false), false), (Some(true), true)] {
+            let r: Config = SwapOptions {
+                config: Some(path.clone()),
+                intermediate,
+                ..make_options()
+            }
+            .into_unverified_config()?
+            .0
+            .try_into()?;
+            match r.algorithm {
+                AlgorithmConfig::Swap { parameters, .. } => {
+                    assert_eq!(parameters.output_intermediate_permutations, expected)
+                }
+                _ => unreachable!(),
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn missing_stop() {
+        test_util::assert_error_contains(
+            SwapOptions {
+                iterations: None,
+                ..make_options()
+            }
+            .into_unverified_config()
+            .map(|_| ()),
+            "option --iterations is required when it is not set by a configuration file",
+        );
+    }
+
+    #[test]
+    fn conflicting_stop_thresholds() {
+        test_util::assert_error_contains(
+            SwapOptions {
+                stop_swaps_accepted: Some(1),
+                stop_acceptance_fraction: Some(0.5),
+                ..make_options()
+            }
+            .into_unverified_config()
+            .map(|_| ()),
+            "options --stop-swaps-accepted and --stop-acceptance-fraction cannot be used together",
+        );
+    }
+
+    #[test]
+    fn invalid_pass() {
+        test_util::assert_error_contains(
+            SwapOptions {
+                pass: vec![String::from("Diagonal")],
+                ..make_options()
+            }
+            .into_unverified_config()
+            .map(|_| ()),
+            "unknown variant `Diagonal`",
+        );
+    }
+
+    #[test]
+    fn operation_mismatch() {
+        let path = test_util::make_test_data_path_string([
+            "config",
+            "operation",
+            "create_permutation",
+            "valid.json",
+        ]);
+        test_util::assert_error_contains(
+            SwapOptions {
+                config: Some(path.clone()),
+                ..make_options()
+            }
+            .into_unverified_config()
+            .map(|_| ()),
+            &format!(
+                "configuration file '{}' does not describe a swap operation",
+                path
+            ),
+        );
+    }
+}
+
+mod permute_options {
+    use super::super::PermuteOptions;
+    use crate::config::{AlgorithmConfig, Config, LosslessImagePath, UnverifiedLosslessImagePath};
+    use std::error::Error;
+
+    #[test]
+    fn detected_image_format() -> Result<(), Box<dyn Error>> {
+        let r: Config = PermuteOptions {
+            config: None,
+            permutation: Some(String::from(
+                "../test_data/image/permutation/identity_permutation.png",
+            )),
+            image: Some(String::from("../test_data/image/image/stripes.png")),
+            conversion: None,
+            output: Some(String::from("permuted_image_out")),
+            boundary: None,
+        }
+        .into_unverified_config()?
+        .0
+        .try_into()?;
+        match r.algorithm {
+            AlgorithmConfig::Permute { images, .. } => {
+                assert_eq!(images.len(), 1);
+                assert_eq!(
+                    images[0].original_image,
+                    LosslessImagePath::from_input_path(UnverifiedLosslessImagePath::Rgba8(
+                        String::from("../test_data/image/image/stripes.png")
+                    ))?
+                    .0
+                );
+                assert_eq!(
+                    images[0].permuted_image_output_path_no_extension,
+                    LosslessImagePath::Rgba8(String::from("permuted_image_out"))
+                );
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+}
+
+mod create_displacement_goal_options {
+    use super::super::CreateDisplacementGoalOptions;
+
+    #[test]
+    fn conflicting_inputs() {
+        let options = CreateDisplacementGoalOptions {
+            config: None,
+            width: Some(3),
+            height: Some(4),
+            displacement_goal: None,
+            permutation: Some(String::from(
+                "../test_data/image/permutation/identity_permutation.png",
+            )),
+            image: None,
+            conversion: None,
+            output: Some(String::from("displacement_goal_out")),
+        };
+        test_util::assert_error_contains(
+            options.into_unverified_config().map(|_| ()),
+            "image dimensions (--width and --height) cannot be used together with input data",
+        );
+    }
+}
@@ -8,10 +8,18 @@ mod options {
 }
 
 mod parse_args {
-    use super::super::parse_args;
+    use super::super::{parse_args, ParsedArgs};
     use crate::config::{AlgorithmConfig, Config, ImagePath, PermutationPath};
     use bpaf::ParseFailure;
     use image_annealing::{compute, ImageDimensions};
+    use std::path::PathBuf;
+
+    fn parse_operation_args(v: Vec<String>) -> Result<(Config, bool), ParseFailure> {
+        match parse_args(v)? {
+            ParsedArgs::Operation { config, check } => Ok((*config, check)),
+            parsed_args => panic!("unexpected parsed arguments {:?}", parsed_args),
+        }
+    }
 
     #[test]
     #[should_panic(expected = "no arguments (not even the program name)")]
@@ -39,18 +47,64 @@ mod parse_args {
             "valid.json",
         ]);
         let v = vec![String::from("one"), String::from("-c"), path];
-        let r = parse_args(v)?;
+        let (r, _) = parse_operation_args(v)?;
         assert_eq!(
             r,
             Config {
                 algorithm: AlgorithmConfig::CreatePermutation {
-                    permutation_output_path_no_extension: PermutationPath::from_raw_clone(
-                        "permutation_out"
+                    permutation_output_path_no_extension: PermutationPath::from_raw(
+                        test_util::make_test_data_path_string([
+                            "config",
+                            "operation",
+                            "create_permutation",
+                            "permutation_out",
+                        ])
                     ),
                 },
                 dispatcher: compute::Config {
-                    image_dimensions: ImageDimensions::try_new(20, 25).unwrap()
-                }
+                    image_dimensions: ImageDimensions::try_new(20, 25).unwrap(),
+                    boundary: Default::default(),
+                },
+                output_policy: Default::default(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn check_flag() -> Result<(), ParseFailure> {
+        let path = test_util::make_test_data_path_string([
+            "config",
+            "operation",
+            "create_permutation",
+            "valid.json",
+        ]);
+        let v = vec![String::from("one"), String::from("-c"), path.clone()];
+        assert!(!parse_operation_args(v)?.1);
+        let v = vec![
+            String::from("one"),
+            String::from("--check"),
+            String::from("-c"),
+            path,
+        ];
+        assert!(parse_operation_args(v)?.1);
+        Ok(())
+    }
+
+    #[test]
+    fn schema_command() -> Result<(), ParseFailure> {
+        let v = vec![String::from("one"), String::from("schema")];
+        assert_eq!(parse_args(v)?, ParsedArgs::Schema { output_file: None });
+        let v = vec![
+            String::from("one"),
+            String::from("schema"),
+            String::from("--output-file"),
+            String::from("schema.json"),
+        ];
+        assert_eq!(
+            parse_args(v)?,
+            ParsedArgs::Schema {
+                output_file: Some(PathBuf::from("schema.json"))
             }
         );
         Ok(())
@@ -76,4 +130,90 @@ mod parse_args {
             "No such command: `other_arg`",
         );
     }
+
+    #[test]
+    fn create_permutation_command() -> Result<(), ParseFailure> {
+        let v = vec![
+            String::from("one"),
+            String::from("create-permutation"),
+            String::from("--width"),
+            String::from("20"),
+            String::from("--height"),
+            String::from("25"),
+            String::from("--output"),
+            String::from("permutation_out"),
+        ];
+        let (r, _) = parse_operation_args(v)?;
+        assert_eq!(
+            r,
+            Config {
+                algorithm: AlgorithmConfig::CreatePermutation {
+                    permutation_output_path_no_extension: PermutationPath::from_raw_clone(
+                        "permutation_out"
+                    ),
+                },
+                dispatcher: compute::Config {
+                    image_dimensions: ImageDimensions::try_new(20, 25).unwrap(),
+                    boundary: Default::default(),
+                },
+                output_policy: Default::default(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn command_config_file_with_overrides() -> Result<(), ParseFailure> {
+        let path = test_util::make_test_data_path_string([
+            "config",
+            "operation",
+            "create_permutation",
+            "valid.json",
+        ]);
+        let v = vec![
+            String::from("one"),
+            String::from("create-permutation"),
+            String::from("-c"),
+            path,
+            String::from("--width"),
+            String::from("30"),
+        ];
+        let (r, _) = parse_operation_args(v)?;
+        assert_eq!(
+            r,
+            Config {
+                algorithm: AlgorithmConfig::CreatePermutation {
+                    permutation_output_path_no_extension: PermutationPath::from_raw(
+                        test_util::make_test_data_path_string([
+                            "config",
+                            "operation",
+                            "create_permutation",
+                            "permutation_out",
+                        ])
+                    ),
+                },
+                dispatcher: compute::Config {
+                    image_dimensions: ImageDimensions::try_new(30, 25).unwrap(),
+                    boundary: Default::default(),
+                },
+                output_policy: Default::default(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn command_missing_option() {
+        let v = vec![
+            String::from("one"),
+            String::from("create-permutation"),
+            String::from("--width"),
+            String::from("20"),
+        ];
+        let message = parse_args(v).unwrap_err().unwrap_stderr();
+        test_util::assert_error_contains::<(), String>(
+            Err(message),
+            "option --height is required when it is not set by a configuration file",
+        );
+    }
 }
@@ -0,0 +1,222 @@
+use crate::config::{
+    self, AlgorithmConfig, Config, CreateDisplacementGoalInputConfig, ImagePath, LosslessImagePath,
+    OutputFiles, SwapParametersConfig, WorkflowInput, WorkflowOperationConfig,
+};
+use image_annealing::{BoundaryMode, ImageDimensions};
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+/// A description of the operation that a verified configuration would run
+#[derive(Debug, PartialEq)]
+pub struct ConfigSummary {
+    pub operation: &'static str,
+    pub image_dimensions: ImageDimensions,
+    pub boundary: BoundaryMode,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<OutputFiles>,
+}
+
+impl ConfigSummary {
+    pub fn new(config: &Config) -> Self {
+        let mut inputs = Vec::new();
+        let operation = match &config.algorithm {
+            AlgorithmConfig::CreateDisplacementGoal { input, .. } => {
+                add_create_displacement_goal_inputs(&mut inputs, input);
+                "CreateDisplacementGoal"
+            }
+            AlgorithmConfig::CreatePermutation { .. } => "CreatePermutation",
+            AlgorithmConfig::Permute {
+                candidate_permutation,
+                images,
+            } => {
+                add_input(&mut inputs, candidate_permutation.as_ref());
+                for image in images {
+                    add_image_inputs(&mut inputs, &image.original_image);
+                }
+                "Permute"
+            }
+            AlgorithmConfig::Swap {
+                candidate_permutation,
+                displacement_goal,
+                parameters,
+                ..
+            } => {
+                add_input(&mut inputs, candidate_permutation.as_ref());
+                add_input(&mut inputs, displacement_goal.as_ref());
+                add_swap_parameters_inputs(&mut inputs, parameters);
+                "Swap"
+            }
+            AlgorithmConfig::ValidatePermutation {
+                candidate_permutation,
+            } => {
+                add_input(&mut inputs, candidate_permutation.as_ref());
+                "ValidatePermutation"
+            }
+            AlgorithmConfig::VisualizeVectorField { vector_field, .. } => {
+                add_input(&mut inputs, vector_field.as_ref());
+                "VisualizeVectorField"
+            }
+            AlgorithmConfig::Workflow { steps } => {
+                for step in steps {
+                    add_workflow_operation_inputs(&mut inputs, &step.operation);
+                }
+                "Workflow"
+            }
+            AlgorithmConfig::Experiment {
+                candidate_permutation,
+                displacement_goal,
+                runs,
+                ..
+            } => {
+                add_input(&mut inputs, candidate_permutation.as_ref());
+                add_input(&mut inputs, displacement_goal.as_ref());
+                for run in runs {
+                    add_swap_parameters_inputs(&mut inputs, &run.parameters);
+                }
+                "Experiment"
+            }
+        };
+        Self {
+            operation,
+            image_dimensions: config.dispatcher.image_dimensions,
+            boundary: config.dispatcher.boundary,
+            inputs,
+            outputs: config.outputs(),
+        }
+    }
+
+    /// Returns the paths of existing files that running the operation could overwrite
+    pub fn existing_outputs(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        config::existing_outputs(&self.outputs)
+    }
+}
+
+impl fmt::Display for ConfigSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Operation: {}", self.operation)?;
+        writeln!(f, "Image dimensions: {}", self.image_dimensions)?;
+        writeln!(f, "Boundary conditions: {}", self.boundary)?;
+        writeln!(f, "Input files:")?;
+        for input in self.inputs.iter() {
+            writeln!(f, "  {}", input)?;
+        }
+        write!(f, "Output files:")?;
+        for output in self.outputs.iter() {
+            write!(f, "\n  {}", output)?;
+        }
+        Ok(())
+    }
+}
+
+fn add_input(inputs: &mut Vec<String>, path: &str) {
+    if !inputs.iter().any(|input| input == path) {
+        inputs.push(String::from(path));
+    }
+}
+
+fn add_image_inputs(inputs: &mut Vec<String>, path: &LosslessImagePath) {
+    for path in path.to_vec() {
+        add_input(inputs, path);
+    }
+}
+
+fn add_create_displacement_goal_inputs(
+    inputs: &mut Vec<String>,
+    input: &CreateDisplacementGoalInputConfig,
+) {
+    if let Some(path) = input.displacement_goal.as_ref() {
+        add_input(inputs, path.as_ref());
+    }
+    if let Some(path) = input.candidate_permutation.as_ref() {
+        add_input(inputs, path.as_ref());
+    }
+    if let Some(path) = input.image.as_ref() {
+        add_image_inputs(inputs, path);
+    }
+}
+
+fn add_swap_parameters_inputs(inputs: &mut Vec<String>, parameters: &SwapParametersConfig) {
+    if let Some(animation_config) = parameters.animation.as_ref() {
+        add_input(inputs, &animation_config.image);
+    }
+}
+
+fn add_workflow_input<T, F: Fn(&mut Vec<String>, &T)>(
+    inputs: &mut Vec<String>,
+    input: Option<&WorkflowInput<T>>,
+    add: F,
+) {
+    if let Some(path) = input.and_then(WorkflowInput::file) {
+        add(inputs, path)
+    }
+}
+
+fn add_vector_field_input<P: ImagePath>(inputs: &mut Vec<String>, path: &P) {
+    add_input(inputs, path.as_ref())
+}
+
+/// Adds the input files of a workflow step to `inputs`
+fn add_workflow_operation_inputs(inputs: &mut Vec<String>, operation: &WorkflowOperationConfig) {
+    match operation {
+        WorkflowOperationConfig::CreateDisplacementGoal {
+            displacement_goal,
+            candidate_permutation,
+            image,
+            ..
+        } => {
+            add_workflow_input(inputs, displacement_goal.as_ref(), add_vector_field_input);
+            add_workflow_input(
+                inputs,
+                candidate_permutation.as_ref(),
+                add_vector_field_input,
+            );
+            add_workflow_input(inputs, image.as_ref(), add_image_inputs);
+        }
+        WorkflowOperationConfig::CreatePermutation { .. } => (),
+        WorkflowOperationConfig::Permute {
+            candidate_permutation,
+            original_image,
+            ..
+        } => {
+            add_workflow_input(inputs, Some(candidate_permutation), add_vector_field_input);
+            add_workflow_input(inputs, Some(original_image), add_image_inputs);
+        }
+        WorkflowOperationConfig::Swap {
+            candidate_permutation,
+            displacement_goal,
+            parameters,
+            ..
+        } => {
+            add_workflow_input(inputs, Some(candidate_permutation), add_vector_field_input);
+            add_workflow_input(inputs, Some(displacement_goal), add_vector_field_input);
+            add_swap_parameters_inputs(inputs, parameters);
+        }
+        WorkflowOperationConfig::ValidatePermutation {
+            candidate_permutation,
+        } => {
+            add_workflow_input(inputs, Some(candidate_permutation), add_vector_field_input);
+        }
+    }
+}
+
+/// Prints a summary of the operation that a verified configuration would run,
+/// including the existing files that running the operation could overwrite,
+/// without running the operation
+pub fn check(config: &Config) -> Result<(), Box<dyn Error>> {
+    let summary = ConfigSummary::new(config);
+    println!("{}", summary);
+    let existing_outputs = summary.existing_outputs()?;
+    if existing_outputs.is_empty() {
+        println!("No existing files would be overwritten");
+    } else {
+        println!("Existing files that could be overwritten:");
+        for path in existing_outputs {
+            println!("  {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+mod config_summary {
+    use super::super::ConfigSummary;
+    use crate::config::{
+        AlgorithmConfig, Config, ImagePath, LosslessImagePath, OutputFiles, PermutationPath,
+        PermuteImageConfig,
+    };
+    use image_annealing::compute;
+    use image_annealing::{BoundaryMode, ImageDimensions};
+
+    #[test]
+    fn permute() {
+        let config = Config {
+            algorithm: AlgorithmConfig::Permute {
+                candidate_permutation: PermutationPath::from_raw_clone("permutation.png"),
+                images: vec![PermuteImageConfig {
+                    original_image: LosslessImagePath::Rgba8(String::from("image.png")),
+                    original_image_conversion: Default::default(),
+                    permuted_image_output_path_no_extension: LosslessImagePath::Rgba8x2(
+                        String::from("permuted1"),
+                        String::from("permuted2"),
+                    ),
+                }],
+            },
+            dispatcher: compute::Config {
+                image_dimensions: ImageDimensions::try_new(20, 25).unwrap(),
+                boundary: BoundaryMode::Toroidal,
+            },
+            output_policy: Default::default(),
+        };
+        let summary = ConfigSummary::new(&config);
+        assert_eq!(
+            summary,
+            ConfigSummary {
+                operation: "Permute",
+                image_dimensions: ImageDimensions::try_new(20, 25).unwrap(),
+                boundary: BoundaryMode::Toroidal,
+                inputs: vec![String::from("permutation.png"), String::from("image.png")],
+                outputs: vec![
+                    OutputFiles::Exact(String::from("permuted1.png")),
+                    OutputFiles::Exact(String::from("permuted2.png")),
+                ],
+            }
+        );
+        assert_eq!(
+            summary.to_string(),
+            "Operation: Permute\n\
+            Image dimensions: (width, height) = (20, 25)\n\
+            Boundary conditions: toroidal\n\
+            Input files:\n  permutation.png\n  image.png\n\
+            Output files:\n  permuted1.png\n  permuted2.png"
+        );
+    }
+
+    #[test]
+    fn create_permutation_with_extension() {
+        let config = Config {
+            algorithm: AlgorithmConfig::CreatePermutation {
+                permutation_output_path_no_extension: PermutationPath::from_raw_clone(
+                    "permutation.png",
+                ),
+            },
+            dispatcher: compute::Config {
+                image_dimensions: ImageDimensions::try_new(20, 25).unwrap(),
+                boundary: Default::default(),
+            },
+            output_policy: Default::default(),
+        };
+        let summary = ConfigSummary::new(&config);
+        assert!(summary.inputs.is_empty());
+        assert_eq!(
+            summary.outputs,
+            vec![OutputFiles::Exact(String::from("permutation.png"))]
+        );
+    }
+}
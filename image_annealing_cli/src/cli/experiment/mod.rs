@@ -0,0 +1,220 @@
+use super::{interrupt, loader, metadata, swap};
+use crate::config::{DisplacementGoalPath, ExperimentRunConfig, PermutationPath, SwapPass};
+use image_annealing::compute::{self, Dispatcher};
+use image_annealing::image_utils::energy;
+use serde::Serialize;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// A row of the summary table of an experiment
+#[derive(Debug, PartialEq, Serialize)]
+struct ExperimentRunSummary {
+    run: String,
+    directory: String,
+    swap_acceptance_threshold: f32,
+    swap_pass_sequence: Vec<SwapPass>,
+    round_count: usize,
+    converged: bool,
+    final_swaps_accepted: Option<usize>,
+    final_swaps_total: Option<usize>,
+    /// The potential energy of the run's final permutation with respect to the displacement goal
+    final_energy: Option<f64>,
+    wall_time_seconds: f64,
+}
+
+impl ExperimentRunSummary {
+    fn new(
+        run: &ExperimentRunConfig,
+        statistics: &swap::SwapStatistics,
+        final_energy: Option<f64>,
+        wall_time: Duration,
+    ) -> Self {
+        Self {
+            run: run.name.clone(),
+            directory: run.directory.clone(),
+            swap_acceptance_threshold: run.parameters.swap_acceptance_threshold,
+            swap_pass_sequence: run
+                .parameters
+                .swap_pass_sequence
+                .iter()
+                .map(|&pass| pass.into())
+                .collect(),
+            round_count: statistics.round_count,
+            converged: statistics.converged,
+            final_swaps_accepted: statistics.final_swap_counts.map(|(accepted, _)| accepted),
+            final_swaps_total: statistics.final_swap_counts.map(|(_, total)| total),
+            final_energy,
+            wall_time_seconds: wall_time.as_secs_f64(),
+        }
+    }
+}
+
+fn format_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        String::from(field)
+    }
+}
+
+fn write_summary_csv<W: Write>(
+    summaries: &[ExperimentRunSummary],
+    mut writer: W,
+) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "run,directory,swap_acceptance_threshold,swap_pass_sequence,round_count,converged,final_swaps_accepted,final_swaps_total,final_energy,wall_time_seconds"
+    )?;
+    for summary in summaries {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{},{},{}",
+            format_csv_field(&summary.run),
+            format_csv_field(&summary.directory),
+            summary.swap_acceptance_threshold,
+            summary
+                .swap_pass_sequence
+                .iter()
+                .map(|pass| format!("{:?}", pass))
+                .collect::<Vec<_>>()
+                .join(" "),
+            summary.round_count,
+            summary.converged,
+            summary
+                .final_swaps_accepted
+                .map(|count| count.to_string())
+                .unwrap_or_default(),
+            summary
+                .final_swaps_total
+                .map(|count| count.to_string())
+                .unwrap_or_default(),
+            summary
+                .final_energy
+                .map(|energy| energy.to_string())
+                .unwrap_or_default(),
+            summary.wall_time_seconds
+        )?;
+    }
+    writer.flush()
+}
+
+/// Writes the summary of the runs finished so far,
+/// so that the summary is available even if a later run fails
+fn write_summary(
+    output_directory: &str,
+    summaries: &[ExperimentRunSummary],
+) -> Result<(), Box<dyn Error>> {
+    let directory = Path::new(output_directory);
+    write_summary_csv(
+        summaries,
+        BufWriter::new(File::create(directory.join("summary.csv"))?),
+    )?;
+    let mut writer = BufWriter::new(File::create(directory.join("summary.json"))?);
+    serde_json::to_writer_pretty(&mut writer, summaries)?;
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn run_and_save_experiment(
+    mut dispatcher: Box<dyn Dispatcher>,
+    candidate_permutation: &PermutationPath,
+    displacement_goal: &DisplacementGoalPath,
+    output_directory: &str,
+    runs: &[ExperimentRunConfig],
+    seed: Option<u64>,
+    dispatcher_config: &compute::Config,
+) -> Result<(), Box<dyn Error>> {
+    let interrupt = interrupt::install_handler();
+    let candidate_permutation_data = loader::load_candidate_permutation(candidate_permutation)?;
+    let displacement_goal_data = loader::load_displacement_goal(displacement_goal)?;
+    let mut base_metadata = metadata::make_run_metadata(
+        "Swap",
+        [candidate_permutation.as_ref(), displacement_goal.as_ref()]
+            .into_iter()
+            .chain(
+                runs.first()
+                    .and_then(|run| run.parameters.animation.as_ref())
+                    .map(|animation_config| animation_config.image.as_str()),
+            ),
+    )?;
+    base_metadata.seed = seed;
+    let mut summaries = Vec::with_capacity(runs.len());
+    for run in runs {
+        println!("Running experiment run '{}'", run.name);
+        std::fs::create_dir_all(&run.directory)?;
+        let mut metadata = base_metadata.clone();
+        metadata.threshold = Some(run.parameters.swap_acceptance_threshold);
+        let start = Instant::now();
+        let output = swap::swap_and_save(
+            dispatcher,
+            candidate_permutation_data.clone(),
+            displacement_goal_data.clone(),
+            0,
+            swap::SwapPermutationOutputs {
+                path_prefix: Some(&run.permutation_output_path_prefix),
+                animation: run
+                    .parameters
+                    .animation
+                    .as_ref()
+                    .map(swap::SwapAnimationOutput::new)
+                    .transpose()?,
+                metadata: metadata.clone(),
+                retain_permutations: false,
+            },
+            swap::SwapRoundOutputs {
+                swap_history: run
+                    .swap_history_output_path_no_extension
+                    .as_deref()
+                    .map(swap::SwapHistoryOutput::new),
+                checkpoint: run.checkpoint.as_ref().map(|checkpoint_config| {
+                    swap::SwapCheckpointOutput::new(
+                        checkpoint_config,
+                        &run.parameters,
+                        dispatcher_config.boundary.into(),
+                        metadata,
+                    )
+                }),
+                statistics_log: run
+                    .parameters
+                    .statistics_log
+                    .as_ref()
+                    .map(swap::SwapStatisticsLogOutput::new),
+                count_swaps: true,
+                boundary: dispatcher_config.boundary,
+                interrupt: Some(interrupt),
+            },
+            &run.parameters,
+        )?;
+        let wall_time = start.elapsed();
+        dispatcher = output.dispatcher;
+        let final_energy = output
+            .final_permutation
+            .as_ref()
+            .map(|permutation| {
+                energy::potential_energy(
+                    permutation,
+                    &displacement_goal_data,
+                    dispatcher_config.boundary,
+                )
+            })
+            .transpose()?;
+        summaries.push(ExperimentRunSummary::new(
+            run,
+            &output.statistics,
+            final_energy,
+            wall_time,
+        ));
+        write_summary(output_directory, &summaries)?;
+    }
+    println!(
+        "Wrote experiment summary to: {}",
+        Path::new(output_directory).join("summary.csv").display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
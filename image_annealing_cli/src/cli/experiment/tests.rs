@@ -0,0 +1,44 @@
+mod write_summary_csv {
+    use super::super::{write_summary_csv, ExperimentRunSummary};
+    use crate::config::SwapPass;
+    use std::error::Error;
+
+    #[test]
+    fn summaries() -> Result<(), Box<dyn Error>> {
+        let summaries = [
+            ExperimentRunSummary {
+                run: String::from("run_0"),
+                directory: String::from("experiment,1/run_0"),
+                swap_acceptance_threshold: 0.5,
+                swap_pass_sequence: vec![SwapPass::Horizontal, SwapPass::OffsetVertical],
+                round_count: 3,
+                converged: true,
+                final_swaps_accepted: Some(0),
+                final_swaps_total: Some(100),
+                final_energy: Some(12.5),
+                wall_time_seconds: 1.25,
+            },
+            ExperimentRunSummary {
+                run: String::from("run_1"),
+                directory: String::from("experiment,1/run_1"),
+                swap_acceptance_threshold: 1.0,
+                swap_pass_sequence: vec![SwapPass::Vertical],
+                round_count: 10,
+                converged: false,
+                final_swaps_accepted: None,
+                final_swaps_total: None,
+                final_energy: None,
+                wall_time_seconds: 2.0,
+            },
+        ];
+        let mut output = Vec::new();
+        write_summary_csv(&summaries, &mut output)?;
+        assert_eq!(
+            String::from_utf8(output)?,
+            "run,directory,swap_acceptance_threshold,swap_pass_sequence,round_count,converged,final_swaps_accepted,final_swaps_total,final_energy,wall_time_seconds\n\
+            run_0,\"experiment,1/run_0\",0.5,Horizontal OffsetVertical,3,true,0,100,12.5,1.25\n\
+            run_1,\"experiment,1/run_1\",1,Vertical,10,false,,,,2\n"
+        );
+        Ok(())
+    }
+}
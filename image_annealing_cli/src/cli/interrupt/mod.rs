@@ -0,0 +1,53 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+
+/// The exit status of the program when a swap run was interrupted
+/// after saving its current permutation
+pub const INTERRUPTED_EXIT_CODE: i32 = 3;
+
+/// The exit status of the program when a second interrupt signal arrived
+/// before the interrupted run could save its current permutation
+pub const ABORTED_EXIT_CODE: i32 = 130;
+
+static INTERRUPT_REQUESTED: AtomicBool = AtomicBool::new(false);
+static INSTALL_HANDLER: Once = Once::new();
+
+/// Installs a handler for SIGINT and SIGTERM, if it has not yet been installed,
+/// and returns the flag that the handler sets when the first signal arrives
+///
+/// The flag is cleared, in case a signal interrupted an earlier run in the same process.
+/// A second signal terminates the process immediately.
+pub fn install_handler() -> &'static AtomicBool {
+    INSTALL_HANDLER.call_once(|| {
+        if let Err(err) = ctrlc::set_handler(|| {
+            if INTERRUPT_REQUESTED.swap(true, Ordering::SeqCst) {
+                eprintln!("Aborting without saving the current permutation");
+                std::process::exit(ABORTED_EXIT_CODE);
+            } else {
+                eprintln!(
+                    "Interrupted: stopping after the current texel swap pass (interrupt again to abort immediately)"
+                );
+            }
+        }) {
+            eprintln!("Failed to install an interrupt signal handler: {}", err);
+        }
+    });
+    INTERRUPT_REQUESTED.store(false, Ordering::SeqCst);
+    &INTERRUPT_REQUESTED
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InterruptedError;
+
+impl fmt::Display for InterruptedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "swap operation interrupted after saving the current permutation"
+        )
+    }
+}
+
+impl Error for InterruptedError {}
@@ -1,4 +1,6 @@
-use super::super::config::{DisplacementGoalPath, LosslessImagePath, PermutationPath};
+use super::super::config::{
+    DisplacementGoalPath, ImageConversionPolicy, LosslessImagePath, PermutationPath,
+};
 use image_annealing::compute::format::{ImageFileReader, LosslessImage};
 use image_annealing::{CandidatePermutation, DisplacementGoal};
 use std::error::Error;
@@ -15,6 +17,9 @@ pub fn load_displacement_goal(
     DisplacementGoal::load(path)
 }
 
-pub fn load_image(path: &LosslessImagePath) -> Result<LosslessImage, Box<dyn Error>> {
-    LosslessImage::load(path.format(), path.to_vec().as_slice())
+pub fn load_image(
+    path: &LosslessImagePath,
+    conversion: ImageConversionPolicy,
+) -> Result<LosslessImage, Box<dyn Error>> {
+    LosslessImage::load_with_conversion(path.format(), path.to_vec().as_slice(), conversion.into())
 }
@@ -0,0 +1,32 @@
+use image_annealing::compute::format::{InputFileHash, RunMetadata};
+use image_annealing_cli_util::hash;
+use std::io;
+
+pub fn hash_input_files<T, P>(input_paths: T) -> Result<Vec<InputFileHash>, io::Error>
+where
+    T: IntoIterator<Item = P>,
+    P: AsRef<str>,
+{
+    input_paths
+        .into_iter()
+        .map(|path| {
+            Ok(InputFileHash {
+                sha256: hash::sha256_file(path.as_ref())?,
+                path: String::from(path.as_ref()),
+            })
+        })
+        .collect()
+}
+
+/// Creates metadata to embed in the output images of an operation,
+/// identifying the operation's input files by their contents
+pub fn make_run_metadata<T, P>(operation: &str, input_paths: T) -> Result<RunMetadata, io::Error>
+where
+    T: IntoIterator<Item = P>,
+    P: AsRef<str>,
+{
+    Ok(RunMetadata {
+        input_hashes: hash_input_files(input_paths)?,
+        ..RunMetadata::new(operation)
+    })
+}
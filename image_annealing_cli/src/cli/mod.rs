@@ -1,22 +1,50 @@
-use crate::config::{AlgorithmConfig, Config, CreateDisplacementGoalInputConfig};
-use image_annealing::compute::format::ImageFileWriter;
+use crate::config::{
+    AlgorithmConfig, Config, CreateDisplacementGoalInputConfig, ImagePath, LosslessImagePath,
+};
 use image_annealing::compute::{
-    self, CreateDisplacementGoalInput, Dispatcher, PermuteInput, ValidatePermutationInput,
+    self, CreateDisplacementGoalInput, Dispatcher, ValidatePermutationInput,
 };
 use std::error::Error;
 
+mod check;
+mod experiment;
+mod interrupt;
 mod loader;
+mod metadata;
+mod permute;
 mod swap;
+mod visualize;
+mod workflow;
+
+pub use check::check;
+pub use interrupt::{InterruptedError, ABORTED_EXIT_CODE, INTERRUPTED_EXIT_CODE};
 
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+pub fn run(mut config: Config) -> Result<(), Box<dyn Error>> {
+    config.apply_output_policy()?;
+    // Vector field visualization runs on the CPU, so it does not need a dispatcher
+    if let AlgorithmConfig::VisualizeVectorField {
+        vector_field,
+        visualization_output_path_no_extension,
+        legend_output_path_no_extension,
+        parameters,
+    } = &config.algorithm
+    {
+        return visualize::run_and_save_visualization(
+            vector_field,
+            visualization_output_path_no_extension,
+            legend_output_path_no_extension.as_deref(),
+            parameters,
+        );
+    }
     let dispatcher = compute::create_dispatcher_block(&config.dispatcher)?;
-    run_and_save(dispatcher, &config.algorithm)?;
+    run_and_save(dispatcher, &config.algorithm, &config.dispatcher)?;
     Ok(())
 }
 
 fn run_and_save(
     dispatcher: Box<dyn Dispatcher>,
     config: &AlgorithmConfig,
+    dispatcher_config: &compute::Config,
 ) -> Result<(), Box<dyn Error>> {
     match config {
         AlgorithmConfig::CreateDisplacementGoal {
@@ -25,9 +53,25 @@ fn run_and_save(
                     displacement_goal,
                     candidate_permutation,
                     image,
+                    image_conversion,
                 },
             displacement_goal_output_path_no_extension: path,
         } => {
+            let input_paths = displacement_goal
+                .iter()
+                .map(|path| -> &str { path.as_ref() })
+                .chain(
+                    candidate_permutation
+                        .iter()
+                        .map(|path| -> &str { path.as_ref() }),
+                )
+                .chain(
+                    image
+                        .iter()
+                        .flat_map(LosslessImagePath::to_vec)
+                        .map(String::as_str),
+                );
+            let metadata = metadata::make_run_metadata("CreateDisplacementGoal", input_paths)?;
             let mut algorithm = dispatcher.create_displacement_goal(
                 CreateDisplacementGoalInput {
                     displacement_goal: displacement_goal
@@ -38,7 +82,10 @@ fn run_and_save(
                         .as_ref()
                         .map(loader::load_candidate_permutation)
                         .transpose()?,
-                    image: image.as_ref().map(loader::load_image).transpose()?,
+                    image: image
+                        .as_ref()
+                        .map(|path| loader::load_image(path, *image_conversion))
+                        .transpose()?,
                 },
                 &Default::default(),
             );
@@ -47,7 +94,7 @@ fn run_and_save(
                 .full_output_block()
                 .unwrap()
                 .output_displacement_goal;
-            let output_path = displacement_goal.save_add_extension(path)?;
+            let output_path = path.save_vector_field(&displacement_goal, "", &metadata)?;
             println!("Wrote displacement goal to: {:?}", output_path);
         }
         AlgorithmConfig::CreatePermutation {
@@ -57,40 +104,87 @@ fn run_and_save(
                 dispatcher.create_permutation(Default::default(), &Default::default());
             algorithm.step_until_finished()?;
             let permutation = algorithm.full_output_block().unwrap().validated_permutation;
-            let output_path = permutation.save_add_extension(path)?;
+            let output_path = path.save_vector_field(
+                &permutation,
+                "",
+                &metadata::make_run_metadata("CreatePermutation", std::iter::empty::<&str>())?,
+            )?;
             println!("Wrote permutation to: {}", output_path.display());
         }
         AlgorithmConfig::Permute {
             candidate_permutation,
-            original_image,
-            permuted_image_output_path_no_extension: path,
-        } => {
-            let mut algorithm = dispatcher.permute(
-                PermuteInput {
-                    candidate_permutation: Some(loader::load_candidate_permutation(
-                        candidate_permutation,
-                    )?),
-                    original_image: Some(loader::load_image(original_image)?),
-                },
-                &Default::default(),
-            );
-            algorithm.step_until_finished()?;
-            let img = algorithm.full_output_block().unwrap().permuted_image;
-            let output_path = img.save_add_extension(path.to_vec().as_slice())?;
-            println!("Wrote permuted image to: {:?}", output_path);
-        }
+            images,
+        } => permute::run_and_save_permute(dispatcher, candidate_permutation, images)?,
         AlgorithmConfig::Swap {
             candidate_permutation,
+            resume,
             displacement_goal,
             permutation_output_path_prefix,
+            swap_history_output_path_no_extension,
+            checkpoint,
             parameters,
-        } => swap::run_and_save_swap(
-            dispatcher,
-            candidate_permutation,
-            displacement_goal,
-            permutation_output_path_prefix,
-            parameters,
-        )?,
+        } => {
+            let mut metadata = metadata::make_run_metadata(
+                "Swap",
+                [candidate_permutation.as_ref(), displacement_goal.as_ref()]
+                    .into_iter()
+                    .chain(
+                        parameters
+                            .animation
+                            .as_ref()
+                            .map(|animation_config| animation_config.image.as_str()),
+                    ),
+            )?;
+            metadata.threshold = Some(parameters.swap_acceptance_threshold);
+            swap::run_and_save_swap(
+                dispatcher,
+                candidate_permutation,
+                resume.as_ref(),
+                displacement_goal,
+                swap::SwapPermutationOutputs {
+                    path_prefix: Some(permutation_output_path_prefix),
+                    animation: parameters
+                        .animation
+                        .as_ref()
+                        .map(swap::SwapAnimationOutput::new)
+                        .transpose()?,
+                    metadata: metadata.clone(),
+                    retain_permutations: false,
+                },
+                swap::SwapRoundOutputs {
+                    swap_history: swap_history_output_path_no_extension
+                        .as_deref()
+                        .map(|path| match resume {
+                            Some(resume) => {
+                                swap::SwapHistoryOutput::resume(path, resume.round_index + 1)
+                            }
+                            None => swap::SwapHistoryOutput::new(path),
+                        }),
+                    checkpoint: checkpoint.as_ref().map(|checkpoint_config| {
+                        swap::SwapCheckpointOutput::new(
+                            checkpoint_config,
+                            parameters,
+                            dispatcher_config.boundary.into(),
+                            metadata,
+                        )
+                    }),
+                    statistics_log: parameters
+                        .statistics_log
+                        .as_ref()
+                        .map(|config| match resume {
+                            Some(resume) => swap::SwapStatisticsLogOutput::resume(
+                                config,
+                                resume.round_index + 1,
+                            ),
+                            None => swap::SwapStatisticsLogOutput::new(config),
+                        }),
+                    count_swaps: false,
+                    boundary: dispatcher_config.boundary,
+                    interrupt: Some(interrupt::install_handler()),
+                },
+                parameters,
+            )?
+        }
         AlgorithmConfig::ValidatePermutation {
             candidate_permutation,
         } => {
@@ -105,6 +199,32 @@ fn run_and_save(
             algorithm.step_until_finished()?;
             println!("Candidate permutation '{}' is valid", candidate_permutation);
         }
+        AlgorithmConfig::Workflow { steps } => {
+            workflow::run_and_save_workflow(dispatcher, steps, dispatcher_config)?
+        }
+        AlgorithmConfig::Experiment {
+            candidate_permutation,
+            displacement_goal,
+            output_directory,
+            runs,
+            seed,
+        } => {
+            if let Some(seed) = seed {
+                println!("Sampled {} experiment runs using seed {}", runs.len(), seed);
+            }
+            experiment::run_and_save_experiment(
+                dispatcher,
+                candidate_permutation,
+                displacement_goal,
+                output_directory,
+                runs,
+                *seed,
+                dispatcher_config,
+            )?
+        }
+        AlgorithmConfig::VisualizeVectorField { .. } => {
+            unreachable!("vector field visualization does not use a dispatcher")
+        }
     }
     Ok(())
 }
@@ -0,0 +1,98 @@
+use super::{loader, metadata};
+use crate::config::{PermutationPath, PermuteImageConfig};
+use image_annealing::compute::format::{ImageFileWriterSaveError, LosslessImage, RunMetadata};
+use image_annealing::compute::{Dispatcher, PermuteInput};
+use image_annealing::CandidatePermutation;
+use std::error::Error;
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+
+// Number of images that can be waiting to be permuted, or waiting to be saved,
+// at any given time
+const CHANNEL_CAPACITY: usize = 2;
+
+pub fn run_and_save_permute(
+    dispatcher: Box<dyn Dispatcher>,
+    candidate_permutation: &PermutationPath,
+    images: &[PermuteImageConfig],
+) -> Result<(), Box<dyn Error>> {
+    let metadata = metadata::make_run_metadata("Permute", [candidate_permutation])?;
+    let candidate_permutation = loader::load_candidate_permutation(candidate_permutation)?;
+    thread::scope(|scope| {
+        // Images are decoded and encoded on separate threads so that file input and output
+        // overlap with GPU work
+        let (input_sender, input_receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        let (output_sender, output_receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        scope.spawn(move || {
+            for image in images {
+                let result =
+                    loader::load_image(&image.original_image, image.original_image_conversion)
+                        .map_err(|e| e.to_string());
+                if input_sender.send(result).is_err() {
+                    break;
+                }
+            }
+        });
+        let writer = scope.spawn(move || save_images(output_receiver, images, metadata));
+        let permute_result = permute_images(
+            dispatcher,
+            candidate_permutation,
+            input_receiver,
+            output_sender,
+        );
+        let save_result = writer.join().unwrap();
+        permute_result?;
+        save_result?;
+        Ok(())
+    })
+}
+
+fn permute_images(
+    mut dispatcher: Box<dyn Dispatcher>,
+    candidate_permutation: CandidatePermutation,
+    input_receiver: Receiver<Result<LosslessImage, String>>,
+    output_sender: SyncSender<LosslessImage>,
+) -> Result<(), Box<dyn Error>> {
+    // The permutation only needs to be sent to the GPU once, as it remains resident
+    // for the permutation of subsequent images.
+    let mut candidate_permutation = Some(candidate_permutation);
+    for input in input_receiver {
+        let mut algorithm = dispatcher.permute(
+            PermuteInput {
+                candidate_permutation: candidate_permutation.take(),
+                original_image: Some(input?),
+            },
+            &Default::default(),
+        );
+        algorithm.step_until_finished()?;
+        let permuted_image = algorithm.full_output_block().unwrap().permuted_image;
+        dispatcher = algorithm.return_to_dispatcher();
+        if output_sender.send(permuted_image).is_err() {
+            // The writer thread has stopped and will report its error
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn save_images(
+    output_receiver: Receiver<LosslessImage>,
+    images: &[PermuteImageConfig],
+    permutation_metadata: RunMetadata,
+) -> Result<(), ImageFileWriterSaveError> {
+    for (permuted_image, image) in output_receiver.into_iter().zip(images) {
+        let mut metadata = permutation_metadata.clone();
+        metadata
+            .input_hashes
+            .extend(metadata::hash_input_files(image.original_image.to_vec())?);
+        let output_path = permuted_image.save_add_extension_with_metadata(
+            image
+                .permuted_image_output_path_no_extension
+                .to_vec()
+                .as_slice(),
+            &metadata,
+        )?;
+        println!("Wrote permuted image to: {:?}", output_path);
+    }
+    Ok(())
+}
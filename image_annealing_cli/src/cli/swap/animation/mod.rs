@@ -0,0 +1,96 @@
+use super::TaggedPermutation;
+use crate::config::SwapAnimationConfig;
+use image_annealing::compute::format::{
+    AnimationEncoder, ImageFormat, LosslessImage, Rgba8Image, RunMetadata,
+};
+use image_annealing::compute::{Dispatcher, PermuteInput, PermuteParameters};
+use image_annealing::CandidatePermutation;
+use std::error::Error;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+/// Renders the intermediate permutations of a swap run as frames of an animation
+///
+/// Frames are rendered by permuting an image on the GPU, using the dispatcher that runs
+/// the swap algorithm, and are encoded as soon as they are rendered.
+pub struct SwapAnimationOutput {
+    image: Option<LosslessImage>,
+    encoder: AnimationEncoder,
+    path_no_extension: String,
+    frame_stride: NonZeroUsize,
+    passes_until_frame: usize,
+}
+
+impl SwapAnimationOutput {
+    /// The format in which the swap algorithm outputs the permuted image for each frame
+    pub const FRAME_FORMAT: ImageFormat = ImageFormat::Rgba8;
+
+    pub fn new(config: &SwapAnimationConfig) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            image: Some(LosslessImage::Rgba8(Rgba8Image::load_with_conversion(
+                &config.image,
+                config.image_conversion.into(),
+            )?)),
+            encoder: AnimationEncoder::new(config.format.into(), &config.parameters),
+            path_no_extension: config.path_no_extension.clone(),
+            frame_stride: config.frame_stride,
+            passes_until_frame: config.frame_stride.get(),
+        })
+    }
+
+    fn add_frame(&mut self, permuted_image: Option<LosslessImage>) -> Result<(), Box<dyn Error>> {
+        match permuted_image {
+            Some(LosslessImage::Rgba8(image)) => Ok(self.encoder.add_frame(&image)?),
+            _ => unreachable!("the swap algorithm outputs a permuted image for every frame"),
+        }
+    }
+
+    /// Renders the first frame, showing the permutation that the swap run starts from
+    ///
+    /// The image remains on the GPU, where the swap algorithm permutes it for later frames.
+    pub fn add_initial(
+        &mut self,
+        dispatcher: Box<dyn Dispatcher>,
+        permutation: CandidatePermutation,
+    ) -> Result<Box<dyn Dispatcher>, Box<dyn Error>> {
+        let mut algorithm = dispatcher.permute(
+            PermuteInput {
+                candidate_permutation: Some(permutation),
+                original_image: self.image.take(),
+            },
+            &PermuteParameters {
+                permuted_image_format: Some(Self::FRAME_FORMAT),
+            },
+        );
+        algorithm.step_until_finished()?;
+        let permuted_image = algorithm.full_output_block().unwrap().permuted_image;
+        self.add_frame(Some(permuted_image))?;
+        Ok(algorithm.return_to_dispatcher())
+    }
+
+    /// Adds a frame for every `frame_stride` swap passes
+    pub fn add_pass(
+        &mut self,
+        tagged_permutation: &mut TaggedPermutation,
+    ) -> Result<(), Box<dyn Error>> {
+        let permuted_image = tagged_permutation.permuted_image.take();
+        self.passes_until_frame -= 1;
+        if self.passes_until_frame == 0 {
+            self.passes_until_frame = self.frame_stride.get();
+            self.add_frame(permuted_image)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the last frame, showing the final permutation, and saves the animation
+    pub fn finish(
+        mut self,
+        tagged_permutation: &mut TaggedPermutation,
+        metadata: &RunMetadata,
+    ) -> Result<PathBuf, Box<dyn Error>> {
+        self.add_frame(tagged_permutation.permuted_image.take())?;
+        let path = self.encoder.make_filename(&self.path_no_extension);
+        metadata.write_embedded(self.encoder.encode()?, &path)?;
+        Ok(path)
+    }
+}
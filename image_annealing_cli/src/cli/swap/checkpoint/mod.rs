@@ -0,0 +1,86 @@
+use super::TaggedPermutation;
+use crate::config::{
+    BoundaryMode, ImagePath, SwapCheckpointConfig, SwapCheckpointFile, SwapParametersConfig,
+    SwapPass,
+};
+use image_annealing::compute::format::RunMetadata;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+/// Saves checkpoints that a later run can resume from, at a regular interval of rounds
+pub struct SwapCheckpointOutput {
+    config: SwapCheckpointConfig,
+    swap_pass_sequence: Vec<SwapPass>,
+    swap_acceptance_threshold: f32,
+    boundary: BoundaryMode,
+    metadata: RunMetadata,
+}
+
+impl SwapCheckpointOutput {
+    pub fn new(
+        config: &SwapCheckpointConfig,
+        parameters: &SwapParametersConfig,
+        boundary: BoundaryMode,
+        metadata: RunMetadata,
+    ) -> Self {
+        Self {
+            config: config.clone(),
+            swap_pass_sequence: parameters
+                .swap_pass_sequence
+                .iter()
+                .map(|&pass| pass.into())
+                .collect(),
+            swap_acceptance_threshold: parameters.swap_acceptance_threshold,
+            boundary,
+            metadata,
+        }
+    }
+
+    pub fn is_due(&self, round_index: usize) -> bool {
+        self.config.is_due(round_index)
+    }
+
+    pub fn save(&self, tagged_permutation: &TaggedPermutation) -> Result<PathBuf, Box<dyn Error>> {
+        let suffix = format!("_round_{}", tagged_permutation.round_index);
+        let permutation_path = self.config.path_prefix.save_vector_field(
+            &tagged_permutation.permutation,
+            &suffix,
+            &RunMetadata {
+                round_index: Some(tagged_permutation.round_index),
+                pass_index: Some(tagged_permutation.pass_index),
+                pass: Some(String::from(tagged_permutation.pass.snake_case_name())),
+                ..self.metadata.clone()
+            },
+        )?;
+        let file = SwapCheckpointFile {
+            version: SwapCheckpointFile::VERSION,
+            permutation_file_name: permutation_path
+                .file_name()
+                .and_then(|file_name| file_name.to_str())
+                .ok_or_else(|| {
+                    format!(
+                        "checkpoint permutation path '{}' does not end in a valid UTF-8 file name",
+                        permutation_path.display()
+                    )
+                })?
+                .to_string(),
+            round_index: tagged_permutation.round_index,
+            pass_index: tagged_permutation.pass_index,
+            swap_pass_sequence: self.swap_pass_sequence.clone(),
+            swap_acceptance_threshold: self.swap_acceptance_threshold,
+            boundary: self.boundary,
+        };
+        let path = SwapCheckpointFile::make_filename(format!(
+            "{}{}",
+            self.config.path_prefix.path_no_extension(),
+            suffix
+        ));
+        let mut writer = BufWriter::new(File::create(&path)?);
+        serde_json::to_writer_pretty(&mut writer, &file)?;
+        writer.flush()?;
+        println!("Wrote checkpoint to: {}", path.display());
+        Ok(path)
+    }
+}
@@ -0,0 +1,107 @@
+use image_annealing::compute::format::{SwapHistoryError, SwapHistoryReader, SwapHistoryWriter};
+use image_annealing::compute::AcceptedSwaps;
+use image_annealing::ImageDimensionsHolder;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+/// A swap history writer and the path of the file that it writes
+type PathWriter = (SwapHistoryWriter<BufWriter<File>>, PathBuf);
+
+/// Writes the swaps accepted during each round to a swap history log,
+/// creating the log when the first round is written
+pub struct SwapHistoryOutput {
+    path_no_extension: String,
+    /// The number of rounds to keep from an existing log, when continuing a resumed run
+    earlier_round_count: usize,
+    writer: Option<PathWriter>,
+}
+
+impl SwapHistoryOutput {
+    pub fn new(path_no_extension: &str) -> Self {
+        Self::resume(path_no_extension, 0)
+    }
+
+    /// Continues the log of a run resumed after the given number of rounds, if the log exists
+    pub fn resume(path_no_extension: &str, earlier_round_count: usize) -> Self {
+        Self {
+            path_no_extension: String::from(path_no_extension),
+            earlier_round_count,
+            writer: None,
+        }
+    }
+
+    fn path(&self) -> PathBuf {
+        SwapHistoryWriter::make_filename(&self.path_no_extension)
+    }
+
+    fn create(&self, first_pass: &AcceptedSwaps) -> Result<PathWriter, Box<dyn Error>> {
+        let path = self.path();
+        if self.earlier_round_count == 0 || !path.is_file() {
+            return SwapHistoryWriter::create(
+                &self.path_no_extension,
+                first_pass.dimensions(),
+                first_pass.boundary(),
+            );
+        }
+        // The existing log may contain rounds after the checkpoint, which the resumed run repeats,
+        // so the earlier rounds are copied to a new log that replaces the existing log when finished
+        let mut reader = SwapHistoryReader::open(&path)?;
+        let partial_path = path.with_extension(format!("{}.part", SwapHistoryWriter::EXTENSION));
+        let mut writer = SwapHistoryWriter::new(
+            BufWriter::new(File::create(&partial_path)?),
+            reader.dimensions(),
+            reader.boundary(),
+        )?;
+        let copied = (0..self.earlier_round_count).try_for_each(|round_index| {
+            let round = reader
+                .next_round()?
+                .ok_or(SwapHistoryError::RoundNotFound {
+                    round_index,
+                    round_count: round_index,
+                })?;
+            writer.write_round(&round)
+        });
+        match copied {
+            Ok(()) => Ok((writer, partial_path)),
+            Err(err) => {
+                drop(writer);
+                std::fs::remove_file(&partial_path)?;
+                Err(err)
+            }
+        }
+    }
+
+    pub fn write_round(&mut self, round: &[AcceptedSwaps]) -> Result<(), Box<dyn Error>> {
+        if self.writer.is_none() {
+            self.writer = Some(self.create(round.first().unwrap())?);
+        }
+        self.writer.as_mut().unwrap().0.write_round(round)
+    }
+
+    pub fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        if let Some((writer, written_path)) = self.writer.take() {
+            writer.finish()?.flush()?;
+            let path = self.path();
+            if written_path != path {
+                std::fs::rename(&written_path, &path)?;
+            }
+            println!("Wrote swap history to: {}", path.display());
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SwapHistoryOutput {
+    /// Finishes the log if the run ended without finishing it, such as when an error occurred,
+    /// so that the rounds written so far can still be read
+    fn drop(&mut self) {
+        if let Err(err) = self.finish() {
+            eprintln!("Failed to finish swap history: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
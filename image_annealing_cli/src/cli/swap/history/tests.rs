@@ -0,0 +1,90 @@
+mod swap_history_output {
+    use super::super::SwapHistoryOutput;
+    use image_annealing::compute::format::{SwapHistoryReader, SwapHistoryWriter};
+    use image_annealing::compute::{AcceptedSwaps, SwapPass};
+    use image_annealing::{BoundaryMode, ImageDimensions};
+    use std::error::Error;
+
+    fn make_round(pass: SwapPass) -> Vec<AcceptedSwaps> {
+        vec![AcceptedSwaps::new(
+            pass,
+            &ImageDimensions::try_new(5, 4).unwrap(),
+            BoundaryMode::Bounded,
+        )]
+    }
+
+    fn read_passes(path_no_extension: &str) -> Result<Vec<SwapPass>, Box<dyn Error>> {
+        let mut reader =
+            SwapHistoryReader::open(SwapHistoryWriter::make_filename(path_no_extension))?;
+        let mut passes = Vec::new();
+        while let Some(round) = reader.next_round()? {
+            passes.push(round[0].pass());
+        }
+        Ok(passes)
+    }
+
+    #[test]
+    fn resume() -> Result<(), Box<dyn Error>> {
+        let path_no_extension =
+            test_util::make_test_output_path_string(["cli_swap_history_output_resume"]);
+        let mut output = SwapHistoryOutput::new(&path_no_extension);
+        for pass in [
+            SwapPass::Horizontal,
+            SwapPass::Vertical,
+            SwapPass::OffsetHorizontal,
+        ] {
+            output.write_round(&make_round(pass))?;
+        }
+        output.finish()?;
+
+        // The third round is repeated by the resumed run
+        let mut output = SwapHistoryOutput::resume(&path_no_extension, 2);
+        output.write_round(&make_round(SwapPass::OffsetVertical))?;
+        output.finish()?;
+
+        let passes = read_passes(&path_no_extension);
+        std::fs::remove_file(SwapHistoryWriter::make_filename(&path_no_extension))?;
+        assert_eq!(
+            passes?,
+            vec![
+                SwapPass::Horizontal,
+                SwapPass::Vertical,
+                SwapPass::OffsetVertical
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn drop_finishes() -> Result<(), Box<dyn Error>> {
+        let path_no_extension =
+            test_util::make_test_output_path_string(["cli_swap_history_output_drop_finishes"]);
+        let mut output = SwapHistoryOutput::new(&path_no_extension);
+        output.write_round(&make_round(SwapPass::Horizontal))?;
+        drop(output);
+
+        let passes = read_passes(&path_no_extension);
+        std::fs::remove_file(SwapHistoryWriter::make_filename(&path_no_extension))?;
+        assert_eq!(passes?, vec![SwapPass::Horizontal]);
+        Ok(())
+    }
+
+    #[test]
+    fn resume_missing_rounds() -> Result<(), Box<dyn Error>> {
+        let path_no_extension =
+            test_util::make_test_output_path_string(["cli_swap_history_output_missing_rounds"]);
+        let mut output = SwapHistoryOutput::new(&path_no_extension);
+        output.write_round(&make_round(SwapPass::Horizontal))?;
+        output.finish()?;
+
+        let mut output = SwapHistoryOutput::resume(&path_no_extension, 2);
+        let result = output.write_round(&make_round(SwapPass::Vertical));
+        output.finish()?;
+        std::fs::remove_file(SwapHistoryWriter::make_filename(&path_no_extension))?;
+        test_util::assert_error_contains(
+            result,
+            "round index 1 is out of range, as the swap history log contains 1 rounds",
+        );
+        Ok(())
+    }
+}
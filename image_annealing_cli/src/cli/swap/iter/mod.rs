@@ -1,11 +1,15 @@
-use crate::config::{IterationCount, SwapStopThreshold};
+use super::stop::{self, RoundMeasurements, StopDecision, SwapStopState};
+use super::SwapRoundOutputs;
+use image_annealing::compute::format::LosslessImage;
 use image_annealing::compute::{
-    OutputStatus, SwapAlgorithm, SwapParameters, SwapPartialOutput, SwapPass,
+    Dispatcher, OutputStatus, SwapAlgorithm, SwapParameters, SwapPartialOutput, SwapPass,
 };
 use image_annealing::ValidatedPermutation;
 use std::error::Error;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct TaggedPermutation {
@@ -13,6 +17,22 @@ pub struct TaggedPermutation {
     pub round_index: usize,
     pub pass_index: usize,
     pub pass: SwapPass,
+    /// The image permuted by the permutation, if the swap algorithm was asked to output one
+    pub permuted_image: Option<LosslessImage>,
+}
+
+/// Statistics describing a finished run of the swap algorithm
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SwapStatistics {
+    /// The number of rounds of swap passes that were run
+    pub round_count: usize,
+    /// Whether the run stopped because a stop criterion other than
+    /// the iteration count or the time limit was met
+    pub converged: bool,
+    /// The numbers of swaps accepted and considered in the final round, if swaps were counted
+    pub final_swap_counts: Option<(usize, usize)>,
+    /// Whether the run stopped early because it was interrupted
+    pub interrupted: bool,
 }
 
 type TaggedPermutationResult = Result<TaggedPermutation, Box<dyn Error>>;
@@ -25,12 +45,14 @@ type Item<'a> = Pin<Box<dyn Future<Output = TaggedPermutationResult> + Send + 'a
 pub struct SwapIter {
     algorithm_option: Option<Box<SwapAlgorithm>>,
     swap_parameters: SwapParameters,
-    threshold: Option<SwapStopThreshold>,
-    iteration_count: Option<IterationCount>,
+    stop: SwapStopState,
+    start: Instant,
     output_intermediate_permutations: bool,
+    round_outputs: SwapRoundOutputs,
     last_pass: SwapPass,
     round_index: usize,
     pass_index: usize,
+    statistics: SwapStatistics,
     finished: bool,
 }
 
@@ -38,34 +60,53 @@ impl<'a> SwapIter {
     pub fn new(
         algorithm: Box<SwapAlgorithm>,
         swap_parameters: SwapParameters,
-        threshold: Option<SwapStopThreshold>,
-        iteration_count: Option<IterationCount>,
+        stop: SwapStopState,
         output_intermediate_permutations: bool,
+        first_round_index: usize,
+        round_outputs: SwapRoundOutputs,
     ) -> Self {
         let last_pass = *swap_parameters.sequence.iter().last().unwrap();
         Self {
             algorithm_option: Some(algorithm),
             swap_parameters,
-            threshold,
-            iteration_count,
+            stop,
+            start: Instant::now(),
             output_intermediate_permutations,
+            round_outputs,
             last_pass,
-            round_index: 0,
+            round_index: first_round_index,
             pass_index: 0,
+            statistics: Default::default(),
             finished: false,
         }
     }
 
+    fn is_interrupted(&self) -> bool {
+        match self.round_outputs.interrupt {
+            Some(flag) => flag.load(Ordering::SeqCst),
+            None => false,
+        }
+    }
+
+    fn record_pass(&mut self) {
+        if let Some(statistics_log) = self.round_outputs.statistics_log.as_mut() {
+            statistics_log.record_pass();
+        }
+    }
+
     async fn generate_item(&mut self) -> TaggedPermutationResult {
         let mut output = None;
         loop {
             let mut algorithm = self.algorithm_option.take().unwrap();
             let mut status = OutputStatus::NoNewOutput;
+            // Whether the run was interrupted partway through a round
+            let mut interrupted = false;
             if self.output_intermediate_permutations {
                 while !status.is_full() && !status.is_final() {
                     status = algorithm.step()?;
                 }
                 let full_output = algorithm.full_output().await.unwrap();
+                self.record_pass();
                 if !status.is_final() && full_output.pass == self.last_pass {
                     status = algorithm.step_until_finished()?
                 }
@@ -74,53 +115,68 @@ impl<'a> SwapIter {
                     round_index: self.round_index,
                     pass_index: self.pass_index,
                     pass: full_output.pass,
+                    permuted_image: full_output.permuted_image,
                 });
                 self.pass_index += 1;
+                interrupted = !status.is_final() && self.is_interrupted();
             } else {
                 while !status.is_final() {
                     status = algorithm.step()?;
                     if status.is_full() {
+                        self.record_pass();
                         self.pass_index += 1;
+                        if !status.is_final() && self.is_interrupted() {
+                            interrupted = true;
+                            break;
+                        }
                     }
                 }
                 self.pass_index = self.pass_index.checked_sub(1).unwrap();
             };
 
-            if status.is_final() {
-                let mut stop = false;
+            let stop = if status.is_final() {
+                self.statistics.round_count += 1;
+                let (swap_counts, accepted_swaps) = if self.stop.needs_swap_counts()
+                    || self.round_outputs.swap_history.is_some()
+                    || self.round_outputs.count_swaps
+                    || self.round_outputs.statistics_log.is_some()
+                {
+                    let SwapPartialOutput {
+                        counts,
+                        accepted_swaps,
+                    } = algorithm.partial_output().await.unwrap();
+                    (Some(counts), accepted_swaps)
+                } else {
+                    (None, None)
+                };
+
+                if let Some(round) = accepted_swaps {
+                    self.round_outputs
+                        .swap_history
+                        .as_mut()
+                        .unwrap()
+                        .write_round(&round)?;
+                }
 
-                if let Some(count) = self.iteration_count {
-                    if self.threshold.is_none() {
-                        println!("Texel swap round {}", self.round_index);
-                    }
-                    if self.round_index == count.get().checked_sub(1).unwrap() {
-                        stop = true;
+                if let Some(counts) = swap_counts.as_ref() {
+                    self.statistics.final_swap_counts = Some((counts.accepted(), counts.total()));
+                    if let Some(statistics_log) = self.round_outputs.statistics_log.as_mut() {
+                        statistics_log.write_round(
+                            self.round_index,
+                            counts.as_ref(),
+                            self.swap_parameters.swap_acceptance_threshold,
+                        )?;
                     }
                 }
 
-                if let Some(threshold_variant) = self.threshold {
-                    let SwapPartialOutput {
-                        counts: swap_counts,
-                    } = algorithm.partial_output().await.unwrap();
-                    println!("Texel swap round {}, {}", self.round_index, swap_counts);
-                    if !stop {
-                        match threshold_variant {
-                            SwapStopThreshold::SwapsAccepted(number_of_swaps) => {
-                                if swap_counts.accepted() <= number_of_swaps {
-                                    stop = true;
-                                }
-                            }
-                            SwapStopThreshold::SwapAcceptanceFraction(fraction_of_swaps) => {
-                                if swap_counts.accepted_fraction() <= fraction_of_swaps.get() {
-                                    stop = true;
-                                }
-                            }
-                        }
+                match swap_counts.as_ref() {
+                    Some(swap_counts) if self.stop.needs_swap_counts() => {
+                        println!("Texel swap round {}, {}", self.round_index, swap_counts)
                     }
+                    _ => println!("Texel swap round {}", self.round_index),
                 }
 
-                if stop {
-                    self.finished = true;
+                let (permutation_hash, energy) = if self.stop.needs_permutation() {
                     if output.is_none() {
                         let full_output = algorithm.full_output().await.unwrap();
                         output = Some(TaggedPermutation {
@@ -128,15 +184,91 @@ impl<'a> SwapIter {
                             round_index: self.round_index,
                             pass_index: self.pass_index,
                             pass: full_output.pass,
-                        })
+                            permuted_image: full_output.permuted_image,
+                        });
+                    }
+                    let permutation = &output.as_ref().unwrap().permutation;
+                    let energy = self.stop.energy(permutation)?;
+                    if let Some(energy) = energy {
+                        println!(
+                            "Potential energy after round {}: {}",
+                            self.round_index, energy
+                        );
                     }
-                    break;
+                    (Some(stop::hash_permutation(permutation)), energy)
                 } else {
-                    let dispatcher = algorithm.return_to_dispatcher();
-                    self.algorithm_option =
-                        Some(dispatcher.swap(Default::default(), &self.swap_parameters));
-                    self.round_index += 1;
+                    (None, None)
+                };
+
+                match self.stop.update(RoundMeasurements {
+                    round_index: self.round_index,
+                    swap_counts: swap_counts
+                        .as_ref()
+                        .map(|counts| (counts.accepted(), counts.accepted_fraction())),
+                    permutation_hash,
+                    energy,
+                    elapsed: self.start.elapsed(),
+                }) {
+                    StopDecision::Continue => false,
+                    StopDecision::Stop { converged } => {
+                        self.statistics.converged = converged;
+                        true
+                    }
+                }
+            } else {
+                false
+            };
+
+            if !stop && status.is_final() && self.is_interrupted() {
+                interrupted = true;
+            }
+            if stop || interrupted {
+                self.finished = true;
+                self.statistics.interrupted = interrupted;
+                if let Some(swap_history) = self.round_outputs.swap_history.as_mut() {
+                    swap_history.finish()?;
                 }
+                if let Some(statistics_log) = self.round_outputs.statistics_log.as_mut() {
+                    statistics_log.finish()?;
+                }
+                if output.is_none() {
+                    let full_output = algorithm.full_output().await.unwrap();
+                    output = Some(TaggedPermutation {
+                        permutation: full_output.output_permutation,
+                        round_index: self.round_index,
+                        pass_index: self.pass_index,
+                        pass: full_output.pass,
+                        permuted_image: full_output.permuted_image,
+                    })
+                }
+                self.algorithm_option = Some(algorithm);
+                break;
+            } else if status.is_final() {
+                if let Some(checkpoint) = self.round_outputs.checkpoint.as_ref() {
+                    if checkpoint.is_due(self.round_index) {
+                        match output.as_ref() {
+                            Some(tagged_permutation) => checkpoint.save(tagged_permutation)?,
+                            None => {
+                                let full_output = algorithm.full_output().await.unwrap();
+                                checkpoint.save(&TaggedPermutation {
+                                    permutation: full_output.output_permutation,
+                                    round_index: self.round_index,
+                                    pass_index: self.pass_index,
+                                    pass: full_output.pass,
+                                    permuted_image: full_output.permuted_image,
+                                })?
+                            }
+                        };
+                    }
+                }
+                if !self.output_intermediate_permutations {
+                    // Only the permutation at the end of the run is output
+                    output = None;
+                }
+                let dispatcher = algorithm.return_to_dispatcher();
+                self.algorithm_option =
+                    Some(dispatcher.swap(Default::default(), &self.swap_parameters));
+                self.round_index += 1;
                 self.pass_index = 0;
             } else {
                 self.algorithm_option = Some(algorithm);
@@ -148,6 +280,18 @@ impl<'a> SwapIter {
         Ok(output.unwrap())
     }
 
+    /// Returns the dispatcher used by the swap algorithm and statistics describing the run,
+    /// once iteration has finished
+    ///
+    /// Panics if iteration has not finished or if iteration stopped because of an error.
+    pub fn finish(self) -> (Box<dyn Dispatcher>, SwapStatistics) {
+        assert!(self.finished);
+        (
+            self.algorithm_option.unwrap().return_to_dispatcher(),
+            self.statistics,
+        )
+    }
+
     pub fn next(&'a mut self) -> Option<Item<'a>> {
         if self.finished {
             None
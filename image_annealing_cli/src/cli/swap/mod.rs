@@ -1,86 +1,222 @@
+use super::interrupt::InterruptedError;
 use super::loader;
-use crate::config::{DisplacementGoalPath, PermutationPath, SwapParametersConfig, SwapStopConfig};
-use futures::join;
+use crate::config::{DisplacementGoalPath, PermutationPath, SwapCheckpoint, SwapParametersConfig};
+use image_annealing::compute::format::{ImageFormat, RunMetadata};
 use image_annealing::compute::{Dispatcher, SwapInput, SwapParameters};
-use image_annealing::{CandidatePermutation, DisplacementGoal};
+use image_annealing::{BoundaryMode, CandidatePermutation, DisplacementGoal, ValidatedPermutation};
 use std::error::Error;
+use std::sync::atomic::AtomicBool;
 
+mod animation;
+mod checkpoint;
+mod history;
 mod iter;
 mod output;
+mod statistics;
+mod stop;
 
+pub use animation::SwapAnimationOutput;
+pub use checkpoint::SwapCheckpointOutput;
+pub use history::SwapHistoryOutput;
+pub use iter::SwapStatistics;
 use iter::{SwapIter, TaggedPermutation};
 use output::TaggedPermutationWriter;
+pub use statistics::SwapStatisticsLogOutput;
+use stop::SwapStopState;
+
+/// Optional outputs that are written at the end of swap rounds
+#[derive(Default)]
+pub struct SwapRoundOutputs {
+    pub swap_history: Option<SwapHistoryOutput>,
+    pub checkpoint: Option<SwapCheckpointOutput>,
+    pub statistics_log: Option<SwapStatisticsLogOutput>,
+    /// The boundary conditions of the run, which are needed to measure
+    /// the potential energy of permutations
+    pub boundary: BoundaryMode,
+    /// Whether to count the swaps accepted in every round, for the run's statistics,
+    /// even if the counts are not needed to decide when to stop
+    pub count_swaps: bool,
+    /// A flag which, once set, makes the run stop after the current pass
+    /// and output the permutation produced by that pass
+    pub interrupt: Option<&'static AtomicBool>,
+}
+
+/// Outputs that are written from the permutations produced by the swap algorithm
+pub struct SwapPermutationOutputs<'a> {
+    pub path_prefix: Option<&'a PermutationPath>,
+    pub animation: Option<SwapAnimationOutput>,
+    /// Metadata describing the run, which is completed with the round and pass
+    /// of each permutation before it is embedded in the permutation's file
+    pub metadata: RunMetadata,
+    /// Whether to keep the permutations in memory so that they can be returned to the caller
+    pub retain_permutations: bool,
+}
+
+/// The state left over after running the swap algorithm
+pub struct SwapOutput {
+    pub dispatcher: Box<dyn Dispatcher>,
+    /// The permutations produced by the swap algorithm, if they were retained,
+    /// ending with the final permutation
+    pub permutations: Vec<ValidatedPermutation>,
+    /// The final permutation produced by the swap algorithm
+    pub final_permutation: Option<ValidatedPermutation>,
+    pub statistics: SwapStatistics,
+}
 
 pub fn run_and_save_swap(
     dispatcher: Box<dyn Dispatcher>,
     candidate_permutation: &PermutationPath,
+    resume: Option<&SwapCheckpoint>,
     displacement_goal: &DisplacementGoalPath,
-    permutation_output_path_prefix: &PermutationPath,
+    permutation_outputs: SwapPermutationOutputs,
+    round_outputs: SwapRoundOutputs,
     parameters: &SwapParametersConfig,
 ) -> Result<(), Box<dyn Error>> {
+    let first_round_index = match resume {
+        Some(checkpoint) => {
+            println!(
+                "Resuming from checkpoint saved after texel swap round {}",
+                checkpoint.round_index
+            );
+            checkpoint.round_index + 1
+        }
+        None => 0,
+    };
+    swap_and_save(
+        dispatcher,
+        loader::load_candidate_permutation(candidate_permutation)?,
+        loader::load_displacement_goal(displacement_goal)?,
+        first_round_index,
+        permutation_outputs,
+        round_outputs,
+        parameters,
+    )?;
+    Ok(())
+}
+
+pub fn swap_and_save(
+    dispatcher: Box<dyn Dispatcher>,
+    candidate_permutation: CandidatePermutation,
+    displacement_goal: DisplacementGoal,
+    first_round_index: usize,
+    permutation_outputs: SwapPermutationOutputs,
+    round_outputs: SwapRoundOutputs,
+    parameters: &SwapParametersConfig,
+) -> Result<SwapOutput, Box<dyn Error>> {
+    let SwapPermutationOutputs {
+        path_prefix,
+        mut animation,
+        metadata,
+        retain_permutations,
+    } = permutation_outputs;
+    let dispatcher = match animation.as_mut() {
+        Some(animation) => animation.add_initial(dispatcher, candidate_permutation.clone())?,
+        None => dispatcher,
+    };
     let mut iter = run_swap(
         dispatcher,
-        Some(loader::load_candidate_permutation(candidate_permutation)?),
-        Some(loader::load_displacement_goal(displacement_goal)?),
+        Some(candidate_permutation),
+        Some(displacement_goal),
+        first_round_index,
+        round_outputs,
         parameters,
+        animation
+            .as_ref()
+            .map(|_| SwapAnimationOutput::FRAME_FORMAT),
     );
-    let writer = TaggedPermutationWriter::new(permutation_output_path_prefix);
+    let mut writer = path_prefix
+        .map(|path_prefix| {
+            TaggedPermutationWriter::new(
+                path_prefix,
+                &parameters.intermediate_permutations.name_template,
+                metadata.clone(),
+            )
+        })
+        .transpose()?;
     let mut output_permutation: Option<TaggedPermutation> = None;
+    let mut permutations = Vec::new();
+    let mut final_permutation = None;
+    let passes_per_round = parameters.swap_pass_sequence.iter().count();
 
     while let Some(result) = iter.next() {
-        output_permutation = Some(match output_permutation.take() {
-            Some(permutation) => {
-                let join_result = futures::executor::block_on(async {
-                    // `join!` is used instead of `try_join!` because the previous permutation
-                    // should be saved even if there is an error creating the next permutation.
-                    join!(writer.save(permutation), result)
-                });
-                join_result.0?;
-                join_result.1
+        // The previous permutation is submitted before waiting for the next permutation
+        // so that it is saved even if there is an error creating the next permutation.
+        if let Some(mut permutation) = output_permutation.take() {
+            if let Some(animation) = animation.as_mut() {
+                animation.add_pass(&mut permutation)?;
+            }
+            if parameters.output_intermediate_permutations
+                && parameters.intermediate_permutations.cadence.includes(
+                    permutation.round_index,
+                    permutation.pass_index,
+                    passes_per_round,
+                )
+            {
+                if retain_permutations {
+                    permutations.push(permutation.permutation.clone());
+                }
+                if let Some(writer) = writer.as_mut() {
+                    futures::executor::block_on(writer.submit(permutation))?;
+                }
             }
-            None => futures::executor::block_on(result),
-        }?)
+        }
+        output_permutation = Some(futures::executor::block_on(result)?);
     }
-    if let Some(permutation) = output_permutation {
-        let path = futures::executor::block_on(writer.save(permutation))?;
-        println!("Wrote final swapped permutation to: {}", path.display());
+    if let Some(mut permutation) = output_permutation {
+        if let Some(animation) = animation {
+            let path = animation.finish(&mut permutation, &metadata)?;
+            println!("Wrote swap animation to: {}", path.display());
+        }
+        if retain_permutations {
+            permutations.push(permutation.permutation.clone());
+        }
+        final_permutation = Some(permutation.permutation.clone());
+        if let Some(writer) = writer.as_mut() {
+            futures::executor::block_on(writer.submit(permutation))?;
+        }
     }
-    Ok(())
+    if let Some(writer) = writer.as_mut() {
+        if let Some(path) = futures::executor::block_on(writer.finish())? {
+            println!("Wrote final swapped permutation to: {}", path.display());
+        }
+    }
+    let (dispatcher, statistics) = iter.finish();
+    if statistics.interrupted {
+        return Err(Box::new(InterruptedError));
+    }
+    Ok(SwapOutput {
+        dispatcher,
+        permutations,
+        final_permutation,
+        statistics,
+    })
 }
 
 fn run_swap(
     dispatcher: Box<dyn Dispatcher>,
     candidate_permutation: Option<CandidatePermutation>,
     displacement_goal: Option<DisplacementGoal>,
+    first_round_index: usize,
+    round_outputs: SwapRoundOutputs,
     parameters: &SwapParametersConfig,
+    permuted_image_format: Option<ImageFormat>,
 ) -> SwapIter {
-    let (swap_parameters, threshold) = match parameters.stop {
-        SwapStopConfig::Bounded {
-            threshold: Some(threshold_variant),
-            ..
-        }
-        | SwapStopConfig::Unbounded(threshold_variant) => (
-            SwapParameters {
-                sequence: parameters.swap_pass_sequence,
-                swap_acceptance_threshold: parameters.swap_acceptance_threshold,
-                count_swap: true,
-            },
-            Some(threshold_variant),
-        ),
-        _ => (
-            SwapParameters::from_sequence_and_threshold(
-                parameters.swap_pass_sequence,
-                parameters.swap_acceptance_threshold,
-            ),
-            None,
-        ),
-    };
-    let iteration_count = match parameters.stop {
-        SwapStopConfig::Bounded {
-            iteration_count: count,
-            ..
-        } => Some(count),
-        SwapStopConfig::Unbounded(_) => None,
+    let criterion = parameters.stop.criterion();
+    let mut swap_parameters = SwapParameters::from_sequence_and_threshold(
+        parameters.swap_pass_sequence,
+        parameters.swap_acceptance_threshold,
+    );
+    swap_parameters.record_accepted_swaps = round_outputs.swap_history.is_some();
+    swap_parameters.count_swap = criterion.needs_swap_counts()
+        || round_outputs.count_swaps
+        || round_outputs.statistics_log.is_some();
+    swap_parameters.permuted_image_format = permuted_image_format;
+    let energy_reference = if criterion.needs_energy() {
+        displacement_goal
+            .clone()
+            .map(|displacement_goal| (displacement_goal, round_outputs.boundary))
+    } else {
+        None
     };
 
     let algorithm = dispatcher.swap(
@@ -93,9 +229,11 @@ fn run_swap(
     SwapIter::new(
         algorithm,
         swap_parameters,
-        threshold,
-        iteration_count,
-        parameters.output_intermediate_permutations,
+        SwapStopState::new(criterion, energy_reference),
+        // The permuted images are output together with the permutation of every pass
+        parameters.output_intermediate_permutations || permuted_image_format.is_some(),
+        first_round_index,
+        round_outputs,
     )
 }
 
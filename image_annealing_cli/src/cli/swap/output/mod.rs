@@ -1,100 +1,97 @@
 use super::TaggedPermutation;
-use crate::config::PermutationPath;
-use futures_intrusive::buffer::ArrayBuf;
-use futures_intrusive::channel::{self, TrySendError};
-use image_annealing::compute::format::{ImageFileWriter, ImageFileWriterSaveResult};
-use std::sync::mpsc;
-use std::thread;
+use crate::config::{ImagePath, PermutationNameFields, PermutationNameTemplate, PermutationPath};
+use futures::future::RemoteHandle;
+use image_annealing::compute::format::{
+    ImageCodecPool, ImageFileWriterSaveError, ImageFileWriterSaveResult, RunMetadata,
+};
+use std::collections::VecDeque;
+use std::io;
+use std::path::PathBuf;
 
-fn save_tagged_permutation(
-    tagged_permutation: TaggedPermutation,
-    path_prefix: &PermutationPath,
-) -> ImageFileWriterSaveResult {
-    let path_no_extension = format!(
-        "{}_round_{}_pass_{}_{}",
-        path_prefix,
-        tagged_permutation.round_index,
-        tagged_permutation.pass_index,
-        tagged_permutation.pass.snake_case_name()
-    );
-    tagged_permutation
-        .permutation
-        .save_add_extension(path_no_extension)
-}
-
-type PathChannelBuffer = ArrayBuf<
-    ImageFileWriterSaveResult,
-    [ImageFileWriterSaveResult; TaggedPermutationWriter::CHANNEL_CAPACITY],
->;
-
-/// A manager for a background thread that saves image files
+/// A writer that encodes and saves image files on a thread pool
 ///
-/// This struct is inspired by the discussion at
-/// https://github.com/image-rs/image/issues/1713
+/// Several permutations can be encoded at the same time, so that saving intermediate
+/// permutations does not hold up the swap algorithm.
 pub struct TaggedPermutationWriter {
-    permutation_sender: Option<mpsc::SyncSender<TaggedPermutation>>,
-    path_receiver: channel::shared::GenericReceiver<
-        parking_lot::RawMutex,
-        ImageFileWriterSaveResult,
-        PathChannelBuffer,
-    >,
-    // TODO Use futures instead of threads if the `image` crate becomes more async-friendly
-    //      (see https://github.com/image-rs/image/issues/1397).
-    //
-    //     To help with asynchronous operations, consider adding a method to `ImageFileWriter`
-    //     to asynchronously encode images into a buffer, and a similar method to `ImageFileReader`,
-    //     but for decoding.
-    worker: Option<thread::JoinHandle<Result<(), TrySendError<ImageFileWriterSaveResult>>>>,
+    path_prefix: PermutationPath,
+    name_template: PermutationNameTemplate,
+    /// The number of permutations submitted so far
+    sequence: usize,
+    metadata: RunMetadata,
+    pool: ImageCodecPool,
+    in_flight: VecDeque<RemoteHandle<ImageFileWriterSaveResult>>,
 }
 
 impl TaggedPermutationWriter {
-    const CHANNEL_CAPACITY: usize = 1;
-
-    pub fn new(path_prefix: &PermutationPath) -> Self {
-        let path_prefix_clone = path_prefix.clone();
-        let (permutation_sender, permutation_receiver) = mpsc::sync_channel(Self::CHANNEL_CAPACITY);
-        let (path_sender, path_receiver) = channel::shared::generic_channel(Self::CHANNEL_CAPACITY);
+    const MAX_IN_FLIGHT: usize = 4;
 
-        let thread = thread::spawn(move || loop {
-            let message = permutation_receiver.recv();
-
-            match message {
-                Ok(tagged_permutation) => {
-                    let result = save_tagged_permutation(tagged_permutation, &path_prefix_clone);
-                    // `TaggedPermutationWriter` currently assumes that at most one `TaggedPermutation`
-                    // is being saved at a time, and that the caller always collects the
-                    // filepath of the saved permutation before submitting another permutation.
-                    if let Err(err) = path_sender.try_send(result) {
-                        break Err(err);
-                    }
-                }
-                Err(_) => {
-                    break Ok(());
-                }
-            }
-        });
+    pub fn new(
+        path_prefix: &PermutationPath,
+        name_template: &PermutationNameTemplate,
+        metadata: RunMetadata,
+    ) -> Result<Self, io::Error> {
+        Ok(Self {
+            path_prefix: path_prefix.clone(),
+            name_template: name_template.clone(),
+            sequence: 0,
+            metadata,
+            pool: ImageCodecPool::with_thread_count(Self::MAX_IN_FLIGHT)?,
+            in_flight: VecDeque::with_capacity(Self::MAX_IN_FLIGHT),
+        })
+    }
 
-        Self {
-            permutation_sender: Some(permutation_sender),
-            path_receiver,
-            worker: Some(thread),
+    /// Starts saving a permutation, first waiting for the oldest save in progress to finish
+    /// if the maximum number of saves are in progress
+    pub async fn submit(
+        &mut self,
+        tagged_permutation: TaggedPermutation,
+    ) -> Result<(), ImageFileWriterSaveError> {
+        if self.in_flight.len() >= Self::MAX_IN_FLIGHT {
+            self.in_flight.pop_front().unwrap().await?;
         }
+        let format = self.path_prefix.format();
+        let output_path = format.make_filename(format!(
+            "{}{}",
+            self.path_prefix.path_no_extension(),
+            self.name_template.render(&PermutationNameFields {
+                sequence: self.sequence,
+                round: tagged_permutation.round_index,
+                pass_index: tagged_permutation.pass_index,
+                pass_name: tagged_permutation.pass.snake_case_name(),
+            })
+        ));
+        self.sequence += 1;
+        let metadata = RunMetadata {
+            round_index: Some(tagged_permutation.round_index),
+            pass_index: Some(tagged_permutation.pass_index),
+            pass: Some(String::from(tagged_permutation.pass.snake_case_name())),
+            ..self.metadata.clone()
+        };
+        self.in_flight.push_back(self.pool.spawn(move || {
+            let data = metadata.embed(format.encode(tagged_permutation.permutation.as_ref())?)?;
+            std::fs::write(&output_path, data)?;
+            Ok(output_path)
+        }));
+        Ok(())
     }
 
-    pub async fn save(&self, tagged_permutation: TaggedPermutation) -> ImageFileWriterSaveResult {
-        let sender = self.permutation_sender.as_ref().unwrap();
-        // `TaggedPermutationWriter` currently assumes that at most one `TaggedPermutation`
-        // is being saved at a time.
-        sender.try_send(tagged_permutation).unwrap();
-        self.path_receiver.receive().await.unwrap()
+    /// Waits for all saves in progress to finish, returning the path of the last file saved
+    pub async fn finish(&mut self) -> Result<Option<PathBuf>, ImageFileWriterSaveError> {
+        let mut last_path = None;
+        while let Some(handle) = self.in_flight.pop_front() {
+            last_path = Some(handle.await?);
+        }
+        Ok(last_path)
     }
 }
 
 impl Drop for TaggedPermutationWriter {
     fn drop(&mut self) {
-        drop(self.permutation_sender.take());
-        let join_handle = self.worker.take().unwrap();
-        // Any errors sending `ImageFileWriterSaveResult` back to the main thread will be unwrapped here.
-        join_handle.join().unwrap().unwrap();
+        // Dropping a `RemoteHandle` cancels its task, so saves in progress are allowed to finish.
+        // Errors are discarded, because the writer is only dropped without calling `finish`
+        // when an error has already occurred.
+        for handle in self.in_flight.drain(..) {
+            let _ = futures::executor::block_on(handle);
+        }
     }
 }
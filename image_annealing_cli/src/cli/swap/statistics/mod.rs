@@ -0,0 +1,189 @@
+use crate::config::{SwapPass, SwapStatisticsLogConfig, SwapStatisticsLogFormat};
+use image_annealing::compute::SwapPassSequenceSwapRatio;
+use serde::Serialize;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, ErrorKind, Write};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+const CSV_HEADER: &str = "round_index,pass_index,pass,swaps_total,swaps_accepted,accepted_fraction,swap_acceptance_threshold,timestamp_seconds,elapsed_seconds";
+
+/// The swap counts of one pass of one round
+#[derive(Debug, PartialEq, Serialize)]
+struct SwapStatisticsRecord {
+    round_index: usize,
+    pass_index: usize,
+    pass: SwapPass,
+    swaps_total: usize,
+    swaps_accepted: usize,
+    accepted_fraction: f64,
+    swap_acceptance_threshold: f32,
+    /// Seconds since the Unix epoch at which the pass finished
+    timestamp_seconds: f64,
+    /// Seconds since the start of the run at which the pass finished
+    elapsed_seconds: f64,
+}
+
+/// Returns the round index of a line of a statistics log, if the line is a record
+fn record_round_index(format: SwapStatisticsLogFormat, line: &str) -> Option<usize> {
+    match format {
+        SwapStatisticsLogFormat::JsonLines => serde_json::from_str::<serde_json::Value>(line)
+            .ok()?["round_index"]
+            .as_u64()
+            .map(|round_index| round_index as usize),
+        SwapStatisticsLogFormat::Csv => line.split(',').next()?.parse().ok(),
+    }
+}
+
+impl SwapStatisticsRecord {
+    fn write<W: Write>(
+        &self,
+        format: SwapStatisticsLogFormat,
+        writer: &mut W,
+    ) -> Result<(), Box<dyn Error>> {
+        match format {
+            SwapStatisticsLogFormat::JsonLines => {
+                serde_json::to_writer(&mut *writer, self)?;
+                writeln!(writer)?;
+            }
+            SwapStatisticsLogFormat::Csv => writeln!(
+                writer,
+                "{},{},{:?},{},{},{},{},{},{}",
+                self.round_index,
+                self.pass_index,
+                self.pass,
+                self.swaps_total,
+                self.swaps_accepted,
+                self.accepted_fraction,
+                self.swap_acceptance_threshold,
+                self.timestamp_seconds,
+                self.elapsed_seconds
+            )?,
+        }
+        Ok(())
+    }
+}
+
+/// Writes the swap counts of each round to a statistics log,
+/// creating the log when the first round is written
+pub struct SwapStatisticsLogOutput {
+    config: SwapStatisticsLogConfig,
+    /// The number of rounds to keep from an existing log, when continuing a resumed run
+    earlier_round_count: usize,
+    start: Instant,
+    /// The times at which the passes of the current round finished, as (timestamp, elapsed) seconds
+    pass_times: Vec<(f64, f64)>,
+    writer: Option<BufWriter<File>>,
+}
+
+impl SwapStatisticsLogOutput {
+    pub fn new(config: &SwapStatisticsLogConfig) -> Self {
+        Self::resume(config, 0)
+    }
+
+    /// Continues the log of a run resumed after the given number of rounds, if the log exists
+    pub fn resume(config: &SwapStatisticsLogConfig, earlier_round_count: usize) -> Self {
+        Self {
+            config: config.clone(),
+            earlier_round_count,
+            start: Instant::now(),
+            pass_times: Vec::new(),
+            writer: None,
+        }
+    }
+
+    fn create(&self) -> Result<BufWriter<File>, Box<dyn Error>> {
+        // The existing log may contain rounds after the checkpoint, which the resumed run repeats,
+        // so only the records of the earlier rounds are kept
+        let existing = if self.earlier_round_count == 0 {
+            String::new()
+        } else {
+            match std::fs::read_to_string(&self.config.path) {
+                Ok(existing) => existing,
+                Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+                Err(err) => return Err(Box::new(err)),
+            }
+        };
+        let mut writer = BufWriter::new(File::create(&self.config.path)?);
+        if self.config.format == SwapStatisticsLogFormat::Csv {
+            writeln!(writer, "{}", CSV_HEADER)?;
+        }
+        for line in existing.lines() {
+            if matches!(
+                record_round_index(self.config.format, line),
+                Some(round_index) if round_index < self.earlier_round_count
+            ) {
+                writeln!(writer, "{}", line)?;
+            }
+        }
+        Ok(writer)
+    }
+
+    fn now(&self) -> (f64, f64) {
+        let timestamp_seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs_f64())
+            .unwrap_or_default();
+        (timestamp_seconds, self.start.elapsed().as_secs_f64())
+    }
+
+    /// Records that the next pass of the current round has finished
+    pub fn record_pass(&mut self) {
+        let time = self.now();
+        self.pass_times.push(time);
+    }
+
+    pub fn write_round(
+        &mut self,
+        round_index: usize,
+        counts: &dyn SwapPassSequenceSwapRatio,
+        swap_acceptance_threshold: f32,
+    ) -> Result<(), Box<dyn Error>> {
+        if self.writer.is_none() {
+            self.writer = Some(self.create()?);
+        }
+        let now = self.now();
+        let pass_times = std::mem::take(&mut self.pass_times);
+        let writer = self.writer.as_mut().unwrap();
+        for (pass_index, pass_counts) in counts.passes().enumerate() {
+            // A pass whose finish was not recorded is timed when the round's counts are read
+            let (timestamp_seconds, elapsed_seconds) =
+                pass_times.get(pass_index).copied().unwrap_or(now);
+            SwapStatisticsRecord {
+                round_index,
+                pass_index,
+                pass: pass_counts.pass().into(),
+                swaps_total: pass_counts.total(),
+                swaps_accepted: pass_counts.accepted(),
+                accepted_fraction: pass_counts.accepted_fraction(),
+                swap_acceptance_threshold,
+                timestamp_seconds,
+                elapsed_seconds,
+            }
+            .write(self.config.format, writer)?;
+        }
+        // Flushing every round lets the log be read while the run continues
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn finish(&mut self) -> Result<(), Box<dyn Error>> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.flush()?;
+            println!("Wrote swap statistics log to: {}", self.config.path);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SwapStatisticsLogOutput {
+    /// Finishes the log if the run ended without finishing it, such as when an error occurred
+    fn drop(&mut self) {
+        if let Err(err) = self.finish() {
+            eprintln!("Failed to finish swap statistics log: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
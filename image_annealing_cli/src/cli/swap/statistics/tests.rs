@@ -0,0 +1,115 @@
+mod swap_statistics_record {
+    use super::super::{SwapStatisticsRecord, CSV_HEADER};
+    use crate::config::{SwapPass, SwapStatisticsLogFormat};
+    use std::error::Error;
+
+    fn make_record() -> SwapStatisticsRecord {
+        SwapStatisticsRecord {
+            round_index: 2,
+            pass_index: 1,
+            pass: SwapPass::OffsetVertical,
+            swaps_total: 8,
+            swaps_accepted: 2,
+            accepted_fraction: 0.25,
+            swap_acceptance_threshold: 0.5,
+            timestamp_seconds: 1000.5,
+            elapsed_seconds: 1.5,
+        }
+    }
+
+    #[test]
+    fn json_lines() -> Result<(), Box<dyn Error>> {
+        let mut output = Vec::new();
+        make_record().write(SwapStatisticsLogFormat::JsonLines, &mut output)?;
+        assert_eq!(
+            String::from_utf8(output)?,
+            "{\"round_index\":2,\"pass_index\":1,\"pass\":\"OffsetVertical\",\"swaps_total\":8,\
+            \"swaps_accepted\":2,\"accepted_fraction\":0.25,\"swap_acceptance_threshold\":0.5,\
+            \"timestamp_seconds\":1000.5,\"elapsed_seconds\":1.5}\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn csv() -> Result<(), Box<dyn Error>> {
+        let mut output = Vec::new();
+        make_record().write(SwapStatisticsLogFormat::Csv, &mut output)?;
+        assert_eq!(
+            String::from_utf8(output)?,
+            "2,1,OffsetVertical,8,2,0.25,0.5,1000.5,1.5\n"
+        );
+        assert_eq!(
+            CSV_HEADER.split(',').count(),
+            "2,1,OffsetVertical,8,2,0.25,0.5,1000.5,1.5"
+                .split(',')
+                .count()
+        );
+        Ok(())
+    }
+}
+
+mod record_round_index {
+    use super::super::{record_round_index, CSV_HEADER};
+    use crate::config::SwapStatisticsLogFormat;
+
+    #[test]
+    fn json_lines() {
+        assert_eq!(
+            record_round_index(
+                SwapStatisticsLogFormat::JsonLines,
+                "{\"round_index\":2,\"pass_index\":1,\"pass\":\"OffsetVertical\"}"
+            ),
+            Some(2)
+        );
+        assert_eq!(
+            record_round_index(SwapStatisticsLogFormat::JsonLines, "{\"round"),
+            None
+        );
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            record_round_index(
+                SwapStatisticsLogFormat::Csv,
+                "2,1,OffsetVertical,8,2,0.25,0.5,1000.5,1.5"
+            ),
+            Some(2)
+        );
+        assert_eq!(
+            record_round_index(SwapStatisticsLogFormat::Csv, CSV_HEADER),
+            None
+        );
+    }
+}
+
+mod swap_statistics_log_output {
+    use super::super::SwapStatisticsLogOutput;
+    use crate::config::{SwapStatisticsLogConfig, SwapStatisticsLogFormat};
+    use std::error::Error;
+
+    #[test]
+    fn resume() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_output_path_string(["cli_swap_statistics_log_resume.csv"]);
+        std::fs::write(
+            &path,
+            "header\n0,0,Horizontal,8,2,0.25,0.5,1000.5,1.5\n\
+            1,0,Horizontal,8,1,0.125,0.5,1001.5,2.5\n",
+        )?;
+        let config = SwapStatisticsLogConfig {
+            path: path.clone(),
+            format: SwapStatisticsLogFormat::Csv,
+        };
+        let created = SwapStatisticsLogOutput::resume(&config, 1)
+            .create()
+            .map(drop);
+        let contents = std::fs::read_to_string(&path);
+        std::fs::remove_file(&path)?;
+        created?;
+        let contents = contents?;
+        let lines = contents.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1], "0,0,Horizontal,8,2,0.25,0.5,1000.5,1.5");
+        Ok(())
+    }
+}
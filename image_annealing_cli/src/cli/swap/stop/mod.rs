@@ -0,0 +1,161 @@
+use crate::config::{SwapStopCriterion, SwapStopThreshold};
+use image_annealing::image_utils::energy;
+use image_annealing::{
+    BoundaryMode, DimensionsMismatchError, DisplacementGoal, ValidatedPermutation, VectorField,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+/// Quantities measured at the end of a round, some of which are only measured
+/// if the stop criterion needs them
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RoundMeasurements {
+    pub round_index: usize,
+    /// The number and the fraction of swaps accepted in the round
+    pub swap_counts: Option<(usize, f64)>,
+    pub permutation_hash: Option<u64>,
+    pub energy: Option<f64>,
+    /// The time since the start of the run
+    pub elapsed: Duration,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StopDecision {
+    Continue,
+    /// `converged` is `false` if the run would have continued without
+    /// the iteration count and time limit criteria
+    Stop {
+        converged: bool,
+    },
+}
+
+/// The state of a round, including what is remembered from earlier rounds
+struct RoundState<'a> {
+    measurements: &'a RoundMeasurements,
+    /// The numbers of swaps accepted in each round so far, ending with the current round
+    accepted_counts: &'a [usize],
+    /// Whether the permutation is the same as at the end of an earlier round
+    recurred: bool,
+}
+
+fn is_met(criterion: &SwapStopCriterion, state: &RoundState, include_limits: bool) -> bool {
+    let measurements = state.measurements;
+    match criterion {
+        SwapStopCriterion::IterationCount(count) => {
+            include_limits && measurements.round_index + 1 >= count.get()
+        }
+        SwapStopCriterion::Threshold(threshold) => match (threshold, measurements.swap_counts) {
+            (SwapStopThreshold::SwapsAccepted(number_of_swaps), Some((accepted, _))) => {
+                accepted <= *number_of_swaps
+            }
+            (SwapStopThreshold::SwapAcceptanceFraction(fraction_of_swaps), Some((_, fraction))) => {
+                fraction <= fraction_of_swaps.get()
+            }
+            (_, None) => false,
+        },
+        SwapStopCriterion::TimeLimit(limit) => include_limits && measurements.elapsed >= *limit,
+        SwapStopCriterion::Plateau {
+            window,
+            minimum_decrease_fraction,
+        } => match state.accepted_counts.len().checked_sub(window.get() + 1) {
+            Some(earlier_index) => {
+                let earlier = state.accepted_counts[earlier_index];
+                let current = *state.accepted_counts.last().unwrap();
+                earlier == 0
+                    || (earlier.saturating_sub(current) as f64)
+                        < minimum_decrease_fraction.get() * earlier as f64
+            }
+            None => false,
+        },
+        SwapStopCriterion::Oscillation => state.recurred,
+        SwapStopCriterion::EnergyTarget(target) => {
+            matches!(measurements.energy, Some(energy) if energy <= target.get())
+        }
+        SwapStopCriterion::Any(criteria) => criteria
+            .iter()
+            .any(|criterion| is_met(criterion, state, include_limits)),
+        SwapStopCriterion::All(criteria) => criteria
+            .iter()
+            .all(|criterion| is_met(criterion, state, include_limits)),
+    }
+}
+
+pub fn hash_permutation(permutation: &ValidatedPermutation) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    permutation.as_raw_slice().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Decides at the end of every round whether a swap run should stop
+pub struct SwapStopState {
+    criterion: SwapStopCriterion,
+    energy_reference: Option<(DisplacementGoal, BoundaryMode)>,
+    accepted_counts: Vec<usize>,
+    permutation_hashes: HashSet<u64>,
+}
+
+impl SwapStopState {
+    /// `energy_reference` is the displacement goal and boundary conditions of the run,
+    /// which are needed if the criterion depends on potential energy
+    pub fn new(
+        criterion: SwapStopCriterion,
+        energy_reference: Option<(DisplacementGoal, BoundaryMode)>,
+    ) -> Self {
+        Self {
+            criterion,
+            energy_reference,
+            accepted_counts: Vec::new(),
+            permutation_hashes: HashSet::new(),
+        }
+    }
+
+    pub fn needs_swap_counts(&self) -> bool {
+        self.criterion.needs_swap_counts()
+    }
+
+    pub fn needs_permutation(&self) -> bool {
+        self.criterion.needs_permutation()
+    }
+
+    /// Returns the potential energy of the permutation, if the criterion depends on it
+    pub fn energy(
+        &self,
+        permutation: &ValidatedPermutation,
+    ) -> Result<Option<f64>, DimensionsMismatchError> {
+        match self.energy_reference.as_ref() {
+            Some((displacement_goal, boundary)) if self.criterion.needs_energy() => Ok(Some(
+                energy::potential_energy(permutation, displacement_goal, *boundary)?,
+            )),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn update(&mut self, measurements: RoundMeasurements) -> StopDecision {
+        if let Some((accepted, _)) = measurements.swap_counts {
+            self.accepted_counts.push(accepted);
+        }
+        // Hashes are compared instead of permutations to bound memory use.
+        // Different permutations are very unlikely to have the same hash.
+        let recurred = match measurements.permutation_hash {
+            Some(hash) => !self.permutation_hashes.insert(hash),
+            None => false,
+        };
+        let state = RoundState {
+            measurements: &measurements,
+            accepted_counts: &self.accepted_counts,
+            recurred,
+        };
+        if is_met(&self.criterion, &state, true) {
+            StopDecision::Stop {
+                converged: is_met(&self.criterion, &state, false),
+            }
+        } else {
+            StopDecision::Continue
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
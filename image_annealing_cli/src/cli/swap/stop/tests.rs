@@ -0,0 +1,189 @@
+mod swap_stop_state {
+    use super::super::{RoundMeasurements, StopDecision, SwapStopState};
+    use crate::config::{
+        IterationCount, NonnegativeProperFraction, NonnegativeRationalNumber, SwapStopCriterion,
+        SwapStopThreshold,
+    };
+    use std::error::Error;
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+
+    fn iteration_count(count: usize) -> SwapStopCriterion {
+        SwapStopCriterion::IterationCount(IterationCount(NonZeroUsize::new(count).unwrap()))
+    }
+
+    fn swaps_accepted(accepted: usize) -> SwapStopCriterion {
+        SwapStopCriterion::Threshold(SwapStopThreshold::SwapsAccepted(accepted))
+    }
+
+    fn with_swap_counts(round_index: usize, accepted: usize) -> RoundMeasurements {
+        RoundMeasurements {
+            round_index,
+            swap_counts: Some((accepted, accepted as f64 / 100.0)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn iteration_count_only() {
+        let mut state = SwapStopState::new(iteration_count(2), None);
+        assert_eq!(
+            state.update(RoundMeasurements::default()),
+            StopDecision::Continue
+        );
+        assert_eq!(
+            state.update(RoundMeasurements {
+                round_index: 1,
+                ..Default::default()
+            }),
+            StopDecision::Stop { converged: false }
+        );
+    }
+
+    #[test]
+    fn any() {
+        let mut state = SwapStopState::new(
+            SwapStopCriterion::Any(vec![iteration_count(3), swaps_accepted(1)]),
+            None,
+        );
+        assert_eq!(state.update(with_swap_counts(0, 5)), StopDecision::Continue);
+        assert_eq!(
+            state.update(with_swap_counts(1, 1)),
+            StopDecision::Stop { converged: true }
+        );
+    }
+
+    #[test]
+    fn all() {
+        let mut state = SwapStopState::new(
+            SwapStopCriterion::All(vec![iteration_count(2), swaps_accepted(1)]),
+            None,
+        );
+        assert_eq!(state.update(with_swap_counts(0, 0)), StopDecision::Continue);
+        assert_eq!(state.update(with_swap_counts(1, 5)), StopDecision::Continue);
+        assert_eq!(
+            state.update(with_swap_counts(2, 1)),
+            StopDecision::Stop { converged: false }
+        );
+    }
+
+    #[test]
+    fn time_limit() {
+        let mut state =
+            SwapStopState::new(SwapStopCriterion::TimeLimit(Duration::from_secs(10)), None);
+        assert_eq!(
+            state.update(RoundMeasurements {
+                elapsed: Duration::from_secs(9),
+                ..Default::default()
+            }),
+            StopDecision::Continue
+        );
+        assert_eq!(
+            state.update(RoundMeasurements {
+                round_index: 1,
+                elapsed: Duration::from_secs(10),
+                ..Default::default()
+            }),
+            StopDecision::Stop { converged: false }
+        );
+    }
+
+    #[test]
+    fn plateau() -> Result<(), Box<dyn Error>> {
+        let mut state = SwapStopState::new(
+            SwapStopCriterion::Plateau {
+                window: NonZeroUsize::new(2).unwrap(),
+                minimum_decrease_fraction: NonnegativeProperFraction::new(0.5)?,
+            },
+            None,
+        );
+        assert_eq!(
+            state.update(with_swap_counts(0, 40)),
+            StopDecision::Continue
+        );
+        assert_eq!(
+            state.update(with_swap_counts(1, 30)),
+            StopDecision::Continue
+        );
+        // Decreased by 75% of the count two rounds earlier
+        assert_eq!(
+            state.update(with_swap_counts(2, 10)),
+            StopDecision::Continue
+        );
+        // Decreased by two thirds of the count two rounds earlier
+        assert_eq!(
+            state.update(with_swap_counts(3, 10)),
+            StopDecision::Continue
+        );
+        // Decreased by 40% of the count two rounds earlier
+        assert_eq!(
+            state.update(with_swap_counts(4, 6)),
+            StopDecision::Stop { converged: true }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn plateau_at_zero() -> Result<(), Box<dyn Error>> {
+        let mut state = SwapStopState::new(
+            SwapStopCriterion::Plateau {
+                window: NonZeroUsize::new(1).unwrap(),
+                minimum_decrease_fraction: NonnegativeProperFraction::new(0.0)?,
+            },
+            None,
+        );
+        assert_eq!(state.update(with_swap_counts(0, 0)), StopDecision::Continue);
+        assert_eq!(
+            state.update(with_swap_counts(1, 0)),
+            StopDecision::Stop { converged: true }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn oscillation() {
+        let mut state = SwapStopState::new(SwapStopCriterion::Oscillation, None);
+        for (round_index, hash) in [1, 2, 3].into_iter().enumerate() {
+            assert_eq!(
+                state.update(RoundMeasurements {
+                    round_index,
+                    permutation_hash: Some(hash),
+                    ..Default::default()
+                }),
+                StopDecision::Continue
+            );
+        }
+        assert_eq!(
+            state.update(RoundMeasurements {
+                round_index: 3,
+                permutation_hash: Some(2),
+                ..Default::default()
+            }),
+            StopDecision::Stop { converged: true }
+        );
+    }
+
+    #[test]
+    fn energy_target() -> Result<(), Box<dyn Error>> {
+        let mut state = SwapStopState::new(
+            SwapStopCriterion::EnergyTarget(NonnegativeRationalNumber::new(1.5)?),
+            None,
+        );
+        assert_eq!(
+            state.update(RoundMeasurements {
+                energy: Some(2.0),
+                ..Default::default()
+            }),
+            StopDecision::Continue
+        );
+        assert_eq!(
+            state.update(RoundMeasurements {
+                round_index: 1,
+                energy: Some(1.5),
+                ..Default::default()
+            }),
+            StopDecision::Stop { converged: true }
+        );
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 mod run_swap {
     use super::super::iter::TaggedPermutation;
-    use crate::config::{SwapParametersConfig, SwapStopConfig, SwapStopThreshold};
+    use super::super::SwapRoundOutputs;
+    use crate::config::{IterationCount, SwapParametersConfig, SwapStopConfig, SwapStopThreshold};
     use async_trait::async_trait;
     use image_annealing::compute::{
         Algorithm, CreateDisplacementGoalAlgorithm, CreateDisplacementGoalInput,
@@ -15,6 +16,8 @@ mod run_swap {
     use image_annealing::{CandidatePermutation, DisplacementGoal, ValidatedPermutation};
     use std::error::Error;
     use std::fmt;
+    use std::num::NonZeroUsize;
+    use std::sync::atomic::AtomicBool;
 
     #[derive(Clone)]
     struct TestSwapRatio(usize, usize);
@@ -90,6 +93,7 @@ mod run_swap {
                     .count()
                     + 1
             }
+            SwapStopConfig::Criteria(_) => unreachable!(),
         }
     }
 
@@ -211,7 +215,9 @@ mod run_swap {
                         .run_swap_input
                         .parameters
                         .swap_acceptance_threshold,
-                    count_swap: self.expected_count_swap_flag()
+                    count_swap: self.expected_count_swap_flag(),
+                    record_accepted_swaps: false,
+                    permuted_image_format: None,
                 }
             );
             if self.swap_round_index == 0 {
@@ -308,6 +314,7 @@ mod run_swap {
             if self.expected_count_swap_flag() && self.step_index == Self::FINAL_STEP_INDEX {
                 Some(SwapPartialOutput {
                     counts: Box::new(self.output_swap_counts[self.swap_round_index - 1].clone()),
+                    accepted_swaps: None,
                 })
             } else {
                 unreachable!()
@@ -332,6 +339,7 @@ mod run_swap {
                 input: None,
                 output_permutation: self.output_permutations.next().unwrap(),
                 pass: self.previous_pass.unwrap(),
+                permuted_image: None,
             })
         }
 
@@ -359,6 +367,9 @@ mod run_swap {
                     swap_acceptance_threshold: 2.0,
                     swap_pass_sequence,
                     output_intermediate_permutations,
+                    intermediate_permutations: Default::default(),
+                    animation: None,
+                    statistics_log: None,
                 };
 
                 let number_of_output_permutations = if output_intermediate_permutations {
@@ -408,7 +419,10 @@ mod run_swap {
                     dispatcher,
                     run_swap_input.candidate_permutation,
                     run_swap_input.displacement_goal,
+                    0,
+                    Default::default(),
                     &run_swap_input.parameters,
+                    None,
                 );
                 let mut tagged_permutations: Vec<TaggedPermutation> =
                     Vec::with_capacity(validated_permutations.len());
@@ -434,6 +448,7 @@ mod run_swap {
                                     round_index: i / passes_per_round,
                                     pass_index: i % passes_per_round,
                                     pass,
+                                    permuted_image: None,
                                 }
                             } else {
                                 TaggedPermutation {
@@ -449,6 +464,7 @@ mod run_swap {
                                         .iter()
                                         .last()
                                         .unwrap(),
+                                    permuted_image: None,
                                 }
                             }
                         })
@@ -892,4 +908,84 @@ mod run_swap {
             }
         }
     }
+
+    #[test]
+    fn interrupted() -> Result<(), Box<dyn Error>> {
+        static INTERRUPT: AtomicBool = AtomicBool::new(true);
+        let parameters = SwapParametersConfig {
+            stop: SwapStopConfig::Bounded {
+                iteration_count: IterationCount(NonZeroUsize::new(1).unwrap()),
+                threshold: None,
+            },
+            swap_acceptance_threshold: 2.0,
+            swap_pass_sequence: SwapPassSequence::from_passes([
+                SwapPass::OffsetVertical,
+                SwapPass::Horizontal,
+            ])?,
+            output_intermediate_permutations: true,
+            intermediate_permutations: Default::default(),
+            animation: None,
+            statistics_log: None,
+        };
+        let width = 5;
+        let validated_permutations = (3..width)
+            .map(|first_pixel_shift| unsafe {
+                validation::vector_field_into_validated_permutation_unchecked(
+                    test_util::permutation::line_with_first_texel_moved(width, first_pixel_shift)
+                        .permutation,
+                )
+            })
+            .collect::<Vec<_>>();
+        let run_swap_input = RunSwapInput {
+            candidate_permutation: Some(
+                CandidatePermutation::from_vector_field(
+                    test_util::permutation::line_with_first_texel_moved(width, 1).permutation,
+                )
+                .unwrap(),
+            ),
+            displacement_goal: Some(
+                DisplacementGoal::from_raw_candidate_permutation(
+                    test_util::permutation::line_with_first_texel_moved(width, 2).permutation,
+                )
+                .unwrap(),
+            ),
+            parameters,
+        };
+        let dispatcher = Box::new(SwapDispatcher::new(
+            run_swap_input.clone(),
+            Vec::new(),
+            validated_permutations.clone(),
+        ));
+
+        let mut iter = super::super::run_swap(
+            dispatcher,
+            run_swap_input.candidate_permutation,
+            run_swap_input.displacement_goal,
+            0,
+            SwapRoundOutputs {
+                interrupt: Some(&INTERRUPT),
+                ..Default::default()
+            },
+            &run_swap_input.parameters,
+            None,
+        );
+        let mut tagged_permutations = Vec::new();
+        while let Some(result) = iter.next() {
+            tagged_permutations.push(futures::executor::block_on(result)?);
+        }
+        assert_eq!(
+            tagged_permutations,
+            vec![TaggedPermutation {
+                permutation: validated_permutations[0].clone(),
+                round_index: 0,
+                pass_index: 0,
+                pass: SwapPass::OffsetVertical,
+                permuted_image: None,
+            }]
+        );
+        let (_, statistics) = iter.finish();
+        assert!(statistics.interrupted);
+        assert_eq!(statistics.round_count, 0);
+        Ok(())
+    }
 }
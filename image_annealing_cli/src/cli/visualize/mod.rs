@@ -0,0 +1,40 @@
+use super::{loader, metadata};
+use crate::config::DisplacementGoalPath;
+use image_annealing::compute::format::{ImageFileWriter, Rgba8Image};
+use image_annealing::image_utils::visualization::{self, VectorFieldVisualizationParameters};
+use std::error::Error;
+use std::num::NonZeroU32;
+
+const LEGEND_SIZE: u32 = 256;
+
+pub fn run_and_save_visualization(
+    vector_field: &DisplacementGoalPath,
+    visualization_output_path_no_extension: &str,
+    legend_output_path_no_extension: Option<&str>,
+    parameters: &VectorFieldVisualizationParameters,
+) -> Result<(), Box<dyn Error>> {
+    let metadata = metadata::make_run_metadata("VisualizeVectorField", [vector_field])?;
+    let vector_field = loader::load_displacement_goal(vector_field)?;
+    let max_magnitude = parameters
+        .max_magnitude
+        .unwrap_or_else(|| visualization::max_magnitude(&vector_field));
+    let output_path = Rgba8Image::make_filename(visualization_output_path_no_extension);
+    metadata.write_embedded(
+        visualization::render(&vector_field, parameters).encode()?,
+        &output_path,
+    )?;
+    println!(
+        "Wrote vector field visualization to: {}",
+        output_path.display()
+    );
+    if let Some(path) = legend_output_path_no_extension {
+        let output_path = visualization::render_legend(NonZeroU32::new(LEGEND_SIZE).unwrap())
+            .save_add_extension(path)?;
+        println!(
+            "Wrote vector field visualization legend, for a maximum magnitude of {}, to: {}",
+            max_magnitude,
+            output_path.display()
+        );
+    }
+    Ok(())
+}
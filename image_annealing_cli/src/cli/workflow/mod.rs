@@ -0,0 +1,392 @@
+use super::interrupt::{self, InterruptedError};
+use super::{loader, metadata, swap};
+use crate::config::{
+    DisplacementGoalPath, ImageConversionPolicy, ImagePath, LosslessImagePath, PermutationPath,
+    WorkflowInput, WorkflowOperationConfig, WorkflowStepConfig,
+};
+use image_annealing::compute::format::LosslessImage;
+use image_annealing::compute::{
+    self, CreateDisplacementGoalInput, Dispatcher, PermuteInput, ValidatePermutationInput,
+};
+use image_annealing::{CandidatePermutation, DisplacementGoal, ValidatedPermutation};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// An output of a workflow step, kept in memory for use by later steps
+enum WorkflowValue {
+    DisplacementGoal(DisplacementGoal),
+    Image(LosslessImage),
+    Permutation(ValidatedPermutation),
+}
+
+/// The inputs available to one run of a workflow step
+struct StepContext<'a> {
+    outputs: &'a HashMap<&'a str, Vec<WorkflowValue>>,
+    /// The step whose outputs are being looped over, and the index of the current output
+    for_each: Option<(&'a str, usize)>,
+    /// A suffix for output file paths that distinguishes the runs of a step in a loop
+    suffix: String,
+}
+
+impl<'a> StepContext<'a> {
+    fn value(&self, reference: &str) -> &WorkflowValue {
+        let values = &self.outputs[reference];
+        match self.for_each {
+            Some((name, index)) if name == reference => &values[index],
+            // Every run of a step produces at least one output
+            _ => values.last().unwrap(),
+        }
+    }
+
+    fn candidate_permutation(
+        &self,
+        input: &WorkflowInput<PermutationPath>,
+    ) -> Result<CandidatePermutation, Box<dyn Error>> {
+        match input {
+            WorkflowInput::File(path) => loader::load_candidate_permutation(path),
+            WorkflowInput::Step(reference) => match self.value(reference) {
+                WorkflowValue::Permutation(permutation) => Ok(permutation.clone().into()),
+                _ => unreachable!("step output kinds are checked when the workflow is loaded"),
+            },
+        }
+    }
+
+    fn displacement_goal(
+        &self,
+        input: &WorkflowInput<DisplacementGoalPath>,
+    ) -> Result<DisplacementGoal, Box<dyn Error>> {
+        match input {
+            WorkflowInput::File(path) => loader::load_displacement_goal(path),
+            WorkflowInput::Step(reference) => match self.value(reference) {
+                WorkflowValue::DisplacementGoal(displacement_goal) => Ok(displacement_goal.clone()),
+                WorkflowValue::Permutation(permutation) => Ok(permutation.clone().into()),
+                WorkflowValue::Image(_) => {
+                    unreachable!("step output kinds are checked when the workflow is loaded")
+                }
+            },
+        }
+    }
+
+    fn image(
+        &self,
+        input: &WorkflowInput<LosslessImagePath>,
+        conversion: ImageConversionPolicy,
+    ) -> Result<LosslessImage, Box<dyn Error>> {
+        match input {
+            WorkflowInput::File(path) => loader::load_image(path, conversion),
+            WorkflowInput::Step(reference) => match self.value(reference) {
+                WorkflowValue::Image(image) => Ok(image.clone()),
+                _ => unreachable!("step output kinds are checked when the workflow is loaded"),
+            },
+        }
+    }
+}
+
+fn add_suffix<P: ImagePath>(path: &P, suffix: &str) -> P {
+    let path_str: &str = path.as_ref();
+    let path_no_extension = path.path_no_extension();
+    P::from_raw(format!(
+        "{}{}{}",
+        path_no_extension,
+        suffix,
+        &path_str[path_no_extension.len()..]
+    ))
+}
+
+fn add_suffix_to_file_stem(path: &str, suffix: &str) -> String {
+    let path = Path::new(path);
+    let mut file_name = path.file_stem().unwrap_or_default().to_os_string();
+    file_name.push(suffix);
+    if let Some(extension) = path.extension() {
+        file_name.push(".");
+        file_name.push(extension);
+    }
+    path.with_file_name(file_name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+pub fn run_and_save_workflow(
+    mut dispatcher: Box<dyn Dispatcher>,
+    steps: &[WorkflowStepConfig],
+    dispatcher_config: &compute::Config,
+) -> Result<(), Box<dyn Error>> {
+    // The interrupt signal handler is installed when the first swap step runs
+    let mut interrupt = None;
+    let mut outputs: HashMap<&str, Vec<WorkflowValue>> = HashMap::new();
+    for step in steps {
+        println!("Running workflow step '{}'", step.name);
+        let mut step_outputs = Vec::new();
+        match step.for_each.as_deref() {
+            Some(reference) => {
+                let run_count = outputs[reference].len();
+                let width = run_count.to_string().len();
+                for index in 0..run_count {
+                    let context = StepContext {
+                        outputs: &outputs,
+                        for_each: Some((reference, index)),
+                        suffix: format!("_{:0width$}", index, width = width),
+                    };
+                    dispatcher = run_and_save_step(
+                        dispatcher,
+                        &step.operation,
+                        &context,
+                        dispatcher_config,
+                        &mut interrupt,
+                        &mut step_outputs,
+                    )?;
+                    check_interrupt(interrupt)?;
+                }
+            }
+            None => {
+                let context = StepContext {
+                    outputs: &outputs,
+                    for_each: None,
+                    suffix: String::new(),
+                };
+                dispatcher = run_and_save_step(
+                    dispatcher,
+                    &step.operation,
+                    &context,
+                    dispatcher_config,
+                    &mut interrupt,
+                    &mut step_outputs,
+                )?;
+                check_interrupt(interrupt)?;
+            }
+        }
+        outputs.insert(&step.name, step_outputs);
+    }
+    Ok(())
+}
+
+/// Stops the workflow after a step if an interrupt signal arrived while it ran
+fn check_interrupt(interrupt: Option<&AtomicBool>) -> Result<(), Box<dyn Error>> {
+    match interrupt {
+        Some(flag) if flag.load(Ordering::SeqCst) => Err(Box::new(InterruptedError)),
+        _ => Ok(()),
+    }
+}
+
+fn run_and_save_step(
+    dispatcher: Box<dyn Dispatcher>,
+    operation: &WorkflowOperationConfig,
+    context: &StepContext,
+    dispatcher_config: &compute::Config,
+    interrupt: &mut Option<&'static AtomicBool>,
+    outputs: &mut Vec<WorkflowValue>,
+) -> Result<Box<dyn Dispatcher>, Box<dyn Error>> {
+    Ok(match operation {
+        WorkflowOperationConfig::CreateDisplacementGoal {
+            displacement_goal,
+            candidate_permutation,
+            image,
+            image_conversion,
+            displacement_goal_output_path_no_extension,
+        } => {
+            let mut algorithm = dispatcher.create_displacement_goal(
+                CreateDisplacementGoalInput {
+                    displacement_goal: displacement_goal
+                        .as_ref()
+                        .map(|input| context.displacement_goal(input))
+                        .transpose()?,
+                    candidate_permutation: candidate_permutation
+                        .as_ref()
+                        .map(|input| context.candidate_permutation(input))
+                        .transpose()?,
+                    image: image
+                        .as_ref()
+                        .map(|input| context.image(input, *image_conversion))
+                        .transpose()?,
+                },
+                &Default::default(),
+            );
+            algorithm.step_until_finished()?;
+            let output_displacement_goal = algorithm
+                .full_output_block()
+                .unwrap()
+                .output_displacement_goal;
+            if let Some(path) = displacement_goal_output_path_no_extension {
+                let input_paths = displacement_goal
+                    .iter()
+                    .filter_map(WorkflowInput::file)
+                    .map(|path| -> &str { path.as_ref() })
+                    .chain(
+                        candidate_permutation
+                            .iter()
+                            .filter_map(WorkflowInput::file)
+                            .map(|path| -> &str { path.as_ref() }),
+                    )
+                    .chain(
+                        image
+                            .iter()
+                            .filter_map(WorkflowInput::file)
+                            .flat_map(LosslessImagePath::to_vec)
+                            .map(String::as_str),
+                    );
+                let output_path = path.save_vector_field(
+                    &output_displacement_goal,
+                    &context.suffix,
+                    &metadata::make_run_metadata("CreateDisplacementGoal", input_paths)?,
+                )?;
+                println!("Wrote displacement goal to: {:?}", output_path);
+            }
+            outputs.push(WorkflowValue::DisplacementGoal(output_displacement_goal));
+            algorithm.return_to_dispatcher()
+        }
+        WorkflowOperationConfig::CreatePermutation {
+            permutation_output_path_no_extension,
+        } => {
+            let mut algorithm =
+                dispatcher.create_permutation(Default::default(), &Default::default());
+            algorithm.step_until_finished()?;
+            let permutation = algorithm.full_output_block().unwrap().validated_permutation;
+            if let Some(path) = permutation_output_path_no_extension {
+                let output_path = path.save_vector_field(
+                    &permutation,
+                    &context.suffix,
+                    &metadata::make_run_metadata("CreatePermutation", std::iter::empty::<&str>())?,
+                )?;
+                println!("Wrote permutation to: {}", output_path.display());
+            }
+            outputs.push(WorkflowValue::Permutation(permutation));
+            algorithm.return_to_dispatcher()
+        }
+        WorkflowOperationConfig::Permute {
+            candidate_permutation,
+            original_image,
+            original_image_conversion,
+            permuted_image_output_path_no_extension,
+        } => {
+            let mut algorithm = dispatcher.permute(
+                PermuteInput {
+                    candidate_permutation: Some(
+                        context.candidate_permutation(candidate_permutation)?,
+                    ),
+                    original_image: Some(
+                        context.image(original_image, *original_image_conversion)?,
+                    ),
+                },
+                &Default::default(),
+            );
+            algorithm.step_until_finished()?;
+            let permuted_image = algorithm.full_output_block().unwrap().permuted_image;
+            if let Some(path) = permuted_image_output_path_no_extension {
+                let input_paths = candidate_permutation
+                    .file()
+                    .map(|path| -> &str { path.as_ref() })
+                    .into_iter()
+                    .chain(
+                        original_image
+                            .file()
+                            .into_iter()
+                            .flat_map(LosslessImagePath::to_vec)
+                            .map(String::as_str),
+                    );
+                let metadata = metadata::make_run_metadata("Permute", input_paths)?;
+                let output_paths = permuted_image.save_add_extension_with_metadata(
+                    path.to_vec()
+                        .into_iter()
+                        .map(|path| format!("{}{}", path, context.suffix))
+                        .collect::<Vec<_>>()
+                        .as_slice(),
+                    &metadata,
+                )?;
+                println!("Wrote permuted image to: {:?}", output_paths);
+            }
+            outputs.push(WorkflowValue::Image(permuted_image));
+            algorithm.return_to_dispatcher()
+        }
+        WorkflowOperationConfig::Swap {
+            candidate_permutation,
+            displacement_goal,
+            permutation_output_path_prefix,
+            swap_history_output_path_no_extension,
+            parameters,
+        } => {
+            let mut metadata = metadata::make_run_metadata(
+                "Swap",
+                candidate_permutation
+                    .file()
+                    .map(|path| -> &str { path.as_ref() })
+                    .into_iter()
+                    .chain(
+                        displacement_goal
+                            .file()
+                            .map(|path| -> &str { path.as_ref() }),
+                    )
+                    .chain(
+                        parameters
+                            .animation
+                            .as_ref()
+                            .map(|animation_config| animation_config.image.as_str()),
+                    ),
+            )?;
+            metadata.threshold = Some(parameters.swap_acceptance_threshold);
+            let path_prefix = permutation_output_path_prefix
+                .as_ref()
+                .map(|path| add_suffix(path, &context.suffix));
+            let animation = parameters
+                .animation
+                .as_ref()
+                .map(|animation_config| {
+                    let mut animation_config = animation_config.clone();
+                    animation_config.path_no_extension.push_str(&context.suffix);
+                    swap::SwapAnimationOutput::new(&animation_config)
+                })
+                .transpose()?;
+            let output = swap::swap_and_save(
+                dispatcher,
+                context.candidate_permutation(candidate_permutation)?,
+                context.displacement_goal(displacement_goal)?,
+                0,
+                swap::SwapPermutationOutputs {
+                    path_prefix: path_prefix.as_ref(),
+                    animation,
+                    metadata,
+                    retain_permutations: true,
+                },
+                swap::SwapRoundOutputs {
+                    swap_history: swap_history_output_path_no_extension.as_ref().map(|path| {
+                        swap::SwapHistoryOutput::new(&format!("{}{}", path, context.suffix))
+                    }),
+                    checkpoint: None,
+                    statistics_log: parameters.statistics_log.as_ref().map(|log_config| {
+                        let mut log_config = log_config.clone();
+                        log_config.path =
+                            add_suffix_to_file_stem(&log_config.path, &context.suffix);
+                        swap::SwapStatisticsLogOutput::new(&log_config)
+                    }),
+                    count_swaps: false,
+                    boundary: dispatcher_config.boundary,
+                    interrupt: Some(*interrupt.get_or_insert_with(interrupt::install_handler)),
+                },
+                parameters,
+            )?;
+            outputs.extend(
+                output
+                    .permutations
+                    .into_iter()
+                    .map(WorkflowValue::Permutation),
+            );
+            output.dispatcher
+        }
+        WorkflowOperationConfig::ValidatePermutation {
+            candidate_permutation,
+        } => {
+            let mut algorithm = dispatcher.validate_permutation(
+                ValidatePermutationInput {
+                    candidate_permutation: context.candidate_permutation(candidate_permutation)?,
+                },
+                &Default::default(),
+            );
+            algorithm.step_until_finished()?;
+            let permutation = algorithm.full_output_block().unwrap().validated_permutation;
+            println!("Candidate permutation is valid");
+            outputs.push(WorkflowValue::Permutation(permutation));
+            algorithm.return_to_dispatcher()
+        }
+    })
+}
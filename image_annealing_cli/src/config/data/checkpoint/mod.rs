@@ -0,0 +1,197 @@
+use super::{
+    BoundaryMode, ImagePath, IterationCount, PermutationPath, ResolvePaths, SwapParametersConfig,
+    SwapPass, UnverifiedIterationCount,
+};
+use image_annealing_cli_util::config_file::ConfigFieldContext;
+use image_annealing_cli_util::io::{self, PathContext, PathVariableError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UnverifiedSwapCheckpointConfig {
+    pub path_prefix: String,
+    pub interval: UnverifiedIterationCount,
+}
+
+impl ResolvePaths for UnverifiedSwapCheckpointConfig {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        self.path_prefix.resolve_paths(context)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapCheckpointConfig {
+    pub path_prefix: PermutationPath,
+    pub interval: IterationCount,
+}
+
+impl SwapCheckpointConfig {
+    /// Returns whether a checkpoint is saved at the end of the round with the given index
+    pub fn is_due(&self, round_index: usize) -> bool {
+        let interval = self.interval.get();
+        round_index % interval == interval - 1
+    }
+}
+
+impl TryFrom<UnverifiedSwapCheckpointConfig> for SwapCheckpointConfig {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: UnverifiedSwapCheckpointConfig) -> Result<Self, Self::Error> {
+        Ok(Self {
+            path_prefix: PermutationPath::from_output_path(value.path_prefix),
+            interval: value.interval.try_into().in_field("interval")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SwapCheckpointError {
+    UnsupportedVersion(u32),
+    ScheduleMismatch,
+    BoundaryMismatch {
+        checkpoint: BoundaryMode,
+        config: BoundaryMode,
+    },
+    NoRoundsLeft {
+        round_index: usize,
+        iteration_count: usize,
+    },
+    HistoryDependentStop,
+}
+
+impl fmt::Display for SwapCheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "checkpoint file version {} is not supported, as the supported version is {}",
+                version,
+                SwapCheckpointFile::VERSION
+            ),
+            Self::ScheduleMismatch => write!(
+                f,
+                "the swap pass sequence and swap acceptance threshold must match those saved in the checkpoint"
+            ),
+            Self::BoundaryMismatch { checkpoint, config } => write!(
+                f,
+                "the checkpoint was saved with {:?} boundary conditions, but the configuration uses {:?} boundary conditions",
+                checkpoint, config
+            ),
+            Self::NoRoundsLeft {
+                round_index,
+                iteration_count,
+            } => write!(
+                f,
+                "the checkpoint was saved after round {}, so no rounds remain out of an iteration count of {}",
+                round_index, iteration_count
+            ),
+            Self::HistoryDependentStop => write!(
+                f,
+                "a run cannot be resumed with the TimeLimitSeconds, Plateau or Oscillation stop criteria, as the checkpoint does not record the elapsed time or the earlier rounds that they depend on"
+            ),
+        }
+    }
+}
+
+impl Error for SwapCheckpointError {}
+
+/// The contents of a checkpoint file, which is saved next to the permutation it refers to
+///
+/// The swap algorithm is deterministic, so the permutation, the indices of the last
+/// completed round and pass, and the swap schedule are enough to resume a run
+/// with identical results. There is no random number generator state to save.
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct SwapCheckpointFile {
+    pub version: u32,
+    pub permutation_file_name: String,
+    pub round_index: usize,
+    pub pass_index: usize,
+    pub swap_pass_sequence: Vec<SwapPass>,
+    pub swap_acceptance_threshold: f32,
+    pub boundary: BoundaryMode,
+}
+
+impl SwapCheckpointFile {
+    pub const VERSION: u32 = 1;
+    pub const EXTENSION: &'static str = "json";
+
+    pub fn make_filename<P: AsRef<Path>>(path_no_extension: P) -> PathBuf {
+        path_no_extension.as_ref().with_extension(Self::EXTENSION)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapCheckpoint {
+    /// The path of the checkpoint file
+    pub path: String,
+    pub round_index: usize,
+    pub pass_index: usize,
+}
+
+impl SwapCheckpoint {
+    pub fn from_input_path<T: AsRef<str>>(
+        unverified_path: T,
+        parameters: &SwapParametersConfig,
+        boundary: BoundaryMode,
+    ) -> Result<(Self, String), Box<dyn Error>> {
+        let path = io::convert_and_check_input_file_path(unverified_path)?;
+        let file: SwapCheckpointFile = serde_json::from_reader(BufReader::new(File::open(&path)?))
+            .map_err(|e| format!("checkpoint file deserialization error, \"{}\"", e))?;
+
+        if file.version != SwapCheckpointFile::VERSION {
+            return Err(Box::new(SwapCheckpointError::UnsupportedVersion(
+                file.version,
+            )));
+        }
+        let swap_pass_sequence: Vec<SwapPass> = parameters
+            .swap_pass_sequence
+            .iter()
+            .map(|&pass| pass.into())
+            .collect();
+        if file.swap_pass_sequence != swap_pass_sequence
+            || file.swap_acceptance_threshold != parameters.swap_acceptance_threshold
+        {
+            return Err(Box::new(SwapCheckpointError::ScheduleMismatch));
+        }
+        if file.boundary != boundary {
+            return Err(Box::new(SwapCheckpointError::BoundaryMismatch {
+                checkpoint: file.boundary,
+                config: boundary,
+            }));
+        }
+        let criterion = parameters.stop.criterion();
+        if criterion.depends_on_history() {
+            return Err(Box::new(SwapCheckpointError::HistoryDependentStop));
+        }
+        if let Some(iteration_count) = criterion.iteration_limit() {
+            if file.round_index >= iteration_count.get().checked_sub(1).unwrap() {
+                return Err(Box::new(SwapCheckpointError::NoRoundsLeft {
+                    round_index: file.round_index,
+                    iteration_count: iteration_count.get(),
+                }));
+            }
+        }
+
+        let permutation_path = Path::new(&path)
+            .with_file_name(&file.permutation_file_name)
+            .to_str()
+            .unwrap()
+            .to_string();
+        Ok((
+            Self {
+                path,
+                round_index: file.round_index,
+                pass_index: file.pass_index,
+            },
+            permutation_path,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,182 @@
+mod swap_checkpoint_from_input_path {
+    use super::super::super::{
+        BoundaryMode, IterationCount, SwapParametersConfig, SwapPass, SwapStopConfig,
+        SwapStopCriterion,
+    };
+    use super::super::{SwapCheckpoint, SwapCheckpointFile};
+    use image_annealing::compute::{self, SwapPassSequence};
+    use std::error::Error;
+    use std::fs::File;
+    use std::num::NonZeroUsize;
+    use std::path::PathBuf;
+
+    fn make_swap_parameters() -> SwapParametersConfig {
+        SwapParametersConfig {
+            stop: SwapStopConfig::Bounded {
+                iteration_count: IterationCount(NonZeroUsize::new(4).unwrap()),
+                threshold: None,
+            },
+            swap_acceptance_threshold: 2.0,
+            swap_pass_sequence: SwapPassSequence::from_passes([
+                compute::SwapPass::Vertical,
+                compute::SwapPass::OffsetVertical,
+            ])
+            .unwrap(),
+            output_intermediate_permutations: false,
+            intermediate_permutations: Default::default(),
+            animation: None,
+            statistics_log: None,
+        }
+    }
+
+    fn make_checkpoint_file(permutation_file_name: String) -> SwapCheckpointFile {
+        SwapCheckpointFile {
+            version: SwapCheckpointFile::VERSION,
+            permutation_file_name,
+            round_index: 2,
+            pass_index: 1,
+            swap_pass_sequence: vec![SwapPass::Vertical, SwapPass::OffsetVertical],
+            swap_acceptance_threshold: 2.0,
+            boundary: BoundaryMode::Bounded,
+        }
+    }
+
+    fn write_checkpoint_file<F: FnOnce(&mut SwapCheckpointFile)>(
+        name: &str,
+        modify: F,
+    ) -> Result<(PathBuf, PathBuf), Box<dyn Error>> {
+        let permutation_file_name = format!("{}.png", name);
+        let permutation_path = test_util::make_test_output_path([permutation_file_name.as_str()]);
+        std::fs::copy(
+            test_util::make_test_data_path(["image", "permutation", "identity_permutation.png"]),
+            &permutation_path,
+        )?;
+        let mut file = make_checkpoint_file(permutation_file_name);
+        modify(&mut file);
+        let path = SwapCheckpointFile::make_filename(test_util::make_test_output_path([name]));
+        serde_json::to_writer(File::create(&path)?, &file)?;
+        Ok((path, permutation_path))
+    }
+
+    fn remove_files(paths: (PathBuf, PathBuf)) -> Result<(), Box<dyn Error>> {
+        std::fs::remove_file(paths.0)?;
+        std::fs::remove_file(paths.1)?;
+        Ok(())
+    }
+
+    #[test]
+    fn valid() -> Result<(), Box<dyn Error>> {
+        let paths = write_checkpoint_file("config_swap_checkpoint_valid", |_| {})?;
+        let (checkpoint, permutation_path) = SwapCheckpoint::from_input_path(
+            paths.0.to_str().unwrap(),
+            &make_swap_parameters(),
+            BoundaryMode::Bounded,
+        )?;
+        assert_eq!(
+            checkpoint,
+            SwapCheckpoint {
+                path: String::from(paths.0.to_str().unwrap()),
+                round_index: 2,
+                pass_index: 1,
+            }
+        );
+        assert_eq!(PathBuf::from(permutation_path), paths.1);
+        remove_files(paths)
+    }
+
+    #[test]
+    fn not_found() {
+        let r = SwapCheckpoint::from_input_path(
+            "../test_data/config/not_found.json",
+            &make_swap_parameters(),
+            BoundaryMode::Bounded,
+        );
+        test_util::assert_error_contains(
+            r,
+            "does not exist", // Note: do not put a platform-dependent path string here
+        );
+    }
+
+    #[test]
+    fn unsupported_version() -> Result<(), Box<dyn Error>> {
+        let paths = write_checkpoint_file("config_swap_checkpoint_unsupported_version", |file| {
+            file.version = 0
+        })?;
+        let r = SwapCheckpoint::from_input_path(
+            paths.0.to_str().unwrap(),
+            &make_swap_parameters(),
+            BoundaryMode::Bounded,
+        );
+        test_util::assert_error_contains(r, "checkpoint file version 0 is not supported");
+        remove_files(paths)
+    }
+
+    #[test]
+    fn schedule_mismatch() -> Result<(), Box<dyn Error>> {
+        let paths = write_checkpoint_file("config_swap_checkpoint_schedule_mismatch", |file| {
+            file.swap_pass_sequence = vec![SwapPass::OffsetVertical, SwapPass::Vertical]
+        })?;
+        let r = SwapCheckpoint::from_input_path(
+            paths.0.to_str().unwrap(),
+            &make_swap_parameters(),
+            BoundaryMode::Bounded,
+        );
+        test_util::assert_error_contains(
+            r,
+            "the swap pass sequence and swap acceptance threshold must match those saved in the checkpoint",
+        );
+        remove_files(paths)
+    }
+
+    #[test]
+    fn boundary_mismatch() -> Result<(), Box<dyn Error>> {
+        let paths = write_checkpoint_file("config_swap_checkpoint_boundary_mismatch", |_| {})?;
+        let r = SwapCheckpoint::from_input_path(
+            paths.0.to_str().unwrap(),
+            &make_swap_parameters(),
+            BoundaryMode::Toroidal,
+        );
+        test_util::assert_error_contains(
+            r,
+            "the checkpoint was saved with Bounded boundary conditions, but the configuration uses Toroidal boundary conditions",
+        );
+        remove_files(paths)
+    }
+
+    #[test]
+    fn no_rounds_left() -> Result<(), Box<dyn Error>> {
+        let paths = write_checkpoint_file("config_swap_checkpoint_no_rounds_left", |file| {
+            file.round_index = 3
+        })?;
+        let r = SwapCheckpoint::from_input_path(
+            paths.0.to_str().unwrap(),
+            &make_swap_parameters(),
+            BoundaryMode::Bounded,
+        );
+        test_util::assert_error_contains(
+            r,
+            "the checkpoint was saved after round 3, so no rounds remain out of an iteration count of 4",
+        );
+        remove_files(paths)
+    }
+
+    #[test]
+    fn history_dependent_stop() -> Result<(), Box<dyn Error>> {
+        let paths = write_checkpoint_file("config_swap_checkpoint_history_dependent_stop", |_| {})?;
+        let mut parameters = make_swap_parameters();
+        parameters.stop = SwapStopConfig::Criteria(SwapStopCriterion::Any(vec![
+            SwapStopCriterion::IterationCount(IterationCount(NonZeroUsize::new(4).unwrap())),
+            SwapStopCriterion::Oscillation,
+        ]));
+        let r = SwapCheckpoint::from_input_path(
+            paths.0.to_str().unwrap(),
+            &parameters,
+            BoundaryMode::Bounded,
+        );
+        test_util::assert_error_contains(
+            r,
+            "a run cannot be resumed with the TimeLimitSeconds, Plateau or Oscillation stop criteria",
+        );
+        remove_files(paths)
+    }
+}
@@ -1,7 +1,8 @@
 use image_annealing::{ImageDimensions, InvalidDimensionError};
+use schemars::JsonSchema;
 use serde::Deserialize;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub struct UnverifiedImageDimensionsConfig {
     pub width: usize,
     pub height: usize,
@@ -0,0 +1,392 @@
+use super::{ImagePath, PermutationPath, SwapCheckpointConfig, SwapParametersConfig, SwapPass};
+use image_annealing::compute::SwapPassSequence;
+use image_annealing_cli_util::config_file::ConfigFieldContext;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Values of a continuous parameter
+#[derive(Clone, Deserialize, JsonSchema)]
+pub enum UnverifiedExperimentValues {
+    List(Vec<f32>),
+    /// Evenly spaced values from `start` to `end`, inclusive, when sampling a grid,
+    /// or the interval from which values are drawn uniformly when sampling randomly
+    Range {
+        start: f32,
+        end: f32,
+        count: usize,
+    },
+}
+
+/// A swap parameter that is varied between the runs of an experiment
+#[derive(Clone, Deserialize, JsonSchema)]
+pub enum UnverifiedExperimentAxis {
+    SwapAcceptanceThreshold(UnverifiedExperimentValues),
+    SwapPassSequence(Vec<Vec<SwapPass>>),
+}
+
+#[derive(Clone, Copy, Deserialize, JsonSchema)]
+pub enum UnverifiedExperimentSampling {
+    /// Run every combination of parameter values
+    Grid,
+    /// Run `count` combinations of parameter values drawn at random
+    Random { count: usize, seed: Option<u64> },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExperimentAxisKind {
+    SwapAcceptanceThreshold,
+    SwapPassSequence,
+}
+
+impl fmt::Display for ExperimentAxisKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SwapAcceptanceThreshold => write!(f, "swap acceptance threshold"),
+            Self::SwapPassSequence => write!(f, "swap pass sequence"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ExperimentError {
+    BaseNotSwap,
+    Resume,
+    EmptyAxis(ExperimentAxisKind),
+    DuplicateAxis(ExperimentAxisKind),
+    ZeroRunCount,
+}
+
+impl fmt::Display for ExperimentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BaseNotSwap => write!(
+                f,
+                "the base configuration of an experiment must describe a swap operation"
+            ),
+            Self::Resume => write!(
+                f,
+                "the runs of an experiment cannot resume from a checkpoint"
+            ),
+            Self::EmptyAxis(kind) => write!(f, "the {} experiment axis has no values", kind),
+            Self::DuplicateAxis(kind) => {
+                write!(f, "there is more than one {} experiment axis", kind)
+            }
+            Self::ZeroRunCount => write!(
+                f,
+                "the number of randomly sampled experiment runs cannot be zero"
+            ),
+        }
+    }
+}
+
+impl Error for ExperimentError {}
+
+/// The outputs and parameters of one run of an experiment
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExperimentRunConfig {
+    pub name: String,
+    /// The directory containing the outputs of the run
+    pub directory: String,
+    pub permutation_output_path_prefix: PermutationPath,
+    pub swap_history_output_path_no_extension: Option<String>,
+    pub checkpoint: Option<SwapCheckpointConfig>,
+    pub parameters: SwapParametersConfig,
+}
+
+impl ExperimentRunConfig {
+    /// Moves the outputs of the run to its subdirectory of a different output directory
+    pub fn rebase(&mut self, output_directory: &str) {
+        let directory = Path::new(output_directory).join(&self.name);
+        self.permutation_output_path_prefix = PermutationPath::from_raw(rebase_path(
+            &directory,
+            self.permutation_output_path_prefix.as_ref(),
+        ));
+        if let Some(path) = self.swap_history_output_path_no_extension.as_mut() {
+            *path = rebase_path(&directory, path);
+        }
+        if let Some(checkpoint) = self.checkpoint.as_mut() {
+            checkpoint.path_prefix =
+                PermutationPath::from_raw(rebase_path(&directory, checkpoint.path_prefix.as_ref()));
+        }
+        if let Some(animation) = self.parameters.animation.as_mut() {
+            animation.path_no_extension = rebase_path(&directory, &animation.path_no_extension);
+        }
+        if let Some(statistics_log) = self.parameters.statistics_log.as_mut() {
+            statistics_log.path = rebase_path(&directory, &statistics_log.path);
+        }
+        self.directory = directory.to_string_lossy().into_owned();
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum ExperimentValues {
+    List(Vec<f32>),
+    Range {
+        start: f32,
+        end: f32,
+        count: NonZeroUsize,
+    },
+}
+
+impl ExperimentValues {
+    fn grid(&self) -> Vec<f32> {
+        match self {
+            Self::List(values) => values.clone(),
+            Self::Range { start, end, count } => {
+                let count = count.get();
+                if count == 1 {
+                    vec![*start]
+                } else {
+                    (0..count)
+                        .map(|i| start + (end - start) * (i as f32 / (count - 1) as f32))
+                        .collect()
+                }
+            }
+        }
+    }
+
+    fn sample(&self, generator: &mut SplitMix64) -> f32 {
+        match self {
+            Self::List(values) => values[generator.next_index(values.len())],
+            Self::Range { start, end, .. } => {
+                start + (end - start) * (generator.next_fraction() as f32)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum ExperimentAxis {
+    SwapAcceptanceThreshold(ExperimentValues),
+    SwapPassSequence(Vec<SwapPassSequence>),
+}
+
+impl ExperimentAxis {
+    fn kind(&self) -> ExperimentAxisKind {
+        match self {
+            Self::SwapAcceptanceThreshold(_) => ExperimentAxisKind::SwapAcceptanceThreshold,
+            Self::SwapPassSequence(_) => ExperimentAxisKind::SwapPassSequence,
+        }
+    }
+
+    /// The parameters of the runs that set this axis to each of its grid values
+    fn grid(&self, parameters: &SwapParametersConfig) -> Vec<SwapParametersConfig> {
+        match self {
+            Self::SwapAcceptanceThreshold(values) => values
+                .grid()
+                .into_iter()
+                .map(|value| SwapParametersConfig {
+                    swap_acceptance_threshold: value,
+                    ..parameters.clone()
+                })
+                .collect(),
+            Self::SwapPassSequence(sequences) => sequences
+                .iter()
+                .map(|&sequence| SwapParametersConfig {
+                    swap_pass_sequence: sequence,
+                    ..parameters.clone()
+                })
+                .collect(),
+        }
+    }
+
+    fn sample(&self, parameters: &mut SwapParametersConfig, generator: &mut SplitMix64) {
+        match self {
+            Self::SwapAcceptanceThreshold(values) => {
+                parameters.swap_acceptance_threshold = values.sample(generator)
+            }
+            Self::SwapPassSequence(sequences) => {
+                parameters.swap_pass_sequence = sequences[generator.next_index(sequences.len())]
+            }
+        }
+    }
+}
+
+impl TryFrom<UnverifiedExperimentAxis> for ExperimentAxis {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: UnverifiedExperimentAxis) -> Result<Self, Self::Error> {
+        let axis = match value {
+            UnverifiedExperimentAxis::SwapAcceptanceThreshold(values) => {
+                Self::SwapAcceptanceThreshold(match values {
+                    UnverifiedExperimentValues::List(list) => ExperimentValues::List(list),
+                    UnverifiedExperimentValues::Range { start, end, count } => {
+                        match NonZeroUsize::new(count) {
+                            Some(count) => ExperimentValues::Range { start, end, count },
+                            None => ExperimentValues::List(Vec::new()),
+                        }
+                    }
+                })
+            }
+            UnverifiedExperimentAxis::SwapPassSequence(sequences) => Self::SwapPassSequence(
+                sequences
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, sequence)| {
+                        SwapPassSequence::from_passes(
+                            sequence
+                                .into_iter()
+                                .map(<image_annealing::compute::SwapPass as From<SwapPass>>::from),
+                        )
+                        .at_index(i)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .in_field("SwapPassSequence")?,
+            ),
+        };
+        let is_empty = match &axis {
+            Self::SwapAcceptanceThreshold(ExperimentValues::List(list)) => list.is_empty(),
+            Self::SwapAcceptanceThreshold(ExperimentValues::Range { .. }) => false,
+            Self::SwapPassSequence(sequences) => sequences.is_empty(),
+        };
+        if is_empty {
+            Err(Box::new(ExperimentError::EmptyAxis(axis.kind())))
+        } else {
+            Ok(axis)
+        }
+    }
+}
+
+/// A small pseudorandom number generator, so that randomly sampled experiments
+/// can be reproduced from their seeds
+///
+/// See <https://prng.di.unimi.it/splitmix64.c>
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in the interval [0, 1)
+    fn next_fraction(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_index(&mut self, len: usize) -> usize {
+        ((self.next_fraction() * len as f64) as usize).min(len - 1)
+    }
+}
+
+fn rebase_path(directory: &Path, path: &str) -> String {
+    directory
+        .join(Path::new(path).file_name().unwrap_or_default())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Creates the runs of an experiment, each of which writes the outputs of the base run
+/// to a separate subdirectory of the output directory
+///
+/// Returns the runs and the seed used to sample them, if they were sampled randomly.
+pub fn experiment_from_config(
+    base: &ExperimentRunConfig,
+    axes: Vec<UnverifiedExperimentAxis>,
+    sampling: Option<UnverifiedExperimentSampling>,
+    output_directory: &str,
+) -> Result<(Vec<ExperimentRunConfig>, Option<u64>), Box<dyn Error>> {
+    let axes = axes
+        .into_iter()
+        .enumerate()
+        .map(|(i, axis)| ExperimentAxis::try_from(axis).at_index(i))
+        .collect::<Result<Vec<_>, _>>()
+        .in_field("axes")?;
+    for (i, axis) in axes.iter().enumerate() {
+        if axes[..i].iter().any(|other| other.kind() == axis.kind()) {
+            return Err(ExperimentError::DuplicateAxis(axis.kind()))
+                .at_index(i)
+                .in_field("axes");
+        }
+    }
+
+    let (parameters, seed) = match sampling.unwrap_or(UnverifiedExperimentSampling::Grid) {
+        UnverifiedExperimentSampling::Grid => (
+            axes.iter()
+                .fold(vec![base.parameters.clone()], |acc, axis| {
+                    acc.iter()
+                        .flat_map(|parameters| axis.grid(parameters))
+                        .collect()
+                }),
+            None,
+        ),
+        UnverifiedExperimentSampling::Random { count, seed } => {
+            if count == 0 {
+                return Err(ExperimentError::ZeroRunCount)
+                    .in_field("count")
+                    .in_field("Random")
+                    .in_field("sampling");
+            }
+            let seed = seed.unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_nanos() as u64)
+                    .unwrap_or_default()
+            });
+            let mut generator = SplitMix64(seed);
+            (
+                (0..count)
+                    .map(|_| {
+                        let mut parameters = base.parameters.clone();
+                        for axis in axes.iter() {
+                            axis.sample(&mut parameters, &mut generator);
+                        }
+                        parameters
+                    })
+                    .collect(),
+                Some(seed),
+            )
+        }
+    };
+
+    let width = parameters.len().to_string().len();
+    let runs = parameters
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut parameters)| {
+            let name = format!("run_{:0width$}", i, width = width);
+            let directory = Path::new(output_directory).join(&name);
+            if let Some(animation) = parameters.animation.as_mut() {
+                animation.path_no_extension = rebase_path(&directory, &animation.path_no_extension);
+            }
+            if let Some(statistics_log) = parameters.statistics_log.as_mut() {
+                statistics_log.path = rebase_path(&directory, &statistics_log.path);
+            }
+            ExperimentRunConfig {
+                name,
+                permutation_output_path_prefix: PermutationPath::from_raw(rebase_path(
+                    &directory,
+                    base.permutation_output_path_prefix.as_ref(),
+                )),
+                swap_history_output_path_no_extension: base
+                    .swap_history_output_path_no_extension
+                    .as_ref()
+                    .map(|path| rebase_path(&directory, path)),
+                checkpoint: base
+                    .checkpoint
+                    .as_ref()
+                    .map(|checkpoint| SwapCheckpointConfig {
+                        path_prefix: PermutationPath::from_raw(rebase_path(
+                            &directory,
+                            checkpoint.path_prefix.as_ref(),
+                        )),
+                        interval: checkpoint.interval,
+                    }),
+                parameters,
+                directory: directory.to_string_lossy().into_owned(),
+            }
+        })
+        .collect();
+    Ok((runs, seed))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,227 @@
+mod experiment_from_config {
+    use super::super::super::{
+        ImagePath, PermutationPath, SwapParametersConfig, SwapPass, SwapStopConfig,
+        SwapStopThreshold,
+    };
+    use super::super::{
+        ExperimentRunConfig, UnverifiedExperimentAxis, UnverifiedExperimentSampling,
+        UnverifiedExperimentValues,
+    };
+    use image_annealing::compute::{self, SwapPassSequence};
+    use std::error::Error;
+    use std::path::Path;
+
+    fn make_base() -> ExperimentRunConfig {
+        ExperimentRunConfig {
+            name: String::new(),
+            directory: String::new(),
+            permutation_output_path_prefix: PermutationPath::from_raw_clone("output/permutation"),
+            swap_history_output_path_no_extension: Some(String::from("output/history")),
+            checkpoint: None,
+            parameters: SwapParametersConfig {
+                stop: SwapStopConfig::Unbounded(SwapStopThreshold::SwapsAccepted(0)),
+                swap_acceptance_threshold: 0.0,
+                swap_pass_sequence: SwapPassSequence::all(),
+                output_intermediate_permutations: false,
+                intermediate_permutations: Default::default(),
+                animation: None,
+                statistics_log: None,
+            },
+        }
+    }
+
+    fn make_path(components: &[&str]) -> String {
+        components
+            .iter()
+            .fold(Path::new("experiment").to_path_buf(), |path, component| {
+                path.join(component)
+            })
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn thresholds(runs: &[ExperimentRunConfig]) -> Vec<f32> {
+        runs.iter()
+            .map(|run| run.parameters.swap_acceptance_threshold)
+            .collect()
+    }
+
+    #[test]
+    fn no_axes() -> Result<(), Box<dyn Error>> {
+        let (runs, seed) =
+            super::super::experiment_from_config(&make_base(), Vec::new(), None, "experiment")?;
+        assert_eq!(
+            runs,
+            vec![ExperimentRunConfig {
+                name: String::from("run_0"),
+                directory: make_path(&["run_0"]),
+                permutation_output_path_prefix: PermutationPath::from_raw(make_path(&[
+                    "run_0",
+                    "permutation"
+                ])),
+                swap_history_output_path_no_extension: Some(make_path(&["run_0", "history"])),
+                ..make_base()
+            }]
+        );
+        assert!(seed.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn grid() -> Result<(), Box<dyn Error>> {
+        let (runs, _) = super::super::experiment_from_config(
+            &make_base(),
+            vec![
+                UnverifiedExperimentAxis::SwapAcceptanceThreshold(
+                    UnverifiedExperimentValues::List(vec![1.0, 2.0]),
+                ),
+                UnverifiedExperimentAxis::SwapPassSequence(vec![
+                    vec![SwapPass::Horizontal],
+                    vec![SwapPass::Vertical, SwapPass::Horizontal],
+                ]),
+            ],
+            Some(UnverifiedExperimentSampling::Grid),
+            "experiment",
+        )?;
+        assert_eq!(thresholds(&runs), vec![1.0, 1.0, 2.0, 2.0]);
+        let horizontal = SwapPassSequence::from(compute::SwapPass::Horizontal);
+        let vertical_horizontal = SwapPassSequence::from_passes([
+            compute::SwapPass::Vertical,
+            compute::SwapPass::Horizontal,
+        ])?;
+        assert_eq!(
+            runs.iter()
+                .map(|run| run.parameters.swap_pass_sequence)
+                .collect::<Vec<_>>(),
+            vec![
+                horizontal,
+                vertical_horizontal,
+                horizontal,
+                vertical_horizontal
+            ]
+        );
+        assert_eq!(
+            runs.iter().map(|run| run.name.as_str()).collect::<Vec<_>>(),
+            vec!["run_0", "run_1", "run_2", "run_3"]
+        );
+        assert_eq!(runs[3].directory, make_path(&["run_3"]));
+        Ok(())
+    }
+
+    #[test]
+    fn range() -> Result<(), Box<dyn Error>> {
+        let (runs, _) = super::super::experiment_from_config(
+            &make_base(),
+            vec![UnverifiedExperimentAxis::SwapAcceptanceThreshold(
+                UnverifiedExperimentValues::Range {
+                    start: 1.0,
+                    end: 2.0,
+                    count: 11,
+                },
+            )],
+            None,
+            "experiment",
+        )?;
+        let values = thresholds(&runs);
+        assert_eq!(values.len(), 11);
+        assert_eq!(values[0], 1.0);
+        assert_eq!(values[5], 1.5);
+        assert_eq!(values[10], 2.0);
+        assert_eq!(runs[0].name, "run_00");
+        Ok(())
+    }
+
+    #[test]
+    fn random() -> Result<(), Box<dyn Error>> {
+        let axes = vec![UnverifiedExperimentAxis::SwapAcceptanceThreshold(
+            UnverifiedExperimentValues::Range {
+                start: 1.0,
+                end: 2.0,
+                count: 2,
+            },
+        )];
+        let sampling = Some(UnverifiedExperimentSampling::Random {
+            count: 5,
+            seed: Some(7),
+        });
+        let (runs, seed) = super::super::experiment_from_config(
+            &make_base(),
+            axes.clone(),
+            sampling,
+            "experiment",
+        )?;
+        assert_eq!(seed, Some(7));
+        let values = thresholds(&runs);
+        assert_eq!(values.len(), 5);
+        assert!(values.iter().all(|value| (1.0..2.0).contains(value)));
+        let (runs_repeated, _) =
+            super::super::experiment_from_config(&make_base(), axes, sampling, "experiment")?;
+        assert_eq!(thresholds(&runs_repeated), values);
+        Ok(())
+    }
+
+    #[test]
+    fn empty_axis() {
+        test_util::assert_error_contains(
+            super::super::experiment_from_config(
+                &make_base(),
+                vec![UnverifiedExperimentAxis::SwapPassSequence(Vec::new())],
+                None,
+                "experiment",
+            ),
+            "the swap pass sequence experiment axis has no values",
+        );
+    }
+
+    #[test]
+    fn duplicate_axis() {
+        test_util::assert_error_contains(
+            super::super::experiment_from_config(
+                &make_base(),
+                vec![
+                    UnverifiedExperimentAxis::SwapAcceptanceThreshold(
+                        UnverifiedExperimentValues::List(vec![1.0]),
+                    ),
+                    UnverifiedExperimentAxis::SwapAcceptanceThreshold(
+                        UnverifiedExperimentValues::List(vec![2.0]),
+                    ),
+                ],
+                None,
+                "experiment",
+            ),
+            "there is more than one swap acceptance threshold experiment axis",
+        );
+    }
+
+    #[test]
+    fn invalid_pass_sequence() {
+        test_util::assert_error_contains(
+            super::super::experiment_from_config(
+                &make_base(),
+                vec![UnverifiedExperimentAxis::SwapPassSequence(vec![vec![
+                    SwapPass::Horizontal,
+                    SwapPass::Horizontal,
+                ]])],
+                None,
+                "experiment",
+            ),
+            "multiple times",
+        );
+    }
+
+    #[test]
+    fn zero_run_count() {
+        test_util::assert_error_contains(
+            super::super::experiment_from_config(
+                &make_base(),
+                Vec::new(),
+                Some(UnverifiedExperimentSampling::Random {
+                    count: 0,
+                    seed: None,
+                }),
+                "experiment",
+            ),
+            "the number of randomly sampled experiment runs cannot be zero",
+        );
+    }
+}
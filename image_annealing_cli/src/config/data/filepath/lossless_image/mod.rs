@@ -1,11 +1,88 @@
-use image_annealing::compute::format::ImageFormat;
+use super::ResolvePaths;
+use image_annealing::compute::format::{
+    self, ImageFormat, ImageLayerFormat, LayeredImageFormat, LayeredImageFormatError,
+};
 use image_annealing::{DimensionsMismatchError, ImageDimensions};
-use image_annealing_cli_util::io;
+use image_annealing_cli_util::io::{self, PathContext, PathVariableError};
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::error::Error;
+use std::fmt;
 use std::path::Path;
 
-#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq)]
+pub enum ImageConversionPolicy {
+    #[default]
+    Strict,
+    Lossless,
+    AllowNarrowing,
+}
+
+impl From<ImageConversionPolicy> for format::ImageConversionPolicy {
+    fn from(value: ImageConversionPolicy) -> Self {
+        match value {
+            ImageConversionPolicy::Strict => Self::Strict,
+            ImageConversionPolicy::Lossless => Self::Lossless,
+            ImageConversionPolicy::AllowNarrowing => Self::AllowNarrowing,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema, PartialEq, Eq)]
+pub enum UnverifiedImageLayerPath {
+    Rgba8(String),
+    Rgba16(String),
+}
+
+impl UnverifiedImageLayerPath {
+    pub fn new(format: ImageLayerFormat, path: String) -> Self {
+        match format {
+            ImageLayerFormat::Rgba8 => Self::Rgba8(path),
+            ImageLayerFormat::Rgba16 => Self::Rgba16(path),
+        }
+    }
+
+    pub fn format(&self) -> ImageLayerFormat {
+        match self {
+            Self::Rgba8(..) => ImageLayerFormat::Rgba8,
+            Self::Rgba16(..) => ImageLayerFormat::Rgba16,
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Rgba8(path) | Self::Rgba16(path) => path,
+        }
+    }
+
+    fn into_path(self) -> String {
+        match self {
+            Self::Rgba8(path) | Self::Rgba16(path) => path,
+        }
+    }
+}
+
+impl ResolvePaths for UnverifiedImageLayerPath {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        match self {
+            Self::Rgba8(path) | Self::Rgba16(path) => path.resolve_paths(context),
+        }
+    }
+}
+
+fn layered_format(
+    layers: &[UnverifiedImageLayerPath],
+) -> Result<LayeredImageFormat, LayeredImageFormatError> {
+    LayeredImageFormat::new(
+        layers
+            .iter()
+            .map(UnverifiedImageLayerPath::format)
+            .collect::<Vec<_>>()
+            .as_slice(),
+    )
+}
+
+#[derive(Debug, Deserialize, JsonSchema, PartialEq, Eq)]
 pub enum UnverifiedLosslessImagePath {
     Rgba8(String),
     Rgba8x2(String, String),
@@ -15,6 +92,46 @@ pub enum UnverifiedLosslessImagePath {
     Rgba16x2(String, String),
     Rgba16Rgba8(String, String),
     Rgba16Rgba8x2(String, String, String),
+    Layered(Vec<UnverifiedImageLayerPath>),
+    /// One or more images whose format is detected from their number of bits per channel.
+    /// An output image with this format takes the format of the input image.
+    Auto(Vec<String>),
+}
+
+impl From<UnverifiedImageLayerPath> for UnverifiedLosslessImagePath {
+    fn from(value: UnverifiedImageLayerPath) -> Self {
+        match value {
+            UnverifiedImageLayerPath::Rgba8(path) => Self::Rgba8(path),
+            UnverifiedImageLayerPath::Rgba16(path) => Self::Rgba16(path),
+        }
+    }
+}
+
+impl ResolvePaths for UnverifiedLosslessImagePath {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        match self {
+            Self::Rgba8(path) | Self::Rgba16(path) => path.resolve_paths(context),
+            Self::Rgba8x2(path1, path2)
+            | Self::Rgba16x2(path1, path2)
+            | Self::Rgba16Rgba8(path1, path2) => {
+                path1.resolve_paths(context)?;
+                path2.resolve_paths(context)
+            }
+            Self::Rgba8x3(path1, path2, path3) | Self::Rgba16Rgba8x2(path1, path2, path3) => {
+                path1.resolve_paths(context)?;
+                path2.resolve_paths(context)?;
+                path3.resolve_paths(context)
+            }
+            Self::Rgba8x4(path1, path2, path3, path4) => {
+                path1.resolve_paths(context)?;
+                path2.resolve_paths(context)?;
+                path3.resolve_paths(context)?;
+                path4.resolve_paths(context)
+            }
+            Self::Layered(layers) => layers.resolve_paths(context),
+            Self::Auto(paths) => paths.resolve_paths(context),
+        }
+    }
 }
 
 impl UnverifiedLosslessImagePath {
@@ -55,6 +172,14 @@ impl UnverifiedLosslessImagePath {
                 paths_iter.next().unwrap().into(),
                 paths_iter.next().unwrap().into(),
             ),
+            ImageFormat::Layered(format) => Self::Layered(
+                format
+                    .layers()
+                    .map(|layer| {
+                        UnverifiedImageLayerPath::new(layer, paths_iter.next().unwrap().into())
+                    })
+                    .collect(),
+            ),
         }
     }
 
@@ -62,8 +187,8 @@ impl UnverifiedLosslessImagePath {
         Self::from_raw(format, paths.iter().map(|path| String::from(path.as_ref())))
     }
 
-    pub fn format(&self) -> ImageFormat {
-        match self {
+    pub fn format(&self) -> Result<ImageFormat, Box<dyn Error>> {
+        Ok(match self {
             Self::Rgba8(..) => ImageFormat::Rgba8,
             Self::Rgba8x2(..) => ImageFormat::Rgba8x2,
             Self::Rgba8x3(..) => ImageFormat::Rgba8x3,
@@ -72,10 +197,32 @@ impl UnverifiedLosslessImagePath {
             Self::Rgba16x2(..) => ImageFormat::Rgba16x2,
             Self::Rgba16Rgba8(..) => ImageFormat::Rgba16Rgba8,
             Self::Rgba16Rgba8x2(..) => ImageFormat::Rgba16Rgba8x2,
-        }
+            Self::Layered(layers) => ImageFormat::Layered(layered_format(layers)?),
+            Self::Auto(paths) => ImageFormat::detect(paths)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutputPathCountError {
+    input_format: ImageFormat,
+    path_count: usize,
+}
+
+impl fmt::Display for OutputPathCountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the output image has the format of the input image, {}, which needs {} paths, but {} paths were provided",
+            self.input_format,
+            self.input_format.layers().len(),
+            self.path_count
+        )
     }
 }
 
+impl Error for OutputPathCountError {}
+
 fn check_dimensions_match2<T: AsRef<Path>, U: AsRef<Path>>(
     image_path1: &T,
     image_path2: &U,
@@ -111,7 +258,7 @@ fn check_dimensions_match4<T: AsRef<Path>, U: AsRef<Path>, V: AsRef<Path>, W: As
     check_dimensions_match2(image_path1, image_path4)
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LosslessImagePath {
     Rgba8(String),
     Rgba8x2(String, String),
@@ -121,6 +268,7 @@ pub enum LosslessImagePath {
     Rgba16x2(String, String),
     Rgba16Rgba8(String, String),
     Rgba16Rgba8x2(String, String, String),
+    Layered(LayeredImageFormat, Vec<String>),
 }
 
 impl LosslessImagePath {
@@ -134,6 +282,7 @@ impl LosslessImagePath {
             Self::Rgba16x2(..) => ImageFormat::Rgba16x2,
             Self::Rgba16Rgba8(..) => ImageFormat::Rgba16Rgba8,
             Self::Rgba16Rgba8x2(..) => ImageFormat::Rgba16Rgba8x2,
+            Self::Layered(format, ..) => ImageFormat::Layered(*format),
         }
     }
 
@@ -149,6 +298,23 @@ impl LosslessImagePath {
             Self::Rgba16x2(path1, path2) => vec![path1, path2],
             Self::Rgba16Rgba8(path1, path2) => vec![path1, path2],
             Self::Rgba16Rgba8x2(path1, path2, path3) => vec![path1, path2, path3],
+            Self::Layered(_, paths) => paths.iter().collect(),
+        }
+    }
+
+    pub fn to_vec_mut(&mut self) -> Vec<&mut String> {
+        match self {
+            Self::Rgba8(path) => vec![path],
+            Self::Rgba8x2(path1, path2) => vec![path1, path2],
+            Self::Rgba8x3(path1, path2, path3) => vec![path1, path2, path3],
+            Self::Rgba8x4(path1, path2, path3, path4) => {
+                vec![path1, path2, path3, path4]
+            }
+            Self::Rgba16(path) => vec![path],
+            Self::Rgba16x2(path1, path2) => vec![path1, path2],
+            Self::Rgba16Rgba8(path1, path2) => vec![path1, path2],
+            Self::Rgba16Rgba8x2(path1, path2, path3) => vec![path1, path2, path3],
+            Self::Layered(_, paths) => paths.iter_mut().collect(),
         }
     }
 
@@ -219,11 +385,36 @@ impl LosslessImagePath {
                 let dimensions = check_dimensions_match3(&path1, &path2, &path3)?;
                 (Self::Rgba16Rgba8x2(path1, path2, path3), dimensions)
             }
+            UnverifiedLosslessImagePath::Layered(unverified_layers) => {
+                let format = layered_format(&unverified_layers)?;
+                let paths = unverified_layers
+                    .into_iter()
+                    .map(|layer| io::convert_and_check_input_file_path(layer.into_path()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let dimensions = ImageDimensions::from_image_path(&paths[0])?;
+                for path in paths.iter().skip(1) {
+                    check_dimensions_match2(&paths[0], path)?;
+                }
+                (Self::Layered(format, paths), dimensions)
+            }
+            UnverifiedLosslessImagePath::Auto(unverified_paths) => {
+                let paths = unverified_paths
+                    .into_iter()
+                    .map(io::convert_and_check_input_file_path)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let format = ImageFormat::detect(&paths)?;
+                Self::from_input_path(UnverifiedLosslessImagePath::from_raw(format, paths))?
+            }
         })
     }
 
-    pub fn from_output_path(path_no_extension: UnverifiedLosslessImagePath) -> Self {
-        match path_no_extension {
+    /// Converts an output path, where an output path with the `Auto` format
+    /// takes the format of the input image
+    pub fn from_output_path(
+        path_no_extension: UnverifiedLosslessImagePath,
+        input_format: ImageFormat,
+    ) -> Result<Self, Box<dyn Error>> {
+        Ok(match path_no_extension {
             UnverifiedLosslessImagePath::Rgba8(unverified_path) => {
                 Self::Rgba8(io::convert_path_separators(unverified_path))
             }
@@ -277,7 +468,26 @@ impl LosslessImagePath {
                 io::convert_path_separators(unverified_path2),
                 io::convert_path_separators(unverified_path3),
             ),
-        }
+            UnverifiedLosslessImagePath::Layered(unverified_layers) => Self::Layered(
+                layered_format(&unverified_layers)?,
+                unverified_layers
+                    .into_iter()
+                    .map(|layer| io::convert_path_separators(layer.into_path()))
+                    .collect(),
+            ),
+            UnverifiedLosslessImagePath::Auto(unverified_paths) => {
+                if unverified_paths.len() != input_format.layers().len() {
+                    return Err(Box::new(OutputPathCountError {
+                        input_format,
+                        path_count: unverified_paths.len(),
+                    }));
+                }
+                Self::from_output_path(
+                    UnverifiedLosslessImagePath::from_raw(input_format, unverified_paths),
+                    input_format,
+                )?
+            }
+        })
     }
 }
 
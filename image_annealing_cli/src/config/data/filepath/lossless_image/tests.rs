@@ -1,4 +1,4 @@
-use super::UnverifiedLosslessImagePath;
+use super::{UnverifiedImageLayerPath, UnverifiedLosslessImagePath};
 
 fn existing_rgba8_path1() -> String {
     test_util::make_test_data_path_string(["image", "image", "stripes.png"])
@@ -69,6 +69,24 @@ fn valid_rgba16_rgba8x2() -> UnverifiedLosslessImagePath {
     )
 }
 
+fn valid_layered() -> UnverifiedLosslessImagePath {
+    UnverifiedLosslessImagePath::Layered(vec![
+        UnverifiedImageLayerPath::Rgba16(existing_rgba16_path1()),
+        UnverifiedImageLayerPath::Rgba8(existing_rgba8_path1()),
+        UnverifiedImageLayerPath::Rgba16(existing_rgba16_path2()),
+    ])
+}
+
+fn layered_format() -> image_annealing::compute::format::LayeredImageFormat {
+    use image_annealing::compute::format::{ImageLayerFormat, LayeredImageFormat};
+    LayeredImageFormat::new(&[
+        ImageLayerFormat::Rgba16,
+        ImageLayerFormat::Rgba8,
+        ImageLayerFormat::Rgba16,
+    ])
+    .unwrap()
+}
+
 mod unverified_lossless_image_path {
     use super::super::UnverifiedLosslessImagePath;
     use image_annealing::compute::format::ImageFormat;
@@ -178,6 +196,22 @@ mod unverified_lossless_image_path {
         }
     }
 
+    #[test]
+    fn from_raw_layered() {
+        let paths = &[
+            super::existing_rgba16_path1(),
+            super::existing_rgba8_path1(),
+            super::existing_rgba16_path2(),
+        ];
+        assert_eq!(
+            UnverifiedLosslessImagePath::from_raw(
+                ImageFormat::Layered(super::layered_format()),
+                paths
+            ),
+            super::valid_layered()
+        );
+    }
+
     #[test]
     fn from_raw_clone() {
         let paths = &["1"];
@@ -189,37 +223,52 @@ mod unverified_lossless_image_path {
     }
 
     mod format {
-        use super::super::super::ImageFormat;
+        use super::super::super::{ImageFormat, ImageLayerFormat, LayeredImageFormat};
 
         #[test]
         fn rgba8() {
-            assert_eq!(super::super::valid_rgba8().format(), ImageFormat::Rgba8);
+            assert_eq!(
+                super::super::valid_rgba8().format().unwrap(),
+                ImageFormat::Rgba8
+            );
         }
 
         #[test]
         fn rgba8x2() {
-            assert_eq!(super::super::valid_rgba8x2().format(), ImageFormat::Rgba8x2);
+            assert_eq!(
+                super::super::valid_rgba8x2().format().unwrap(),
+                ImageFormat::Rgba8x2
+            );
         }
 
         #[test]
         fn rgba8x3() {
-            assert_eq!(super::super::valid_rgba8x3().format(), ImageFormat::Rgba8x3);
+            assert_eq!(
+                super::super::valid_rgba8x3().format().unwrap(),
+                ImageFormat::Rgba8x3
+            );
         }
 
         #[test]
         fn rgba8x4() {
-            assert_eq!(super::super::valid_rgba8x4().format(), ImageFormat::Rgba8x4);
+            assert_eq!(
+                super::super::valid_rgba8x4().format().unwrap(),
+                ImageFormat::Rgba8x4
+            );
         }
 
         #[test]
         fn rgba16() {
-            assert_eq!(super::super::valid_rgba16().format(), ImageFormat::Rgba16);
+            assert_eq!(
+                super::super::valid_rgba16().format().unwrap(),
+                ImageFormat::Rgba16
+            );
         }
 
         #[test]
         fn rgba16x2() {
             assert_eq!(
-                super::super::valid_rgba16x2().format(),
+                super::super::valid_rgba16x2().format().unwrap(),
                 ImageFormat::Rgba16x2
             );
         }
@@ -227,7 +276,7 @@ mod unverified_lossless_image_path {
         #[test]
         fn rgba16_rgba8() {
             assert_eq!(
-                super::super::valid_rgba16_rgba8().format(),
+                super::super::valid_rgba16_rgba8().format().unwrap(),
                 ImageFormat::Rgba16Rgba8
             );
         }
@@ -235,10 +284,50 @@ mod unverified_lossless_image_path {
         #[test]
         fn rgba16_rgba8x2() {
             assert_eq!(
-                super::super::valid_rgba16_rgba8x2().format(),
+                super::super::valid_rgba16_rgba8x2().format().unwrap(),
                 ImageFormat::Rgba16Rgba8x2
             );
         }
+
+        #[test]
+        fn layered() {
+            assert_eq!(
+                super::super::valid_layered().format().unwrap(),
+                ImageFormat::Layered(super::super::layered_format())
+            );
+        }
+
+        #[test]
+        fn layered_empty() {
+            test_util::assert_error_contains(
+                super::super::super::UnverifiedLosslessImagePath::Layered(Vec::new()).format(),
+                "a layered image must have at least one layer",
+            );
+        }
+
+        #[test]
+        fn auto() {
+            assert_eq!(
+                super::super::super::UnverifiedLosslessImagePath::Auto(vec![
+                    super::super::existing_rgba8_path1(),
+                    super::super::existing_rgba16_path1(),
+                ])
+                .format()
+                .unwrap(),
+                ImageFormat::Layered(
+                    LayeredImageFormat::new(&[ImageLayerFormat::Rgba8, ImageLayerFormat::Rgba16])
+                        .unwrap()
+                )
+            );
+        }
+
+        #[test]
+        fn auto_empty() {
+            test_util::assert_error_contains(
+                super::super::super::UnverifiedLosslessImagePath::Auto(Vec::new()).format(),
+                "a layered image must have at least one layer",
+            );
+        }
     }
 }
 
@@ -296,6 +385,17 @@ mod lossless_image_path {
         )
     }
 
+    fn valid_layered() -> LosslessImagePath {
+        LosslessImagePath::Layered(
+            super::layered_format(),
+            vec![
+                super::existing_rgba16_path1(),
+                super::existing_rgba8_path1(),
+                super::existing_rgba16_path2(),
+            ],
+        )
+    }
+
     mod format {
         use super::super::super::ImageFormat;
 
@@ -344,6 +444,14 @@ mod lossless_image_path {
                 ImageFormat::Rgba16Rgba8x2
             );
         }
+
+        #[test]
+        fn layered() {
+            assert_eq!(
+                super::valid_layered().format(),
+                ImageFormat::Layered(super::super::layered_format())
+            );
+        }
     }
 
     mod to_vec {
@@ -437,6 +545,15 @@ mod lossless_image_path {
                 paths.iter().collect::<Vec<&String>>()
             );
         }
+
+        #[test]
+        fn layered() {
+            let paths = vec![String::from("1"), String::from("2"), String::from("3")];
+            assert_eq!(
+                LosslessImagePath::Layered(super::super::layered_format(), paths.clone()).to_vec(),
+                paths.iter().collect::<Vec<&String>>()
+            );
+        }
     }
 
     mod from_input_path {
@@ -570,6 +687,36 @@ mod lossless_image_path {
                 );
                 Ok(())
             }
+
+            #[test]
+            fn layered() -> Result<(), Box<dyn Error>> {
+                assert_eq!(
+                    LosslessImagePath::from_input_path(super::super::super::valid_layered())?,
+                    (
+                        super::super::valid_layered(),
+                        super::valid_image_dimensions()
+                    )
+                );
+                Ok(())
+            }
+
+            #[test]
+            fn auto() -> Result<(), Box<dyn Error>> {
+                assert_eq!(
+                    LosslessImagePath::from_input_path(
+                        super::super::super::super::UnverifiedLosslessImagePath::Auto(vec![
+                            super::super::super::existing_rgba16_path1(),
+                            super::super::super::existing_rgba8_path1(),
+                            super::super::super::existing_rgba8_path2(),
+                        ])
+                    )?,
+                    (
+                        super::super::valid_rgba16_rgba8x2(),
+                        super::valid_image_dimensions()
+                    )
+                );
+                Ok(())
+            }
         }
 
         mod first_image_missing {
@@ -1096,6 +1243,21 @@ mod lossless_image_path {
                     super::mismatch_error_message(),
                 );
             }
+
+            #[test]
+            fn layered() {
+                test_util::assert_error_contains(
+                    LosslessImagePath::from_input_path(UnverifiedLosslessImagePath::Layered(vec![
+                        super::super::super::super::UnverifiedImageLayerPath::Rgba16(
+                            super::super::super::existing_rgba16_path1(),
+                        ),
+                        super::super::super::super::UnverifiedImageLayerPath::Rgba8(
+                            super::large_rgba8_path(),
+                        ),
+                    ])),
+                    super::mismatch_error_message(),
+                );
+            }
         }
 
         mod first_third_mismatch {
@@ -1158,12 +1320,17 @@ mod lossless_image_path {
     }
 
     mod from_output_path {
-        use super::super::super::super::LosslessImagePath;
+        use super::super::super::super::{LosslessImagePath, UnverifiedLosslessImagePath};
+        use image_annealing::compute::format::ImageFormat;
 
         #[test]
         fn rgba8() {
             assert_eq!(
-                LosslessImagePath::from_output_path(super::super::valid_rgba8()),
+                LosslessImagePath::from_output_path(
+                    super::super::valid_rgba8(),
+                    ImageFormat::Rgba8
+                )
+                .unwrap(),
                 super::valid_rgba8()
             );
         }
@@ -1171,7 +1338,11 @@ mod lossless_image_path {
         #[test]
         fn rgba8x2() {
             assert_eq!(
-                LosslessImagePath::from_output_path(super::super::valid_rgba8x2()),
+                LosslessImagePath::from_output_path(
+                    super::super::valid_rgba8x2(),
+                    ImageFormat::Rgba8
+                )
+                .unwrap(),
                 super::valid_rgba8x2()
             );
         }
@@ -1179,7 +1350,11 @@ mod lossless_image_path {
         #[test]
         fn rgba8x3() {
             assert_eq!(
-                LosslessImagePath::from_output_path(super::super::valid_rgba8x3()),
+                LosslessImagePath::from_output_path(
+                    super::super::valid_rgba8x3(),
+                    ImageFormat::Rgba8
+                )
+                .unwrap(),
                 super::valid_rgba8x3()
             );
         }
@@ -1187,7 +1362,11 @@ mod lossless_image_path {
         #[test]
         fn rgba8x4() {
             assert_eq!(
-                LosslessImagePath::from_output_path(super::super::valid_rgba8x4()),
+                LosslessImagePath::from_output_path(
+                    super::super::valid_rgba8x4(),
+                    ImageFormat::Rgba8
+                )
+                .unwrap(),
                 super::valid_rgba8x4()
             );
         }
@@ -1195,7 +1374,11 @@ mod lossless_image_path {
         #[test]
         fn rgba16() {
             assert_eq!(
-                LosslessImagePath::from_output_path(super::super::valid_rgba16()),
+                LosslessImagePath::from_output_path(
+                    super::super::valid_rgba16(),
+                    ImageFormat::Rgba8
+                )
+                .unwrap(),
                 super::valid_rgba16()
             );
         }
@@ -1203,7 +1386,11 @@ mod lossless_image_path {
         #[test]
         fn rgba16x2() {
             assert_eq!(
-                LosslessImagePath::from_output_path(super::super::valid_rgba16x2()),
+                LosslessImagePath::from_output_path(
+                    super::super::valid_rgba16x2(),
+                    ImageFormat::Rgba8
+                )
+                .unwrap(),
                 super::valid_rgba16x2()
             );
         }
@@ -1211,7 +1398,11 @@ mod lossless_image_path {
         #[test]
         fn rgba16_rgba8() {
             assert_eq!(
-                LosslessImagePath::from_output_path(super::super::valid_rgba16_rgba8()),
+                LosslessImagePath::from_output_path(
+                    super::super::valid_rgba16_rgba8(),
+                    ImageFormat::Rgba8
+                )
+                .unwrap(),
                 super::valid_rgba16_rgba8()
             );
         }
@@ -1219,9 +1410,80 @@ mod lossless_image_path {
         #[test]
         fn rgba16_rgba8x2() {
             assert_eq!(
-                LosslessImagePath::from_output_path(super::super::valid_rgba16_rgba8x2()),
+                LosslessImagePath::from_output_path(
+                    super::super::valid_rgba16_rgba8x2(),
+                    ImageFormat::Rgba8
+                )
+                .unwrap(),
                 super::valid_rgba16_rgba8x2()
             );
         }
+
+        #[test]
+        fn layered() {
+            assert_eq!(
+                LosslessImagePath::from_output_path(
+                    super::super::valid_layered(),
+                    ImageFormat::Rgba8
+                )
+                .unwrap(),
+                super::valid_layered()
+            );
+        }
+
+        #[test]
+        fn auto() {
+            assert_eq!(
+                LosslessImagePath::from_output_path(
+                    UnverifiedLosslessImagePath::Auto(vec![
+                        super::super::existing_rgba16_path1(),
+                        super::super::existing_rgba8_path1(),
+                    ]),
+                    ImageFormat::Rgba16Rgba8
+                )
+                .unwrap(),
+                super::valid_rgba16_rgba8()
+            );
+        }
+
+        #[test]
+        fn auto_path_count_mismatch() {
+            test_util::assert_error_contains(
+                LosslessImagePath::from_output_path(
+                    UnverifiedLosslessImagePath::Auto(vec![super::super::existing_rgba8_path1()]),
+                    ImageFormat::Rgba16Rgba8,
+                ),
+                "the output image has the format of the input image, 16-bit RGBA + 8-bit RGBA, which needs 2 paths, but 1 paths were provided",
+            );
+        }
+    }
+}
+
+mod image_conversion_policy {
+    use super::super::ImageConversionPolicy;
+    use image_annealing::compute::format::ImageConversionPolicy as ImageAnnealingImageConversionPolicy;
+
+    #[test]
+    fn default() {
+        assert_eq!(
+            ImageConversionPolicy::default(),
+            ImageConversionPolicy::Strict
+        );
+    }
+
+    #[test]
+    fn into_image_conversion_policy() {
+        assert_eq!(
+            ImageAnnealingImageConversionPolicy::from(ImageConversionPolicy::Strict),
+            ImageAnnealingImageConversionPolicy::Strict
+        );
+        assert_eq!(
+            ImageAnnealingImageConversionPolicy::from(ImageConversionPolicy::Lossless),
+            ImageAnnealingImageConversionPolicy::Lossless
+        );
+        assert_eq!(
+            ImageAnnealingImageConversionPolicy::from(ImageConversionPolicy::AllowNarrowing),
+            ImageAnnealingImageConversionPolicy::AllowNarrowing
+        );
     }
 }
@@ -1,5 +1,9 @@
 mod lossless_image;
+mod resolve;
 mod vector_field;
 
-pub use lossless_image::{LosslessImagePath, UnverifiedLosslessImagePath};
+pub use lossless_image::{
+    ImageConversionPolicy, LosslessImagePath, UnverifiedImageLayerPath, UnverifiedLosslessImagePath,
+};
+pub use resolve::ResolvePaths;
 pub use vector_field::{DisplacementGoalPath, ImagePath, PermutationPath};
@@ -0,0 +1,29 @@
+use image_annealing_cli_util::io::{PathContext, PathVariableError};
+
+/// Configuration data containing paths that are interpreted relative to a configuration file
+pub trait ResolvePaths {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError>;
+}
+
+impl ResolvePaths for String {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        *self = context.resolve(&self)?;
+        Ok(())
+    }
+}
+
+impl<T: ResolvePaths> ResolvePaths for Option<T> {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        if let Some(value) = self.as_mut() {
+            value.resolve_paths(context)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: ResolvePaths> ResolvePaths for Vec<T> {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        self.iter_mut()
+            .try_for_each(|value| value.resolve_paths(context))
+    }
+}
@@ -1,3 +1,6 @@
+use image_annealing::compute::format::{
+    ImageFileWriterSaveResult, RunMetadata, VectorFieldFormat, VectorFieldImageBuffer,
+};
 use image_annealing::ImageDimensions;
 use image_annealing_cli_util::io;
 use std::error::Error;
@@ -18,13 +21,36 @@ where
         unverified_path: T,
     ) -> Result<(Self, ImageDimensions), Box<dyn Error>> {
         let path = io::convert_and_check_input_file_path(unverified_path)?;
-        let dimensions = ImageDimensions::from_image_path(&path)?;
+        let dimensions = VectorFieldFormat::from_path_or_default(&path).dimensions(&path)?;
         Ok((Self::from_raw(path), dimensions))
     }
 
     fn from_output_path<T: AsRef<str>>(path_no_extension: T) -> Self {
         Self::from_raw(io::convert_path_separators(path_no_extension))
     }
+
+    /// The file format selected by the extension of the path, if any,
+    /// or the 8-bit RGBA PNG format otherwise
+    fn format(&self) -> VectorFieldFormat {
+        VectorFieldFormat::from_path_or_default(self)
+    }
+
+    fn path_no_extension(&self) -> &str {
+        self.format().strip_extension(self.as_ref())
+    }
+
+    /// Saves a vector field with metadata embedded, if the file format can store metadata
+    fn save_vector_field<T: AsRef<VectorFieldImageBuffer>>(
+        &self,
+        vector_field: &T,
+        suffix: &str,
+        metadata: &RunMetadata,
+    ) -> ImageFileWriterSaveResult {
+        let format = self.format();
+        let output_path = format.make_filename(format!("{}{}", self.path_no_extension(), suffix));
+        metadata.write_embedded(format.encode(vector_field.as_ref())?, &output_path)?;
+        Ok(output_path)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
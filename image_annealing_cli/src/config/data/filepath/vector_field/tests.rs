@@ -87,6 +87,9 @@ mod image_path {
 
 mod permutation_path {
     use super::super::{ImagePath, PermutationPath};
+    use image_annealing::compute::format::{ImageFileReader, RunMetadata, VectorFieldFormat};
+    use image_annealing::{CandidatePermutation, ImageDimensions, VectorField};
+    use std::error::Error;
     use std::path::Path;
 
     #[test]
@@ -123,6 +126,45 @@ mod permutation_path {
             path
         );
     }
+
+    #[test]
+    fn format() {
+        for (path, format, path_no_extension) in [
+            ("a/b", VectorFieldFormat::Rgba8Png, "a/b"),
+            ("a/b.png", VectorFieldFormat::Rgba8Png, "a/b"),
+            ("a/b.rg16.png", VectorFieldFormat::Rg16Png, "a/b"),
+            ("a/b.npy", VectorFieldFormat::Npy, "a/b"),
+            ("a/b.raw", VectorFieldFormat::Raw, "a/b"),
+        ] {
+            let path = PermutationPath::from_raw_clone(path);
+            assert_eq!(path.format(), format);
+            assert_eq!(path.path_no_extension(), path_no_extension);
+        }
+    }
+
+    #[test]
+    fn save_vector_field_and_from_input_path() -> Result<(), Box<dyn Error>> {
+        let dimensions = ImageDimensions::try_new(3, 2)?;
+        let permutation = CandidatePermutation::identity(&dimensions);
+        let path = PermutationPath::from_output_path(test_util::make_test_output_path_string([
+            "config_permutation_path_save_vector_field.npy",
+        ]));
+        let output_path =
+            path.save_vector_field(&permutation, "_suffix", &RunMetadata::new("Test"))?;
+        assert_eq!(
+            output_path,
+            test_util::make_test_output_path([
+                "config_permutation_path_save_vector_field_suffix.npy"
+            ])
+        );
+        let (input_path, input_dimensions) =
+            PermutationPath::from_input_path(output_path.to_str().unwrap())?;
+        assert_eq!(input_path.format(), VectorFieldFormat::Npy);
+        assert_eq!(input_dimensions, dimensions);
+        assert_eq!(CandidatePermutation::load(&input_path)?, permutation);
+        std::fs::remove_file(output_path)?;
+        Ok(())
+    }
 }
 
 mod displacement_goal_path {
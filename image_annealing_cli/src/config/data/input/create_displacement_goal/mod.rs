@@ -1,25 +1,42 @@
 use super::super::{
-    DisplacementGoalPath, ImagePath, LosslessImagePath, PermutationPath,
-    UnverifiedImageDimensionsConfig, UnverifiedLosslessImagePath,
+    DisplacementGoalPath, ImageConversionPolicy, ImagePath, LosslessImagePath, PermutationPath,
+    ResolvePaths, UnverifiedImageDimensionsConfig, UnverifiedLosslessImagePath,
 };
 use image_annealing::ImageDimensions;
+use image_annealing_cli_util::config_file::ConfigFieldContext;
+use image_annealing_cli_util::io::{PathContext, PathVariableError};
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::error::Error;
 use std::fmt;
 
-#[derive(Default, Deserialize)]
+#[derive(Default, Deserialize, JsonSchema)]
 pub struct UnverifiedCreateDisplacementGoalInputDataConfig {
-    displacement_goal: Option<String>,
-    candidate_permutation: Option<String>,
-    image: Option<UnverifiedLosslessImagePath>,
+    pub displacement_goal: Option<String>,
+    pub candidate_permutation: Option<String>,
+    pub image: Option<UnverifiedLosslessImagePath>,
+    pub image_conversion: Option<ImageConversionPolicy>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub enum UnverifiedCreateDisplacementGoalInputConfig {
     ImageDimensions(UnverifiedImageDimensionsConfig),
     Input(UnverifiedCreateDisplacementGoalInputDataConfig),
 }
 
+impl ResolvePaths for UnverifiedCreateDisplacementGoalInputConfig {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        match self {
+            Self::ImageDimensions(_) => Ok(()),
+            Self::Input(data) => {
+                data.displacement_goal.resolve_paths(context)?;
+                data.candidate_permutation.resolve_paths(context)?;
+                data.image.resolve_paths(context)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct NoInputDataError;
 
@@ -43,11 +60,12 @@ fn check_dimensions<T: IntoIterator<Item = ImageDimensions>>(
     }
 }
 
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct CreateDisplacementGoalInputConfig {
     pub displacement_goal: Option<DisplacementGoalPath>,
     pub candidate_permutation: Option<PermutationPath>,
     pub image: Option<LosslessImagePath>,
+    pub image_conversion: ImageConversionPolicy,
 }
 
 impl CreateDisplacementGoalInputConfig {
@@ -57,7 +75,12 @@ impl CreateDisplacementGoalInputConfig {
         Ok(match config {
             UnverifiedCreateDisplacementGoalInputConfig::ImageDimensions(
                 image_dimensions_config,
-            ) => (Default::default(), image_dimensions_config.try_into()?),
+            ) => (
+                Default::default(),
+                image_dimensions_config
+                    .try_into()
+                    .in_field("ImageDimensions")?,
+            ),
             UnverifiedCreateDisplacementGoalInputConfig::Input(value) => {
                 let mut displacement_goal: Option<DisplacementGoalPath> = None;
                 let mut candidate_permutation: Option<PermutationPath> = None;
@@ -65,19 +88,25 @@ impl CreateDisplacementGoalInputConfig {
                 let mut dimensions: Vec<ImageDimensions> = Vec::new();
                 if let Some(path) = value.displacement_goal {
                     let (displacement_goal_checked, displacement_goal_dimensions) =
-                        DisplacementGoalPath::from_input_path(path)?;
+                        DisplacementGoalPath::from_input_path(path)
+                            .in_field("displacement_goal")
+                            .in_field("Input")?;
                     displacement_goal = Some(displacement_goal_checked);
                     dimensions.push(displacement_goal_dimensions);
                 }
                 if let Some(path) = value.candidate_permutation {
                     let (candidate_permutation_checked, permutation_dimensions) =
-                        PermutationPath::from_input_path(path)?;
+                        PermutationPath::from_input_path(path)
+                            .in_field("candidate_permutation")
+                            .in_field("Input")?;
                     candidate_permutation = Some(candidate_permutation_checked);
                     dimensions.push(permutation_dimensions);
                 }
                 if let Some(path) = value.image {
                     let (image_checked, image_dimensions) =
-                        LosslessImagePath::from_input_path(path)?;
+                        LosslessImagePath::from_input_path(path)
+                            .in_field("image")
+                            .in_field("Input")?;
                     image = Some(image_checked);
                     dimensions.push(image_dimensions);
                 }
@@ -86,8 +115,9 @@ impl CreateDisplacementGoalInputConfig {
                         displacement_goal,
                         candidate_permutation,
                         image,
+                        image_conversion: value.image_conversion.unwrap_or_default(),
                     },
-                    check_dimensions(dimensions)?,
+                    check_dimensions(dimensions).in_field("Input")?,
                 )
             }
         })
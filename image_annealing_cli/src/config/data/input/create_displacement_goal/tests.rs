@@ -2,8 +2,9 @@ mod create_displacement_goal_input_config {
 
     mod from_config {
         use super::super::super::{
-            CreateDisplacementGoalInputConfig, DisplacementGoalPath, ImagePath, LosslessImagePath,
-            PermutationPath, UnverifiedCreateDisplacementGoalInputConfig,
+            CreateDisplacementGoalInputConfig, DisplacementGoalPath, ImageConversionPolicy,
+            ImagePath, LosslessImagePath, PermutationPath,
+            UnverifiedCreateDisplacementGoalInputConfig,
             UnverifiedCreateDisplacementGoalInputDataConfig, UnverifiedImageDimensionsConfig,
             UnverifiedLosslessImagePath,
         };
@@ -40,6 +41,7 @@ mod create_displacement_goal_input_config {
                     image: Some(UnverifiedLosslessImagePath::Rgba8(String::from(
                         "../test_data/image/image/stripes.png",
                     ))),
+                    image_conversion: Some(ImageConversionPolicy::AllowNarrowing),
                 },
             );
             let (displacement_goal_path, image_dimensions) =
@@ -63,6 +65,7 @@ mod create_displacement_goal_input_config {
                 displacement_goal: Some(displacement_goal_path),
                 candidate_permutation: Some(candidate_permutation_path),
                 image: Some(image_path),
+                image_conversion: ImageConversionPolicy::AllowNarrowing,
             };
             assert_eq!(
                 CreateDisplacementGoalInputConfig::from_config(unverified_config)?,
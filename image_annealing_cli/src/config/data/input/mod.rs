@@ -1,6 +1,11 @@
 mod create_displacement_goal;
+mod permute;
 
 pub use create_displacement_goal::{
     CreateDisplacementGoalInputConfig, UnverifiedCreateDisplacementGoalInputConfig,
     UnverifiedCreateDisplacementGoalInputDataConfig,
 };
+pub use permute::{
+    permute_images_from_config, PermuteImageConfig, PermuteInputError, UnverifiedPermuteImageInput,
+    UnverifiedPermuteImageOutput,
+};
@@ -0,0 +1,493 @@
+use super::super::{
+    check_output_directory, ImageConversionPolicy, LosslessImagePath, ResolvePaths,
+    UnverifiedImageLayerPath, UnverifiedLosslessImagePath,
+};
+use image_annealing::compute::format::{ImageFileWriter, Rgba8Image};
+use image_annealing::{DimensionsMismatchError, ImageDimensions};
+use image_annealing_cli_util::config_file::ConfigFieldContext;
+use image_annealing_cli_util::io::{self, PathContext, PathVariableError};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::de::value::StringDeserializer;
+use serde::de::{DeserializeSeed, Deserializer, EnumAccess, VariantAccess, Visitor};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+/// The input images of a permute operation, which are permuted by the same permutation
+#[derive(Debug, PartialEq, Eq)]
+pub enum UnverifiedPermuteImageInput {
+    /// A single image, given by its path alone
+    Image(UnverifiedLosslessImagePath),
+    List(Vec<UnverifiedLosslessImagePath>),
+    Glob(UnverifiedImageLayerPath),
+}
+
+/// The output paths of a permute operation's images, which correspond to its input images
+#[derive(Debug, PartialEq, Eq)]
+pub enum UnverifiedPermuteImageOutput {
+    /// The path of a single image, given by itself
+    Image(UnverifiedLosslessImagePath),
+    List(Vec<UnverifiedLosslessImagePath>),
+    /// The directory in which the images matching a `Glob` pattern are saved
+    /// under the names of the input images
+    Directory(String),
+}
+
+/// The variants of the permute operation's images that describe more than one image
+#[derive(Deserialize, JsonSchema)]
+enum UnverifiedPermuteImageInputBatch {
+    List(Vec<UnverifiedLosslessImagePath>),
+    Glob(UnverifiedImageLayerPath),
+}
+
+#[derive(Deserialize, JsonSchema)]
+enum UnverifiedPermuteImageOutputBatch {
+    List(Vec<UnverifiedLosslessImagePath>),
+    Directory(String),
+}
+
+/// The JSON Schema of a permute operation's images, which are either one image path
+/// or a batch of images
+#[derive(JsonSchema)]
+#[serde(untagged)]
+#[allow(dead_code)]
+enum UnverifiedPermuteImages<B> {
+    Image(UnverifiedLosslessImagePath),
+    Batch(B),
+}
+
+impl<'de> Deserialize<'de> for UnverifiedPermuteImageInput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(
+            match deserialize_images::<D, UnverifiedPermuteImageInputBatch>(deserializer)? {
+                UnverifiedPermuteImages::Image(path) => Self::Image(path),
+                UnverifiedPermuteImages::Batch(UnverifiedPermuteImageInputBatch::List(paths)) => {
+                    Self::List(paths)
+                }
+                UnverifiedPermuteImages::Batch(UnverifiedPermuteImageInputBatch::Glob(pattern)) => {
+                    Self::Glob(pattern)
+                }
+            },
+        )
+    }
+}
+
+impl JsonSchema for UnverifiedPermuteImageInput {
+    fn schema_name() -> String {
+        String::from("UnverifiedPermuteImageInput")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        UnverifiedPermuteImages::<UnverifiedPermuteImageInputBatch>::json_schema(gen)
+    }
+}
+
+impl<'de> Deserialize<'de> for UnverifiedPermuteImageOutput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(
+            match deserialize_images::<D, UnverifiedPermuteImageOutputBatch>(deserializer)? {
+                UnverifiedPermuteImages::Image(path) => Self::Image(path),
+                UnverifiedPermuteImages::Batch(UnverifiedPermuteImageOutputBatch::List(paths)) => {
+                    Self::List(paths)
+                }
+                UnverifiedPermuteImages::Batch(UnverifiedPermuteImageOutputBatch::Directory(
+                    directory,
+                )) => Self::Directory(directory),
+            },
+        )
+    }
+}
+
+impl JsonSchema for UnverifiedPermuteImageOutput {
+    fn schema_name() -> String {
+        String::from("UnverifiedPermuteImageOutput")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        UnverifiedPermuteImages::<UnverifiedPermuteImageOutputBatch>::json_schema(gen)
+    }
+}
+
+/// The names of the variants of the permute operation's images that describe more than one image,
+/// none of which is the name of a format of an image path
+const BATCH_VARIANTS: &[&str] = &["List", "Glob", "Directory"];
+
+/// Deserializes either a batch of images or an image path, depending on the name of the variant,
+/// so that errors in an image path are reported as they would be for any other image path
+fn deserialize_images<'de, D: Deserializer<'de>, B: Deserialize<'de>>(
+    deserializer: D,
+) -> Result<UnverifiedPermuteImages<B>, D::Error> {
+    deserializer.deserialize_enum(
+        "UnverifiedPermuteImages",
+        BATCH_VARIANTS,
+        PermuteImagesVisitor(PhantomData),
+    )
+}
+
+struct PermuteImagesVisitor<B>(PhantomData<B>);
+
+impl<'de, B: Deserialize<'de>> Visitor<'de> for PermuteImagesVisitor<B> {
+    type Value = UnverifiedPermuteImages<B>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an image path, or a batch of images")
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        let (name, value) = data.variant::<String>()?;
+        let variant = ReadVariantDeserializer { name, value };
+        if BATCH_VARIANTS.contains(&variant.name.as_str()) {
+            B::deserialize(variant).map(UnverifiedPermuteImages::Batch)
+        } else {
+            UnverifiedLosslessImagePath::deserialize(variant).map(UnverifiedPermuteImages::Image)
+        }
+    }
+}
+
+/// Presents a variant whose name has already been read as an enumeration variant
+struct ReadVariantDeserializer<A> {
+    name: String,
+    value: A,
+}
+
+impl<'de, A: VariantAccess<'de>> Deserializer<'de> for ReadVariantDeserializer<A> {
+    type Error = A::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> EnumAccess<'de> for ReadVariantDeserializer<A> {
+    type Error = A::Error;
+    type Variant = A;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(StringDeserializer::<A::Error>::new(self.name))?;
+        Ok((variant, self.value))
+    }
+}
+
+impl ResolvePaths for UnverifiedPermuteImageInput {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        match self {
+            Self::Image(path) => path.resolve_paths(context),
+            Self::List(paths) => paths.resolve_paths(context),
+            Self::Glob(pattern) => {
+                let format = pattern.format();
+                *pattern =
+                    UnverifiedImageLayerPath::new(format, context.resolve_pattern(pattern.path())?);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ResolvePaths for UnverifiedPermuteImageOutput {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        match self {
+            Self::Image(path) => path.resolve_paths(context),
+            Self::List(paths) => paths.resolve_paths(context),
+            Self::Directory(directory) => directory.resolve_paths(context),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum PermuteInputError {
+    Empty,
+    /// The output paths are not of the kind that corresponds to the input images
+    OutputMismatch,
+    /// The numbers of input images and output paths in `List`s differ
+    LengthMismatch {
+        inputs: usize,
+        outputs: usize,
+    },
+    DuplicateOutput(String),
+    OverwritesInput(String),
+}
+
+impl fmt::Display for PermuteInputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "there are no images to permute"),
+            Self::OutputMismatch => write!(
+                f,
+                "a single input image needs a single output path, a List of input images needs a List of output paths, and a Glob pattern needs an output Directory"
+            ),
+            Self::LengthMismatch { inputs, outputs } => write!(
+                f,
+                "there are {} input images, but {} output paths",
+                inputs, outputs
+            ),
+            Self::DuplicateOutput(path) => write!(
+                f,
+                "more than one image would be written to output path '{}'",
+                path
+            ),
+            Self::OverwritesInput(path) => write!(
+                f,
+                "output path '{}' would overwrite an input image",
+                path
+            ),
+        }
+    }
+}
+
+impl Error for PermuteInputError {}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermuteImageConfig {
+    pub original_image: LosslessImagePath,
+    pub original_image_conversion: ImageConversionPolicy,
+    pub permuted_image_output_path_no_extension: LosslessImagePath,
+}
+
+/// The position of an image in the configuration of a permute operation,
+/// which is where errors in the image are reported
+#[derive(Clone, Copy)]
+enum ImagePosition {
+    Image,
+    List(usize),
+    Glob,
+}
+
+impl ImagePosition {
+    fn in_input<T, E: Into<Box<dyn Error>>>(
+        self,
+        result: Result<T, E>,
+    ) -> Result<T, Box<dyn Error>> {
+        let result = result.map_err(Into::into);
+        match self {
+            Self::Image => result,
+            Self::List(i) => result.at_index(i).in_field("List"),
+            // Images matched by a glob pattern are not listed in the configuration file,
+            // so errors in them are reported at the pattern
+            Self::Glob => result.in_field("Glob"),
+        }
+        .in_field("original_image")
+    }
+
+    fn in_output<T, E: Into<Box<dyn Error>>>(
+        self,
+        result: Result<T, E>,
+    ) -> Result<T, Box<dyn Error>> {
+        let result = result.map_err(Into::into);
+        match self {
+            Self::Image => result,
+            Self::List(i) => result.at_index(i).in_field("List"),
+            Self::Glob => result.in_field("Directory"),
+        }
+        .in_field("permuted_image_output_path_no_extension")
+    }
+}
+
+/// An input image and its output path, which have yet to be verified
+struct UnverifiedPermuteImage {
+    original_image: UnverifiedLosslessImagePath,
+    permuted_image_output_path_no_extension: UnverifiedLosslessImagePath,
+    position: ImagePosition,
+}
+
+fn expand_glob(
+    pattern: UnverifiedImageLayerPath,
+    output_directory: String,
+    create_directories: bool,
+) -> Result<Vec<UnverifiedPermuteImage>, Box<dyn Error>> {
+    let output_directory = io::convert_path_separators(output_directory);
+    ImagePosition::Glob.in_output(check_output_directory(
+        &output_directory,
+        create_directories,
+    ))?;
+    let format = pattern.format();
+    let mut images = Vec::new();
+    for entry in
+        ImagePosition::Glob.in_input(glob::glob(&io::convert_path_separators(pattern.path())))?
+    {
+        let path = ImagePosition::Glob.in_input(entry)?;
+        if !path.is_file() {
+            continue;
+        }
+        let output_path = match path.file_stem() {
+            Some(stem) => Path::new(&output_directory).join(stem),
+            None => continue,
+        };
+        images.push(UnverifiedPermuteImage {
+            original_image: UnverifiedImageLayerPath::new(
+                format,
+                path.to_string_lossy().into_owned(),
+            )
+            .into(),
+            permuted_image_output_path_no_extension: UnverifiedImageLayerPath::new(
+                format,
+                output_path.to_string_lossy().into_owned(),
+            )
+            .into(),
+            position: ImagePosition::Glob,
+        });
+    }
+    Ok(images)
+}
+
+fn pair_images(
+    original_image: UnverifiedPermuteImageInput,
+    permuted_image_output_path_no_extension: UnverifiedPermuteImageOutput,
+    create_directories: bool,
+) -> Result<Vec<UnverifiedPermuteImage>, Box<dyn Error>> {
+    match (original_image, permuted_image_output_path_no_extension) {
+        (
+            UnverifiedPermuteImageInput::Image(input),
+            UnverifiedPermuteImageOutput::Image(output),
+        ) => Ok(vec![UnverifiedPermuteImage {
+            original_image: input,
+            permuted_image_output_path_no_extension: output,
+            position: ImagePosition::Image,
+        }]),
+        (
+            UnverifiedPermuteImageInput::List(inputs),
+            UnverifiedPermuteImageOutput::List(outputs),
+        ) => {
+            if inputs.len() != outputs.len() {
+                return Err(PermuteInputError::LengthMismatch {
+                    inputs: inputs.len(),
+                    outputs: outputs.len(),
+                })
+                .in_field("List")
+                .in_field("permuted_image_output_path_no_extension");
+            }
+            Ok(inputs
+                .into_iter()
+                .zip(outputs)
+                .enumerate()
+                .map(|(i, (input, output))| UnverifiedPermuteImage {
+                    original_image: input,
+                    permuted_image_output_path_no_extension: output,
+                    position: ImagePosition::List(i),
+                })
+                .collect())
+        }
+        (
+            UnverifiedPermuteImageInput::Glob(pattern),
+            UnverifiedPermuteImageOutput::Directory(output_directory),
+        ) => expand_glob(pattern, output_directory, create_directories),
+        _ => Err(PermuteInputError::OutputMismatch)
+            .in_field("permuted_image_output_path_no_extension"),
+    }
+}
+
+/// Returns the absolute path of an output file, if its directory exists
+fn canonicalize_output_path<P: AsRef<Path>>(path: P) -> Option<PathBuf> {
+    let path = path.as_ref();
+    let directory = match path.parent() {
+        Some(directory) if !directory.as_os_str().is_empty() => directory,
+        _ => Path::new("."),
+    };
+    Some(directory.canonicalize().ok()?.join(path.file_name()?))
+}
+
+fn check_input_not_overwritten(
+    image: &PermuteImageConfig,
+    input_paths: &HashSet<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    for path in image.permuted_image_output_path_no_extension.to_vec() {
+        if matches!(
+            canonicalize_output_path(Rgba8Image::make_filename(path)),
+            Some(output_path) if input_paths.contains(&output_path)
+        ) {
+            return Err(Box::new(PermuteInputError::OverwritesInput(path.clone())));
+        }
+    }
+    Ok(())
+}
+
+fn image_from_config(
+    config: UnverifiedPermuteImage,
+    original_image_conversion: ImageConversionPolicy,
+    permutation_dimensions: &ImageDimensions,
+    output_paths: &mut HashSet<String>,
+) -> Result<PermuteImageConfig, Box<dyn Error>> {
+    let position = config.position;
+    let (original_image, dimensions) =
+        position.in_input(LosslessImagePath::from_input_path(config.original_image))?;
+    if dimensions != *permutation_dimensions {
+        return position.in_input(Err(DimensionsMismatchError::new(
+            dimensions,
+            *permutation_dimensions,
+        )));
+    }
+    let permuted_image_output_path_no_extension =
+        position.in_output(LosslessImagePath::from_output_path(
+            config.permuted_image_output_path_no_extension,
+            original_image.format(),
+        ))?;
+    for path in permuted_image_output_path_no_extension.to_vec() {
+        if !output_paths.insert(path.clone()) {
+            return position.in_output(Err(PermuteInputError::DuplicateOutput(path.clone())));
+        }
+    }
+    Ok(PermuteImageConfig {
+        original_image,
+        original_image_conversion,
+        permuted_image_output_path_no_extension,
+    })
+}
+
+pub fn permute_images_from_config(
+    original_image: UnverifiedPermuteImageInput,
+    original_image_conversion: Option<ImageConversionPolicy>,
+    permuted_image_output_path_no_extension: UnverifiedPermuteImageOutput,
+    permutation_dimensions: &ImageDimensions,
+    create_directories: bool,
+) -> Result<Vec<PermuteImageConfig>, Box<dyn Error>> {
+    let unverified_images = pair_images(
+        original_image,
+        permuted_image_output_path_no_extension,
+        create_directories,
+    )?;
+    if unverified_images.is_empty() {
+        return Err(PermuteInputError::Empty).in_field("original_image");
+    }
+    let original_image_conversion = original_image_conversion.unwrap_or_default();
+    let mut output_paths = HashSet::new();
+    let mut images = Vec::with_capacity(unverified_images.len());
+    let mut positions = Vec::with_capacity(unverified_images.len());
+    for unverified_image in unverified_images {
+        positions.push(unverified_image.position);
+        images.push(image_from_config(
+            unverified_image,
+            original_image_conversion,
+            permutation_dimensions,
+            &mut output_paths,
+        )?);
+    }
+    // Images are permuted while earlier images are saved,
+    // so no output file can replace any input file of a batch
+    if images.len() > 1 {
+        let input_paths = images
+            .iter()
+            .flat_map(|image| image.original_image.to_vec())
+            .filter_map(|path| Path::new(path).canonicalize().ok())
+            .collect::<HashSet<_>>();
+        for (image, position) in images.iter().zip(positions) {
+            position.in_output(check_input_not_overwritten(image, &input_paths))?;
+        }
+    }
+    Ok(images)
+}
+
+#[cfg(test)]
+mod tests;
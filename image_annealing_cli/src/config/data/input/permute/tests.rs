@@ -0,0 +1,292 @@
+mod permute_images_from_config {
+    use super::super::super::super::{
+        ImageConversionPolicy, LosslessImagePath, UnverifiedImageLayerPath,
+        UnverifiedLosslessImagePath,
+    };
+    use super::super::{
+        permute_images_from_config, PermuteImageConfig, UnverifiedPermuteImageInput,
+        UnverifiedPermuteImageOutput,
+    };
+    use image_annealing::ImageDimensions;
+    use std::error::Error;
+
+    fn make_list(paths: &[&str]) -> Vec<UnverifiedLosslessImagePath> {
+        paths
+            .iter()
+            .map(|path| UnverifiedLosslessImagePath::Rgba8(String::from(*path)))
+            .collect()
+    }
+
+    fn make_glob(pattern: &str) -> UnverifiedPermuteImageInput {
+        UnverifiedPermuteImageInput::Glob(UnverifiedImageLayerPath::Rgba8(String::from(pattern)))
+    }
+
+    fn make_directory(directory: &str) -> UnverifiedPermuteImageOutput {
+        UnverifiedPermuteImageOutput::Directory(String::from(directory))
+    }
+
+    #[test]
+    fn valid_image() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            permute_images_from_config(
+                UnverifiedPermuteImageInput::Image(UnverifiedLosslessImagePath::Rgba8(
+                    String::from("../test_data/image/image/stripes.png")
+                )),
+                None,
+                UnverifiedPermuteImageOutput::Image(UnverifiedLosslessImagePath::Rgba8(
+                    String::from("../test_data/image/image/stripes")
+                )),
+                &ImageDimensions::try_new(20, 25)?,
+                false
+            )?,
+            vec![PermuteImageConfig {
+                original_image: LosslessImagePath::Rgba8(test_util::make_test_data_path_string([
+                    "image",
+                    "image",
+                    "stripes.png"
+                ])),
+                original_image_conversion: Default::default(),
+                permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
+                    test_util::make_test_data_path_string(["image", "image", "stripes"])
+                ),
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn valid_list() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            permute_images_from_config(
+                UnverifiedPermuteImageInput::List(make_list(&[
+                    "../test_data/image/image/stripes.png",
+                    "../test_data/image/image/green.png",
+                ])),
+                Some(ImageConversionPolicy::Lossless),
+                UnverifiedPermuteImageOutput::List(make_list(&["stripes_out", "green_out"])),
+                &ImageDimensions::try_new(20, 25)?,
+                false
+            )?,
+            vec![
+                PermuteImageConfig {
+                    original_image: LosslessImagePath::Rgba8(
+                        test_util::make_test_data_path_string(["image", "image", "stripes.png"])
+                    ),
+                    original_image_conversion: ImageConversionPolicy::Lossless,
+                    permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
+                        String::from("stripes_out")
+                    ),
+                },
+                PermuteImageConfig {
+                    original_image: LosslessImagePath::Rgba8(
+                        test_util::make_test_data_path_string(["image", "image", "green.png"])
+                    ),
+                    original_image_conversion: ImageConversionPolicy::Lossless,
+                    permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
+                        String::from("green_out")
+                    ),
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn valid_glob() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            permute_images_from_config(
+                UnverifiedPermuteImageInput::Glob(UnverifiedImageLayerPath::Rgba16(String::from(
+                    "../test_data/image/image/p*.png",
+                ))),
+                None,
+                make_directory("../test_output"),
+                &ImageDimensions::try_new(20, 25)?,
+                false
+            )?,
+            vec![
+                PermuteImageConfig {
+                    original_image: LosslessImagePath::Rgba16(
+                        test_util::make_test_data_path_string(["image", "image", "parque.png"])
+                    ),
+                    original_image_conversion: Default::default(),
+                    permuted_image_output_path_no_extension: LosslessImagePath::Rgba16(
+                        test_util::make_test_output_path_string(["parque"])
+                    ),
+                },
+                PermuteImageConfig {
+                    original_image: LosslessImagePath::Rgba16(
+                        test_util::make_test_data_path_string(["image", "image", "pastel.png"])
+                    ),
+                    original_image_conversion: Default::default(),
+                    permuted_image_output_path_no_extension: LosslessImagePath::Rgba16(
+                        test_util::make_test_output_path_string(["pastel"])
+                    ),
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn empty_list() -> Result<(), Box<dyn Error>> {
+        test_util::assert_error_contains(
+            permute_images_from_config(
+                UnverifiedPermuteImageInput::List(Vec::new()),
+                None,
+                UnverifiedPermuteImageOutput::List(Vec::new()),
+                &ImageDimensions::try_new(20, 25)?,
+                false,
+            ),
+            "there are no images to permute (configuration field `original_image`)",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn empty_glob() -> Result<(), Box<dyn Error>> {
+        test_util::assert_error_contains(
+            permute_images_from_config(
+                make_glob("../test_data/image/image/not_found*.png"),
+                None,
+                make_directory("../test_output"),
+                &ImageDimensions::try_new(20, 25)?,
+                false,
+            ),
+            "there are no images to permute",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn output_mismatch() -> Result<(), Box<dyn Error>> {
+        test_util::assert_error_contains(
+            permute_images_from_config(
+                make_glob("../test_data/image/image/p*.png"),
+                None,
+                UnverifiedPermuteImageOutput::List(make_list(&["parque", "pastel"])),
+                &ImageDimensions::try_new(20, 25)?,
+                false,
+            ),
+            "a Glob pattern needs an output Directory (configuration field `permuted_image_output_path_no_extension`)",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn length_mismatch() -> Result<(), Box<dyn Error>> {
+        test_util::assert_error_contains(
+            permute_images_from_config(
+                UnverifiedPermuteImageInput::List(make_list(&[
+                    "../test_data/image/image/stripes.png",
+                    "../test_data/image/image/green.png",
+                ])),
+                None,
+                UnverifiedPermuteImageOutput::List(make_list(&["stripes_out"])),
+                &ImageDimensions::try_new(20, 25)?,
+                false,
+            ),
+            "there are 2 input images, but 1 output paths (configuration field `permuted_image_output_path_no_extension.List`)",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn glob_output_directory_not_found() -> Result<(), Box<dyn Error>> {
+        test_util::assert_error_contains(
+            permute_images_from_config(
+                make_glob("../test_data/image/image/p*.png"),
+                None,
+                make_directory("../test_output/not_found"),
+                &ImageDimensions::try_new(20, 25)?,
+                false,
+            ),
+            "does not exist",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn glob_output_directory_created() -> Result<(), Box<dyn Error>> {
+        let config = permute_images_from_config(
+            make_glob("../test_data/image/image/p*.png"),
+            None,
+            make_directory("../test_output/not_found"),
+            &ImageDimensions::try_new(20, 25)?,
+            true,
+        )?;
+        assert!(!config.is_empty());
+        assert!(!std::path::Path::new("../test_output/not_found").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn dimensions_mismatch() -> Result<(), Box<dyn Error>> {
+        test_util::assert_error_contains(
+            permute_images_from_config(
+                make_glob("../test_data/image/image/stripes*.png"),
+                None,
+                make_directory("../test_output"),
+                &ImageDimensions::try_new(20, 25)?,
+                false,
+            ),
+            "mismatch in image dimensions, (width, height) = (21, 25) and (width, height) = (20, 25)",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn duplicate_output() -> Result<(), Box<dyn Error>> {
+        test_util::assert_error_contains(
+            permute_images_from_config(
+                UnverifiedPermuteImageInput::List(make_list(&[
+                    "../test_data/image/image/stripes.png",
+                    "../test_data/image/image/green.png",
+                ])),
+                None,
+                UnverifiedPermuteImageOutput::List(make_list(&["out", "out"])),
+                &ImageDimensions::try_new(20, 25)?,
+                false,
+            ),
+            "more than one image would be written to output path 'out' (configuration field `permuted_image_output_path_no_extension.List[1]`)",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn list_overwrites_input() -> Result<(), Box<dyn Error>> {
+        test_util::assert_error_contains(
+            permute_images_from_config(
+                UnverifiedPermuteImageInput::List(make_list(&[
+                    "../test_data/image/image/stripes.png",
+                    "../test_data/image/image/green.png",
+                ])),
+                None,
+                UnverifiedPermuteImageOutput::List(make_list(&[
+                    "stripes_out",
+                    "../test_data/image/image/stripes",
+                ])),
+                &ImageDimensions::try_new(20, 25)?,
+                false,
+            ),
+            "output path '../test_data/image/image/stripes' would overwrite an input image (configuration field `permuted_image_output_path_no_extension.List[1]`)",
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn glob_overwrites_input() -> Result<(), Box<dyn Error>> {
+        test_util::assert_error_contains(
+            permute_images_from_config(
+                UnverifiedPermuteImageInput::Glob(UnverifiedImageLayerPath::Rgba16(String::from(
+                    "../test_data/image/image/p*.png",
+                ))),
+                None,
+                make_directory("../test_data/image/image"),
+                &ImageDimensions::try_new(20, 25)?,
+                false,
+            ),
+            "would overwrite an input image (configuration field `permuted_image_output_path_no_extension.Directory`)",
+        );
+        Ok(())
+    }
+}
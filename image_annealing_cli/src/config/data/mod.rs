@@ -1,30 +1,71 @@
+use image_annealing::image_utils::visualization::VectorFieldVisualizationParameters;
 use image_annealing::{compute, DimensionsMismatchError, ImageDimensions};
+use image_annealing_cli_util::config_file::ConfigFieldContext;
+use image_annealing_cli_util::io::{self, PathContext, PathVariableError};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::de::value::StringDeserializer;
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, IgnoredAny, MapAccess, VariantAccess, Visitor,
+};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::error::Error;
+use std::fmt;
 
+mod checkpoint;
 mod dimension;
+mod experiment;
 mod filepath;
 mod input;
 mod number;
+mod output;
 mod parameters;
+mod workflow;
 
+pub use checkpoint::{
+    SwapCheckpoint, SwapCheckpointConfig, SwapCheckpointError, SwapCheckpointFile,
+    UnverifiedSwapCheckpointConfig,
+};
 pub use dimension::UnverifiedImageDimensionsConfig;
+pub use experiment::{
+    ExperimentAxisKind, ExperimentError, ExperimentRunConfig, UnverifiedExperimentAxis,
+    UnverifiedExperimentSampling, UnverifiedExperimentValues,
+};
 pub use filepath::{
-    DisplacementGoalPath, ImagePath, LosslessImagePath, PermutationPath,
-    UnverifiedLosslessImagePath,
+    DisplacementGoalPath, ImageConversionPolicy, ImagePath, LosslessImagePath, PermutationPath,
+    ResolvePaths, UnverifiedImageLayerPath, UnverifiedLosslessImagePath,
 };
 pub use input::{
-    CreateDisplacementGoalInputConfig, UnverifiedCreateDisplacementGoalInputConfig,
-    UnverifiedCreateDisplacementGoalInputDataConfig,
+    CreateDisplacementGoalInputConfig, PermuteImageConfig, PermuteInputError,
+    UnverifiedCreateDisplacementGoalInputConfig, UnverifiedCreateDisplacementGoalInputDataConfig,
+    UnverifiedPermuteImageInput, UnverifiedPermuteImageOutput,
 };
 pub use number::{
     InvalidNonnegativeProperFractionError, InvalidNonnegativeRationalNumberError,
     NonnegativeProperFraction, NonnegativeRationalNumber,
 };
+pub use output::{
+    check_output_directory, existing_outputs, ExistingOutputPolicy, OutputFiles,
+    OutputPolicyConfig, OutputPolicyError, UnverifiedOutputPolicyConfig,
+};
 pub use parameters::{
-    InvalidIterationCountError, IterationCount, SwapParametersConfig, SwapPass, SwapStopConfig,
-    SwapStopThreshold, UnverifiedIterationCount, UnverifiedSwapParametersConfig,
-    UnverifiedSwapStopConfig, UnverifiedSwapStopThreshold,
+    AnimationFormat, BoundaryMode, IntermediatePermutationCadence, IntermediatePermutationsConfig,
+    InvalidFrameStrideError, InvalidIntermediatePermutationCadenceError,
+    InvalidIterationCountError, InvalidSwapStopCriterionError,
+    InvalidVectorFieldVisualizationParametersError, IterationCount, PermutationNameFields,
+    PermutationNameTemplate, PermutationNameTemplateError, SwapAnimationConfig,
+    SwapParametersConfig, SwapPass, SwapStatisticsLogConfig, SwapStatisticsLogFormat,
+    SwapStopConfig, SwapStopCriterion, SwapStopThreshold, UnverifiedIntermediatePermutationCadence,
+    UnverifiedIntermediatePermutationsConfig, UnverifiedIterationCount,
+    UnverifiedSwapAnimationConfig, UnverifiedSwapParametersConfig,
+    UnverifiedSwapStatisticsLogConfig, UnverifiedSwapStopConfig, UnverifiedSwapStopCriterion,
+    UnverifiedSwapStopThreshold, UnverifiedVectorFieldVisualizationParametersConfig,
+};
+pub use workflow::{
+    UnverifiedWorkflowInput, UnverifiedWorkflowOperationConfig, UnverifiedWorkflowStepConfig,
+    WorkflowError, WorkflowInput, WorkflowOperationConfig, WorkflowOutputKind, WorkflowStepConfig,
 };
 
 fn check_dimensions_match2<'a>(
@@ -41,7 +82,30 @@ fn check_dimensions_match2<'a>(
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Debug, Clone)]
+pub enum SwapInputError {
+    Missing,
+    Conflicting,
+}
+
+impl fmt::Display for SwapInputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Missing => write!(
+                f,
+                "either a candidate permutation or a checkpoint to resume from must be provided"
+            ),
+            Self::Conflicting => write!(
+                f,
+                "a candidate permutation and a checkpoint to resume from cannot both be provided"
+            ),
+        }
+    }
+}
+
+impl Error for SwapInputError {}
+
+#[derive(Deserialize, JsonSchema)]
 pub enum UnverifiedConfig {
     CreateDisplacementGoal {
         input: UnverifiedCreateDisplacementGoalInputConfig,
@@ -53,21 +117,327 @@ pub enum UnverifiedConfig {
     },
     Permute {
         candidate_permutation: String,
-        original_image: UnverifiedLosslessImagePath,
-        permuted_image_output_path_no_extension: UnverifiedLosslessImagePath,
+        original_image: UnverifiedPermuteImageInput,
+        original_image_conversion: Option<ImageConversionPolicy>,
+        permuted_image_output_path_no_extension: UnverifiedPermuteImageOutput,
+        boundary: Option<BoundaryMode>,
     },
     Swap {
-        candidate_permutation: String,
+        candidate_permutation: Option<String>,
+        resume: Option<String>,
         displacement_goal: String,
         permutation_output_path_prefix: String,
+        swap_history_output_path_no_extension: Option<String>,
+        checkpoint: Option<UnverifiedSwapCheckpointConfig>,
         parameters: UnverifiedSwapParametersConfig,
+        boundary: Option<BoundaryMode>,
     },
     ValidatePermutation {
         candidate_permutation: String,
+        boundary: Option<BoundaryMode>,
+    },
+    VisualizeVectorField {
+        vector_field: String,
+        visualization_output_path_no_extension: String,
+        legend_output_path_no_extension: Option<String>,
+        parameters: Option<UnverifiedVectorFieldVisualizationParametersConfig>,
+    },
+    Workflow {
+        image_dimensions: Option<UnverifiedImageDimensionsConfig>,
+        steps: Vec<UnverifiedWorkflowStepConfig>,
+        boundary: Option<BoundaryMode>,
+    },
+    Experiment {
+        /// A swap operation whose parameters are varied by the runs of the experiment
+        base: Box<UnverifiedConfig>,
+        axes: Vec<UnverifiedExperimentAxis>,
+        sampling: Option<UnverifiedExperimentSampling>,
+        output_directory: String,
     },
 }
 
-#[derive(Debug, PartialEq)]
+impl UnverifiedConfig {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::CreateDisplacementGoal { .. } => "CreateDisplacementGoal",
+            Self::CreatePermutation { .. } => "CreatePermutation",
+            Self::Permute { .. } => "Permute",
+            Self::Swap { .. } => "Swap",
+            Self::ValidatePermutation { .. } => "ValidatePermutation",
+            Self::VisualizeVectorField { .. } => "VisualizeVectorField",
+            Self::Workflow { .. } => "Workflow",
+            Self::Experiment { .. } => "Experiment",
+        }
+    }
+}
+
+impl ResolvePaths for UnverifiedConfig {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        match self {
+            Self::CreateDisplacementGoal {
+                input,
+                displacement_goal_output_path_no_extension,
+            } => {
+                input.resolve_paths(context)?;
+                displacement_goal_output_path_no_extension.resolve_paths(context)
+            }
+            Self::CreatePermutation {
+                permutation_output_path_no_extension,
+                ..
+            } => permutation_output_path_no_extension.resolve_paths(context),
+            Self::Permute {
+                candidate_permutation,
+                original_image,
+                permuted_image_output_path_no_extension,
+                ..
+            } => {
+                candidate_permutation.resolve_paths(context)?;
+                original_image.resolve_paths(context)?;
+                permuted_image_output_path_no_extension.resolve_paths(context)
+            }
+            Self::Swap {
+                candidate_permutation,
+                resume,
+                displacement_goal,
+                permutation_output_path_prefix,
+                swap_history_output_path_no_extension,
+                checkpoint,
+                parameters,
+                ..
+            } => {
+                candidate_permutation.resolve_paths(context)?;
+                resume.resolve_paths(context)?;
+                displacement_goal.resolve_paths(context)?;
+                permutation_output_path_prefix.resolve_paths(context)?;
+                swap_history_output_path_no_extension.resolve_paths(context)?;
+                checkpoint.resolve_paths(context)?;
+                parameters.resolve_paths(context)
+            }
+            Self::ValidatePermutation {
+                candidate_permutation,
+                ..
+            } => candidate_permutation.resolve_paths(context),
+            Self::VisualizeVectorField {
+                vector_field,
+                visualization_output_path_no_extension,
+                legend_output_path_no_extension,
+                ..
+            } => {
+                vector_field.resolve_paths(context)?;
+                visualization_output_path_no_extension.resolve_paths(context)?;
+                legend_output_path_no_extension.resolve_paths(context)
+            }
+            Self::Workflow { steps, .. } => steps.resolve_paths(context),
+            Self::Experiment {
+                base,
+                output_directory,
+                ..
+            } => {
+                base.resolve_paths(context)?;
+                output_directory.resolve_paths(context)
+            }
+        }
+    }
+}
+
+/// The contents of a configuration file, which describe an operation
+/// and how to interpret the paths in its configuration
+///
+/// Deserialized without `#[serde(flatten)]`, which would buffer the operation's configuration
+/// and lose track of the paths and locations of errors in it
+#[derive(JsonSchema)]
+pub struct UnverifiedConfigFile {
+    /// Values of `${NAME}` references in paths, which take precedence over environment variables
+    pub variables: Option<HashMap<String, String>>,
+    /// Whether relative paths are resolved relative to the directory containing the configuration file,
+    /// as opposed to the working directory (defaults to `true`)
+    pub paths_relative_to_config_file: Option<bool>,
+    /// How to treat output files that already exist, and output directories that do not exist
+    pub output_policy: Option<UnverifiedOutputPolicyConfig>,
+    #[serde(flatten)]
+    #[schemars(with = "EmbeddedUnverifiedConfig")]
+    pub config: UnverifiedConfig,
+}
+
+/// The keys of a configuration file: its properties, followed by the names of the operations
+/// (the variants of `UnverifiedConfig`)
+const FILE_KEYS: &[&str] = &[
+    "variables",
+    "paths_relative_to_config_file",
+    "output_policy",
+    "CreateDisplacementGoal",
+    "CreatePermutation",
+    "Permute",
+    "Swap",
+    "ValidatePermutation",
+    "VisualizeVectorField",
+    "Workflow",
+    "Experiment",
+];
+
+const OPERATION_NAMES: &[&str] = FILE_KEYS.split_at(3).1;
+
+impl<'de> Deserialize<'de> for UnverifiedConfigFile {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(UnverifiedConfigFileVisitor)
+    }
+}
+
+struct UnverifiedConfigFileVisitor;
+
+impl<'de> Visitor<'de> for UnverifiedConfigFileVisitor {
+    type Value = UnverifiedConfigFile;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a map containing the configuration of an operation")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut variables = None;
+        let mut paths_relative_to_config_file = None;
+        let mut output_policy = None;
+        let mut config = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "variables" => variables = map.next_value()?,
+                "paths_relative_to_config_file" => {
+                    paths_relative_to_config_file = map.next_value()?
+                }
+                "output_policy" => output_policy = map.next_value()?,
+                // As with `#[serde(flatten)]`, only the first operation is used
+                name if config.is_none() && OPERATION_NAMES.contains(&name) => {
+                    config = Some(map.next_value_seed(OperationSeed(key))?);
+                }
+                name if OPERATION_NAMES.contains(&name) => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+                _ => return Err(de::Error::unknown_field(&key, FILE_KEYS)),
+            }
+        }
+        Ok(UnverifiedConfigFile {
+            variables,
+            paths_relative_to_config_file,
+            output_policy,
+            config: config.ok_or_else(|| {
+                de::Error::custom(format!(
+                    "missing operation, expected one of {}",
+                    OPERATION_NAMES.join(", ")
+                ))
+            })?,
+        })
+    }
+}
+
+/// Deserializes the configuration of the operation named by a key of a configuration file
+/// from the value of the key
+struct OperationSeed(String);
+
+impl<'de> DeserializeSeed<'de> for OperationSeed {
+    type Value = UnverifiedConfig;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        UnverifiedConfig::deserialize(OperationDeserializer {
+            name: self.0,
+            deserializer,
+        })
+    }
+}
+
+/// Presents an operation's name and the value holding its configuration as an enumeration variant
+struct OperationDeserializer<D> {
+    name: String,
+    deserializer: D,
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for OperationDeserializer<D> {
+    type Error = D::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, D: Deserializer<'de>> EnumAccess<'de> for OperationDeserializer<D> {
+    type Error = D::Error;
+    type Variant = OperationValue<D>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(StringDeserializer::<D::Error>::new(self.name))?;
+        Ok((variant, OperationValue(self.deserializer)))
+    }
+}
+
+struct OperationValue<D>(D);
+
+impl<'de, D: Deserializer<'de>> VariantAccess<'de> for OperationValue<D> {
+    type Error = D::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        <()>::deserialize(self.0)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self.0)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_struct("", fields, visitor)
+    }
+}
+
+/// The JSON Schema of an operation's configuration that is flattened into a configuration file
+///
+/// The variants of the schema of an `UnverifiedConfig` forbid properties other than
+/// the name of the operation, which would forbid the other properties of the file.
+struct EmbeddedUnverifiedConfig;
+
+impl JsonSchema for EmbeddedUnverifiedConfig {
+    fn schema_name() -> String {
+        String::from("EmbeddedUnverifiedConfig")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema = UnverifiedConfig::json_schema(gen).into_object();
+        if let Some(variants) = schema
+            .subschemas
+            .as_mut()
+            .and_then(|subschemas| subschemas.one_of.as_mut())
+        {
+            for variant in variants.iter_mut() {
+                if let Schema::Object(variant) = variant {
+                    variant.object().additional_properties = None;
+                }
+            }
+        }
+        Schema::Object(schema)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum AlgorithmConfig {
     CreateDisplacementGoal {
         input: CreateDisplacementGoalInputConfig,
@@ -78,37 +448,84 @@ pub enum AlgorithmConfig {
     },
     Permute {
         candidate_permutation: PermutationPath,
-        original_image: LosslessImagePath,
-        permuted_image_output_path_no_extension: LosslessImagePath,
+        /// The images to permute, in the order in which they are permuted
+        images: Vec<PermuteImageConfig>,
     },
     Swap {
         candidate_permutation: PermutationPath,
+        resume: Option<SwapCheckpoint>,
         displacement_goal: DisplacementGoalPath,
         permutation_output_path_prefix: PermutationPath,
+        swap_history_output_path_no_extension: Option<String>,
+        checkpoint: Option<SwapCheckpointConfig>,
         parameters: SwapParametersConfig,
     },
     ValidatePermutation {
         candidate_permutation: PermutationPath,
     },
+    VisualizeVectorField {
+        // Any vector field, including a permutation, can be loaded as a displacement goal
+        vector_field: DisplacementGoalPath,
+        visualization_output_path_no_extension: String,
+        legend_output_path_no_extension: Option<String>,
+        parameters: VectorFieldVisualizationParameters,
+    },
+    Workflow {
+        steps: Vec<WorkflowStepConfig>,
+    },
+    Experiment {
+        candidate_permutation: PermutationPath,
+        displacement_goal: DisplacementGoalPath,
+        output_directory: String,
+        runs: Vec<ExperimentRunConfig>,
+        /// The seed used to sample the runs, if they were sampled randomly
+        seed: Option<u64>,
+    },
 }
 
 #[derive(Debug, PartialEq)]
 pub struct Config {
     pub algorithm: AlgorithmConfig,
     pub dispatcher: compute::Config,
+    pub output_policy: OutputPolicyConfig,
 }
 
 impl TryFrom<UnverifiedConfig> for Config {
     type Error = Box<dyn Error>;
 
+    /// Errors are [`ConfigFieldError`](image_annealing_cli_util::config_file::ConfigFieldError)
+    /// values recording the fields that caused them, whenever the fields are known
     fn try_from(value: UnverifiedConfig) -> Result<Self, Self::Error> {
-        let (algorithm_config, image_dimensions) = match value {
+        Self::try_from_with_output_policy(value, Default::default())
+    }
+}
+
+impl Config {
+    /// Verifies a configuration, and checks that its output policy can be applied
+    ///
+    /// Errors are [`ConfigFieldError`](image_annealing_cli_util::config_file::ConfigFieldError)
+    /// values recording the fields that caused them, whenever the fields are known
+    pub fn try_from_with_output_policy(
+        value: UnverifiedConfig,
+        output_policy: OutputPolicyConfig,
+    ) -> Result<Self, Box<dyn Error>> {
+        let name = value.name();
+        let config = Self::try_from_operation(value, output_policy).in_field(name)?;
+        config.check_output_policy().in_field("output_policy")?;
+        Ok(config)
+    }
+
+    fn try_from_operation(
+        value: UnverifiedConfig,
+        output_policy: OutputPolicyConfig,
+    ) -> Result<Self, Box<dyn Error>> {
+        let (algorithm_config, image_dimensions, boundary) = match value {
             UnverifiedConfig::CreateDisplacementGoal {
                 input,
                 displacement_goal_output_path_no_extension,
             } => {
                 let (input_checked, image_dimensions) =
-                    CreateDisplacementGoalInputConfig::from_config(input)?;
+                    CreateDisplacementGoalInputConfig::from_config(input).in_field("input")?;
                 (
                     AlgorithmConfig::CreateDisplacementGoal {
                         input: input_checked,
@@ -118,6 +535,7 @@ impl TryFrom<UnverifiedConfig> for Config {
                             ),
                     },
                     image_dimensions,
+                    None,
                 )
             }
             UnverifiedConfig::CreatePermutation {
@@ -129,69 +547,237 @@ impl TryFrom<UnverifiedConfig> for Config {
                         permutation_output_path_no_extension,
                     ),
                 },
-                image_dimensions.try_into()?,
+                image_dimensions.try_into().in_field("image_dimensions")?,
+                None,
             ),
             UnverifiedConfig::Permute {
                 candidate_permutation,
                 original_image,
+                original_image_conversion,
                 permuted_image_output_path_no_extension,
+                boundary,
             } => {
                 let (candidate_permutation_checked, permutation_dimensions) =
-                    PermutationPath::from_input_path(candidate_permutation)?;
-                let (original_image_checked, image_dimensions) =
-                    LosslessImagePath::from_input_path(original_image)?;
-                check_dimensions_match2(&image_dimensions, &permutation_dimensions)?;
+                    PermutationPath::from_input_path(candidate_permutation)
+                        .in_field("candidate_permutation")?;
                 (
                     AlgorithmConfig::Permute {
                         candidate_permutation: candidate_permutation_checked,
-                        original_image: original_image_checked,
-                        permuted_image_output_path_no_extension:
-                            LosslessImagePath::from_output_path(
-                                permuted_image_output_path_no_extension,
-                            ),
+                        images: input::permute_images_from_config(
+                            original_image,
+                            original_image_conversion,
+                            permuted_image_output_path_no_extension,
+                            &permutation_dimensions,
+                            output_policy.create_directories,
+                        )?,
                     },
-                    image_dimensions,
+                    permutation_dimensions,
+                    boundary,
                 )
             }
             UnverifiedConfig::Swap {
                 candidate_permutation,
+                resume,
                 displacement_goal,
                 permutation_output_path_prefix,
+                swap_history_output_path_no_extension,
+                checkpoint,
                 parameters,
+                boundary,
             } => {
+                let parameters_checked: SwapParametersConfig =
+                    parameters.try_into().in_field("parameters")?;
+                // When resuming, the candidate permutation is the one saved in the checkpoint
+                let (candidate_permutation, candidate_permutation_field, resume_checked) =
+                    match (candidate_permutation, resume) {
+                        (Some(candidate_permutation), None) => {
+                            (candidate_permutation, "candidate_permutation", None)
+                        }
+                        (None, Some(resume)) => {
+                            let (checkpoint, permutation_path) = SwapCheckpoint::from_input_path(
+                                resume,
+                                &parameters_checked,
+                                boundary.unwrap_or_default(),
+                            )
+                            .in_field("resume")?;
+                            (permutation_path, "resume", Some(checkpoint))
+                        }
+                        (None, None) => return Err(Box::new(SwapInputError::Missing)),
+                        (Some(_), Some(_)) => return Err(Box::new(SwapInputError::Conflicting)),
+                    };
                 let (candidate_permutation_checked, permutation_dimensions) =
-                    PermutationPath::from_input_path(candidate_permutation)?;
+                    PermutationPath::from_input_path(candidate_permutation)
+                        .in_field(candidate_permutation_field)?;
                 let (displacement_goal_checked, displacement_goal_dimensions) =
-                    DisplacementGoalPath::from_input_path(displacement_goal)?;
-                check_dimensions_match2(&permutation_dimensions, &displacement_goal_dimensions)?;
+                    DisplacementGoalPath::from_input_path(displacement_goal)
+                        .in_field("displacement_goal")?;
+                check_dimensions_match2(&permutation_dimensions, &displacement_goal_dimensions)
+                    .in_field("displacement_goal")?;
+                if let Some(animation) = parameters_checked.animation.as_ref() {
+                    ImageDimensions::from_image_path(&animation.image)
+                        .and_then(|animation_dimensions| {
+                            check_dimensions_match2(&permutation_dimensions, &animation_dimensions)
+                                .map(|_| ())
+                        })
+                        .in_field("image")
+                        .in_field("animation")
+                        .in_field("parameters")?;
+                }
                 (
                     AlgorithmConfig::Swap {
                         candidate_permutation: candidate_permutation_checked,
+                        resume: resume_checked,
                         displacement_goal: displacement_goal_checked,
                         permutation_output_path_prefix: PermutationPath::from_output_path(
                             permutation_output_path_prefix,
                         ),
-                        parameters: parameters.try_into()?,
+                        swap_history_output_path_no_extension:
+                            swap_history_output_path_no_extension.map(io::convert_path_separators),
+                        checkpoint: checkpoint
+                            .map(TryInto::try_into)
+                            .transpose()
+                            .in_field("checkpoint")?,
+                        parameters: parameters_checked,
                     },
                     permutation_dimensions,
+                    boundary,
                 )
             }
             UnverifiedConfig::ValidatePermutation {
                 candidate_permutation,
+                boundary,
             } => {
                 let (candidate_permutation_path, image_dimensions) =
-                    PermutationPath::from_input_path(candidate_permutation)?;
+                    PermutationPath::from_input_path(candidate_permutation)
+                        .in_field("candidate_permutation")?;
                 (
                     AlgorithmConfig::ValidatePermutation {
                         candidate_permutation: candidate_permutation_path,
                     },
                     image_dimensions,
+                    boundary,
+                )
+            }
+            UnverifiedConfig::VisualizeVectorField {
+                vector_field,
+                visualization_output_path_no_extension,
+                legend_output_path_no_extension,
+                parameters,
+            } => {
+                let (vector_field_path, image_dimensions) =
+                    DisplacementGoalPath::from_input_path(vector_field).in_field("vector_field")?;
+                (
+                    AlgorithmConfig::VisualizeVectorField {
+                        vector_field: vector_field_path,
+                        visualization_output_path_no_extension: io::convert_path_separators(
+                            visualization_output_path_no_extension,
+                        ),
+                        legend_output_path_no_extension: legend_output_path_no_extension
+                            .map(io::convert_path_separators),
+                        parameters: parameters
+                            .unwrap_or_default()
+                            .try_into()
+                            .in_field("parameters")?,
+                    },
+                    image_dimensions,
+                    None,
+                )
+            }
+            UnverifiedConfig::Workflow {
+                image_dimensions,
+                steps,
+                boundary,
+            } => {
+                let (steps_checked, input_dimensions) =
+                    workflow::workflow_from_config(steps).in_field("steps")?;
+                let image_dimensions = match (image_dimensions, input_dimensions) {
+                    (Some(image_dimensions), Some(input_dimensions)) => {
+                        ImageDimensions::try_from(image_dimensions)
+                            .map_err(Into::into)
+                            .and_then(|image_dimensions| {
+                                check_dimensions_match2(&image_dimensions, &input_dimensions)
+                                    .copied()
+                            })
+                            .in_field("image_dimensions")?
+                    }
+                    (Some(image_dimensions), None) => {
+                        image_dimensions.try_into().in_field("image_dimensions")?
+                    }
+                    (None, Some(input_dimensions)) => input_dimensions,
+                    (None, None) => return Err(Box::new(WorkflowError::MissingImageDimensions)),
+                };
+                (
+                    AlgorithmConfig::Workflow {
+                        steps: steps_checked,
+                    },
+                    image_dimensions,
+                    boundary,
+                )
+            }
+            UnverifiedConfig::Experiment {
+                base,
+                axes,
+                sampling,
+                output_directory,
+            } => {
+                let boundary = match base.as_ref() {
+                    UnverifiedConfig::Swap { boundary, .. } => *boundary,
+                    _ => return Err(ExperimentError::BaseNotSwap).in_field("base"),
+                };
+                let base_checked = Config::try_from(*base).in_field("base")?;
+                let (candidate_permutation, displacement_goal, base_run) =
+                    match base_checked.algorithm {
+                        AlgorithmConfig::Swap {
+                            candidate_permutation,
+                            resume: None,
+                            displacement_goal,
+                            permutation_output_path_prefix,
+                            swap_history_output_path_no_extension,
+                            checkpoint,
+                            parameters,
+                        } => (
+                            candidate_permutation,
+                            displacement_goal,
+                            ExperimentRunConfig {
+                                name: String::new(),
+                                directory: String::new(),
+                                permutation_output_path_prefix,
+                                swap_history_output_path_no_extension,
+                                checkpoint,
+                                parameters,
+                            },
+                        ),
+                        _ => return Err(ExperimentError::Resume).in_field("base"),
+                    };
+                let output_directory = io::convert_path_separators(output_directory);
+                check_output_directory(&output_directory, output_policy.create_directories)
+                    .in_field("output_directory")?;
+                let (runs, seed) = experiment::experiment_from_config(
+                    &base_run,
+                    axes,
+                    sampling,
+                    &output_directory,
+                )?;
+                (
+                    AlgorithmConfig::Experiment {
+                        candidate_permutation,
+                        displacement_goal,
+                        output_directory,
+                        runs,
+                        seed,
+                    },
+                    base_checked.dispatcher.image_dimensions,
+                    boundary,
                 )
             }
         };
         Ok(Config {
             algorithm: algorithm_config,
-            dispatcher: compute::Config { image_dimensions },
+            dispatcher: compute::Config {
+                image_dimensions,
+                boundary: boundary.unwrap_or_default().into(),
+            },
+            output_policy,
         })
     }
 }
@@ -0,0 +1,917 @@
+use super::{
+    AlgorithmConfig, Config, ImagePath, LosslessImagePath, PermutationNameFields, PermutationPath,
+    SwapCheckpointConfig, SwapCheckpointFile, SwapParametersConfig, SwapStopCriterion,
+    WorkflowOperationConfig, WorkflowStepConfig,
+};
+use image_annealing::compute::format::{
+    AnimationFormat, ImageFileWriter, Rgba8Image, SwapHistoryWriter,
+};
+use image_annealing_cli_util::config_file::ConfigFieldContext;
+use image_annealing_cli_util::io::{self, DirectoryError};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Files that an operation would write
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OutputFiles {
+    /// A file with exactly the given path
+    Exact(String),
+    /// Files whose paths match a glob pattern, as their number is only known when they are written
+    Pattern {
+        pattern: String,
+        /// The directory containing the files
+        directory: String,
+        /// Paths of matching files that the operation reads instead of writing
+        excluded: Vec<String>,
+    },
+}
+
+impl OutputFiles {
+    /// The directory that must exist for the outputs to be written, unless it is the working directory
+    fn directory(&self) -> Option<&Path> {
+        match self {
+            Self::Exact(path) => Path::new(path).parent(),
+            Self::Pattern { directory, .. } => Some(Path::new(directory)),
+        }
+        .filter(|directory| !directory.as_os_str().is_empty())
+    }
+
+    /// Returns the paths of existing files that writing these outputs could overwrite
+    pub fn existing(&self) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+        match self {
+            Self::Exact(path) => Ok(Some(PathBuf::from(path))
+                .filter(|path| path.is_file())
+                .into_iter()
+                .collect()),
+            Self::Pattern {
+                pattern, excluded, ..
+            } => {
+                let mut paths = Vec::new();
+                for entry in glob::glob(pattern)? {
+                    let path = entry?;
+                    if path.is_file() && !excluded.iter().any(|other| path == Path::new(other)) {
+                        paths.push(path);
+                    }
+                }
+                Ok(paths)
+            }
+        }
+    }
+}
+
+impl fmt::Display for OutputFiles {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Exact(path) => write!(f, "{}", path),
+            Self::Pattern { pattern, .. } => write!(f, "{}", pattern),
+        }
+    }
+}
+
+/// Returns the paths of existing files that writing any of the outputs could overwrite
+pub fn existing_outputs(outputs: &[OutputFiles]) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    let mut found = HashSet::new();
+    for output in outputs.iter() {
+        for path in output.existing()? {
+            if found.insert(path.clone()) {
+                paths.push(path);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// What to do when files that an operation would write already exist
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq)]
+pub enum ExistingOutputPolicy {
+    /// Overwrite the existing files
+    #[default]
+    Overwrite,
+    /// Fail verification of the configuration
+    Refuse,
+    /// Append the smallest suffix `_1`, `_2`, ... to the file stems of all outputs
+    /// such that none of them exist
+    ///
+    /// The suffix is appended to the output directory of an experiment instead.
+    Suffix,
+}
+
+#[derive(Default, Deserialize, JsonSchema)]
+pub struct UnverifiedOutputPolicyConfig {
+    /// Whether to create missing directories that will contain output files (defaults to `false`)
+    pub create_directories: Option<bool>,
+    /// Defaults to `Overwrite`
+    pub existing_files: Option<ExistingOutputPolicy>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct OutputPolicyConfig {
+    pub create_directories: bool,
+    pub existing_files: ExistingOutputPolicy,
+}
+
+impl From<UnverifiedOutputPolicyConfig> for OutputPolicyConfig {
+    fn from(value: UnverifiedOutputPolicyConfig) -> Self {
+        Self {
+            create_directories: value.create_directories.unwrap_or_default(),
+            existing_files: value.existing_files.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum OutputPolicyError {
+    ExistingFiles(Vec<PathBuf>),
+    CreateDirectory {
+        directory: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl fmt::Display for OutputPolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ExistingFiles(paths) => {
+                write!(f, "refusing to overwrite existing output files")?;
+                for (i, path) in paths.iter().enumerate() {
+                    write!(f, "{} '{}'", if i == 0 { ":" } else { "," }, path.display())?;
+                }
+                Ok(())
+            }
+            Self::CreateDirectory { directory, source } => write!(
+                f,
+                "failed to create output directory '{}': {}",
+                directory.display(),
+                source
+            ),
+        }
+    }
+}
+
+impl Error for OutputPolicyError {}
+
+/// Creates a directory and its missing parents
+fn create_output_directory<P: AsRef<Path>>(directory: P) -> Result<(), OutputPolicyError> {
+    let directory = directory.as_ref();
+    std::fs::create_dir_all(directory).map_err(|source| OutputPolicyError::CreateDirectory {
+        directory: directory.to_path_buf(),
+        source,
+    })
+}
+
+/// Checks that a directory exists, or, if it will be created, that its closest existing
+/// ancestor is a directory
+pub fn check_output_directory<P: AsRef<Path>>(
+    directory: P,
+    create_directories: bool,
+) -> Result<(), DirectoryError> {
+    let directory = directory.as_ref();
+    if create_directories {
+        match directory.ancestors().find(|ancestor| ancestor.exists()) {
+            Some(ancestor) => io::check_directory_path(ancestor),
+            None => Ok(()),
+        }
+    } else {
+        io::check_directory_path(directory)
+    }
+}
+
+impl Config {
+    /// Returns the files that running the operation would write
+    pub fn outputs(&self) -> Vec<OutputFiles> {
+        self.algorithm.outputs()
+    }
+
+    /// Checks that the output policy can be applied without changing anything,
+    /// so that the operation does not fail because of it after it starts
+    ///
+    /// Errors are [`ConfigFieldError`](image_annealing_cli_util::config_file::ConfigFieldError)
+    /// values recording the field of the policy that caused them.
+    pub fn check_output_policy(&self) -> Result<(), Box<dyn Error>> {
+        let outputs = self.outputs();
+        for output in outputs.iter() {
+            if let Some(directory) = output.directory() {
+                if self.output_policy.create_directories {
+                    check_output_directory(directory, true).in_field("create_directories")?;
+                } else if !self.algorithm.creates_directory(directory) {
+                    check_output_directory(directory, false)?;
+                }
+            }
+        }
+        if self.output_policy.existing_files == ExistingOutputPolicy::Refuse {
+            let existing = existing_outputs(&outputs)?;
+            if !existing.is_empty() {
+                return Err(OutputPolicyError::ExistingFiles(existing)).in_field("existing_files");
+            }
+        }
+        Ok(())
+    }
+
+    /// Renames the outputs to avoid overwriting existing files, and creates missing
+    /// output directories, depending on the output policy
+    ///
+    /// This should be called right before the operation runs, as it changes the file system.
+    pub fn apply_output_policy(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.output_policy.existing_files == ExistingOutputPolicy::Suffix
+            && !existing_outputs(&self.outputs())?.is_empty()
+        {
+            let mut n = 1;
+            while !self.is_unused_suffix(&format!("_{}", n))? {
+                n += 1;
+            }
+            let suffix = format!("_{}", n);
+            println!(
+                "Appending '{}' to the output paths to avoid overwriting existing files",
+                suffix
+            );
+            self.algorithm.add_output_suffix(&suffix);
+        }
+        if self.output_policy.create_directories {
+            for output in self.outputs() {
+                if let Some(directory) = output.directory() {
+                    create_output_directory(directory)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn is_unused_suffix(&self, suffix: &str) -> Result<bool, Box<dyn Error>> {
+        Ok(match &self.algorithm {
+            AlgorithmConfig::Experiment {
+                output_directory, ..
+            } => !Path::new(&format!("{}{}", output_directory, suffix)).exists(),
+            algorithm => {
+                let mut algorithm = algorithm.clone();
+                algorithm.add_output_suffix(suffix);
+                existing_outputs(&algorithm.outputs())?.is_empty()
+            }
+        })
+    }
+}
+
+impl AlgorithmConfig {
+    /// Returns whether running the operation creates the given output directory,
+    /// as an experiment creates a subdirectory of its output directory for each run
+    fn creates_directory(&self, directory: &Path) -> bool {
+        match self {
+            Self::Experiment {
+                output_directory, ..
+            } => directory != Path::new(output_directory),
+            _ => false,
+        }
+    }
+
+    /// Returns the files that running the operation would write
+    pub fn outputs(&self) -> Vec<OutputFiles> {
+        let no_suffix = RunSuffix::Exact(String::new());
+        let mut outputs = Vec::new();
+        match self {
+            Self::CreateDisplacementGoal {
+                displacement_goal_output_path_no_extension,
+                ..
+            } => outputs.push(vector_field_output(
+                displacement_goal_output_path_no_extension,
+                &no_suffix,
+            )),
+            Self::CreatePermutation {
+                permutation_output_path_no_extension,
+            } => outputs.push(vector_field_output(
+                permutation_output_path_no_extension,
+                &no_suffix,
+            )),
+            Self::Permute { images, .. } => {
+                for image in images {
+                    outputs.extend(image_outputs(
+                        &image.permuted_image_output_path_no_extension,
+                        &no_suffix,
+                    ));
+                }
+            }
+            Self::Swap {
+                candidate_permutation,
+                resume,
+                permutation_output_path_prefix,
+                swap_history_output_path_no_extension,
+                checkpoint,
+                parameters,
+                ..
+            } => {
+                let (first_round_index, excluded) = match resume {
+                    // The checkpoint may be one of the checkpoints that the run would save
+                    Some(resume) => (
+                        resume.round_index + 1,
+                        vec![
+                            resume.path.clone(),
+                            String::from(AsRef::<str>::as_ref(candidate_permutation)),
+                        ],
+                    ),
+                    None => (0, Vec::new()),
+                };
+                outputs.extend(swap_outputs(
+                    &SwapRun {
+                        first_round_index,
+                        suffix: &no_suffix,
+                        excluded: &excluded,
+                    },
+                    Some(permutation_output_path_prefix),
+                    swap_history_output_path_no_extension.as_deref(),
+                    checkpoint.as_ref(),
+                    parameters,
+                ));
+            }
+            Self::ValidatePermutation { .. } => (),
+            Self::VisualizeVectorField {
+                visualization_output_path_no_extension,
+                legend_output_path_no_extension,
+                ..
+            } => {
+                for path in std::iter::once(visualization_output_path_no_extension)
+                    .chain(legend_output_path_no_extension.iter())
+                {
+                    outputs.push(
+                        OutputPath::new(path)
+                            .into_output(|path| Rgba8Image::make_filename(path), &[]),
+                    );
+                }
+            }
+            Self::Workflow { steps } => outputs.extend(workflow_outputs(steps)),
+            Self::Experiment {
+                output_directory,
+                runs,
+                ..
+            } => {
+                for run in runs {
+                    outputs.extend(swap_outputs(
+                        &SwapRun {
+                            first_round_index: 0,
+                            suffix: &no_suffix,
+                            excluded: &[],
+                        },
+                        Some(&run.permutation_output_path_prefix),
+                        run.swap_history_output_path_no_extension.as_deref(),
+                        run.checkpoint.as_ref(),
+                        &run.parameters,
+                    ));
+                }
+                for file_name in ["summary.csv", "summary.json"] {
+                    outputs.push(OutputFiles::Exact(
+                        Path::new(output_directory)
+                            .join(file_name)
+                            .to_string_lossy()
+                            .into_owned(),
+                    ));
+                }
+            }
+        }
+        outputs
+    }
+
+    fn add_output_suffix(&mut self, suffix: &str) {
+        match self {
+            Self::CreateDisplacementGoal {
+                displacement_goal_output_path_no_extension,
+                ..
+            } => suffix_vector_field_path(displacement_goal_output_path_no_extension, suffix),
+            Self::CreatePermutation {
+                permutation_output_path_no_extension,
+            } => suffix_vector_field_path(permutation_output_path_no_extension, suffix),
+            Self::Permute { images, .. } => {
+                for image in images {
+                    suffix_image_path(&mut image.permuted_image_output_path_no_extension, suffix);
+                }
+            }
+            Self::Swap {
+                resume,
+                permutation_output_path_prefix,
+                swap_history_output_path_no_extension,
+                checkpoint,
+                parameters,
+                ..
+            } => suffix_swap_outputs(
+                Some(permutation_output_path_prefix),
+                swap_history_output_path_no_extension.as_mut(),
+                checkpoint.as_mut(),
+                parameters,
+                resume.is_some(),
+                suffix,
+            ),
+            Self::ValidatePermutation { .. } => (),
+            Self::VisualizeVectorField {
+                visualization_output_path_no_extension,
+                legend_output_path_no_extension,
+                ..
+            } => {
+                visualization_output_path_no_extension.push_str(suffix);
+                if let Some(path) = legend_output_path_no_extension.as_mut() {
+                    path.push_str(suffix);
+                }
+            }
+            Self::Workflow { steps } => {
+                for step in steps {
+                    suffix_workflow_operation_outputs(&mut step.operation, suffix);
+                }
+            }
+            Self::Experiment {
+                output_directory,
+                runs,
+                ..
+            } => {
+                output_directory.push_str(suffix);
+                for run in runs {
+                    run.rebase(output_directory);
+                }
+            }
+        }
+    }
+}
+
+/// Text appended to the output paths of a run of a workflow step in a loop
+enum RunSuffix {
+    /// Text that is known before the step runs
+    Exact(String),
+    /// The index of any run, as the number of runs is only known when the step runs
+    AnyIndex,
+}
+
+/// The smallest and largest numbers of values that a workflow step could output
+#[derive(Clone, Copy)]
+struct OutputCount {
+    min: usize,
+    max: usize,
+}
+
+impl OutputCount {
+    const ONE: Self = Self { min: 1, max: 1 };
+
+    fn include(self, count: usize) -> Self {
+        Self {
+            min: self.min.min(count),
+            max: self.max.max(count),
+        }
+    }
+
+    /// The suffixes that a step looping over this number of values could append to its outputs,
+    /// which are run indices padded with zeros to the width of the number of runs
+    fn suffixes(&self) -> Vec<RunSuffix> {
+        let width = |count: usize| count.to_string().len();
+        let mut suffixes = Vec::new();
+        for index in 0..self.max {
+            for width in width(self.min.max(index + 1))..=width(self.max) {
+                suffixes.push(RunSuffix::Exact(format!(
+                    "_{:0width$}",
+                    index,
+                    width = width
+                )));
+            }
+        }
+        suffixes
+    }
+}
+
+/// The path of an output file, which becomes a glob pattern when a part of it
+/// is only known when the file is written
+#[derive(Clone)]
+struct OutputPath {
+    text: String,
+    directory: String,
+    is_pattern: bool,
+}
+
+impl OutputPath {
+    fn new(path: &str) -> Self {
+        Self {
+            text: String::from(path),
+            directory: Path::new(path)
+                .parent()
+                .map(|directory| directory.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            is_pattern: false,
+        }
+    }
+
+    fn push(mut self, text: &str) -> Self {
+        if self.is_pattern {
+            self.text.push_str(&glob::Pattern::escape(text));
+        } else {
+            self.text.push_str(text);
+        }
+        self
+    }
+
+    fn push_pattern(mut self, pattern: &str) -> Self {
+        if !self.is_pattern {
+            self.text = glob::Pattern::escape(&self.text);
+            self.is_pattern = true;
+        }
+        // Consecutive wildcards are only valid as a path component of their own
+        if self.text.ends_with('*') {
+            self.text
+                .push_str(pattern.strip_prefix('*').unwrap_or(pattern));
+        } else {
+            self.text.push_str(pattern);
+        }
+        self
+    }
+
+    fn push_suffix(self, suffix: &RunSuffix) -> Self {
+        match suffix {
+            RunSuffix::Exact(text) => self.push(text),
+            RunSuffix::AnyIndex => self.push("_").push_pattern("*"),
+        }
+    }
+
+    /// Adds a file extension to the path, using the function that adds it when the file is written
+    fn into_output<F: FnOnce(&str) -> PathBuf>(
+        self,
+        make_filename: F,
+        excluded: &[String],
+    ) -> OutputFiles {
+        let path = make_filename(&self.text).to_string_lossy().into_owned();
+        if self.is_pattern {
+            OutputFiles::Pattern {
+                pattern: path,
+                directory: self.directory,
+                excluded: excluded.to_vec(),
+            }
+        } else {
+            OutputFiles::Exact(path)
+        }
+    }
+}
+
+/// The round that a swap run starts from, and how its outputs are distinguished
+/// from those of other runs
+struct SwapRun<'a> {
+    first_round_index: usize,
+    suffix: &'a RunSuffix,
+    /// Paths of input files of the run that its outputs could match
+    excluded: &'a [String],
+}
+
+/// The permutations that a swap run could output
+struct SwapPermutations {
+    /// The names appended to the output path prefix
+    names: Vec<String>,
+    count: OutputCount,
+}
+
+impl SwapPermutations {
+    /// Returns `None` if the number of rounds of the run is not bounded
+    fn new(parameters: &SwapParametersConfig, first_round_index: usize) -> Option<Self> {
+        let criterion = parameters.stop.criterion();
+        let round_count = criterion.iteration_limit()?.get();
+        // Other criteria can stop the run at the end of any round
+        let can_stop_early = !matches!(criterion, SwapStopCriterion::IterationCount(_));
+        let passes = parameters.swap_pass_sequence.iter().collect::<Vec<_>>();
+        let intermediate = &parameters.intermediate_permutations;
+        let mut names = Vec::new();
+        let mut rendered = HashSet::new();
+        let mut count: Option<OutputCount> = None;
+        let mut sequence = 0;
+        for round in first_round_index..round_count {
+            for (pass_index, pass) in passes.iter().enumerate() {
+                let is_intermediate = parameters.output_intermediate_permutations
+                    && intermediate
+                        .cadence
+                        .includes(round, pass_index, passes.len());
+                let is_final =
+                    pass_index + 1 == passes.len() && (can_stop_early || round + 1 == round_count);
+                if is_intermediate || is_final {
+                    let name = intermediate.name_template.render(&PermutationNameFields {
+                        sequence,
+                        round,
+                        pass_index,
+                        pass_name: pass.snake_case_name(),
+                    });
+                    if rendered.insert(name.clone()) {
+                        names.push(name);
+                    }
+                }
+                if is_final {
+                    count = Some(match count {
+                        Some(count) => count.include(sequence + 1),
+                        None => OutputCount {
+                            min: sequence + 1,
+                            max: sequence + 1,
+                        },
+                    });
+                }
+                if is_intermediate {
+                    sequence += 1;
+                }
+            }
+        }
+        count.map(|count| Self { names, count })
+    }
+}
+
+fn path_string(path: PathBuf) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+fn vector_field_output<P: ImagePath>(path: &P, suffix: &RunSuffix) -> OutputFiles {
+    let format = path.format();
+    OutputPath::new(path.path_no_extension())
+        .push_suffix(suffix)
+        .into_output(|path| format.make_filename(path), &[])
+}
+
+fn image_outputs(path_no_extension: &LosslessImagePath, suffix: &RunSuffix) -> Vec<OutputFiles> {
+    path_no_extension
+        .to_vec()
+        .into_iter()
+        .map(|path| {
+            OutputPath::new(path)
+                .push_suffix(suffix)
+                .into_output(|path| Rgba8Image::make_filename(path), &[])
+        })
+        .collect()
+}
+
+fn swap_outputs(
+    run: &SwapRun,
+    permutation_output_path_prefix: Option<&PermutationPath>,
+    swap_history_output_path_no_extension: Option<&str>,
+    checkpoint: Option<&SwapCheckpointConfig>,
+    parameters: &SwapParametersConfig,
+) -> Vec<OutputFiles> {
+    let permutations = SwapPermutations::new(parameters, run.first_round_index);
+    let mut outputs = Vec::new();
+    if let Some(path_prefix) = permutation_output_path_prefix {
+        let format = path_prefix.format();
+        let path = || OutputPath::new(path_prefix.path_no_extension()).push_suffix(run.suffix);
+        let make_filename = |path: &str| format.make_filename(path);
+        match permutations.as_ref() {
+            Some(permutations) => outputs.extend(
+                permutations
+                    .names
+                    .iter()
+                    .map(|name| path().push(name).into_output(make_filename, run.excluded)),
+            ),
+            None => outputs.push(
+                path()
+                    .push_pattern(&parameters.intermediate_permutations.name_template.pattern())
+                    .into_output(make_filename, run.excluded),
+            ),
+        }
+    }
+    // A resumed run continues the logs of the interrupted run instead of overwriting them
+    let continues_logs = run.first_round_index != 0;
+    if let Some(path) = swap_history_output_path_no_extension.filter(|_| !continues_logs) {
+        outputs.push(
+            OutputPath::new(path)
+                .push_suffix(run.suffix)
+                .into_output(|path| SwapHistoryWriter::make_filename(path), &[]),
+        );
+    }
+    if let Some(checkpoint_config) = checkpoint {
+        let format = checkpoint_config.path_prefix.format();
+        let path = || {
+            OutputPath::new(checkpoint_config.path_prefix.path_no_extension())
+                .push_suffix(run.suffix)
+                .push("_round_")
+        };
+        let paths = match permutations {
+            Some(_) => (run.first_round_index
+                ..parameters.stop.criterion().iteration_limit().unwrap().get())
+                .filter(|&round| checkpoint_config.is_due(round))
+                .map(|round| path().push(&round.to_string()))
+                .collect(),
+            None => vec![path().push_pattern("*")],
+        };
+        for path in paths {
+            outputs.push(
+                path.clone()
+                    .into_output(|path| format.make_filename(path), run.excluded),
+            );
+            outputs.push(
+                path.into_output(|path| SwapCheckpointFile::make_filename(path), run.excluded),
+            );
+        }
+    }
+    if let Some(animation_config) = parameters.animation.as_ref() {
+        let format = AnimationFormat::from(animation_config.format);
+        outputs.push(
+            OutputPath::new(&animation_config.path_no_extension)
+                .push_suffix(run.suffix)
+                .into_output(
+                    |path| Path::new(path).with_extension(format.extension()),
+                    &[],
+                ),
+        );
+    }
+    if let Some(log_config) = parameters
+        .statistics_log
+        .as_ref()
+        .filter(|_| !continues_logs)
+    {
+        // The suffix is appended to the file stem
+        let path = Path::new(&log_config.path);
+        let extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy());
+        outputs.push(
+            OutputPath::new(&path_string(
+                path.with_file_name(path.file_stem().unwrap_or_default()),
+            ))
+            .push_suffix(run.suffix)
+            .into_output(
+                |path| match extension {
+                    Some(extension) => PathBuf::from(format!("{}.{}", path, extension)),
+                    None => PathBuf::from(path),
+                },
+                &[],
+            ),
+        );
+    }
+    outputs
+}
+
+fn workflow_outputs(steps: &[WorkflowStepConfig]) -> Vec<OutputFiles> {
+    let mut outputs = Vec::new();
+    // The numbers of values that each step outputs, which are only bounded if
+    // the step runs a bounded number of swap rounds
+    let mut counts: HashMap<&str, Option<OutputCount>> = HashMap::new();
+    for step in steps {
+        let run_count = workflow_operation_output_count(&step.operation);
+        let (suffixes, count) = match step.for_each.as_deref() {
+            Some(reference) => match counts.get(reference).copied().flatten() {
+                Some(runs) => (
+                    runs.suffixes(),
+                    run_count.map(|run_count| OutputCount {
+                        min: runs.min * run_count.min,
+                        max: runs.max * run_count.max,
+                    }),
+                ),
+                None => (vec![RunSuffix::AnyIndex], None),
+            },
+            None => (vec![RunSuffix::Exact(String::new())], run_count),
+        };
+        for suffix in suffixes.iter() {
+            outputs.extend(workflow_operation_outputs(&step.operation, suffix));
+        }
+        counts.insert(&step.name, count);
+    }
+    outputs
+}
+
+/// The numbers of values that one run of a workflow step could output,
+/// or `None` if there is no largest number
+fn workflow_operation_output_count(operation: &WorkflowOperationConfig) -> Option<OutputCount> {
+    match operation {
+        WorkflowOperationConfig::Swap { parameters, .. } => {
+            SwapPermutations::new(parameters, 0).map(|permutations| permutations.count)
+        }
+        _ => Some(OutputCount::ONE),
+    }
+}
+
+fn workflow_operation_outputs(
+    operation: &WorkflowOperationConfig,
+    suffix: &RunSuffix,
+) -> Vec<OutputFiles> {
+    match operation {
+        WorkflowOperationConfig::CreateDisplacementGoal {
+            displacement_goal_output_path_no_extension,
+            ..
+        } => displacement_goal_output_path_no_extension
+            .iter()
+            .map(|path| vector_field_output(path, suffix))
+            .collect(),
+        WorkflowOperationConfig::CreatePermutation {
+            permutation_output_path_no_extension,
+        } => permutation_output_path_no_extension
+            .iter()
+            .map(|path| vector_field_output(path, suffix))
+            .collect(),
+        WorkflowOperationConfig::Permute {
+            permuted_image_output_path_no_extension,
+            ..
+        } => permuted_image_output_path_no_extension
+            .iter()
+            .flat_map(|path| image_outputs(path, suffix))
+            .collect(),
+        WorkflowOperationConfig::Swap {
+            permutation_output_path_prefix,
+            swap_history_output_path_no_extension,
+            parameters,
+            ..
+        } => swap_outputs(
+            &SwapRun {
+                first_round_index: 0,
+                suffix,
+                excluded: &[],
+            },
+            permutation_output_path_prefix.as_ref(),
+            swap_history_output_path_no_extension.as_deref(),
+            None,
+            parameters,
+        ),
+        WorkflowOperationConfig::ValidatePermutation { .. } => Vec::new(),
+    }
+}
+
+fn add_suffix_before_extension(path: &str, suffix: &str) -> String {
+    let path = Path::new(path);
+    match path.extension() {
+        Some(extension) => format!(
+            "{}{}.{}",
+            path.with_extension("").to_string_lossy(),
+            suffix,
+            extension.to_string_lossy()
+        ),
+        None => format!("{}{}", path.to_string_lossy(), suffix),
+    }
+}
+
+fn suffix_vector_field_path<P: ImagePath>(path: &mut P, suffix: &str) {
+    let path_no_extension = path.path_no_extension();
+    let extension = &AsRef::<str>::as_ref(path)[path_no_extension.len()..];
+    *path = P::from_raw(format!("{}{}{}", path_no_extension, suffix, extension));
+}
+
+fn suffix_image_path(path_no_extension: &mut LosslessImagePath, suffix: &str) {
+    for path in path_no_extension.to_vec_mut() {
+        path.push_str(suffix);
+    }
+}
+
+fn suffix_swap_outputs(
+    permutation_output_path_prefix: Option<&mut PermutationPath>,
+    swap_history_output_path_no_extension: Option<&mut String>,
+    checkpoint: Option<&mut SwapCheckpointConfig>,
+    parameters: &mut SwapParametersConfig,
+    continues_logs: bool,
+    suffix: &str,
+) {
+    if let Some(path_prefix) = permutation_output_path_prefix {
+        suffix_vector_field_path(path_prefix, suffix);
+    }
+    if let Some(path) = swap_history_output_path_no_extension.filter(|_| !continues_logs) {
+        path.push_str(suffix);
+    }
+    if let Some(checkpoint_config) = checkpoint {
+        suffix_vector_field_path(&mut checkpoint_config.path_prefix, suffix);
+    }
+    if let Some(animation_config) = parameters.animation.as_mut() {
+        animation_config.path_no_extension.push_str(suffix);
+    }
+    if let Some(log_config) = parameters
+        .statistics_log
+        .as_mut()
+        .filter(|_| !continues_logs)
+    {
+        log_config.path = add_suffix_before_extension(&log_config.path, suffix);
+    }
+}
+
+fn suffix_workflow_operation_outputs(operation: &mut WorkflowOperationConfig, suffix: &str) {
+    match operation {
+        WorkflowOperationConfig::CreateDisplacementGoal {
+            displacement_goal_output_path_no_extension,
+            ..
+        } => {
+            if let Some(path) = displacement_goal_output_path_no_extension.as_mut() {
+                suffix_vector_field_path(path, suffix);
+            }
+        }
+        WorkflowOperationConfig::CreatePermutation {
+            permutation_output_path_no_extension,
+        } => {
+            if let Some(path) = permutation_output_path_no_extension.as_mut() {
+                suffix_vector_field_path(path, suffix);
+            }
+        }
+        WorkflowOperationConfig::Permute {
+            permuted_image_output_path_no_extension,
+            ..
+        } => {
+            if let Some(path) = permuted_image_output_path_no_extension.as_mut() {
+                suffix_image_path(path, suffix);
+            }
+        }
+        WorkflowOperationConfig::Swap {
+            permutation_output_path_prefix,
+            swap_history_output_path_no_extension,
+            parameters,
+            ..
+        } => suffix_swap_outputs(
+            permutation_output_path_prefix.as_mut(),
+            swap_history_output_path_no_extension.as_mut(),
+            None,
+            parameters,
+            false,
+            suffix,
+        ),
+        WorkflowOperationConfig::ValidatePermutation { .. } => (),
+    }
+}
+
+#[cfg(test)]
+mod tests;
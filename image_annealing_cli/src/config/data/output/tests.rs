@@ -0,0 +1,330 @@
+mod output_files {
+    use super::super::{existing_outputs, OutputFiles};
+    use std::error::Error;
+    use std::fs::File;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn directory() {
+        assert_eq!(
+            OutputFiles::Exact(String::from("out/permutation.png")).directory(),
+            Some(Path::new("out"))
+        );
+        assert_eq!(
+            OutputFiles::Exact(String::from("log.csv")).directory(),
+            None
+        );
+        assert_eq!(
+            OutputFiles::Pattern {
+                pattern: String::from("out/swap_*.png"),
+                directory: String::from("out"),
+                excluded: Vec::new(),
+            }
+            .directory(),
+            Some(Path::new("out"))
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            OutputFiles::Exact(String::from("out/log.csv")).to_string(),
+            "out/log.csv"
+        );
+        assert_eq!(
+            OutputFiles::Pattern {
+                pattern: String::from("out/swap_*.png"),
+                directory: String::from("out"),
+                excluded: Vec::new(),
+            }
+            .to_string(),
+            "out/swap_*.png"
+        );
+    }
+
+    #[test]
+    fn existing() -> Result<(), Box<dyn Error>> {
+        let paths = [
+            test_util::make_test_output_path_string(["cli_output_files_existing_1.png"]),
+            test_util::make_test_output_path_string(["cli_output_files_existing_2.png"]),
+        ];
+        for path in paths.iter() {
+            File::create(path)?;
+        }
+        let pattern = OutputFiles::Pattern {
+            pattern: test_util::make_test_output_path_string(["cli_output_files_existing_*.png"]),
+            directory: test_util::make_test_output_path_string::<_, &str>([]),
+            excluded: vec![paths[0].clone()],
+        };
+        let outputs = [
+            OutputFiles::Exact(paths[1].clone()),
+            OutputFiles::Exact(test_util::make_test_output_path_string([
+                "cli_output_files_existing_3.png",
+            ])),
+            pattern.clone(),
+        ];
+        let exact = outputs[0].existing();
+        let not_found = outputs[1].existing();
+        let matched = pattern.existing();
+        let all = existing_outputs(&outputs);
+        for path in paths.iter() {
+            std::fs::remove_file(path)?;
+        }
+        assert_eq!(exact?, vec![PathBuf::from(&paths[1])]);
+        assert!(not_found?.is_empty());
+        assert_eq!(matched?, vec![PathBuf::from(&paths[1])]);
+        assert_eq!(all?, vec![PathBuf::from(&paths[1])]);
+        Ok(())
+    }
+}
+
+mod algorithm_config_outputs {
+    use crate::config::{
+        AlgorithmConfig, DisplacementGoalPath, ImagePath, IterationCount, LosslessImagePath,
+        OutputFiles, PermutationPath, PermuteImageConfig, SwapCheckpointConfig,
+        SwapParametersConfig, SwapStatisticsLogConfig, SwapStatisticsLogFormat, SwapStopConfig,
+        SwapStopThreshold,
+    };
+    use image_annealing::compute;
+    use std::num::NonZeroUsize;
+
+    fn exact(path: &str) -> OutputFiles {
+        OutputFiles::Exact(String::from(path))
+    }
+
+    fn make_swap_config(stop: SwapStopConfig) -> AlgorithmConfig {
+        AlgorithmConfig::Swap {
+            candidate_permutation: PermutationPath::from_raw_clone("in/permutation.png"),
+            resume: None,
+            displacement_goal: DisplacementGoalPath::from_raw_clone("in/goal.png"),
+            permutation_output_path_prefix: PermutationPath::from_raw_clone("out/permutation"),
+            swap_history_output_path_no_extension: Some(String::from("out/history")),
+            checkpoint: Some(SwapCheckpointConfig {
+                path_prefix: PermutationPath::from_raw_clone("out/checkpoint"),
+                interval: IterationCount(NonZeroUsize::new(2).unwrap()),
+            }),
+            parameters: SwapParametersConfig {
+                stop,
+                swap_acceptance_threshold: 2.0,
+                swap_pass_sequence: compute::SwapPassSequence::from_passes([
+                    compute::SwapPass::Vertical,
+                    compute::SwapPass::OffsetVertical,
+                ])
+                .unwrap(),
+                output_intermediate_permutations: false,
+                intermediate_permutations: Default::default(),
+                animation: None,
+                statistics_log: Some(SwapStatisticsLogConfig {
+                    path: String::from("out/log.csv"),
+                    format: SwapStatisticsLogFormat::Csv,
+                }),
+            },
+        }
+    }
+
+    #[test]
+    fn permute() {
+        let config = AlgorithmConfig::Permute {
+            candidate_permutation: PermutationPath::from_raw_clone("permutation.png"),
+            images: vec![PermuteImageConfig {
+                original_image: LosslessImagePath::Rgba8(String::from("image.png")),
+                original_image_conversion: Default::default(),
+                permuted_image_output_path_no_extension: LosslessImagePath::Rgba8x2(
+                    String::from("out/permuted1"),
+                    String::from("out/permuted2"),
+                ),
+            }],
+        };
+        assert_eq!(
+            config.outputs(),
+            vec![exact("out/permuted1.png"), exact("out/permuted2.png")]
+        );
+    }
+
+    #[test]
+    fn swap_bounded() {
+        let config = make_swap_config(SwapStopConfig::Bounded {
+            iteration_count: IterationCount(NonZeroUsize::new(4).unwrap()),
+            threshold: None,
+        });
+        assert_eq!(
+            config.outputs(),
+            vec![
+                exact("out/permutation_round_3_pass_1_offset_vertical.png"),
+                exact("out/history.swaphist"),
+                exact("out/checkpoint_round_1.png"),
+                exact("out/checkpoint_round_1.json"),
+                exact("out/checkpoint_round_3.png"),
+                exact("out/checkpoint_round_3.json"),
+                exact("out/log.csv"),
+            ]
+        );
+    }
+
+    #[test]
+    fn swap_unbounded() {
+        let config = make_swap_config(SwapStopConfig::Unbounded(SwapStopThreshold::SwapsAccepted(
+            1,
+        )));
+        let pattern = |pattern: &str| OutputFiles::Pattern {
+            pattern: String::from(pattern),
+            directory: String::from("out"),
+            excluded: Vec::new(),
+        };
+        assert_eq!(
+            config.outputs(),
+            vec![
+                pattern("out/permutation_round_*_pass_*_*.png"),
+                exact("out/history.swaphist"),
+                pattern("out/checkpoint_round_*.png"),
+                pattern("out/checkpoint_round_*.json"),
+                exact("out/log.csv"),
+            ]
+        );
+    }
+}
+
+mod output_policy {
+    use super::super::{ExistingOutputPolicy, OutputPolicyConfig};
+    use crate::config::{AlgorithmConfig, Config, ImagePath, PermutationPath};
+    use image_annealing::compute;
+    use image_annealing::ImageDimensions;
+    use std::error::Error;
+    use std::fs::File;
+
+    fn create_permutation_config(
+        path: &str,
+        create_directories: bool,
+        existing_files: ExistingOutputPolicy,
+    ) -> Config {
+        Config {
+            algorithm: AlgorithmConfig::CreatePermutation {
+                permutation_output_path_no_extension: PermutationPath::from_raw_clone(path),
+            },
+            dispatcher: compute::Config {
+                image_dimensions: ImageDimensions::try_new(20, 25).unwrap(),
+                boundary: Default::default(),
+            },
+            output_policy: OutputPolicyConfig {
+                create_directories,
+                existing_files,
+            },
+        }
+    }
+
+    #[test]
+    fn overwrite() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_output_path_string(["cli_output_policy_overwrite.png"]);
+        File::create(&path)?;
+        let mut config = create_permutation_config(&path, false, ExistingOutputPolicy::Overwrite);
+        let checked = config.check_output_policy();
+        let applied = config.apply_output_policy();
+        std::fs::remove_file(&path)?;
+        checked?;
+        applied?;
+        assert_eq!(
+            config,
+            create_permutation_config(&path, false, ExistingOutputPolicy::Overwrite)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn refuse() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_output_path_string(["cli_output_policy_refuse.png"]);
+        File::create(&path)?;
+        let config = create_permutation_config(&path, false, ExistingOutputPolicy::Refuse);
+        let result = config.check_output_policy();
+        std::fs::remove_file(&path)?;
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!(
+                "refusing to overwrite existing output files: '{}' (configuration field `existing_files`)",
+                path
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn refuse_none_existing() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_output_path_string(["cli_output_policy_refuse_none.png"]);
+        let mut config = create_permutation_config(&path, false, ExistingOutputPolicy::Refuse);
+        config.check_output_policy()?;
+        config.apply_output_policy()?;
+        assert_eq!(
+            config,
+            create_permutation_config(&path, false, ExistingOutputPolicy::Refuse)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn suffix() -> Result<(), Box<dyn Error>> {
+        let path_no_extension =
+            test_util::make_test_output_path_string(["cli_output_policy_suffix"]);
+        let existing = [
+            format!("{}.png", path_no_extension),
+            format!("{}_1.png", path_no_extension),
+        ];
+        for path in existing.iter() {
+            File::create(path)?;
+        }
+        let mut config =
+            create_permutation_config(&existing[0], false, ExistingOutputPolicy::Suffix);
+        let checked = config.check_output_policy();
+        let applied = config.apply_output_policy();
+        for path in existing.iter() {
+            std::fs::remove_file(path)?;
+        }
+        checked?;
+        applied?;
+        assert_eq!(
+            config,
+            create_permutation_config(
+                &format!("{}_2.png", path_no_extension),
+                false,
+                ExistingOutputPolicy::Suffix
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn create_directories() -> Result<(), Box<dyn Error>> {
+        let directory = test_util::make_test_output_path(["cli_output_policy_create_directories"]);
+        if directory.exists() {
+            std::fs::remove_dir_all(&directory)?;
+        }
+        let path = directory.join("nested").join("permutation");
+        let mut config = create_permutation_config(
+            &path.to_string_lossy(),
+            true,
+            ExistingOutputPolicy::Overwrite,
+        );
+        config.check_output_policy()?;
+        let checked_without_creating = !directory.exists();
+        config.apply_output_policy()?;
+        let created = path.parent().unwrap().is_dir();
+        std::fs::remove_dir_all(&directory)?;
+        assert!(checked_without_creating);
+        assert!(created);
+        Ok(())
+    }
+
+    #[test]
+    fn create_directories_under_file() -> Result<(), Box<dyn Error>> {
+        let file = test_util::make_test_output_path(["cli_output_policy_under_file"]);
+        File::create(&file)?;
+        let path = file.join("nested").join("permutation");
+        let config = create_permutation_config(
+            &path.to_string_lossy(),
+            true,
+            ExistingOutputPolicy::Overwrite,
+        );
+        let result = config.check_output_policy();
+        std::fs::remove_file(&file)?;
+        test_util::assert_error_contains(result, "(configuration field `create_directories`)");
+        Ok(())
+    }
+}
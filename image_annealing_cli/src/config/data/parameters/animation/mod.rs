@@ -0,0 +1,97 @@
+use super::super::{ImageConversionPolicy, ResolvePaths};
+use image_annealing::compute::format::{self, AnimationParameters};
+use image_annealing_cli_util::config_file::ConfigFieldContext;
+use image_annealing_cli_util::io::{self, PathContext, PathVariableError};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::num::NonZeroUsize;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq)]
+pub enum AnimationFormat {
+    #[default]
+    Apng,
+    Gif,
+}
+
+impl From<AnimationFormat> for format::AnimationFormat {
+    fn from(value: AnimationFormat) -> Self {
+        match value {
+            AnimationFormat::Apng => Self::Apng,
+            AnimationFormat::Gif => Self::Gif,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, JsonSchema)]
+pub struct UnverifiedSwapAnimationConfig {
+    pub image: String,
+    pub image_conversion: Option<ImageConversionPolicy>,
+    pub path_no_extension: String,
+    pub format: Option<AnimationFormat>,
+    pub frame_delay_milliseconds: Option<u16>,
+    pub frame_stride: Option<usize>,
+    pub loop_count: Option<u16>,
+}
+
+impl ResolvePaths for UnverifiedSwapAnimationConfig {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        self.image.resolve_paths(context)?;
+        self.path_no_extension.resolve_paths(context)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InvalidFrameStrideError;
+
+impl fmt::Display for InvalidFrameStrideError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "animation frame stride cannot be zero")
+    }
+}
+
+impl Error for InvalidFrameStrideError {}
+
+/// An animation of an image being permuted by the intermediate permutations of a swap run
+///
+/// The first frame shows the image permuted by the initial permutation, and each following
+/// frame shows the image after another `frame_stride` swap passes. The last frame always shows
+/// the image permuted by the final permutation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapAnimationConfig {
+    pub image: String,
+    pub image_conversion: ImageConversionPolicy,
+    pub path_no_extension: String,
+    pub format: AnimationFormat,
+    pub frame_stride: NonZeroUsize,
+    pub parameters: AnimationParameters,
+}
+
+impl TryFrom<UnverifiedSwapAnimationConfig> for SwapAnimationConfig {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: UnverifiedSwapAnimationConfig) -> Result<Self, Self::Error> {
+        Ok(Self {
+            image: io::convert_and_check_input_file_path(value.image).in_field("image")?,
+            image_conversion: value.image_conversion.unwrap_or_default(),
+            path_no_extension: io::convert_path_separators(value.path_no_extension),
+            format: value.format.unwrap_or_default(),
+            frame_stride: NonZeroUsize::new(value.frame_stride.unwrap_or(1))
+                .ok_or(InvalidFrameStrideError)
+                .in_field("frame_stride")?,
+            parameters: {
+                let default_parameters = AnimationParameters::default();
+                AnimationParameters {
+                    frame_delay_milliseconds: value
+                        .frame_delay_milliseconds
+                        .unwrap_or(default_parameters.frame_delay_milliseconds),
+                    loop_count: value.loop_count.unwrap_or(default_parameters.loop_count),
+                }
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
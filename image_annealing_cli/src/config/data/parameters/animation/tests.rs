@@ -0,0 +1,80 @@
+mod swap_animation_config_try_from_unverified_swap_animation_config {
+    use super::super::super::super::ImageConversionPolicy;
+    use super::super::{AnimationFormat, SwapAnimationConfig, UnverifiedSwapAnimationConfig};
+    use image_annealing::compute::format::AnimationParameters;
+    use std::error::Error;
+    use std::num::NonZeroUsize;
+
+    fn make_unverified_config() -> UnverifiedSwapAnimationConfig {
+        UnverifiedSwapAnimationConfig {
+            image: test_util::make_test_data_path_string(["image", "image", "stripes.png"]),
+            image_conversion: None,
+            path_no_extension: String::from("animation"),
+            format: None,
+            frame_delay_milliseconds: None,
+            frame_stride: None,
+            loop_count: None,
+        }
+    }
+
+    #[test]
+    fn defaults() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            SwapAnimationConfig::try_from(make_unverified_config())?,
+            SwapAnimationConfig {
+                image: test_util::make_test_data_path_string(["image", "image", "stripes.png"]),
+                image_conversion: ImageConversionPolicy::Strict,
+                path_no_extension: String::from("animation"),
+                format: AnimationFormat::Apng,
+                frame_stride: NonZeroUsize::new(1).unwrap(),
+                parameters: AnimationParameters::default(),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn all_fields() -> Result<(), Box<dyn Error>> {
+        let config = SwapAnimationConfig::try_from(UnverifiedSwapAnimationConfig {
+            image_conversion: Some(ImageConversionPolicy::Lossless),
+            format: Some(AnimationFormat::Gif),
+            frame_delay_milliseconds: Some(20),
+            frame_stride: Some(4),
+            loop_count: Some(3),
+            ..make_unverified_config()
+        })?;
+        assert_eq!(config.image_conversion, ImageConversionPolicy::Lossless);
+        assert_eq!(config.format, AnimationFormat::Gif);
+        assert_eq!(config.frame_stride.get(), 4);
+        assert_eq!(
+            config.parameters,
+            AnimationParameters {
+                frame_delay_milliseconds: 20,
+                loop_count: 3,
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn zero_frame_stride() {
+        test_util::assert_error_contains(
+            SwapAnimationConfig::try_from(UnverifiedSwapAnimationConfig {
+                frame_stride: Some(0),
+                ..make_unverified_config()
+            }),
+            "animation frame stride cannot be zero",
+        );
+    }
+
+    #[test]
+    fn missing_image() {
+        test_util::assert_error_contains(
+            SwapAnimationConfig::try_from(UnverifiedSwapAnimationConfig {
+                image: test_util::make_test_data_path_string(["image", "image", "not_found.png"]),
+                ..make_unverified_config()
+            }),
+            "does not exist",
+        );
+    }
+}
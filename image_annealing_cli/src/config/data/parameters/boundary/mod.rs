@@ -0,0 +1,30 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+pub enum BoundaryMode {
+    #[default]
+    Bounded,
+    Toroidal,
+}
+
+impl From<image_annealing::BoundaryMode> for BoundaryMode {
+    fn from(value: image_annealing::BoundaryMode) -> Self {
+        match value {
+            image_annealing::BoundaryMode::Bounded => Self::Bounded,
+            image_annealing::BoundaryMode::Toroidal => Self::Toroidal,
+        }
+    }
+}
+
+impl From<BoundaryMode> for image_annealing::BoundaryMode {
+    fn from(value: BoundaryMode) -> Self {
+        match value {
+            BoundaryMode::Bounded => Self::Bounded,
+            BoundaryMode::Toroidal => Self::Toroidal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,33 @@
+mod from_and_into_boundary_mode {
+    use super::super::BoundaryMode;
+    use image_annealing::BoundaryMode as ImageAnnealingBoundaryMode;
+
+    #[test]
+    fn bounded() {
+        assert_eq!(
+            BoundaryMode::from(ImageAnnealingBoundaryMode::Bounded),
+            BoundaryMode::Bounded
+        );
+        assert_eq!(
+            ImageAnnealingBoundaryMode::from(BoundaryMode::Bounded),
+            ImageAnnealingBoundaryMode::Bounded
+        );
+    }
+
+    #[test]
+    fn toroidal() {
+        assert_eq!(
+            BoundaryMode::from(ImageAnnealingBoundaryMode::Toroidal),
+            BoundaryMode::Toroidal
+        );
+        assert_eq!(
+            ImageAnnealingBoundaryMode::from(BoundaryMode::Toroidal),
+            ImageAnnealingBoundaryMode::Toroidal
+        );
+    }
+}
+
+#[test]
+fn default() {
+    assert_eq!(super::BoundaryMode::default(), super::BoundaryMode::Bounded);
+}
@@ -0,0 +1,335 @@
+use image_annealing_cli_util::config_file::ConfigFieldContext;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::num::NonZeroUsize;
+
+#[derive(Clone, Copy, Deserialize, JsonSchema)]
+pub enum UnverifiedIntermediatePermutationCadence {
+    EveryPass(usize),
+    EveryRound(usize),
+    EndOfRound,
+    Logarithmic,
+}
+
+#[derive(Clone, Deserialize, JsonSchema)]
+pub struct UnverifiedIntermediatePermutationsConfig {
+    pub cadence: Option<UnverifiedIntermediatePermutationCadence>,
+    /// Template of the text appended to the output path prefix to name each permutation,
+    /// before the file extension (defaults to `_round_{round}_pass_{pass_index}_{pass_name}`)
+    pub name_template: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InvalidIntermediatePermutationCadenceError;
+
+impl fmt::Display for InvalidIntermediatePermutationCadenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "intermediate permutation output interval cannot be zero")
+    }
+}
+
+impl Error for InvalidIntermediatePermutationCadenceError {}
+
+/// Which of the permutations produced by the swap passes of a run are output,
+/// in addition to the final permutation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IntermediatePermutationCadence {
+    /// The permutation produced by every given number of swap passes, counted over all rounds
+    EveryPass(NonZeroUsize),
+    /// The permutation at the end of every given number of rounds
+    EveryRound(NonZeroUsize),
+    /// The permutation at the end of every round
+    EndOfRound,
+    /// The permutations produced by the first, second, fourth, eighth, ... swap passes
+    Logarithmic,
+}
+
+impl IntermediatePermutationCadence {
+    /// Returns whether to output the permutation produced by the swap pass
+    /// with the given index in the round with the given index
+    pub fn includes(&self, round_index: usize, pass_index: usize, passes_per_round: usize) -> bool {
+        let overall_pass_index = round_index * passes_per_round + pass_index;
+        let end_of_round = pass_index + 1 == passes_per_round;
+        match self {
+            Self::EveryPass(interval) => overall_pass_index % interval.get() == interval.get() - 1,
+            Self::EveryRound(interval) => {
+                end_of_round && round_index % interval.get() == interval.get() - 1
+            }
+            Self::EndOfRound => end_of_round,
+            Self::Logarithmic => (overall_pass_index + 1).is_power_of_two(),
+        }
+    }
+
+    /// Returns whether each round outputs at most one permutation
+    fn per_round(&self) -> bool {
+        matches!(self, Self::EveryRound(_) | Self::EndOfRound)
+    }
+}
+
+impl TryFrom<UnverifiedIntermediatePermutationCadence> for IntermediatePermutationCadence {
+    type Error = InvalidIntermediatePermutationCadenceError;
+
+    fn try_from(value: UnverifiedIntermediatePermutationCadence) -> Result<Self, Self::Error> {
+        match value {
+            UnverifiedIntermediatePermutationCadence::EveryPass(interval) => Ok(Self::EveryPass(
+                NonZeroUsize::new(interval).ok_or(InvalidIntermediatePermutationCadenceError)?,
+            )),
+            UnverifiedIntermediatePermutationCadence::EveryRound(interval) => Ok(Self::EveryRound(
+                NonZeroUsize::new(interval).ok_or(InvalidIntermediatePermutationCadenceError)?,
+            )),
+            UnverifiedIntermediatePermutationCadence::EndOfRound => Ok(Self::EndOfRound),
+            UnverifiedIntermediatePermutationCadence::Logarithmic => Ok(Self::Logarithmic),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PermutationNameTemplateError {
+    UnknownPlaceholder(String),
+    UnterminatedPlaceholder,
+    UnmatchedBrace,
+    InvalidWidth(String),
+    NotUnique,
+}
+
+impl fmt::Display for PermutationNameTemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownPlaceholder(name) => write!(
+                f,
+                "unknown placeholder '{{{}}}' in name template, expected one of {{sequence}}, {{round}}, {{pass_index}} or {{pass_name}}",
+                name
+            ),
+            Self::UnterminatedPlaceholder => {
+                write!(f, "placeholder in name template is missing a closing '}}'")
+            }
+            Self::UnmatchedBrace => write!(
+                f,
+                "unmatched '}}' in name template (write '}}}}' for a literal '}}')"
+            ),
+            Self::InvalidWidth(width) => write!(
+                f,
+                "invalid width '{}' of placeholder in name template, expected a number of digits",
+                width
+            ),
+            Self::NotUnique => write!(
+                f,
+                "name template would give several permutations the same name, as it refers to neither {{sequence}} nor {{round}} together with {{pass_index}} or {{pass_name}}"
+            ),
+        }
+    }
+}
+
+impl Error for PermutationNameTemplateError {}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum PlaceholderKind {
+    Sequence,
+    Round,
+    PassIndex,
+    PassName,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum TemplateSegment {
+    Literal(String),
+    Placeholder { kind: PlaceholderKind, width: usize },
+}
+
+/// The values that a name template can refer to
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PermutationNameFields<'a> {
+    /// The number of permutations that were output earlier in the run
+    pub sequence: usize,
+    pub round: usize,
+    pub pass_index: usize,
+    pub pass_name: &'a str,
+}
+
+/// A template for the text appended to the output path prefix of a swap run
+/// to name each permutation that it outputs
+///
+/// Placeholders are enclosed in braces, as in `{round}`, and a placeholder of a number
+/// can specify a width to which the number is padded with zeros, as in `{sequence:5}`.
+/// Literal braces are written as `{{` and `}}`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermutationNameTemplate(Vec<TemplateSegment>);
+
+impl PermutationNameTemplate {
+    const DEFAULT: &'static str = "_round_{round}_pass_{pass_index}_{pass_name}";
+
+    pub fn parse(template: &str) -> Result<Self, PermutationNameTemplateError> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => return Err(PermutationNameTemplateError::UnmatchedBrace),
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => placeholder.push(c),
+                            None => {
+                                return Err(PermutationNameTemplateError::UnterminatedPlaceholder)
+                            }
+                        }
+                    }
+                    if !literal.is_empty() {
+                        segments.push(TemplateSegment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Self::parse_placeholder(&placeholder)?);
+                }
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(TemplateSegment::Literal(literal));
+        }
+        Ok(Self(segments))
+    }
+
+    fn parse_placeholder(
+        placeholder: &str,
+    ) -> Result<TemplateSegment, PermutationNameTemplateError> {
+        let (name, width) = match placeholder.split_once(':') {
+            Some((name, width)) => (
+                name,
+                width
+                    .parse()
+                    .map_err(|_| PermutationNameTemplateError::InvalidWidth(String::from(width)))?,
+            ),
+            None => (placeholder, 0),
+        };
+        let kind = match name {
+            "sequence" => PlaceholderKind::Sequence,
+            "round" => PlaceholderKind::Round,
+            "pass_index" => PlaceholderKind::PassIndex,
+            "pass_name" => PlaceholderKind::PassName,
+            _ => {
+                return Err(PermutationNameTemplateError::UnknownPlaceholder(
+                    String::from(name),
+                ))
+            }
+        };
+        Ok(TemplateSegment::Placeholder { kind, width })
+    }
+
+    fn refers_to(&self, kind: PlaceholderKind) -> bool {
+        self.0.iter().any(|segment| {
+            matches!(segment, TemplateSegment::Placeholder { kind: other, .. } if *other == kind)
+        })
+    }
+
+    /// Returns whether the template gives different names to all permutations
+    /// output at the given cadence
+    fn is_unique(&self, cadence: &IntermediatePermutationCadence) -> bool {
+        self.refers_to(PlaceholderKind::Sequence)
+            || (self.refers_to(PlaceholderKind::Round)
+                && (cadence.per_round()
+                    || self.refers_to(PlaceholderKind::PassIndex)
+                    || self.refers_to(PlaceholderKind::PassName)))
+    }
+
+    pub fn render(&self, fields: &PermutationNameFields) -> String {
+        let mut name = String::new();
+        for segment in self.0.iter() {
+            match segment {
+                TemplateSegment::Literal(text) => name.push_str(text),
+                TemplateSegment::Placeholder { kind, width } => {
+                    let width = *width;
+                    match kind {
+                        PlaceholderKind::Sequence => {
+                            name.push_str(&format!("{:0width$}", fields.sequence, width = width))
+                        }
+                        PlaceholderKind::Round => {
+                            name.push_str(&format!("{:0width$}", fields.round, width = width))
+                        }
+                        PlaceholderKind::PassIndex => {
+                            name.push_str(&format!("{:0width$}", fields.pass_index, width = width))
+                        }
+                        PlaceholderKind::PassName => name.push_str(fields.pass_name),
+                    }
+                }
+            }
+        }
+        name
+    }
+
+    /// Returns a glob pattern matching every name that the template can render
+    pub fn pattern(&self) -> String {
+        let mut pattern = String::new();
+        for segment in self.0.iter() {
+            match segment {
+                TemplateSegment::Literal(text) => pattern.push_str(&glob::Pattern::escape(text)),
+                // Consecutive wildcards are only valid as a path component of their own
+                TemplateSegment::Placeholder { .. } if pattern.ends_with('*') => (),
+                TemplateSegment::Placeholder { .. } => pattern.push('*'),
+            }
+        }
+        pattern
+    }
+}
+
+impl Default for PermutationNameTemplate {
+    fn default() -> Self {
+        Self::parse(Self::DEFAULT).unwrap()
+    }
+}
+
+/// How the intermediate permutations of a swap run are output, if they are output
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntermediatePermutationsConfig {
+    pub cadence: IntermediatePermutationCadence,
+    pub name_template: PermutationNameTemplate,
+}
+
+impl Default for IntermediatePermutationsConfig {
+    fn default() -> Self {
+        Self {
+            cadence: IntermediatePermutationCadence::EveryPass(NonZeroUsize::new(1).unwrap()),
+            name_template: Default::default(),
+        }
+    }
+}
+
+impl TryFrom<UnverifiedIntermediatePermutationsConfig> for IntermediatePermutationsConfig {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: UnverifiedIntermediatePermutationsConfig) -> Result<Self, Self::Error> {
+        let default = Self::default();
+        let cadence = match value.cadence {
+            Some(cadence) => cadence.try_into().in_field("cadence")?,
+            None => default.cadence,
+        };
+        let name_template = match value.name_template {
+            Some(template) => {
+                let template =
+                    PermutationNameTemplate::parse(&template).in_field("name_template")?;
+                if !template.is_unique(&cadence) {
+                    return Err(PermutationNameTemplateError::NotUnique).in_field("name_template");
+                }
+                template
+            }
+            None => default.name_template,
+        };
+        Ok(Self {
+            cadence,
+            name_template,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,195 @@
+mod intermediate_permutation_cadence {
+    use super::super::IntermediatePermutationCadence;
+    use std::num::NonZeroUsize;
+
+    fn included(cadence: IntermediatePermutationCadence) -> Vec<(usize, usize)> {
+        (0..4)
+            .flat_map(|round_index| (0..3).map(move |pass_index| (round_index, pass_index)))
+            .filter(|(round_index, pass_index)| cadence.includes(*round_index, *pass_index, 3))
+            .collect()
+    }
+
+    #[test]
+    fn every_pass() {
+        assert_eq!(
+            included(IntermediatePermutationCadence::EveryPass(
+                NonZeroUsize::new(5).unwrap()
+            )),
+            vec![(1, 1), (3, 0)]
+        );
+    }
+
+    #[test]
+    fn every_round() {
+        assert_eq!(
+            included(IntermediatePermutationCadence::EveryRound(
+                NonZeroUsize::new(2).unwrap()
+            )),
+            vec![(1, 2), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn end_of_round() {
+        assert_eq!(
+            included(IntermediatePermutationCadence::EndOfRound),
+            vec![(0, 2), (1, 2), (2, 2), (3, 2)]
+        );
+    }
+
+    #[test]
+    fn logarithmic() {
+        assert_eq!(
+            included(IntermediatePermutationCadence::Logarithmic),
+            vec![(0, 0), (0, 1), (1, 0), (2, 1)]
+        );
+    }
+}
+
+mod permutation_name_template {
+    use super::super::{
+        PermutationNameFields, PermutationNameTemplate, PermutationNameTemplateError,
+    };
+
+    const FIELDS: PermutationNameFields = PermutationNameFields {
+        sequence: 7,
+        round: 2,
+        pass_index: 1,
+        pass_name: "offset_vertical",
+    };
+
+    #[test]
+    fn default() {
+        assert_eq!(
+            PermutationNameTemplate::default().render(&FIELDS),
+            "_round_2_pass_1_offset_vertical"
+        );
+    }
+
+    #[test]
+    fn width() {
+        assert_eq!(
+            PermutationNameTemplate::parse("_{sequence:4}_{round:2}")
+                .unwrap()
+                .render(&FIELDS),
+            "_0007_02"
+        );
+    }
+
+    #[test]
+    fn escaped_braces() {
+        assert_eq!(
+            PermutationNameTemplate::parse("{{{pass_name}}}")
+                .unwrap()
+                .render(&FIELDS),
+            "{offset_vertical}"
+        );
+    }
+
+    #[test]
+    fn pattern() {
+        assert_eq!(
+            PermutationNameTemplate::default().pattern(),
+            "_round_*_pass_*_*"
+        );
+        assert_eq!(
+            PermutationNameTemplate::parse("[{round}]{pass_index}{pass_name}")
+                .unwrap()
+                .pattern(),
+            "[[]*[]]*"
+        );
+    }
+
+    #[test]
+    fn unknown_placeholder() {
+        assert_eq!(
+            PermutationNameTemplate::parse("_{pass}"),
+            Err(PermutationNameTemplateError::UnknownPlaceholder(
+                String::from("pass")
+            ))
+        );
+    }
+
+    #[test]
+    fn unterminated_placeholder() {
+        assert_eq!(
+            PermutationNameTemplate::parse("_{round"),
+            Err(PermutationNameTemplateError::UnterminatedPlaceholder)
+        );
+    }
+
+    #[test]
+    fn unmatched_brace() {
+        assert_eq!(
+            PermutationNameTemplate::parse("_round}"),
+            Err(PermutationNameTemplateError::UnmatchedBrace)
+        );
+    }
+
+    #[test]
+    fn invalid_width() {
+        assert_eq!(
+            PermutationNameTemplate::parse("_{sequence:x}"),
+            Err(PermutationNameTemplateError::InvalidWidth(String::from(
+                "x"
+            )))
+        );
+    }
+}
+
+mod intermediate_permutations_config_try_from_unverified_intermediate_permutations_config {
+    use super::super::{
+        IntermediatePermutationCadence, IntermediatePermutationsConfig, PermutationNameTemplate,
+        UnverifiedIntermediatePermutationCadence, UnverifiedIntermediatePermutationsConfig,
+    };
+    use std::error::Error;
+
+    #[test]
+    fn defaults() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            IntermediatePermutationsConfig::try_from(UnverifiedIntermediatePermutationsConfig {
+                cadence: None,
+                name_template: None,
+            })?,
+            IntermediatePermutationsConfig::default()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn round_template_with_round_cadence() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            IntermediatePermutationsConfig::try_from(UnverifiedIntermediatePermutationsConfig {
+                cadence: Some(UnverifiedIntermediatePermutationCadence::EndOfRound),
+                name_template: Some(String::from("_{round:3}")),
+            })?,
+            IntermediatePermutationsConfig {
+                cadence: IntermediatePermutationCadence::EndOfRound,
+                name_template: PermutationNameTemplate::parse("_{round:3}")?,
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn zero_interval() {
+        test_util::assert_error_contains(
+            IntermediatePermutationsConfig::try_from(UnverifiedIntermediatePermutationsConfig {
+                cadence: Some(UnverifiedIntermediatePermutationCadence::EveryPass(0)),
+                name_template: None,
+            }),
+            "intermediate permutation output interval cannot be zero (configuration field `cadence`)",
+        );
+    }
+
+    #[test]
+    fn not_unique() {
+        test_util::assert_error_contains(
+            IntermediatePermutationsConfig::try_from(UnverifiedIntermediatePermutationsConfig {
+                cadence: Some(UnverifiedIntermediatePermutationCadence::Logarithmic),
+                name_template: Some(String::from("_{round}")),
+            }),
+            "(configuration field `name_template`)",
+        );
+    }
+}
@@ -1,7 +1,31 @@
+mod animation;
+mod boundary;
+mod intermediate;
+mod statistics;
+mod stop;
 mod swap;
+mod visualization;
 
+pub use animation::{
+    AnimationFormat, InvalidFrameStrideError, SwapAnimationConfig, UnverifiedSwapAnimationConfig,
+};
+pub use boundary::BoundaryMode;
+pub use intermediate::{
+    IntermediatePermutationCadence, IntermediatePermutationsConfig,
+    InvalidIntermediatePermutationCadenceError, PermutationNameFields, PermutationNameTemplate,
+    PermutationNameTemplateError, UnverifiedIntermediatePermutationCadence,
+    UnverifiedIntermediatePermutationsConfig,
+};
+pub use statistics::{
+    SwapStatisticsLogConfig, SwapStatisticsLogFormat, UnverifiedSwapStatisticsLogConfig,
+};
+pub use stop::{InvalidSwapStopCriterionError, SwapStopCriterion, UnverifiedSwapStopCriterion};
 pub use swap::{
     InvalidIterationCountError, IterationCount, SwapParametersConfig, SwapPass, SwapStopConfig,
     SwapStopThreshold, UnverifiedIterationCount, UnverifiedSwapParametersConfig,
     UnverifiedSwapStopConfig, UnverifiedSwapStopThreshold,
 };
+pub use visualization::{
+    InvalidVectorFieldVisualizationParametersError,
+    UnverifiedVectorFieldVisualizationParametersConfig,
+};
@@ -0,0 +1,57 @@
+use super::super::ResolvePaths;
+use image_annealing_cli_util::io::{self, PathContext, PathVariableError};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
+pub enum SwapStatisticsLogFormat {
+    /// One JSON object per line
+    JsonLines,
+    /// Comma-separated values, with a header row
+    Csv,
+}
+
+impl SwapStatisticsLogFormat {
+    /// Chooses CSV for paths with a `.csv` extension, and JSON Lines otherwise
+    fn from_path(path: &str) -> Self {
+        match Path::new(path).extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("csv") => Self::Csv,
+            _ => Self::JsonLines,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, JsonSchema)]
+pub struct UnverifiedSwapStatisticsLogConfig {
+    pub path: String,
+    pub format: Option<SwapStatisticsLogFormat>,
+}
+
+impl ResolvePaths for UnverifiedSwapStatisticsLogConfig {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        self.path.resolve_paths(context)
+    }
+}
+
+/// A log of the numbers of swaps accepted in every pass of every round of a swap run
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapStatisticsLogConfig {
+    pub path: String,
+    pub format: SwapStatisticsLogFormat,
+}
+
+impl From<UnverifiedSwapStatisticsLogConfig> for SwapStatisticsLogConfig {
+    fn from(value: UnverifiedSwapStatisticsLogConfig) -> Self {
+        let path = io::convert_path_separators(value.path);
+        Self {
+            format: value
+                .format
+                .unwrap_or_else(|| SwapStatisticsLogFormat::from_path(&path)),
+            path,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,43 @@
+mod swap_statistics_log_config_from_unverified_swap_statistics_log_config {
+    use super::super::{
+        SwapStatisticsLogConfig, SwapStatisticsLogFormat, UnverifiedSwapStatisticsLogConfig,
+    };
+
+    #[test]
+    fn default_format() {
+        assert_eq!(
+            SwapStatisticsLogConfig::from(UnverifiedSwapStatisticsLogConfig {
+                path: String::from("statistics.jsonl"),
+                format: None,
+            }),
+            SwapStatisticsLogConfig {
+                path: String::from("statistics.jsonl"),
+                format: SwapStatisticsLogFormat::JsonLines,
+            }
+        );
+    }
+
+    #[test]
+    fn csv_extension() {
+        assert_eq!(
+            SwapStatisticsLogConfig::from(UnverifiedSwapStatisticsLogConfig {
+                path: String::from("statistics.CSV"),
+                format: None,
+            })
+            .format,
+            SwapStatisticsLogFormat::Csv
+        );
+    }
+
+    #[test]
+    fn explicit_format() {
+        assert_eq!(
+            SwapStatisticsLogConfig::from(UnverifiedSwapStatisticsLogConfig {
+                path: String::from("statistics.csv"),
+                format: Some(SwapStatisticsLogFormat::JsonLines),
+            })
+            .format,
+            SwapStatisticsLogFormat::JsonLines
+        );
+    }
+}
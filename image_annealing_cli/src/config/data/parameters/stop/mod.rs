@@ -0,0 +1,185 @@
+use super::super::number::{NonnegativeProperFraction, NonnegativeRationalNumber};
+use super::swap::{
+    IterationCount, SwapStopThreshold, UnverifiedIterationCount, UnverifiedSwapStopThreshold,
+};
+use image_annealing_cli_util::config_file::ConfigFieldContext;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+#[derive(Clone, Deserialize, JsonSchema)]
+pub enum UnverifiedSwapStopCriterion {
+    IterationCount(UnverifiedIterationCount),
+    Threshold(UnverifiedSwapStopThreshold),
+    TimeLimitSeconds(f64),
+    Plateau {
+        window: usize,
+        minimum_decrease_fraction: f64,
+    },
+    Oscillation,
+    EnergyTarget(f64),
+    Any(Vec<UnverifiedSwapStopCriterion>),
+    All(Vec<UnverifiedSwapStopCriterion>),
+}
+
+#[derive(Debug, Clone)]
+pub enum InvalidSwapStopCriterionError {
+    EmptyCombination,
+    ZeroPlateauWindow,
+}
+
+impl fmt::Display for InvalidSwapStopCriterionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::EmptyCombination => write!(f, "a combination of stop criteria cannot be empty"),
+            Self::ZeroPlateauWindow => write!(f, "plateau window cannot be zero"),
+        }
+    }
+}
+
+impl Error for InvalidSwapStopCriterionError {}
+
+/// A condition, checked at the end of every round, under which a swap run stops
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SwapStopCriterion {
+    /// The given number of rounds have been run
+    IterationCount(IterationCount),
+    /// The swaps accepted in the round are at most the threshold
+    Threshold(SwapStopThreshold),
+    /// The run has lasted at least the given time
+    TimeLimit(Duration),
+    /// The number of swaps accepted in the round is not less than the number accepted
+    /// `window` rounds earlier by at least `minimum_decrease_fraction` of that number
+    Plateau {
+        window: NonZeroUsize,
+        minimum_decrease_fraction: NonnegativeProperFraction,
+    },
+    /// The permutation at the end of the round is the same as at the end of an earlier round
+    Oscillation,
+    /// The potential energy of the permutation is at most the target
+    EnergyTarget(NonnegativeRationalNumber),
+    /// At least one of the criteria is met
+    Any(Vec<SwapStopCriterion>),
+    /// All of the criteria are met
+    All(Vec<SwapStopCriterion>),
+}
+
+impl SwapStopCriterion {
+    fn contains<F: Fn(&Self) -> bool>(&self, predicate: &F) -> bool {
+        match self {
+            Self::Any(criteria) | Self::All(criteria) => criteria
+                .iter()
+                .any(|criterion| criterion.contains(predicate)),
+            criterion => predicate(criterion),
+        }
+    }
+
+    /// Whether checking the criterion requires counting the swaps accepted in each round
+    pub fn needs_swap_counts(&self) -> bool {
+        self.contains(&|criterion| matches!(criterion, Self::Threshold(_) | Self::Plateau { .. }))
+    }
+
+    /// Whether checking the criterion requires the permutation at the end of each round
+    pub fn needs_permutation(&self) -> bool {
+        self.contains(&|criterion| matches!(criterion, Self::Oscillation | Self::EnergyTarget(_)))
+    }
+
+    /// Whether checking the criterion depends on earlier rounds or on the time since the start
+    /// of the run, which a checkpoint does not record
+    pub fn depends_on_history(&self) -> bool {
+        self.contains(&|criterion| {
+            matches!(
+                criterion,
+                Self::TimeLimit(_) | Self::Plateau { .. } | Self::Oscillation
+            )
+        })
+    }
+
+    /// Whether checking the criterion requires the potential energy of each round's permutation
+    pub fn needs_energy(&self) -> bool {
+        self.contains(&|criterion| matches!(criterion, Self::EnergyTarget(_)))
+    }
+
+    /// The number of rounds after which the criterion is certain to be met, if there is one
+    pub fn iteration_limit(&self) -> Option<IterationCount> {
+        match self {
+            Self::IterationCount(count) => Some(*count),
+            Self::Any(criteria) => criteria
+                .iter()
+                .filter_map(Self::iteration_limit)
+                .min_by_key(|count| count.get()),
+            Self::All(criteria) => criteria
+                .iter()
+                .map(Self::iteration_limit)
+                .collect::<Option<Vec<_>>>()
+                .and_then(|counts| counts.into_iter().max_by_key(|count| count.get())),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<UnverifiedSwapStopCriterion> for SwapStopCriterion {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: UnverifiedSwapStopCriterion) -> Result<Self, Self::Error> {
+        match value {
+            UnverifiedSwapStopCriterion::IterationCount(count) => Ok(Self::IterationCount(
+                count.try_into().in_field("IterationCount")?,
+            )),
+            UnverifiedSwapStopCriterion::Threshold(threshold) => {
+                Ok(Self::Threshold(threshold.try_into().in_field("Threshold")?))
+            }
+            UnverifiedSwapStopCriterion::TimeLimitSeconds(seconds) => Ok(Self::TimeLimit(
+                Duration::try_from_secs_f64(
+                    NonnegativeRationalNumber::new(seconds)
+                        .in_field("TimeLimitSeconds")?
+                        .get(),
+                )
+                .in_field("TimeLimitSeconds")?,
+            )),
+            UnverifiedSwapStopCriterion::Plateau {
+                window,
+                minimum_decrease_fraction,
+            } => Ok(Self::Plateau {
+                window: NonZeroUsize::new(window)
+                    .ok_or(InvalidSwapStopCriterionError::ZeroPlateauWindow)
+                    .in_field("window")
+                    .in_field("Plateau")?,
+                minimum_decrease_fraction: minimum_decrease_fraction
+                    .try_into()
+                    .in_field("minimum_decrease_fraction")
+                    .in_field("Plateau")?,
+            }),
+            UnverifiedSwapStopCriterion::Oscillation => Ok(Self::Oscillation),
+            UnverifiedSwapStopCriterion::EnergyTarget(energy) => Ok(Self::EnergyTarget(
+                energy.try_into().in_field("EnergyTarget")?,
+            )),
+            UnverifiedSwapStopCriterion::Any(criteria) => {
+                Ok(Self::Any(try_from_combination(criteria).in_field("Any")?))
+            }
+            UnverifiedSwapStopCriterion::All(criteria) => {
+                Ok(Self::All(try_from_combination(criteria).in_field("All")?))
+            }
+        }
+    }
+}
+
+fn try_from_combination(
+    criteria: Vec<UnverifiedSwapStopCriterion>,
+) -> Result<Vec<SwapStopCriterion>, Box<dyn Error>> {
+    if criteria.is_empty() {
+        Err(Box::new(InvalidSwapStopCriterionError::EmptyCombination))
+    } else {
+        criteria
+            .into_iter()
+            .enumerate()
+            .map(|(i, criterion)| criterion.try_into().at_index(i))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests;
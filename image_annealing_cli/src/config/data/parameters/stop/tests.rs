@@ -0,0 +1,162 @@
+mod swap_stop_criterion_try_from_unverified_swap_stop_criterion {
+    use super::super::super::super::number::{
+        NonnegativeProperFraction, NonnegativeRationalNumber,
+    };
+    use super::super::super::swap::{
+        IterationCount, SwapStopThreshold, UnverifiedIterationCount, UnverifiedSwapStopThreshold,
+    };
+    use super::super::{SwapStopCriterion, UnverifiedSwapStopCriterion};
+    use std::error::Error;
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+
+    #[test]
+    fn combination() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            SwapStopCriterion::try_from(UnverifiedSwapStopCriterion::Any(vec![
+                UnverifiedSwapStopCriterion::IterationCount(UnverifiedIterationCount(100)),
+                UnverifiedSwapStopCriterion::TimeLimitSeconds(1.5),
+                UnverifiedSwapStopCriterion::All(vec![
+                    UnverifiedSwapStopCriterion::Threshold(
+                        UnverifiedSwapStopThreshold::SwapsAccepted(10)
+                    ),
+                    UnverifiedSwapStopCriterion::Plateau {
+                        window: 5,
+                        minimum_decrease_fraction: 0.25,
+                    },
+                ]),
+                UnverifiedSwapStopCriterion::Oscillation,
+                UnverifiedSwapStopCriterion::EnergyTarget(2.0),
+            ]))?,
+            SwapStopCriterion::Any(vec![
+                SwapStopCriterion::IterationCount(IterationCount(NonZeroUsize::new(100).unwrap())),
+                SwapStopCriterion::TimeLimit(Duration::from_millis(1500)),
+                SwapStopCriterion::All(vec![
+                    SwapStopCriterion::Threshold(SwapStopThreshold::SwapsAccepted(10)),
+                    SwapStopCriterion::Plateau {
+                        window: NonZeroUsize::new(5).unwrap(),
+                        minimum_decrease_fraction: NonnegativeProperFraction::new(0.25)?,
+                    },
+                ]),
+                SwapStopCriterion::Oscillation,
+                SwapStopCriterion::EnergyTarget(NonnegativeRationalNumber::new(2.0)?),
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn empty_combination() {
+        test_util::assert_error_contains(
+            SwapStopCriterion::try_from(UnverifiedSwapStopCriterion::All(Vec::new())),
+            "a combination of stop criteria cannot be empty",
+        );
+    }
+
+    #[test]
+    fn zero_plateau_window() {
+        test_util::assert_error_contains(
+            SwapStopCriterion::try_from(UnverifiedSwapStopCriterion::Plateau {
+                window: 0,
+                minimum_decrease_fraction: 0.1,
+            }),
+            "plateau window cannot be zero (configuration field `Plateau.window`)",
+        );
+    }
+
+    #[test]
+    fn invalid_combined_criterion() {
+        test_util::assert_error_contains(
+            SwapStopCriterion::try_from(UnverifiedSwapStopCriterion::Any(vec![
+                UnverifiedSwapStopCriterion::Oscillation,
+                UnverifiedSwapStopCriterion::EnergyTarget(-1.0),
+            ])),
+            "(configuration field `Any[1].EnergyTarget`)",
+        );
+    }
+
+    #[test]
+    fn negative_time_limit() {
+        test_util::assert_error_contains(
+            SwapStopCriterion::try_from(UnverifiedSwapStopCriterion::TimeLimitSeconds(-1.0)),
+            "-1 is negative",
+        );
+    }
+
+    #[test]
+    fn time_limit_overflow() {
+        test_util::assert_error_contains(
+            SwapStopCriterion::try_from(UnverifiedSwapStopCriterion::TimeLimitSeconds(1e20)),
+            "(configuration field `TimeLimitSeconds`)",
+        );
+    }
+
+    #[test]
+    fn nested_error() {
+        test_util::assert_error_contains(
+            SwapStopCriterion::try_from(UnverifiedSwapStopCriterion::Any(vec![
+                UnverifiedSwapStopCriterion::Oscillation,
+                UnverifiedSwapStopCriterion::IterationCount(UnverifiedIterationCount(0)),
+            ])),
+            "iteration count cannot be zero",
+        );
+    }
+}
+
+mod swap_stop_criterion {
+    use super::super::super::swap::{IterationCount, SwapStopThreshold};
+    use super::super::SwapStopCriterion;
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+
+    fn iteration_count(count: usize) -> SwapStopCriterion {
+        SwapStopCriterion::IterationCount(IterationCount(NonZeroUsize::new(count).unwrap()))
+    }
+
+    #[test]
+    fn needs() {
+        let criterion = SwapStopCriterion::Any(vec![
+            iteration_count(1),
+            SwapStopCriterion::All(vec![
+                SwapStopCriterion::Threshold(SwapStopThreshold::SwapsAccepted(0)),
+                SwapStopCriterion::Oscillation,
+            ]),
+        ]);
+        assert!(criterion.needs_swap_counts());
+        assert!(criterion.needs_permutation());
+        assert!(!criterion.needs_energy());
+        assert!(criterion.depends_on_history());
+        assert!(!iteration_count(1).needs_swap_counts());
+        assert!(!iteration_count(1).needs_permutation());
+        assert!(!iteration_count(1).depends_on_history());
+    }
+
+    #[test]
+    fn iteration_limit() {
+        assert_eq!(iteration_count(3).iteration_limit().unwrap().get(), 3);
+        assert_eq!(
+            SwapStopCriterion::Any(vec![
+                iteration_count(3),
+                SwapStopCriterion::Oscillation,
+                iteration_count(2),
+            ])
+            .iteration_limit()
+            .unwrap()
+            .get(),
+            2
+        );
+        assert_eq!(
+            SwapStopCriterion::All(vec![iteration_count(3), iteration_count(4)])
+                .iteration_limit()
+                .unwrap()
+                .get(),
+            4
+        );
+        assert!(SwapStopCriterion::All(vec![
+            iteration_count(3),
+            SwapStopCriterion::TimeLimit(Duration::from_secs(1)),
+        ])
+        .iteration_limit()
+        .is_none());
+    }
+}
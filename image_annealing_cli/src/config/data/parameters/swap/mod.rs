@@ -1,26 +1,37 @@
 use super::super::number::{InvalidNonnegativeProperFractionError, NonnegativeProperFraction};
+use super::super::ResolvePaths;
+use super::animation::{SwapAnimationConfig, UnverifiedSwapAnimationConfig};
+use super::intermediate::{
+    IntermediatePermutationsConfig, UnverifiedIntermediatePermutationsConfig,
+};
+use super::statistics::{SwapStatisticsLogConfig, UnverifiedSwapStatisticsLogConfig};
+use super::stop::{SwapStopCriterion, UnverifiedSwapStopCriterion};
 use image_annealing::compute::SwapPassSequence;
-use serde::Deserialize;
+use image_annealing_cli_util::config_file::ConfigFieldContext;
+use image_annealing_cli_util::io::{PathContext, PathVariableError};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
 use std::num::NonZeroUsize;
 
-#[derive(Clone, Copy, Deserialize)]
+#[derive(Clone, Copy, Deserialize, JsonSchema)]
 pub enum UnverifiedSwapStopThreshold {
     SwapsAccepted(usize),
     SwapAcceptanceFraction(f64),
 }
 
-#[derive(Clone, Copy, Deserialize)]
+#[derive(Clone, Copy, Deserialize, JsonSchema)]
 pub struct UnverifiedIterationCount(pub usize);
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, JsonSchema)]
 pub enum UnverifiedSwapStopConfig {
     Bounded {
         iteration_count: UnverifiedIterationCount,
         threshold: Option<UnverifiedSwapStopThreshold>,
     },
     Unbounded(UnverifiedSwapStopThreshold),
+    Criteria(UnverifiedSwapStopCriterion),
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -79,6 +90,28 @@ pub enum SwapStopConfig {
         threshold: Option<SwapStopThreshold>,
     },
     Unbounded(SwapStopThreshold),
+    Criteria(SwapStopCriterion),
+}
+
+impl SwapStopConfig {
+    /// Expresses the stop condition as a single criterion
+    pub fn criterion(&self) -> SwapStopCriterion {
+        match self {
+            Self::Bounded {
+                iteration_count,
+                threshold: None,
+            } => SwapStopCriterion::IterationCount(*iteration_count),
+            Self::Bounded {
+                iteration_count,
+                threshold: Some(threshold),
+            } => SwapStopCriterion::Any(vec![
+                SwapStopCriterion::IterationCount(*iteration_count),
+                SwapStopCriterion::Threshold(*threshold),
+            ]),
+            Self::Unbounded(threshold) => SwapStopCriterion::Threshold(*threshold),
+            Self::Criteria(criterion) => criterion.clone(),
+        }
+    }
 }
 
 impl TryFrom<UnverifiedSwapStopConfig> for SwapStopConfig {
@@ -90,20 +123,28 @@ impl TryFrom<UnverifiedSwapStopConfig> for SwapStopConfig {
                 iteration_count,
                 threshold,
             } => Ok(Self::Bounded {
-                iteration_count: iteration_count.try_into()?,
+                iteration_count: iteration_count
+                    .try_into()
+                    .in_field("iteration_count")
+                    .in_field("Bounded")?,
                 threshold: match threshold {
-                    Some(inner) => Some(inner.try_into()?),
+                    Some(inner) => {
+                        Some(inner.try_into().in_field("threshold").in_field("Bounded")?)
+                    }
                     None => None,
                 },
             }),
             UnverifiedSwapStopConfig::Unbounded(threshold) => {
-                Ok(Self::Unbounded(threshold.try_into()?))
+                Ok(Self::Unbounded(threshold.try_into().in_field("Unbounded")?))
+            }
+            UnverifiedSwapStopConfig::Criteria(criterion) => {
+                Ok(Self::Criteria(criterion.try_into().in_field("Criteria")?))
             }
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 pub enum SwapPass {
     Horizontal,
     Vertical,
@@ -133,12 +174,22 @@ impl From<SwapPass> for image_annealing::compute::SwapPass {
     }
 }
 
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, JsonSchema)]
 pub struct UnverifiedSwapParametersConfig {
     pub stop: UnverifiedSwapStopConfig,
     pub swap_acceptance_threshold: f32,
     pub swap_pass_sequence: Vec<SwapPass>,
     pub output_intermediate_permutations: bool,
+    pub intermediate_permutations: Option<UnverifiedIntermediatePermutationsConfig>,
+    pub animation: Option<UnverifiedSwapAnimationConfig>,
+    pub statistics_log: Option<UnverifiedSwapStatisticsLogConfig>,
+}
+
+impl ResolvePaths for UnverifiedSwapParametersConfig {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        self.animation.resolve_paths(context)?;
+        self.statistics_log.resolve_paths(context)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -147,6 +198,9 @@ pub struct SwapParametersConfig {
     pub swap_acceptance_threshold: f32,
     pub swap_pass_sequence: SwapPassSequence,
     pub output_intermediate_permutations: bool,
+    pub intermediate_permutations: IntermediatePermutationsConfig,
+    pub animation: Option<SwapAnimationConfig>,
+    pub statistics_log: Option<SwapStatisticsLogConfig>,
 }
 
 impl TryFrom<UnverifiedSwapParametersConfig> for SwapParametersConfig {
@@ -154,15 +208,28 @@ impl TryFrom<UnverifiedSwapParametersConfig> for SwapParametersConfig {
 
     fn try_from(value: UnverifiedSwapParametersConfig) -> Result<Self, Self::Error> {
         Ok(Self {
-            stop: value.stop.try_into()?,
+            stop: value.stop.try_into().in_field("stop")?,
             swap_acceptance_threshold: value.swap_acceptance_threshold,
             swap_pass_sequence: SwapPassSequence::from_passes(
                 value
                     .swap_pass_sequence
                     .into_iter()
                     .map(<image_annealing::compute::SwapPass as From<SwapPass>>::from),
-            )?,
+            )
+            .in_field("swap_pass_sequence")?,
             output_intermediate_permutations: value.output_intermediate_permutations,
+            intermediate_permutations: value
+                .intermediate_permutations
+                .map(TryInto::try_into)
+                .transpose()
+                .in_field("intermediate_permutations")?
+                .unwrap_or_default(),
+            animation: value
+                .animation
+                .map(TryInto::try_into)
+                .transpose()
+                .in_field("animation")?,
+            statistics_log: value.statistics_log.map(Into::into),
         })
     }
 }
@@ -89,7 +89,7 @@ mod swap_stop_config_try_from_unverified_swap_stop_config {
                     iteration_count: UnverifiedIterationCount(0),
                     threshold: None,
                 }),
-                "iteration count cannot be zero",
+                "iteration count cannot be zero (configuration field `Bounded.iteration_count`)",
             );
         }
 
@@ -210,6 +210,7 @@ mod swap_parameters_config_try_from_unverified_swap_parameters_config {
         SwapParametersConfig, SwapPass, SwapStopConfig, SwapStopThreshold,
         UnverifiedSwapParametersConfig, UnverifiedSwapStopConfig, UnverifiedSwapStopThreshold,
     };
+    use crate::config::UnverifiedIntermediatePermutationsConfig;
     use image_annealing::compute::{self, SwapPassSequence};
     use std::error::Error;
 
@@ -224,6 +225,9 @@ mod swap_parameters_config_try_from_unverified_swap_parameters_config {
                 swap_acceptance_threshold,
                 swap_pass_sequence: vec![SwapPass::OffsetHorizontal, SwapPass::Vertical],
                 output_intermediate_permutations: true,
+                intermediate_permutations: None,
+                animation: None,
+                statistics_log: None,
             })?,
             SwapParametersConfig {
                 stop: SwapStopConfig::Unbounded(SwapStopThreshold::SwapsAccepted(0)),
@@ -233,6 +237,9 @@ mod swap_parameters_config_try_from_unverified_swap_parameters_config {
                     compute::SwapPass::Vertical
                 ])?,
                 output_intermediate_permutations: true,
+                intermediate_permutations: Default::default(),
+                animation: None,
+                statistics_log: None,
             }
         );
         Ok(())
@@ -248,6 +255,9 @@ mod swap_parameters_config_try_from_unverified_swap_parameters_config {
                 swap_acceptance_threshold: Default::default(),
                 swap_pass_sequence: vec![SwapPass::OffsetHorizontal, SwapPass::Vertical],
                 output_intermediate_permutations: true,
+                intermediate_permutations: None,
+                animation: None,
+                statistics_log: None,
             }),
             "1 is not less than one",
         );
@@ -263,6 +273,9 @@ mod swap_parameters_config_try_from_unverified_swap_parameters_config {
                 swap_acceptance_threshold: Default::default(),
                 swap_pass_sequence: vec![],
                 output_intermediate_permutations: true,
+                intermediate_permutations: None,
+                animation: None,
+                statistics_log: None,
             }),
             "selection of swap passes is empty",
         );
@@ -282,8 +295,32 @@ mod swap_parameters_config_try_from_unverified_swap_parameters_config {
                     SwapPass::OffsetHorizontal,
                 ],
                 output_intermediate_permutations: true,
+                intermediate_permutations: None,
+                animation: None,
+                statistics_log: None,
             }),
             "attempt to select horizontal swaps, with offset pass multiple times",
         );
     }
+
+    #[test]
+    fn invalid_name_template() {
+        test_util::assert_error_contains(
+            SwapParametersConfig::try_from(UnverifiedSwapParametersConfig {
+                stop: UnverifiedSwapStopConfig::Unbounded(
+                    UnverifiedSwapStopThreshold::SwapsAccepted(0),
+                ),
+                swap_acceptance_threshold: Default::default(),
+                swap_pass_sequence: vec![SwapPass::OffsetHorizontal, SwapPass::Vertical],
+                output_intermediate_permutations: true,
+                intermediate_permutations: Some(UnverifiedIntermediatePermutationsConfig {
+                    cadence: None,
+                    name_template: Some(String::from("_{pass_name")),
+                }),
+                animation: None,
+                statistics_log: None,
+            }),
+            "(configuration field `intermediate_permutations.name_template`)",
+        );
+    }
 }
@@ -0,0 +1,72 @@
+use image_annealing::image_utils::visualization::VectorFieldVisualizationParameters;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::error::Error;
+use std::fmt;
+use std::num::NonZeroU32;
+
+#[derive(Clone, Copy, Default, Deserialize, JsonSchema)]
+pub struct UnverifiedVectorFieldVisualizationParametersConfig {
+    pub max_magnitude: Option<f64>,
+    pub arrow_spacing: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub enum InvalidVectorFieldVisualizationParametersError {
+    MaxMagnitude(f64),
+    ArrowSpacing,
+}
+
+impl fmt::Display for InvalidVectorFieldVisualizationParametersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MaxMagnitude(value) => {
+                write!(
+                    f,
+                    "maximum magnitude {} is not a positive finite number",
+                    value
+                )
+            }
+            Self::ArrowSpacing => write!(f, "arrow spacing cannot be zero"),
+        }
+    }
+}
+
+impl Error for InvalidVectorFieldVisualizationParametersError {}
+
+impl TryFrom<UnverifiedVectorFieldVisualizationParametersConfig>
+    for VectorFieldVisualizationParameters
+{
+    type Error = InvalidVectorFieldVisualizationParametersError;
+
+    fn try_from(
+        value: UnverifiedVectorFieldVisualizationParametersConfig,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
+            max_magnitude: value
+                .max_magnitude
+                .map(|max_magnitude| {
+                    if max_magnitude.is_finite() && max_magnitude > 0.0 {
+                        Ok(max_magnitude)
+                    } else {
+                        Err(
+                            InvalidVectorFieldVisualizationParametersError::MaxMagnitude(
+                                max_magnitude,
+                            ),
+                        )
+                    }
+                })
+                .transpose()?,
+            arrow_spacing: value
+                .arrow_spacing
+                .map(|spacing| {
+                    NonZeroU32::new(spacing)
+                        .ok_or(InvalidVectorFieldVisualizationParametersError::ArrowSpacing)
+                })
+                .transpose()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
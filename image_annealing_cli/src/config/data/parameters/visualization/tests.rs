@@ -0,0 +1,73 @@
+mod vector_field_visualization_parameters_try_from_unverified_config {
+    use super::super::UnverifiedVectorFieldVisualizationParametersConfig;
+    use image_annealing::image_utils::visualization::VectorFieldVisualizationParameters;
+    use std::error::Error;
+    use std::num::NonZeroU32;
+
+    #[test]
+    fn defaults() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            VectorFieldVisualizationParameters::try_from(
+                UnverifiedVectorFieldVisualizationParametersConfig::default()
+            )?,
+            VectorFieldVisualizationParameters::default()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn all_fields() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            VectorFieldVisualizationParameters::try_from(
+                UnverifiedVectorFieldVisualizationParametersConfig {
+                    max_magnitude: Some(2.5),
+                    arrow_spacing: Some(8),
+                }
+            )?,
+            VectorFieldVisualizationParameters {
+                max_magnitude: Some(2.5),
+                arrow_spacing: NonZeroU32::new(8),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn zero_max_magnitude() {
+        test_util::assert_error_contains(
+            VectorFieldVisualizationParameters::try_from(
+                UnverifiedVectorFieldVisualizationParametersConfig {
+                    max_magnitude: Some(0.0),
+                    arrow_spacing: None,
+                },
+            ),
+            "maximum magnitude 0 is not a positive finite number",
+        );
+    }
+
+    #[test]
+    fn infinite_max_magnitude() {
+        test_util::assert_error_contains(
+            VectorFieldVisualizationParameters::try_from(
+                UnverifiedVectorFieldVisualizationParametersConfig {
+                    max_magnitude: Some(f64::INFINITY),
+                    arrow_spacing: None,
+                },
+            ),
+            "maximum magnitude inf is not a positive finite number",
+        );
+    }
+
+    #[test]
+    fn zero_arrow_spacing() {
+        test_util::assert_error_contains(
+            VectorFieldVisualizationParameters::try_from(
+                UnverifiedVectorFieldVisualizationParametersConfig {
+                    max_magnitude: None,
+                    arrow_spacing: Some(0),
+                },
+            ),
+            "arrow spacing cannot be zero",
+        );
+    }
+}
@@ -28,7 +28,11 @@ mod config_try_from_unverified_config {
                         displacement_goal_output_path_no_extension:
                             DisplacementGoalPath::from_raw_clone("displacement_goal_out"),
                     },
-                    dispatcher: compute::Config { image_dimensions }
+                    dispatcher: compute::Config {
+                        image_dimensions,
+                        boundary: Default::default(),
+                    },
+                    output_policy: Default::default(),
                 }
             );
             Ok(())
@@ -77,8 +81,10 @@ mod config_try_from_unverified_config {
                         ),
                     },
                     dispatcher: compute::Config {
-                        image_dimensions: ImageDimensions::try_new(20, 25)?
-                    }
+                        image_dimensions: ImageDimensions::try_new(20, 25)?,
+                        boundary: Default::default(),
+                    },
+                    output_policy: Default::default(),
                 }
             );
             Ok(())
@@ -100,8 +106,9 @@ mod config_try_from_unverified_config {
 
     mod permute {
         use super::super::super::{
-            AlgorithmConfig, Config, ImagePath, LosslessImagePath, PermutationPath,
-            UnverifiedConfig, UnverifiedLosslessImagePath,
+            AlgorithmConfig, Config, ImageConversionPolicy, ImagePath, LosslessImagePath,
+            PermutationPath, PermuteImageConfig, UnverifiedConfig, UnverifiedLosslessImagePath,
+            UnverifiedPermuteImageInput, UnverifiedPermuteImageOutput,
         };
         use image_annealing::compute;
         use std::error::Error;
@@ -112,12 +119,16 @@ mod config_try_from_unverified_config {
                 candidate_permutation: String::from(
                     "../test_data/image/permutation/identity_permutation.png",
                 ),
-                original_image: UnverifiedLosslessImagePath::Rgba8(String::from(
-                    "../test_data/image/image/stripes.png",
-                )),
-                permuted_image_output_path_no_extension: UnverifiedLosslessImagePath::Rgba8(
-                    String::from("permuted_image_out"),
+                original_image: UnverifiedPermuteImageInput::Image(
+                    UnverifiedLosslessImagePath::Rgba8(String::from(
+                        "../test_data/image/image/stripes.png",
+                    )),
                 ),
+                original_image_conversion: Some(ImageConversionPolicy::Lossless),
+                permuted_image_output_path_no_extension: UnverifiedPermuteImageOutput::Image(
+                    UnverifiedLosslessImagePath::Rgba8(String::from("permuted_image_out")),
+                ),
+                boundary: None,
             };
             let r: Config = unverified_config.try_into()?;
             let (candidate_permutation_path, image_dimensions) =
@@ -131,18 +142,78 @@ mod config_try_from_unverified_config {
                 Config {
                     algorithm: AlgorithmConfig::Permute {
                         candidate_permutation: candidate_permutation_path,
-                        original_image: LosslessImagePath::Rgba8(
-                            test_util::make_test_data_path_string([
-                                "image",
-                                "image",
-                                "stripes.png"
-                            ])
-                        ),
-                        permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
-                            String::from("permuted_image_out")
-                        ),
+                        images: vec![PermuteImageConfig {
+                            original_image: LosslessImagePath::Rgba8(
+                                test_util::make_test_data_path_string([
+                                    "image",
+                                    "image",
+                                    "stripes.png"
+                                ])
+                            ),
+                            original_image_conversion: ImageConversionPolicy::Lossless,
+                            permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
+                                String::from("permuted_image_out")
+                            ),
+                        }],
                     },
-                    dispatcher: compute::Config { image_dimensions }
+                    dispatcher: compute::Config {
+                        image_dimensions,
+                        boundary: Default::default(),
+                    },
+                    output_policy: Default::default(),
+                }
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn valid_list() -> Result<(), Box<dyn Error>> {
+            let unverified_config = UnverifiedConfig::Permute {
+                candidate_permutation: String::from(
+                    "../test_data/image/permutation/identity_permutation.png",
+                ),
+                original_image: UnverifiedPermuteImageInput::List(vec![
+                    UnverifiedLosslessImagePath::Rgba8(String::from(
+                        "../test_data/image/image/stripes.png",
+                    )),
+                ]),
+                original_image_conversion: None,
+                permuted_image_output_path_no_extension: UnverifiedPermuteImageOutput::List(vec![
+                    UnverifiedLosslessImagePath::Rgba8(String::from("permuted_image_out")),
+                ]),
+                boundary: None,
+            };
+            let r: Config = unverified_config.try_into()?;
+            let (candidate_permutation_path, image_dimensions) =
+                PermutationPath::from_input_path(test_util::make_test_data_path_string([
+                    "image",
+                    "permutation",
+                    "identity_permutation.png",
+                ]))?;
+            assert_eq!(
+                r,
+                Config {
+                    algorithm: AlgorithmConfig::Permute {
+                        candidate_permutation: candidate_permutation_path,
+                        images: vec![PermuteImageConfig {
+                            original_image: LosslessImagePath::Rgba8(
+                                test_util::make_test_data_path_string([
+                                    "image",
+                                    "image",
+                                    "stripes.png"
+                                ])
+                            ),
+                            original_image_conversion: Default::default(),
+                            permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
+                                String::from("permuted_image_out")
+                            ),
+                        }],
+                    },
+                    dispatcher: compute::Config {
+                        image_dimensions,
+                        boundary: Default::default(),
+                    },
+                    output_policy: Default::default(),
                 }
             );
             Ok(())
@@ -152,12 +223,16 @@ mod config_try_from_unverified_config {
         fn invalid_permutation() {
             let unverified_config = UnverifiedConfig::Permute {
                 candidate_permutation: String::from("../test_data/image/permutation/not_found.png"),
-                original_image: UnverifiedLosslessImagePath::Rgba8(String::from(
-                    "../test_data/image/image/stripes.png",
-                )),
-                permuted_image_output_path_no_extension: UnverifiedLosslessImagePath::Rgba8(
-                    String::from("permuted_image_out"),
+                original_image: UnverifiedPermuteImageInput::Image(
+                    UnverifiedLosslessImagePath::Rgba8(String::from(
+                        "../test_data/image/image/stripes.png",
+                    )),
                 ),
+                original_image_conversion: None,
+                permuted_image_output_path_no_extension: UnverifiedPermuteImageOutput::Image(
+                    UnverifiedLosslessImagePath::Rgba8(String::from("permuted_image_out")),
+                ),
+                boundary: None,
             };
             let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
             test_util::assert_error_contains(
@@ -172,12 +247,16 @@ mod config_try_from_unverified_config {
                 candidate_permutation: String::from(
                     "../test_data/image/permutation/identity_permutation.png",
                 ),
-                original_image: UnverifiedLosslessImagePath::Rgba8(String::from(
-                    "../test_data/image/image/not_found.png",
-                )),
-                permuted_image_output_path_no_extension: UnverifiedLosslessImagePath::Rgba8(
-                    String::from("permuted_image_out"),
+                original_image: UnverifiedPermuteImageInput::Image(
+                    UnverifiedLosslessImagePath::Rgba8(String::from(
+                        "../test_data/image/image/not_found.png",
+                    )),
+                ),
+                original_image_conversion: None,
+                permuted_image_output_path_no_extension: UnverifiedPermuteImageOutput::Image(
+                    UnverifiedLosslessImagePath::Rgba8(String::from("permuted_image_out")),
                 ),
+                boundary: None,
             };
             let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
             test_util::assert_error_contains(
@@ -192,12 +271,16 @@ mod config_try_from_unverified_config {
                 candidate_permutation: String::from(
                     "../test_data/image/permutation/identity_permutation.png",
                 ),
-                original_image: UnverifiedLosslessImagePath::Rgba8(String::from(
-                    "../test_data/image/image/stripes_large.png",
-                )),
-                permuted_image_output_path_no_extension: UnverifiedLosslessImagePath::Rgba8(
-                    String::from("permuted_image_out"),
+                original_image: UnverifiedPermuteImageInput::Image(
+                    UnverifiedLosslessImagePath::Rgba8(String::from(
+                        "../test_data/image/image/stripes_large.png",
+                    )),
+                ),
+                original_image_conversion: None,
+                permuted_image_output_path_no_extension: UnverifiedPermuteImageOutput::Image(
+                    UnverifiedLosslessImagePath::Rgba8(String::from("permuted_image_out")),
                 ),
+                boundary: None,
             };
             let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
             test_util::assert_error_contains(
@@ -226,6 +309,9 @@ mod config_try_from_unverified_config {
                 swap_acceptance_threshold: SWAP_ACCEPTANCE_THRESHOLD,
                 swap_pass_sequence: vec![SwapPass::Vertical, SwapPass::OffsetVertical],
                 output_intermediate_permutations: false,
+                intermediate_permutations: None,
+                animation: None,
+                statistics_log: None,
             }
         }
 
@@ -239,20 +325,27 @@ mod config_try_from_unverified_config {
                 ])
                 .unwrap(),
                 output_intermediate_permutations: false,
+                intermediate_permutations: Default::default(),
+                animation: None,
+                statistics_log: None,
             }
         }
 
         #[test]
         fn valid() -> Result<(), Box<dyn Error>> {
             let unverified_config = UnverifiedConfig::Swap {
-                candidate_permutation: String::from(
+                candidate_permutation: Some(String::from(
                     "../test_data/image/permutation/identity_permutation.png",
-                ),
+                )),
+                resume: None,
                 displacement_goal: String::from(
                     "../test_data/image/displacement_goal/identity_displacement_goal.png",
                 ),
                 permutation_output_path_prefix: String::from("permutation_out"),
+                swap_history_output_path_no_extension: None,
+                checkpoint: None,
                 parameters: make_unverified_swap_parameters(),
+                boundary: None,
             };
             let r: Config = unverified_config.try_into()?;
             let (candidate_permutation_path, image_dimensions) =
@@ -266,6 +359,7 @@ mod config_try_from_unverified_config {
                 Config {
                     algorithm: AlgorithmConfig::Swap {
                         candidate_permutation: candidate_permutation_path,
+                        resume: None,
                         displacement_goal: DisplacementGoalPath::from_raw(
                             test_util::make_test_data_path_string([
                                 "image",
@@ -276,9 +370,15 @@ mod config_try_from_unverified_config {
                         permutation_output_path_prefix: PermutationPath::from_raw_clone(
                             "permutation_out"
                         ),
+                        swap_history_output_path_no_extension: None,
+                        checkpoint: None,
                         parameters: make_swap_parameters()
                     },
-                    dispatcher: compute::Config { image_dimensions }
+                    dispatcher: compute::Config {
+                        image_dimensions,
+                        boundary: Default::default(),
+                    },
+                    output_policy: Default::default(),
                 }
             );
             Ok(())
@@ -287,12 +387,18 @@ mod config_try_from_unverified_config {
         #[test]
         fn invalid_permutation() {
             let unverified_config = UnverifiedConfig::Swap {
-                candidate_permutation: String::from("../test_data/image/permutation/not_found.png"),
+                candidate_permutation: Some(String::from(
+                    "../test_data/image/permutation/not_found.png",
+                )),
+                resume: None,
                 displacement_goal: String::from(
                     "../test_data/image/displacement_goal/identity_displacement_goal.png",
                 ),
                 permutation_output_path_prefix: String::from("permutation_out"),
+                swap_history_output_path_no_extension: None,
+                checkpoint: None,
                 parameters: make_unverified_swap_parameters(),
+                boundary: None,
             };
             let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
             test_util::assert_error_contains(
@@ -304,14 +410,18 @@ mod config_try_from_unverified_config {
         #[test]
         fn invalid_displacement_goal() {
             let unverified_config = UnverifiedConfig::Swap {
-                candidate_permutation: String::from(
+                candidate_permutation: Some(String::from(
                     "../test_data/image/permutation/identity_permutation.png",
-                ),
+                )),
+                resume: None,
                 displacement_goal: String::from(
                     "../test_data/image/displacement_goal/not_found.png",
                 ),
                 permutation_output_path_prefix: String::from("permutation_out"),
+                swap_history_output_path_no_extension: None,
+                checkpoint: None,
                 parameters: make_unverified_swap_parameters(),
+                boundary: None,
             };
             let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
             test_util::assert_error_contains(
@@ -323,14 +433,18 @@ mod config_try_from_unverified_config {
         #[test]
         fn invalid_dimensions() {
             let unverified_config = UnverifiedConfig::Swap {
-                candidate_permutation: String::from(
+                candidate_permutation: Some(String::from(
                     "../test_data/image/permutation/identity_permutation.png",
-                ),
+                )),
+                resume: None,
                 displacement_goal: String::from(
                     "../test_data/image/displacement_goal/identity_larger_displacement_goal.png",
                 ),
                 permutation_output_path_prefix: String::from("permutation_out"),
+                swap_history_output_path_no_extension: None,
+                checkpoint: None,
                 parameters: make_unverified_swap_parameters(),
+                boundary: None,
             };
             let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
             test_util::assert_error_contains(
@@ -342,28 +456,76 @@ mod config_try_from_unverified_config {
         #[test]
         fn invalid_swap_parameters() {
             let unverified_config = UnverifiedConfig::Swap {
-                candidate_permutation: String::from(
+                candidate_permutation: Some(String::from(
                     "../test_data/image/permutation/identity_permutation.png",
-                ),
+                )),
+                resume: None,
                 displacement_goal: String::from(
                     "../test_data/image/displacement_goal/identity_displacement_goal.png",
                 ),
                 permutation_output_path_prefix: String::from("permutation_out"),
+                swap_history_output_path_no_extension: None,
+                checkpoint: None,
                 parameters: UnverifiedSwapParametersConfig {
                     stop: UnverifiedSwapStopConfig::Unbounded(
                         UnverifiedSwapStopThreshold::SwapAcceptanceFraction(2.0),
                     ),
                     ..make_unverified_swap_parameters()
                 },
+                boundary: None,
             };
             let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
             test_util::assert_error_contains(r, "2 is not less than one");
         }
+
+        #[test]
+        fn missing_input() {
+            let unverified_config = UnverifiedConfig::Swap {
+                candidate_permutation: None,
+                resume: None,
+                displacement_goal: String::from(
+                    "../test_data/image/displacement_goal/identity_displacement_goal.png",
+                ),
+                permutation_output_path_prefix: String::from("permutation_out"),
+                swap_history_output_path_no_extension: None,
+                checkpoint: None,
+                parameters: make_unverified_swap_parameters(),
+                boundary: None,
+            };
+            let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
+            test_util::assert_error_contains(
+                r,
+                "either a candidate permutation or a checkpoint to resume from must be provided",
+            );
+        }
+
+        #[test]
+        fn conflicting_input() {
+            let unverified_config = UnverifiedConfig::Swap {
+                candidate_permutation: Some(String::from(
+                    "../test_data/image/permutation/identity_permutation.png",
+                )),
+                resume: Some(String::from("checkpoint.json")),
+                displacement_goal: String::from(
+                    "../test_data/image/displacement_goal/identity_displacement_goal.png",
+                ),
+                permutation_output_path_prefix: String::from("permutation_out"),
+                swap_history_output_path_no_extension: None,
+                checkpoint: None,
+                parameters: make_unverified_swap_parameters(),
+                boundary: None,
+            };
+            let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
+            test_util::assert_error_contains(
+                r,
+                "a candidate permutation and a checkpoint to resume from cannot both be provided",
+            );
+        }
     }
 
     mod validate_permutation {
         use super::super::super::{
-            AlgorithmConfig, Config, ImagePath, PermutationPath, UnverifiedConfig,
+            AlgorithmConfig, BoundaryMode, Config, ImagePath, PermutationPath, UnverifiedConfig,
         };
         use image_annealing::compute;
         use std::error::Error;
@@ -374,6 +536,7 @@ mod config_try_from_unverified_config {
                 candidate_permutation: String::from(
                     "../test_data/image/permutation/identity_permutation.png",
                 ),
+                boundary: None,
             };
             let r: Config = unverified_config.try_into()?;
             let (candidate_permutation_path, image_dimensions) =
@@ -388,16 +551,37 @@ mod config_try_from_unverified_config {
                     algorithm: AlgorithmConfig::ValidatePermutation {
                         candidate_permutation: candidate_permutation_path
                     },
-                    dispatcher: compute::Config { image_dimensions }
+                    dispatcher: compute::Config {
+                        image_dimensions,
+                        boundary: Default::default(),
+                    },
+                    output_policy: Default::default(),
                 }
             );
             Ok(())
         }
 
+        #[test]
+        fn toroidal() -> Result<(), Box<dyn Error>> {
+            let unverified_config = UnverifiedConfig::ValidatePermutation {
+                candidate_permutation: String::from(
+                    "../test_data/image/permutation/identity_permutation.png",
+                ),
+                boundary: Some(BoundaryMode::Toroidal),
+            };
+            let r: Config = unverified_config.try_into()?;
+            assert_eq!(
+                r.dispatcher.boundary,
+                image_annealing::BoundaryMode::Toroidal
+            );
+            Ok(())
+        }
+
         #[test]
         fn invalid_permutation() {
             let unverified_config = UnverifiedConfig::ValidatePermutation {
                 candidate_permutation: String::from("../test_data/image/permutation/not_found.png"),
+                boundary: None,
             };
             let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
             test_util::assert_error_contains(
@@ -406,4 +590,277 @@ mod config_try_from_unverified_config {
             );
         }
     }
+
+    mod visualize_vector_field {
+        use super::super::super::{
+            AlgorithmConfig, Config, DisplacementGoalPath, ImagePath, UnverifiedConfig,
+            UnverifiedVectorFieldVisualizationParametersConfig,
+        };
+        use image_annealing::compute;
+        use image_annealing::image_utils::visualization::VectorFieldVisualizationParameters;
+        use std::error::Error;
+        use std::num::NonZeroU32;
+
+        #[test]
+        fn valid() -> Result<(), Box<dyn Error>> {
+            let unverified_config = UnverifiedConfig::VisualizeVectorField {
+                vector_field: String::from(
+                    "../test_data/image/permutation/identity_permutation.png",
+                ),
+                visualization_output_path_no_extension: String::from("visualization"),
+                legend_output_path_no_extension: Some(String::from("legend")),
+                parameters: Some(UnverifiedVectorFieldVisualizationParametersConfig {
+                    max_magnitude: Some(4.0),
+                    arrow_spacing: Some(16),
+                }),
+            };
+            let r: Config = unverified_config.try_into()?;
+            let (vector_field_path, image_dimensions) =
+                DisplacementGoalPath::from_input_path(test_util::make_test_data_path_string([
+                    "image",
+                    "permutation",
+                    "identity_permutation.png",
+                ]))?;
+            assert_eq!(
+                r,
+                Config {
+                    algorithm: AlgorithmConfig::VisualizeVectorField {
+                        vector_field: vector_field_path,
+                        visualization_output_path_no_extension: String::from("visualization"),
+                        legend_output_path_no_extension: Some(String::from("legend")),
+                        parameters: VectorFieldVisualizationParameters {
+                            max_magnitude: Some(4.0),
+                            arrow_spacing: NonZeroU32::new(16),
+                        },
+                    },
+                    dispatcher: compute::Config {
+                        image_dimensions,
+                        boundary: Default::default(),
+                    },
+                    output_policy: Default::default(),
+                }
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn invalid_parameters() {
+            let unverified_config = UnverifiedConfig::VisualizeVectorField {
+                vector_field: String::from(
+                    "../test_data/image/permutation/identity_permutation.png",
+                ),
+                visualization_output_path_no_extension: String::from("visualization"),
+                legend_output_path_no_extension: None,
+                parameters: Some(UnverifiedVectorFieldVisualizationParametersConfig {
+                    max_magnitude: Some(-1.0),
+                    arrow_spacing: None,
+                }),
+            };
+            let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
+            test_util::assert_error_contains(
+                r,
+                "maximum magnitude -1 is not a positive finite number",
+            );
+        }
+    }
+
+    mod workflow {
+        use super::super::super::{
+            AlgorithmConfig, BoundaryMode, Config, UnverifiedConfig,
+            UnverifiedImageDimensionsConfig, UnverifiedWorkflowOperationConfig,
+            UnverifiedWorkflowStepConfig, WorkflowOperationConfig, WorkflowStepConfig,
+        };
+        use image_annealing::{compute, ImageDimensions};
+        use std::error::Error;
+
+        fn make_steps() -> Vec<UnverifiedWorkflowStepConfig> {
+            vec![UnverifiedWorkflowStepConfig {
+                name: String::from("initial"),
+                for_each: None,
+                operation: UnverifiedWorkflowOperationConfig::CreatePermutation {
+                    permutation_output_path_no_extension: None,
+                },
+            }]
+        }
+
+        #[test]
+        fn valid() -> Result<(), Box<dyn Error>> {
+            let image_dimensions = ImageDimensions::try_new(20, 25)?;
+            let unverified_config = UnverifiedConfig::Workflow {
+                image_dimensions: Some(UnverifiedImageDimensionsConfig {
+                    width: image_dimensions.width(),
+                    height: image_dimensions.height(),
+                }),
+                steps: make_steps(),
+                boundary: Some(BoundaryMode::Toroidal),
+            };
+            let r: Config = unverified_config.try_into()?;
+            assert_eq!(
+                r,
+                Config {
+                    algorithm: AlgorithmConfig::Workflow {
+                        steps: vec![WorkflowStepConfig {
+                            name: String::from("initial"),
+                            for_each: None,
+                            operation: WorkflowOperationConfig::CreatePermutation {
+                                permutation_output_path_no_extension: None,
+                            },
+                        }],
+                    },
+                    dispatcher: compute::Config {
+                        image_dimensions,
+                        boundary: BoundaryMode::Toroidal.into(),
+                    },
+                    output_policy: Default::default(),
+                }
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn missing_dimensions() {
+            let unverified_config = UnverifiedConfig::Workflow {
+                image_dimensions: None,
+                steps: make_steps(),
+                boundary: None,
+            };
+            let r = <Config as TryFrom<UnverifiedConfig>>::try_from(unverified_config);
+            test_util::assert_error_contains(
+                r,
+                "image dimensions must be provided when no workflow step reads an input file",
+            );
+        }
+    }
+
+    mod experiment {
+        use super::super::super::{
+            AlgorithmConfig, BoundaryMode, Config, UnverifiedConfig, UnverifiedExperimentAxis,
+            UnverifiedExperimentValues, UnverifiedImageDimensionsConfig,
+            UnverifiedSwapParametersConfig, UnverifiedSwapStopConfig, UnverifiedSwapStopThreshold,
+        };
+        use image_annealing::ImageDimensions;
+        use std::error::Error;
+
+        fn make_base() -> UnverifiedConfig {
+            UnverifiedConfig::Swap {
+                candidate_permutation: Some(String::from(
+                    "../test_data/image/permutation/identity_permutation.png",
+                )),
+                resume: None,
+                displacement_goal: String::from(
+                    "../test_data/image/displacement_goal/identity_displacement_goal.png",
+                ),
+                permutation_output_path_prefix: String::from("permutation_out"),
+                swap_history_output_path_no_extension: None,
+                checkpoint: None,
+                parameters: UnverifiedSwapParametersConfig {
+                    stop: UnverifiedSwapStopConfig::Unbounded(
+                        UnverifiedSwapStopThreshold::SwapsAccepted(0),
+                    ),
+                    swap_acceptance_threshold: 0.0,
+                    swap_pass_sequence: Vec::new(),
+                    output_intermediate_permutations: false,
+                    intermediate_permutations: None,
+                    animation: None,
+                    statistics_log: None,
+                },
+                boundary: Some(BoundaryMode::Toroidal),
+            }
+        }
+
+        #[test]
+        fn valid() -> Result<(), Box<dyn Error>> {
+            let mut base = make_base();
+            if let UnverifiedConfig::Swap { parameters, .. } = &mut base {
+                parameters.swap_pass_sequence = vec![super::super::super::SwapPass::Horizontal];
+            }
+            let unverified_config = UnverifiedConfig::Experiment {
+                base: Box::new(base),
+                axes: vec![UnverifiedExperimentAxis::SwapAcceptanceThreshold(
+                    UnverifiedExperimentValues::List(vec![1.0, 2.0, 3.0]),
+                )],
+                sampling: None,
+                output_directory: test_util::make_test_data_path_string(["config"]),
+            };
+            let r: Config = unverified_config.try_into()?;
+            assert_eq!(
+                r.dispatcher.image_dimensions,
+                ImageDimensions::try_new(20, 25)?
+            );
+            assert_eq!(r.dispatcher.boundary, BoundaryMode::Toroidal.into());
+            match r.algorithm {
+                AlgorithmConfig::Experiment { runs, seed, .. } => {
+                    assert_eq!(runs.len(), 3);
+                    assert!(seed.is_none());
+                }
+                _ => unreachable!(),
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn invalid_base() {
+            test_util::assert_error_contains(
+                Config::try_from(UnverifiedConfig::Experiment {
+                    base: Box::new(make_base()),
+                    axes: Vec::new(),
+                    sampling: None,
+                    output_directory: test_util::make_test_data_path_string(["config"]),
+                }),
+                "selection of swap passes is empty",
+            );
+        }
+
+        #[test]
+        fn base_not_swap() {
+            test_util::assert_error_contains(
+                Config::try_from(UnverifiedConfig::Experiment {
+                    base: Box::new(UnverifiedConfig::CreatePermutation {
+                        image_dimensions: UnverifiedImageDimensionsConfig {
+                            width: 20,
+                            height: 25,
+                        },
+                        permutation_output_path_no_extension: String::from("permutation_out"),
+                    }),
+                    axes: Vec::new(),
+                    sampling: None,
+                    output_directory: test_util::make_test_data_path_string(["config"]),
+                }),
+                "the base configuration of an experiment must describe a swap operation",
+            );
+        }
+    }
+}
+
+mod unverified_config_file {
+    use super::super::{UnverifiedConfig, UnverifiedConfigFile, OPERATION_NAMES};
+    use std::error::Error;
+
+    #[test]
+    fn operation_names() -> Result<(), Box<dyn Error>> {
+        let schema = serde_json::to_value(schemars::schema_for!(UnverifiedConfig))?;
+        let mut names = schema["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|variant| variant["required"][0].as_str().unwrap())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        let mut operation_names = OPERATION_NAMES.to_vec();
+        operation_names.sort_unstable();
+        assert_eq!(names, operation_names);
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_key() {
+        let r = serde_json::from_str::<UnverifiedConfigFile>(
+            r#"{"ValidatePermutation": {"candidate_permutation": "p.png"}, "output": "out"}"#,
+        );
+        assert!(r
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("unknown field `output`"));
+    }
 }
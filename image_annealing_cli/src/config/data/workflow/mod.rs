@@ -0,0 +1,551 @@
+use super::{
+    DisplacementGoalPath, ImageConversionPolicy, ImagePath, LosslessImagePath, PermutationPath,
+    ResolvePaths, SwapParametersConfig, UnverifiedLosslessImagePath,
+    UnverifiedSwapParametersConfig,
+};
+use image_annealing::compute::format::ImageFormat;
+use image_annealing::ImageDimensions;
+use image_annealing_cli_util::config_file::ConfigFieldContext;
+use image_annealing_cli_util::io::{self, PathContext, PathVariableError};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// An input to a workflow step, which is either read from a file
+/// or taken from the output of an earlier step
+#[derive(Debug, Deserialize, JsonSchema, PartialEq, Eq)]
+pub enum UnverifiedWorkflowInput<T> {
+    File(T),
+    Step(String),
+}
+
+impl<T: ResolvePaths> ResolvePaths for UnverifiedWorkflowInput<T> {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        match self {
+            Self::File(path) => path.resolve_paths(context),
+            Self::Step(_) => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WorkflowInput<T> {
+    File(T),
+    Step(String),
+}
+
+impl<T> WorkflowInput<T> {
+    pub fn file(&self) -> Option<&T> {
+        match self {
+            Self::File(path) => Some(path),
+            Self::Step(_) => None,
+        }
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub enum UnverifiedWorkflowOperationConfig {
+    CreateDisplacementGoal {
+        displacement_goal: Option<UnverifiedWorkflowInput<String>>,
+        candidate_permutation: Option<UnverifiedWorkflowInput<String>>,
+        image: Option<UnverifiedWorkflowInput<UnverifiedLosslessImagePath>>,
+        image_conversion: Option<ImageConversionPolicy>,
+        displacement_goal_output_path_no_extension: Option<String>,
+    },
+    CreatePermutation {
+        permutation_output_path_no_extension: Option<String>,
+    },
+    Permute {
+        candidate_permutation: UnverifiedWorkflowInput<String>,
+        original_image: UnverifiedWorkflowInput<UnverifiedLosslessImagePath>,
+        original_image_conversion: Option<ImageConversionPolicy>,
+        permuted_image_output_path_no_extension: Option<UnverifiedLosslessImagePath>,
+    },
+    Swap {
+        candidate_permutation: UnverifiedWorkflowInput<String>,
+        displacement_goal: UnverifiedWorkflowInput<String>,
+        permutation_output_path_prefix: Option<String>,
+        swap_history_output_path_no_extension: Option<String>,
+        parameters: UnverifiedSwapParametersConfig,
+    },
+    ValidatePermutation {
+        candidate_permutation: UnverifiedWorkflowInput<String>,
+    },
+}
+
+impl UnverifiedWorkflowOperationConfig {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::CreateDisplacementGoal { .. } => "CreateDisplacementGoal",
+            Self::CreatePermutation { .. } => "CreatePermutation",
+            Self::Permute { .. } => "Permute",
+            Self::Swap { .. } => "Swap",
+            Self::ValidatePermutation { .. } => "ValidatePermutation",
+        }
+    }
+}
+
+impl ResolvePaths for UnverifiedWorkflowOperationConfig {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        match self {
+            Self::CreateDisplacementGoal {
+                displacement_goal,
+                candidate_permutation,
+                image,
+                displacement_goal_output_path_no_extension,
+                ..
+            } => {
+                displacement_goal.resolve_paths(context)?;
+                candidate_permutation.resolve_paths(context)?;
+                image.resolve_paths(context)?;
+                displacement_goal_output_path_no_extension.resolve_paths(context)
+            }
+            Self::CreatePermutation {
+                permutation_output_path_no_extension,
+            } => permutation_output_path_no_extension.resolve_paths(context),
+            Self::Permute {
+                candidate_permutation,
+                original_image,
+                permuted_image_output_path_no_extension,
+                ..
+            } => {
+                candidate_permutation.resolve_paths(context)?;
+                original_image.resolve_paths(context)?;
+                permuted_image_output_path_no_extension.resolve_paths(context)
+            }
+            Self::Swap {
+                candidate_permutation,
+                displacement_goal,
+                permutation_output_path_prefix,
+                swap_history_output_path_no_extension,
+                parameters,
+            } => {
+                candidate_permutation.resolve_paths(context)?;
+                displacement_goal.resolve_paths(context)?;
+                permutation_output_path_prefix.resolve_paths(context)?;
+                swap_history_output_path_no_extension.resolve_paths(context)?;
+                parameters.resolve_paths(context)
+            }
+            Self::ValidatePermutation {
+                candidate_permutation,
+            } => candidate_permutation.resolve_paths(context),
+        }
+    }
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct UnverifiedWorkflowStepConfig {
+    pub name: String,
+    /// The name of an earlier step whose outputs this step is run once for
+    pub for_each: Option<String>,
+    pub operation: UnverifiedWorkflowOperationConfig,
+}
+
+impl ResolvePaths for UnverifiedWorkflowStepConfig {
+    fn resolve_paths(&mut self, context: &PathContext) -> Result<(), PathVariableError> {
+        self.operation.resolve_paths(context)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WorkflowOperationConfig {
+    CreateDisplacementGoal {
+        displacement_goal: Option<WorkflowInput<DisplacementGoalPath>>,
+        candidate_permutation: Option<WorkflowInput<PermutationPath>>,
+        image: Option<WorkflowInput<LosslessImagePath>>,
+        image_conversion: ImageConversionPolicy,
+        displacement_goal_output_path_no_extension: Option<DisplacementGoalPath>,
+    },
+    CreatePermutation {
+        permutation_output_path_no_extension: Option<PermutationPath>,
+    },
+    Permute {
+        candidate_permutation: WorkflowInput<PermutationPath>,
+        original_image: WorkflowInput<LosslessImagePath>,
+        original_image_conversion: ImageConversionPolicy,
+        permuted_image_output_path_no_extension: Option<LosslessImagePath>,
+    },
+    Swap {
+        candidate_permutation: WorkflowInput<PermutationPath>,
+        displacement_goal: WorkflowInput<DisplacementGoalPath>,
+        permutation_output_path_prefix: Option<PermutationPath>,
+        swap_history_output_path_no_extension: Option<String>,
+        parameters: SwapParametersConfig,
+    },
+    ValidatePermutation {
+        candidate_permutation: WorkflowInput<PermutationPath>,
+    },
+}
+
+impl WorkflowOperationConfig {
+    pub fn output_kind(&self) -> WorkflowOutputKind {
+        match self {
+            Self::CreateDisplacementGoal { .. } => WorkflowOutputKind::DisplacementGoal,
+            Self::Permute { .. } => WorkflowOutputKind::Image,
+            Self::CreatePermutation { .. }
+            | Self::Swap { .. }
+            | Self::ValidatePermutation { .. } => WorkflowOutputKind::Permutation,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkflowStepConfig {
+    pub name: String,
+    pub for_each: Option<String>,
+    pub operation: WorkflowOperationConfig,
+}
+
+/// The type of data output by a workflow step
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum WorkflowOutputKind {
+    DisplacementGoal,
+    Image,
+    Permutation,
+}
+
+impl fmt::Display for WorkflowOutputKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::DisplacementGoal => write!(f, "displacement goal"),
+            Self::Image => write!(f, "image"),
+            Self::Permutation => write!(f, "permutation"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum WorkflowError {
+    Empty,
+    DuplicateStep(String),
+    UnknownStep {
+        step: String,
+        reference: String,
+    },
+    OutputKindMismatch {
+        step: String,
+        reference: String,
+        expected: WorkflowOutputKind,
+        found: WorkflowOutputKind,
+    },
+    UnknownImageFormat {
+        step: String,
+        reference: String,
+    },
+    MissingImageDimensions,
+}
+
+impl fmt::Display for WorkflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the workflow has no steps"),
+            Self::DuplicateStep(name) => {
+                write!(f, "more than one workflow step is named '{}'", name)
+            }
+            Self::UnknownStep { step, reference } => write!(
+                f,
+                "workflow step '{}' refers to '{}', which is not the name of an earlier step",
+                step, reference
+            ),
+            Self::OutputKindMismatch {
+                step,
+                reference,
+                expected,
+                found,
+            } => write!(
+                f,
+                "workflow step '{}' cannot use the {} output of step '{}' as a {}",
+                step, found, reference, expected
+            ),
+            Self::UnknownImageFormat { step, reference } => write!(
+                f,
+                "workflow step '{}' uses the image output of step '{}', whose format is unknown",
+                step, reference
+            ),
+            Self::MissingImageDimensions => write!(
+                f,
+                "image dimensions must be provided when no workflow step reads an input file"
+            ),
+        }
+    }
+}
+
+impl Error for WorkflowError {}
+
+/// Tracks the steps checked so far, the formats of the images they output,
+/// and the dimensions of the input files they read
+struct WorkflowChecker {
+    output_kinds: HashMap<String, WorkflowOutputKind>,
+    image_formats: HashMap<String, ImageFormat>,
+    dimensions: Vec<ImageDimensions>,
+}
+
+impl WorkflowChecker {
+    fn check_reference(
+        &self,
+        step: &str,
+        reference: &str,
+        expected: WorkflowOutputKind,
+    ) -> Result<(), WorkflowError> {
+        match self.output_kinds.get(reference) {
+            None => Err(WorkflowError::UnknownStep {
+                step: String::from(step),
+                reference: String::from(reference),
+            }),
+            // A permutation is a valid displacement goal, as it is when loaded from a file
+            Some(&found)
+                if found == expected
+                    || (expected == WorkflowOutputKind::DisplacementGoal
+                        && found == WorkflowOutputKind::Permutation) =>
+            {
+                Ok(())
+            }
+            Some(&found) => Err(WorkflowError::OutputKindMismatch {
+                step: String::from(step),
+                reference: String::from(reference),
+                expected,
+                found,
+            }),
+        }
+    }
+
+    fn vector_field_input<P: ImagePath>(
+        &mut self,
+        step: &str,
+        input: UnverifiedWorkflowInput<String>,
+        expected: WorkflowOutputKind,
+    ) -> Result<WorkflowInput<P>, Box<dyn Error>> {
+        Ok(match input {
+            UnverifiedWorkflowInput::File(path) => {
+                let (path_checked, dimensions) = P::from_input_path(path).in_field("File")?;
+                self.dimensions.push(dimensions);
+                WorkflowInput::File(path_checked)
+            }
+            UnverifiedWorkflowInput::Step(reference) => {
+                self.check_reference(step, &reference, expected)
+                    .in_field("Step")?;
+                WorkflowInput::Step(reference)
+            }
+        })
+    }
+
+    /// Checks an image input, returning the checked input and the format of the image
+    fn image_input(
+        &mut self,
+        step: &str,
+        input: UnverifiedWorkflowInput<UnverifiedLosslessImagePath>,
+    ) -> Result<(WorkflowInput<LosslessImagePath>, ImageFormat), Box<dyn Error>> {
+        Ok(match input {
+            UnverifiedWorkflowInput::File(path) => {
+                let (path_checked, dimensions) =
+                    LosslessImagePath::from_input_path(path).in_field("File")?;
+                self.dimensions.push(dimensions);
+                let format = path_checked.format();
+                (WorkflowInput::File(path_checked), format)
+            }
+            UnverifiedWorkflowInput::Step(reference) => {
+                self.check_reference(step, &reference, WorkflowOutputKind::Image)
+                    .in_field("Step")?;
+                let format = *self
+                    .image_formats
+                    .get(&reference)
+                    .ok_or_else(|| WorkflowError::UnknownImageFormat {
+                        step: String::from(step),
+                        reference: reference.clone(),
+                    })
+                    .in_field("Step")?;
+                (WorkflowInput::Step(reference), format)
+            }
+        })
+    }
+
+    fn check_operation(
+        &mut self,
+        step: &str,
+        operation: UnverifiedWorkflowOperationConfig,
+    ) -> Result<WorkflowOperationConfig, Box<dyn Error>> {
+        let name = operation.name();
+        self.check_operation_fields(step, operation).in_field(name)
+    }
+
+    fn check_operation_fields(
+        &mut self,
+        step: &str,
+        operation: UnverifiedWorkflowOperationConfig,
+    ) -> Result<WorkflowOperationConfig, Box<dyn Error>> {
+        Ok(match operation {
+            UnverifiedWorkflowOperationConfig::CreateDisplacementGoal {
+                displacement_goal,
+                candidate_permutation,
+                image,
+                image_conversion,
+                displacement_goal_output_path_no_extension,
+            } => WorkflowOperationConfig::CreateDisplacementGoal {
+                displacement_goal: displacement_goal
+                    .map(|input| {
+                        self.vector_field_input(step, input, WorkflowOutputKind::DisplacementGoal)
+                    })
+                    .transpose()
+                    .in_field("displacement_goal")?,
+                candidate_permutation: candidate_permutation
+                    .map(|input| {
+                        self.vector_field_input(step, input, WorkflowOutputKind::Permutation)
+                    })
+                    .transpose()
+                    .in_field("candidate_permutation")?,
+                image: image
+                    .map(|input| self.image_input(step, input).map(|(input, _)| input))
+                    .transpose()
+                    .in_field("image")?,
+                image_conversion: image_conversion.unwrap_or_default(),
+                displacement_goal_output_path_no_extension:
+                    displacement_goal_output_path_no_extension
+                        .map(DisplacementGoalPath::from_output_path),
+            },
+            UnverifiedWorkflowOperationConfig::CreatePermutation {
+                permutation_output_path_no_extension,
+            } => WorkflowOperationConfig::CreatePermutation {
+                permutation_output_path_no_extension: permutation_output_path_no_extension
+                    .map(PermutationPath::from_output_path),
+            },
+            UnverifiedWorkflowOperationConfig::Permute {
+                candidate_permutation,
+                original_image,
+                original_image_conversion,
+                permuted_image_output_path_no_extension,
+            } => {
+                let candidate_permutation = self
+                    .vector_field_input(
+                        step,
+                        candidate_permutation,
+                        WorkflowOutputKind::Permutation,
+                    )
+                    .in_field("candidate_permutation")?;
+                let (original_image, format) = self
+                    .image_input(step, original_image)
+                    .in_field("original_image")?;
+                // The permuted image has the same format as the original image
+                self.image_formats.insert(String::from(step), format);
+                WorkflowOperationConfig::Permute {
+                    candidate_permutation,
+                    original_image,
+                    original_image_conversion: original_image_conversion.unwrap_or_default(),
+                    permuted_image_output_path_no_extension:
+                        permuted_image_output_path_no_extension
+                            .map(|path| LosslessImagePath::from_output_path(path, format))
+                            .transpose()
+                            .in_field("permuted_image_output_path_no_extension")?,
+                }
+            }
+            UnverifiedWorkflowOperationConfig::Swap {
+                candidate_permutation,
+                displacement_goal,
+                permutation_output_path_prefix,
+                swap_history_output_path_no_extension,
+                parameters,
+            } => {
+                let parameters_checked: SwapParametersConfig =
+                    parameters.try_into().in_field("parameters")?;
+                if let Some(animation) = parameters_checked.animation.as_ref() {
+                    self.dimensions.push(
+                        ImageDimensions::from_image_path(&animation.image)
+                            .in_field("image")
+                            .in_field("animation")
+                            .in_field("parameters")?,
+                    );
+                }
+                WorkflowOperationConfig::Swap {
+                    candidate_permutation: self
+                        .vector_field_input(
+                            step,
+                            candidate_permutation,
+                            WorkflowOutputKind::Permutation,
+                        )
+                        .in_field("candidate_permutation")?,
+                    displacement_goal: self
+                        .vector_field_input(
+                            step,
+                            displacement_goal,
+                            WorkflowOutputKind::DisplacementGoal,
+                        )
+                        .in_field("displacement_goal")?,
+                    permutation_output_path_prefix: permutation_output_path_prefix
+                        .map(PermutationPath::from_output_path),
+                    swap_history_output_path_no_extension: swap_history_output_path_no_extension
+                        .map(io::convert_path_separators),
+                    parameters: parameters_checked,
+                }
+            }
+            UnverifiedWorkflowOperationConfig::ValidatePermutation {
+                candidate_permutation,
+            } => WorkflowOperationConfig::ValidatePermutation {
+                candidate_permutation: self
+                    .vector_field_input(
+                        step,
+                        candidate_permutation,
+                        WorkflowOutputKind::Permutation,
+                    )
+                    .in_field("candidate_permutation")?,
+            },
+        })
+    }
+}
+
+/// Checks the steps of a workflow, returning the checked steps and the dimensions
+/// of all of the input files read by the workflow's steps, if there are any
+pub fn workflow_from_config(
+    steps: Vec<UnverifiedWorkflowStepConfig>,
+) -> Result<(Vec<WorkflowStepConfig>, Option<ImageDimensions>), Box<dyn Error>> {
+    if steps.is_empty() {
+        return Err(Box::new(WorkflowError::Empty));
+    }
+    let mut checker = WorkflowChecker {
+        output_kinds: HashMap::new(),
+        image_formats: HashMap::new(),
+        dimensions: Vec::new(),
+    };
+    let mut steps_checked = Vec::with_capacity(steps.len());
+    for (i, step) in steps.into_iter().enumerate() {
+        if let Some(reference) = step.for_each.as_ref() {
+            if !checker.output_kinds.contains_key(reference) {
+                return Err(WorkflowError::UnknownStep {
+                    step: step.name,
+                    reference: reference.clone(),
+                })
+                .in_field("for_each")
+                .at_index(i);
+            }
+        }
+        let operation = checker
+            .check_operation(&step.name, step.operation)
+            .in_field("operation")
+            .at_index(i)?;
+        if checker
+            .output_kinds
+            .insert(step.name.clone(), operation.output_kind())
+            .is_some()
+        {
+            return Err(WorkflowError::DuplicateStep(step.name))
+                .in_field("name")
+                .at_index(i);
+        }
+        steps_checked.push(WorkflowStepConfig {
+            name: step.name,
+            for_each: step.for_each,
+            operation,
+        });
+    }
+    let mut dimensions = checker.dimensions.into_iter();
+    let image_dimensions = match dimensions.next() {
+        Some(first_dimensions) => Some(dimensions.try_fold(first_dimensions, |dim1, dim2| {
+            super::check_dimensions_match2(&dim1, &dim2).copied()
+        })?),
+        None => None,
+    };
+    Ok((steps_checked, image_dimensions))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,233 @@
+mod workflow_from_config {
+    use super::super::super::{
+        ImageConversionPolicy, ImagePath, LosslessImagePath, PermutationPath,
+        UnverifiedLosslessImagePath,
+    };
+    use super::super::{
+        UnverifiedWorkflowInput, UnverifiedWorkflowOperationConfig, UnverifiedWorkflowStepConfig,
+        WorkflowInput, WorkflowOperationConfig, WorkflowStepConfig,
+    };
+    use image_annealing::ImageDimensions;
+    use std::error::Error;
+
+    fn create_permutation_step(name: &str) -> UnverifiedWorkflowStepConfig {
+        UnverifiedWorkflowStepConfig {
+            name: String::from(name),
+            for_each: None,
+            operation: UnverifiedWorkflowOperationConfig::CreatePermutation {
+                permutation_output_path_no_extension: None,
+            },
+        }
+    }
+
+    fn permute_step(
+        name: &str,
+        for_each: Option<&str>,
+        candidate_permutation: UnverifiedWorkflowInput<String>,
+    ) -> UnverifiedWorkflowStepConfig {
+        UnverifiedWorkflowStepConfig {
+            name: String::from(name),
+            for_each: for_each.map(String::from),
+            operation: UnverifiedWorkflowOperationConfig::Permute {
+                candidate_permutation,
+                original_image: UnverifiedWorkflowInput::File(UnverifiedLosslessImagePath::Rgba8(
+                    String::from("../test_data/image/image/stripes.png"),
+                )),
+                original_image_conversion: None,
+                permuted_image_output_path_no_extension: Some(UnverifiedLosslessImagePath::Rgba8(
+                    String::from("permuted"),
+                )),
+            },
+        }
+    }
+
+    #[test]
+    fn valid() -> Result<(), Box<dyn Error>> {
+        let (steps, dimensions) = super::super::workflow_from_config(vec![
+            create_permutation_step("initial"),
+            permute_step(
+                "permute",
+                Some("initial"),
+                UnverifiedWorkflowInput::Step(String::from("initial")),
+            ),
+        ])?;
+        assert_eq!(
+            steps,
+            vec![
+                WorkflowStepConfig {
+                    name: String::from("initial"),
+                    for_each: None,
+                    operation: WorkflowOperationConfig::CreatePermutation {
+                        permutation_output_path_no_extension: None,
+                    },
+                },
+                WorkflowStepConfig {
+                    name: String::from("permute"),
+                    for_each: Some(String::from("initial")),
+                    operation: WorkflowOperationConfig::Permute {
+                        candidate_permutation: WorkflowInput::Step(String::from("initial")),
+                        original_image: WorkflowInput::File(LosslessImagePath::Rgba8(
+                            String::from("../test_data/image/image/stripes.png"),
+                        )),
+                        original_image_conversion: ImageConversionPolicy::Strict,
+                        permuted_image_output_path_no_extension: Some(LosslessImagePath::Rgba8(
+                            String::from("permuted"),
+                        )),
+                    },
+                },
+            ]
+        );
+        assert_eq!(dimensions, Some(ImageDimensions::try_new(20, 25)?));
+        Ok(())
+    }
+
+    #[test]
+    fn no_input_files() -> Result<(), Box<dyn Error>> {
+        let (_, dimensions) =
+            super::super::workflow_from_config(vec![create_permutation_step("initial")])?;
+        assert!(dimensions.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn file_input() -> Result<(), Box<dyn Error>> {
+        let (steps, _) = super::super::workflow_from_config(vec![permute_step(
+            "permute",
+            None,
+            UnverifiedWorkflowInput::File(String::from(
+                "../test_data/image/permutation/identity_permutation.png",
+            )),
+        )])?;
+        match &steps[0].operation {
+            WorkflowOperationConfig::Permute {
+                candidate_permutation,
+                ..
+            } => assert_eq!(
+                *candidate_permutation,
+                WorkflowInput::File(PermutationPath::from_raw_clone(
+                    "../test_data/image/permutation/identity_permutation.png"
+                ))
+            ),
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn auto_output_format_from_step() -> Result<(), Box<dyn Error>> {
+        let (steps, _) = super::super::workflow_from_config(vec![
+            create_permutation_step("initial"),
+            permute_step(
+                "permute",
+                None,
+                UnverifiedWorkflowInput::Step(String::from("initial")),
+            ),
+            UnverifiedWorkflowStepConfig {
+                name: String::from("permute_again"),
+                for_each: None,
+                operation: UnverifiedWorkflowOperationConfig::Permute {
+                    candidate_permutation: UnverifiedWorkflowInput::Step(String::from("initial")),
+                    original_image: UnverifiedWorkflowInput::Step(String::from("permute")),
+                    original_image_conversion: None,
+                    permuted_image_output_path_no_extension: Some(
+                        UnverifiedLosslessImagePath::Auto(vec![String::from("permuted_again")]),
+                    ),
+                },
+            },
+        ])?;
+        match &steps[2].operation {
+            WorkflowOperationConfig::Permute {
+                permuted_image_output_path_no_extension,
+                ..
+            } => assert_eq!(
+                *permuted_image_output_path_no_extension,
+                Some(LosslessImagePath::Rgba8(String::from("permuted_again")))
+            ),
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn empty() {
+        test_util::assert_error_contains(
+            super::super::workflow_from_config(Vec::new()),
+            "the workflow has no steps",
+        );
+    }
+
+    #[test]
+    fn duplicate_step() {
+        test_util::assert_error_contains(
+            super::super::workflow_from_config(vec![
+                create_permutation_step("initial"),
+                create_permutation_step("initial"),
+            ]),
+            "more than one workflow step is named 'initial'",
+        );
+    }
+
+    #[test]
+    fn later_step() {
+        test_util::assert_error_contains(
+            super::super::workflow_from_config(vec![
+                permute_step(
+                    "permute",
+                    None,
+                    UnverifiedWorkflowInput::Step(String::from("initial")),
+                ),
+                create_permutation_step("initial"),
+            ]),
+            "workflow step 'permute' refers to 'initial', which is not the name of an earlier step",
+        );
+    }
+
+    #[test]
+    fn unknown_loop_step() {
+        test_util::assert_error_contains(
+            super::super::workflow_from_config(vec![
+                create_permutation_step("initial"),
+                permute_step(
+                    "permute",
+                    Some("swap"),
+                    UnverifiedWorkflowInput::Step(String::from("initial")),
+                ),
+            ]),
+            "workflow step 'permute' refers to 'swap', which is not the name of an earlier step",
+        );
+    }
+
+    #[test]
+    fn output_kind_mismatch() {
+        test_util::assert_error_contains(
+            super::super::workflow_from_config(vec![
+                create_permutation_step("initial"),
+                permute_step(
+                    "first",
+                    None,
+                    UnverifiedWorkflowInput::Step(String::from("initial")),
+                ),
+                permute_step(
+                    "second",
+                    None,
+                    UnverifiedWorkflowInput::Step(String::from("first")),
+                ),
+            ]),
+            "workflow step 'second' cannot use the image output of step 'first' as a permutation",
+        );
+    }
+
+    #[test]
+    fn dimensions_mismatch() {
+        test_util::assert_error_contains(
+            super::super::workflow_from_config(vec![permute_step(
+                "permute",
+                None,
+                UnverifiedWorkflowInput::File(String::from(
+                    "../test_data/image/displacement_goal/identity_larger_displacement_goal.png",
+                )),
+            )]),
+            "mismatch in image dimensions",
+        );
+    }
+}
@@ -1,18 +1,56 @@
-use super::data::{Config, UnverifiedConfig};
+use super::data::{
+    Config, OutputPolicyConfig, ResolvePaths, UnverifiedConfig, UnverifiedConfigFile,
+};
+use image_annealing_cli_util::config_file::ConfigFileSource;
+use image_annealing_cli_util::io::PathContext;
 use std::error::Error;
-use std::fs::File;
-use std::io::BufReader;
 use std::path::Path;
 
+/// Reads a configuration file and its output policy, expanding variables in its paths,
+/// and resolving relative paths against the directory containing the file unless the file opts out
+pub fn parse_unverified_config_file<P: AsRef<Path>>(
+    filename: P,
+) -> Result<(UnverifiedConfig, OutputPolicyConfig), Box<dyn Error>> {
+    parse_unverified_config_source(&ConfigFileSource::read(&filename)?, filename)
+}
+
+fn parse_unverified_config_source<P: AsRef<Path>>(
+    source: &ConfigFileSource,
+    filename: P,
+) -> Result<(UnverifiedConfig, OutputPolicyConfig), Box<dyn Error>> {
+    let UnverifiedConfigFile {
+        variables,
+        paths_relative_to_config_file,
+        output_policy,
+        mut config,
+    } = source.deserialize()?;
+    let base_directory = if paths_relative_to_config_file.unwrap_or(true) {
+        filename.as_ref().parent().map(Path::to_path_buf)
+    } else {
+        None
+    };
+    config.resolve_paths(&PathContext::new(
+        base_directory,
+        variables.unwrap_or_default(),
+    ))?;
+    Ok((config, output_policy.map(Into::into).unwrap_or_default()))
+}
+
+/// Reads and verifies a configuration file, and checks that its output policy can be applied,
+/// showing the location in the file of the field that caused an error, if any
 pub fn parse_config_file<P: AsRef<Path>>(filename: P) -> Result<Config, Box<dyn Error>> {
-    image_annealing_cli_util::io::check_input_file_path(&filename)?;
-    let file = File::open(filename)?;
-    let reader = BufReader::new(file);
-    let unverified_config: UnverifiedConfig = serde_json::from_reader(reader)
-        .map_err(|e| format!("configuration file deserialization error, \"{}\"", e))?;
+    let source = ConfigFileSource::read(&filename)?;
+    let (unverified_config, output_policy) = parse_unverified_config_source(&source, filename)?;
+    Config::try_from_with_output_policy(unverified_config, output_policy)
+        .map_err(|e| source.annotate_error(e))
+}
 
-    let config = Config::try_from(unverified_config)?;
-    Ok(config)
+/// Writes the JSON Schema of configuration files to a file,
+/// or to standard output if no file path is given
+pub fn write_config_file_schema<P: AsRef<Path>>(
+    output_file: Option<P>,
+) -> Result<(), Box<dyn Error>> {
+    image_annealing_cli_util::config_file::write_schema::<UnverifiedConfigFile, P>(output_file)
 }
 
 #[cfg(test)]
@@ -1,5 +1,8 @@
 mod parse_config_file {
-    use super::super::super::{AlgorithmConfig, Config, ImagePath, PermutationPath};
+    use super::super::super::{
+        AlgorithmConfig, Config, ImageConversionPolicy, ImagePath, LosslessImagePath,
+        PermutationPath, PermuteImageConfig,
+    };
     use super::super::parse_config_file;
     use image_annealing::{compute, ImageDimensions};
     use std::error::Error;
@@ -26,30 +29,58 @@ mod parse_config_file {
     }
 
     #[test]
-    fn valid_create_permutation_config_file() -> Result<(), Box<dyn Error>> {
+    fn malformed_toml_config_file() {
         let path = test_util::make_test_data_path([
             "config",
             "operation",
             "create_permutation",
-            "valid.json",
+            "malformed.toml",
         ]);
+        test_util::assert_error_contains(parse_config_file(path), "at line 5 column");
+    }
+
+    fn check_valid_create_permutation_config_file(filename: &str) -> Result<(), Box<dyn Error>> {
+        let path =
+            test_util::make_test_data_path(["config", "operation", "create_permutation", filename]);
         let r = parse_config_file(path)?;
         assert_eq!(
             r,
             Config {
                 algorithm: AlgorithmConfig::CreatePermutation {
-                    permutation_output_path_no_extension: PermutationPath::from_raw_clone(
-                        "permutation_out"
+                    permutation_output_path_no_extension: PermutationPath::from_raw(
+                        test_util::make_test_data_path_string([
+                            "config",
+                            "operation",
+                            "create_permutation",
+                            "permutation_out",
+                        ])
                     ),
                 },
                 dispatcher: compute::Config {
-                    image_dimensions: ImageDimensions::try_new(20, 25)?
-                }
+                    image_dimensions: ImageDimensions::try_new(20, 25)?,
+                    boundary: Default::default(),
+                },
+                output_policy: Default::default(),
             }
         );
         Ok(())
     }
 
+    #[test]
+    fn valid_create_permutation_config_file() -> Result<(), Box<dyn Error>> {
+        check_valid_create_permutation_config_file("valid.json")
+    }
+
+    #[test]
+    fn valid_create_permutation_toml_config_file() -> Result<(), Box<dyn Error>> {
+        check_valid_create_permutation_config_file("valid.toml")
+    }
+
+    #[test]
+    fn valid_create_permutation_yaml_config_file() -> Result<(), Box<dyn Error>> {
+        check_valid_create_permutation_config_file("valid.yaml")
+    }
+
     #[test]
     fn invalid_create_permutation_config_file() {
         let path = test_util::make_test_data_path([
@@ -60,4 +91,193 @@ mod parse_config_file {
         ]);
         test_util::assert_error_contains(parse_config_file(path), "width is zero");
     }
+
+    #[test]
+    fn invalid_field_location() {
+        let path = test_util::make_test_data_path([
+            "config",
+            "operation",
+            "create_permutation",
+            "invalid.json",
+        ]);
+        let message = parse_config_file(path).unwrap_err().to_string();
+        assert!(message.contains("(configuration field `CreatePermutation.image_dimensions`)"));
+        assert!(message.contains("invalid.json:3:5"));
+        assert!(message.contains("3 │     \"image_dimensions\": {"));
+    }
+
+    #[test]
+    fn invalid_type_location() {
+        let path = test_util::make_test_data_path([
+            "config",
+            "operation",
+            "create_permutation",
+            "invalid_type.yaml",
+        ]);
+        let message = parse_config_file(path).unwrap_err().to_string();
+        assert!(
+            message.contains("(configuration field `CreatePermutation.image_dimensions.width`)")
+        );
+        assert!(message.contains("invalid_type.yaml:3:5"));
+        assert!(message.contains("3 │     width: twenty"));
+    }
+
+    #[test]
+    fn refuse_overwrite() {
+        let path = test_util::make_test_data_path([
+            "config",
+            "operation",
+            "create_permutation",
+            "refuse_overwrite.json",
+        ]);
+        let message = parse_config_file(path).unwrap_err().to_string();
+        assert!(message.contains("refusing to overwrite existing output files"));
+        assert!(message.contains("identity_permutation.png"));
+        assert!(message.contains("(configuration field `output_policy.existing_files`)"));
+        assert!(message.contains("refuse_overwrite.json:3:5"));
+    }
+
+    #[test]
+    fn variables() -> Result<(), Box<dyn Error>> {
+        std::env::set_var("IMAGE_ANNEALING_CLI_TEST_SUFFIX", "out");
+        let path = test_util::make_test_data_path([
+            "config",
+            "operation",
+            "create_permutation",
+            "variables.json",
+        ]);
+        let r = parse_config_file(path)?;
+        assert_eq!(
+            r.algorithm,
+            AlgorithmConfig::CreatePermutation {
+                permutation_output_path_no_extension: PermutationPath::from_raw(
+                    test_util::make_test_data_path_string([
+                        "config",
+                        "operation",
+                        "create_permutation",
+                        "permutation_out",
+                    ])
+                ),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn undefined_variable() {
+        let path = test_util::make_test_data_path([
+            "config",
+            "operation",
+            "create_permutation",
+            "undefined_variable.json",
+        ]);
+        test_util::assert_error_contains(
+            parse_config_file(path),
+            "refers to variable 'IMAGE_ANNEALING_CLI_TEST_UNDEFINED'",
+        );
+    }
+
+    #[test]
+    fn paths_relative_to_working_directory() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_data_path([
+            "config",
+            "operation",
+            "create_permutation",
+            "working_directory.json",
+        ]);
+        let r = parse_config_file(path)?;
+        assert_eq!(
+            r.algorithm,
+            AlgorithmConfig::CreatePermutation {
+                permutation_output_path_no_extension: PermutationPath::from_raw_clone(
+                    "permutation_out"
+                ),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn input_paths_relative_to_config_file() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_data_path(["config", "operation", "permute", "valid.json"]);
+        let r = parse_config_file(path)?;
+        assert_eq!(
+            r.algorithm,
+            AlgorithmConfig::Permute {
+                candidate_permutation: PermutationPath::from_raw(
+                    test_util::make_test_data_path_string([
+                        "config",
+                        "operation",
+                        "permute",
+                        "..",
+                        "..",
+                        "..",
+                        "image",
+                        "permutation",
+                        "identity_permutation.png",
+                    ])
+                ),
+                images: vec![PermuteImageConfig {
+                    original_image: LosslessImagePath::Rgba8(
+                        test_util::make_test_data_path_string([
+                            "config",
+                            "operation",
+                            "permute",
+                            "..",
+                            "..",
+                            "..",
+                            "image",
+                            "image",
+                            "stripes.png",
+                        ])
+                    ),
+                    original_image_conversion: ImageConversionPolicy::Strict,
+                    permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
+                        test_util::make_test_data_path_string([
+                            "config",
+                            "operation",
+                            "permute",
+                            "permuted_image_out",
+                        ])
+                    ),
+                }],
+            }
+        );
+        Ok(())
+    }
+}
+
+mod write_config_file_schema {
+    use super::super::write_config_file_schema;
+    use std::error::Error;
+
+    #[test]
+    fn write_to_file() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_output_path(["config_file_schema.json"]);
+        write_config_file_schema(Some(&path))?;
+        let contents = std::fs::read_to_string(&path);
+        std::fs::remove_file(&path)?;
+        let schema: serde_json::Value = serde_json::from_str(&contents?)?;
+        assert_eq!(schema["title"], "UnverifiedConfigFile");
+        assert!(schema["properties"]["variables"].is_object());
+        assert!(schema["properties"]["paths_relative_to_config_file"].is_object());
+
+        let variants = schema["oneOf"].as_array().unwrap();
+        assert_eq!(variants.len(), 8);
+        assert!(variants
+            .iter()
+            .any(|variant| variant["required"][0] == "Swap"));
+        // The properties of the file are allowed alongside the operation
+        assert!(variants
+            .iter()
+            .all(|variant| variant.get("additionalProperties").is_none()));
+        // An operation nested in an experiment is not a file,
+        // so it can only contain the name of the operation
+        assert!(schema["definitions"]["UnverifiedConfig"]["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|variant| variant["additionalProperties"] == false));
+        Ok(())
+    }
 }
@@ -2,13 +2,30 @@ mod data;
 mod io;
 
 pub use data::{
-    AlgorithmConfig, Config, CreateDisplacementGoalInputConfig, DisplacementGoalPath, ImagePath,
-    InvalidIterationCountError, InvalidNonnegativeProperFractionError,
-    InvalidNonnegativeRationalNumberError, IterationCount, LosslessImagePath,
-    NonnegativeProperFraction, NonnegativeRationalNumber, PermutationPath, SwapParametersConfig,
-    SwapStopConfig, SwapStopThreshold, UnverifiedConfig,
-    UnverifiedCreateDisplacementGoalInputConfig, UnverifiedCreateDisplacementGoalInputDataConfig,
-    UnverifiedImageDimensionsConfig, UnverifiedIterationCount, UnverifiedSwapParametersConfig,
-    UnverifiedSwapStopConfig, UnverifiedSwapStopThreshold,
+    check_output_directory, existing_outputs, AlgorithmConfig, AnimationFormat, BoundaryMode,
+    Config, CreateDisplacementGoalInputConfig, DisplacementGoalPath, ExistingOutputPolicy,
+    ExperimentAxisKind, ExperimentError, ExperimentRunConfig, ImageConversionPolicy, ImagePath,
+    IntermediatePermutationCadence, IntermediatePermutationsConfig, InvalidFrameStrideError,
+    InvalidIntermediatePermutationCadenceError, InvalidIterationCountError,
+    InvalidNonnegativeProperFractionError, InvalidNonnegativeRationalNumberError,
+    InvalidSwapStopCriterionError, InvalidVectorFieldVisualizationParametersError, IterationCount,
+    LosslessImagePath, NonnegativeProperFraction, NonnegativeRationalNumber, OutputFiles,
+    OutputPolicyConfig, OutputPolicyError, PermutationNameFields, PermutationNameTemplate,
+    PermutationNameTemplateError, PermutationPath, PermuteImageConfig, PermuteInputError,
+    ResolvePaths, SwapAnimationConfig, SwapCheckpoint, SwapCheckpointConfig, SwapCheckpointError,
+    SwapCheckpointFile, SwapInputError, SwapParametersConfig, SwapPass, SwapStatisticsLogConfig,
+    SwapStatisticsLogFormat, SwapStopConfig, SwapStopCriterion, SwapStopThreshold,
+    UnverifiedConfig, UnverifiedConfigFile, UnverifiedCreateDisplacementGoalInputConfig,
+    UnverifiedCreateDisplacementGoalInputDataConfig, UnverifiedExperimentAxis,
+    UnverifiedExperimentSampling, UnverifiedExperimentValues, UnverifiedImageDimensionsConfig,
+    UnverifiedImageLayerPath, UnverifiedIntermediatePermutationCadence,
+    UnverifiedIntermediatePermutationsConfig, UnverifiedIterationCount,
+    UnverifiedLosslessImagePath, UnverifiedOutputPolicyConfig, UnverifiedPermuteImageInput,
+    UnverifiedPermuteImageOutput, UnverifiedSwapAnimationConfig, UnverifiedSwapCheckpointConfig,
+    UnverifiedSwapParametersConfig, UnverifiedSwapStatisticsLogConfig, UnverifiedSwapStopConfig,
+    UnverifiedSwapStopCriterion, UnverifiedSwapStopThreshold,
+    UnverifiedVectorFieldVisualizationParametersConfig, UnverifiedWorkflowInput,
+    UnverifiedWorkflowOperationConfig, UnverifiedWorkflowStepConfig, WorkflowError, WorkflowInput,
+    WorkflowOperationConfig, WorkflowOutputKind, WorkflowStepConfig,
 };
-pub use io::parse_config_file;
+pub use io::{parse_config_file, parse_unverified_config_file, write_config_file_schema};
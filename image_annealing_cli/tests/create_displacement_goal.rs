@@ -24,7 +24,9 @@ fn create_displacement_goal_valid() -> Result<(), Box<dyn Error>> {
         },
         dispatcher: compute::Config {
             image_dimensions: dimensions,
+            boundary: Default::default(),
         },
+        output_policy: Default::default(),
     };
     cli::run(config)?;
 
@@ -54,7 +56,11 @@ fn create_displacement_goal_invalid() -> Result<(), Box<dyn Error>> {
                 test_util::make_test_output_path_string(["cli_create_displacement_goal_invalid"]),
             ),
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(cli::run(config), "out of bounds mapping (x, y, delta_x, delta_y) = (3, 10, 257, 511) for an image of dimensions (width, height) = (20, 25)");
     Ok(())
@@ -78,7 +84,11 @@ fn invalid_displacement_goal_format() -> Result<(), Box<dyn Error>> {
                 ]),
             ),
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(
         cli::run(config),
@@ -105,7 +115,11 @@ fn invalid_permutation_format() -> Result<(), Box<dyn Error>> {
                 ]),
             ),
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(
         cli::run(config),
@@ -130,7 +144,11 @@ fn invalid_image_format() -> Result<(), Box<dyn Error>> {
                 ]),
             ),
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(
         cli::run(config),
@@ -151,7 +169,9 @@ fn save_missing_directory() -> Result<(), Box<dyn Error>> {
         },
         dispatcher: compute::Config {
             image_dimensions: dimensions,
+            boundary: Default::default(),
         },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(cli::run(config), "No such file or directory");
     Ok(())
@@ -18,7 +18,9 @@ fn create_permutation() -> Result<(), Box<dyn Error>> {
         },
         dispatcher: compute::Config {
             image_dimensions: dimensions,
+            boundary: Default::default(),
         },
+        output_policy: Default::default(),
     };
     cli::run(config)?;
 
@@ -40,7 +42,9 @@ fn save_missing_directory() -> Result<(), Box<dyn Error>> {
         },
         dispatcher: compute::Config {
             image_dimensions: ImageDimensions::try_new(3, 4)?,
+            boundary: Default::default(),
         },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(cli::run(config), "No such file or directory");
     Ok(())
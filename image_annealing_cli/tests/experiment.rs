@@ -0,0 +1,127 @@
+use image_annealing::compute::conversion::{self, VectorFieldEntry};
+use image_annealing::compute::format::{ImageFileWriter, VectorFieldImageBuffer};
+use image_annealing::compute::{self, SwapPassSequence};
+use image_annealing::ImageDimensions;
+use image_annealing_cli::cli;
+use image_annealing_cli::config::{
+    AlgorithmConfig, Config, DisplacementGoalPath, ExperimentRunConfig, ImagePath, IterationCount,
+    PermutationPath, SwapParametersConfig, SwapStopConfig,
+};
+use std::error::Error;
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+#[test]
+fn experiment_valid() -> Result<(), Box<dyn Error>> {
+    let output_directory = test_util::make_test_output_path_string(["cli_experiment"]);
+    assert!(!Path::new(&output_directory).exists());
+    std::fs::create_dir(&output_directory)?;
+
+    let dimensions = ImageDimensions::try_new(1, 6)?;
+    let input_permutation = conversion::to_image(
+        &dimensions,
+        &[
+            VectorFieldEntry(0, 1),
+            VectorFieldEntry(0, -1),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+        ],
+    );
+    let input_permutation_path =
+        input_permutation.save_add_extension(test_util::make_test_output_path([
+            "cli_experiment_input_permutation",
+        ]))?;
+    let input_displacement_goal = conversion::to_image(
+        &dimensions,
+        &[
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, -3),
+            VectorFieldEntry(0, -3),
+            VectorFieldEntry(0, -3),
+        ],
+    );
+    let input_displacement_goal_path =
+        input_displacement_goal.save_add_extension(test_util::make_test_output_path([
+            "cli_experiment_input_displacement_goal",
+        ]))?;
+
+    let runs = [0.0, 1.0]
+        .into_iter()
+        .enumerate()
+        .map(|(i, swap_acceptance_threshold)| {
+            let name = format!("run_{}", i);
+            let directory = Path::new(&output_directory).join(&name);
+            ExperimentRunConfig {
+                name,
+                permutation_output_path_prefix: PermutationPath::from_raw(
+                    directory.join("permutation").to_str().unwrap(),
+                ),
+                directory: String::from(directory.to_str().unwrap()),
+                swap_history_output_path_no_extension: None,
+                checkpoint: None,
+                parameters: SwapParametersConfig {
+                    stop: SwapStopConfig::Bounded {
+                        iteration_count: IterationCount(NonZeroUsize::new(2).unwrap()),
+                        threshold: None,
+                    },
+                    swap_acceptance_threshold,
+                    swap_pass_sequence: SwapPassSequence::from_passes([
+                        compute::SwapPass::Vertical,
+                        compute::SwapPass::OffsetVertical,
+                    ])
+                    .unwrap(),
+                    output_intermediate_permutations: false,
+                    intermediate_permutations: Default::default(),
+                    animation: None,
+                    statistics_log: None,
+                },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let config = Config {
+        algorithm: AlgorithmConfig::Experiment {
+            candidate_permutation: PermutationPath::from_raw_clone(
+                input_permutation_path.to_str().unwrap(),
+            ),
+            displacement_goal: DisplacementGoalPath::from_raw_clone(
+                input_displacement_goal_path.to_str().unwrap(),
+            ),
+            output_directory: output_directory.clone(),
+            runs,
+            seed: None,
+        },
+        dispatcher: compute::Config {
+            image_dimensions: dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
+    };
+    cli::run(config)?;
+
+    for run in ["run_0", "run_1"] {
+        assert!(VectorFieldImageBuffer::make_filename(
+            Path::new(&output_directory)
+                .join(run)
+                .join("permutation_round_1_pass_1_offset_vertical")
+                .to_str()
+                .unwrap()
+        )
+        .is_file());
+    }
+    let summary = std::fs::read_to_string(Path::new(&output_directory).join("summary.csv"))?;
+    let rows = summary.lines().collect::<Vec<_>>();
+    assert_eq!(rows.len(), 3);
+    assert!(rows[1].starts_with("run_0,"));
+    assert!(rows[2].starts_with("run_1,"));
+    assert!(Path::new(&output_directory).join("summary.json").is_file());
+
+    std::fs::remove_file(input_permutation_path)?;
+    std::fs::remove_file(input_displacement_goal_path)?;
+    std::fs::remove_dir_all(output_directory)?;
+    Ok(())
+}
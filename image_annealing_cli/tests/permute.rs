@@ -4,7 +4,7 @@ use image_annealing::compute::format::{
 };
 use image_annealing_cli::cli;
 use image_annealing_cli::config::{
-    AlgorithmConfig, Config, ImagePath, LosslessImagePath, PermutationPath,
+    AlgorithmConfig, Config, ImagePath, LosslessImagePath, PermutationPath, PermuteImageConfig,
 };
 use std::error::Error;
 use test_util::permutation::DimensionsAndPermutation;
@@ -35,14 +35,19 @@ fn permute_valid() -> Result<(), Box<dyn Error>> {
             candidate_permutation: PermutationPath::from_raw_clone(
                 input_permutation_path.to_str().unwrap(),
             ),
-            original_image: LosslessImagePath::Rgba16(String::from(
-                input_image_path.to_str().unwrap(),
-            )),
-            permuted_image_output_path_no_extension: LosslessImagePath::Rgba16(path),
+            images: vec![PermuteImageConfig {
+                original_image: LosslessImagePath::Rgba16(String::from(
+                    input_image_path.to_str().unwrap(),
+                )),
+                original_image_conversion: Default::default(),
+                permuted_image_output_path_no_extension: LosslessImagePath::Rgba16(path),
+            }],
         },
         dispatcher: compute::Config {
             image_dimensions: dimensions,
+            boundary: Default::default(),
         },
+        output_policy: Default::default(),
     };
     cli::run(config)?;
 
@@ -55,6 +60,74 @@ fn permute_valid() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn permute_list_valid() -> Result<(), Box<dyn Error>> {
+    let DimensionsAndPermutation {
+        permutation: input_permutation,
+        dimensions,
+    } = test_util::permutation::bit_interpretation_cases();
+    let input_permutation_path_prefix =
+        test_util::make_test_output_path(["cli_permute_list_input_permutation"]);
+    let input_permutation_path =
+        input_permutation.save_add_extension(input_permutation_path_prefix)?;
+
+    let mut input_images = vec![test_util::image::coordinates_to_colors(&dimensions)];
+    input_images
+        .push(test_util::permutation::bit_interpretation_cases_forward_permute(&input_images[0]));
+    let mut images = Vec::new();
+    let mut input_image_paths = Vec::new();
+    let mut full_output_paths = Vec::new();
+    for (i, input_image) in input_images.iter().enumerate() {
+        let input_image_path = input_image.save_add_extension(test_util::make_test_output_path(
+            [format!("cli_permute_list_input_image_{}", i)],
+        ))?;
+        let path = test_util::make_test_output_path_string([format!("cli_permute_list_{}", i)]);
+        let full_output_path = Rgba8Image::make_filename(&path);
+        assert!(!full_output_path.is_file());
+        images.push(PermuteImageConfig {
+            original_image: LosslessImagePath::Rgba16(String::from(
+                input_image_path.to_str().unwrap(),
+            )),
+            original_image_conversion: Default::default(),
+            permuted_image_output_path_no_extension: LosslessImagePath::Rgba16(path),
+        });
+        input_image_paths.push(input_image_path);
+        full_output_paths.push(full_output_path);
+    }
+
+    let config = Config {
+        algorithm: AlgorithmConfig::Permute {
+            candidate_permutation: PermutationPath::from_raw_clone(
+                input_permutation_path.to_str().unwrap(),
+            ),
+            images,
+        },
+        dispatcher: compute::Config {
+            image_dimensions: dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
+    };
+    cli::run(config)?;
+
+    for ((input_image, input_image_path), full_output_path) in input_images
+        .iter()
+        .zip(input_image_paths)
+        .zip(full_output_paths)
+    {
+        let output_image = Rgba16ImageBuffer::load(&full_output_path)?;
+        assert_eq!(
+            output_image,
+            test_util::permutation::bit_interpretation_cases_forward_permute(input_image)
+        );
+        std::fs::remove_file(input_image_path)?;
+        std::fs::remove_file(full_output_path)?;
+    }
+    std::fs::remove_file(input_permutation_path)?;
+
+    Ok(())
+}
+
 #[test]
 fn permute_invalid() -> Result<(), Box<dyn Error>> {
     let (candidate_permutation_path, image_dimensions) =
@@ -63,20 +136,26 @@ fn permute_invalid() -> Result<(), Box<dyn Error>> {
             "permutation",
             "invalid_permutation.png",
         ]))?;
-    let config = Config {
-        algorithm: AlgorithmConfig::Permute {
-            candidate_permutation: candidate_permutation_path,
-            original_image: LosslessImagePath::Rgba8(test_util::make_test_data_path_string([
-                "image",
-                "image",
-                "stripes.png",
-            ])),
-            permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
-                test_util::make_test_output_path_string(["cli_permute_invalid"]),
-            ),
-        },
-        dispatcher: compute::Config { image_dimensions },
-    };
+    let config =
+        Config {
+            algorithm: AlgorithmConfig::Permute {
+                candidate_permutation: candidate_permutation_path,
+                images: vec![PermuteImageConfig {
+                    original_image: LosslessImagePath::Rgba8(
+                        test_util::make_test_data_path_string(["image", "image", "stripes.png"]),
+                    ),
+                    original_image_conversion: Default::default(),
+                    permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
+                        test_util::make_test_output_path_string(["cli_permute_invalid"]),
+                    ),
+                }],
+            },
+            dispatcher: compute::Config {
+                image_dimensions,
+                boundary: Default::default(),
+            },
+            output_policy: Default::default(),
+        };
     test_util::assert_error_contains(cli::run(config), "out of bounds mapping (x, y, delta_x, delta_y) = (3, 10, 257, 511) for an image of dimensions (width, height) = (20, 25)");
     Ok(())
 }
@@ -87,20 +166,28 @@ fn invalid_permutation_format() -> Result<(), Box<dyn Error>> {
         PermutationPath::from_input_path(test_util::make_test_data_path_string([
             "image", "image", "red.png",
         ]))?;
-    let config = Config {
-        algorithm: AlgorithmConfig::Permute {
-            candidate_permutation: candidate_permutation_path,
-            original_image: LosslessImagePath::Rgba8(test_util::make_test_data_path_string([
-                "image",
-                "image",
-                "stripes.png",
-            ])),
-            permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
-                test_util::make_test_output_path_string(["cli_permute_invalid_permutation_format"]),
-            ),
-        },
-        dispatcher: compute::Config { image_dimensions },
-    };
+    let config =
+        Config {
+            algorithm: AlgorithmConfig::Permute {
+                candidate_permutation: candidate_permutation_path,
+                images: vec![PermuteImageConfig {
+                    original_image: LosslessImagePath::Rgba8(
+                        test_util::make_test_data_path_string(["image", "image", "stripes.png"]),
+                    ),
+                    original_image_conversion: Default::default(),
+                    permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
+                        test_util::make_test_output_path_string([
+                            "cli_permute_invalid_permutation_format",
+                        ]),
+                    ),
+                }],
+            },
+            dispatcher: compute::Config {
+                image_dimensions,
+                boundary: Default::default(),
+            },
+            output_policy: Default::default(),
+        };
     test_util::assert_error_contains(
         cli::run(config),
         &format!("not the expected format of {}", ImageFormat::Rgba8),
@@ -119,16 +206,23 @@ fn invalid_image_format() -> Result<(), Box<dyn Error>> {
     let config = Config {
         algorithm: AlgorithmConfig::Permute {
             candidate_permutation: candidate_permutation_path,
-            original_image: LosslessImagePath::Rgba16(test_util::make_test_data_path_string([
-                "image",
-                "image",
-                "stripes.png",
-            ])),
-            permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
-                test_util::make_test_output_path_string(["cli_permute_invalid_image_format"]),
-            ),
+            images: vec![PermuteImageConfig {
+                original_image: LosslessImagePath::Rgba16(test_util::make_test_data_path_string([
+                    "image",
+                    "image",
+                    "stripes.png",
+                ])),
+                original_image_conversion: Default::default(),
+                permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(
+                    test_util::make_test_output_path_string(["cli_permute_invalid_image_format"]),
+                ),
+            }],
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(
         cli::run(config),
@@ -146,18 +240,24 @@ fn save_missing_directory() -> Result<(), Box<dyn Error>> {
             "permutation",
             "identity_permutation.png",
         ]))?;
-    let config = Config {
-        algorithm: AlgorithmConfig::Permute {
-            candidate_permutation: candidate_permutation_path,
-            original_image: LosslessImagePath::Rgba8(test_util::make_test_data_path_string([
-                "image",
-                "image",
-                "stripes.png",
-            ])),
-            permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(path),
-        },
-        dispatcher: compute::Config { image_dimensions },
-    };
+    let config =
+        Config {
+            algorithm: AlgorithmConfig::Permute {
+                candidate_permutation: candidate_permutation_path,
+                images: vec![PermuteImageConfig {
+                    original_image: LosslessImagePath::Rgba8(
+                        test_util::make_test_data_path_string(["image", "image", "stripes.png"]),
+                    ),
+                    original_image_conversion: Default::default(),
+                    permuted_image_output_path_no_extension: LosslessImagePath::Rgba8(path),
+                }],
+            },
+            dispatcher: compute::Config {
+                image_dimensions,
+                boundary: Default::default(),
+            },
+            output_policy: Default::default(),
+        };
     test_util::assert_error_contains(cli::run(config), "No such file or directory");
     Ok(())
 }
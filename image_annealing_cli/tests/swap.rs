@@ -1,13 +1,17 @@
 use image_annealing::compute::conversion::{self, VectorFieldEntry};
 use image_annealing::compute::format::{
-    ImageFileReader, ImageFileWriter, ImageFormat, VectorFieldImageBuffer,
+    ImageFileReader, ImageFileWriter, ImageFormat, RunMetadata, SwapHistoryReader,
+    SwapHistoryWriter, VectorFieldImageBuffer,
 };
 use image_annealing::compute::{self, SwapPassSequence};
+use image_annealing::image_utils::validation;
 use image_annealing::ImageDimensions;
 use image_annealing_cli::cli;
 use image_annealing_cli::config::{
-    AlgorithmConfig, Config, DisplacementGoalPath, ImagePath, IterationCount, PermutationPath,
-    SwapParametersConfig, SwapStopConfig,
+    AlgorithmConfig, AnimationFormat, Config, DisplacementGoalPath, ImageConversionPolicy,
+    ImagePath, IterationCount, PermutationPath, SwapAnimationConfig, SwapCheckpointFile,
+    SwapParametersConfig, SwapPass, SwapStopConfig, UnverifiedConfig, UnverifiedIterationCount,
+    UnverifiedSwapCheckpointConfig, UnverifiedSwapParametersConfig, UnverifiedSwapStopConfig,
 };
 use std::error::Error;
 use std::num::NonZeroUsize;
@@ -25,6 +29,9 @@ fn make_swap_parameters() -> SwapParametersConfig {
         ])
         .unwrap(),
         output_intermediate_permutations: true,
+        intermediate_permutations: Default::default(),
+        animation: None,
+        statistics_log: None,
     }
 }
 
@@ -85,15 +92,20 @@ fn swap_valid() -> Result<(), Box<dyn Error>> {
             candidate_permutation: PermutationPath::from_raw_clone(
                 input_permutation_path.to_str().unwrap(),
             ),
+            resume: None,
             displacement_goal: DisplacementGoalPath::from_raw_clone(
                 input_displacement_goal_path.to_str().unwrap(),
             ),
             permutation_output_path_prefix: PermutationPath::from_raw(path_prefix),
+            swap_history_output_path_no_extension: None,
+            checkpoint: None,
             parameters: make_swap_parameters(),
         },
         dispatcher: compute::Config {
             image_dimensions: dimensions,
+            boundary: Default::default(),
         },
+        output_policy: Default::default(),
     };
     cli::run(config)?;
 
@@ -130,6 +142,21 @@ fn swap_valid() -> Result<(), Box<dyn Error>> {
             &conversion::to_vec(&output_permutation),
             expected_permutation
         );
+
+        let metadata = RunMetadata::read(full_output_path)?.unwrap();
+        assert_eq!(metadata.operation, "Swap");
+        assert_eq!(metadata.round_index, Some(i / 2));
+        assert_eq!(metadata.pass_index, Some(i % 2));
+        assert_eq!(
+            metadata.pass.as_deref(),
+            Some(if i % 2 == 0 {
+                "vertical"
+            } else {
+                "offset_vertical"
+            })
+        );
+        assert_eq!(metadata.threshold, Some(0.0));
+        assert_eq!(metadata.input_hashes.len(), 2);
     }
 
     std::fs::remove_file(input_permutation_path)?;
@@ -141,6 +168,340 @@ fn swap_valid() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn swap_history() -> Result<(), Box<dyn Error>> {
+    let path_prefix = test_util::make_test_output_path_string(["cli_swap_history"]);
+    let final_output_path = VectorFieldImageBuffer::make_filename(format!(
+        "{}_round_1_pass_1_offset_vertical",
+        path_prefix
+    ));
+    let history_path_no_extension =
+        test_util::make_test_output_path_string(["cli_swap_history_log"]);
+    let history_path = SwapHistoryWriter::make_filename(&history_path_no_extension);
+    assert!(!final_output_path.is_file());
+    assert!(!history_path.is_file());
+
+    let dimensions = ImageDimensions::try_new(1, 6)?;
+    let input_permutation = conversion::to_image(
+        &dimensions,
+        &[
+            VectorFieldEntry(0, 1),
+            VectorFieldEntry(0, -1),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+        ],
+    );
+    let input_permutation_path =
+        input_permutation.save_add_extension(test_util::make_test_output_path([
+            "cli_swap_history_input_permutation",
+        ]))?;
+    let input_displacement_goal = conversion::to_image(
+        &dimensions,
+        &[
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, -3),
+            VectorFieldEntry(0, -3),
+            VectorFieldEntry(0, -3),
+        ],
+    );
+    let input_displacement_goal_path =
+        input_displacement_goal.save_add_extension(test_util::make_test_output_path([
+            "cli_swap_history_input_displacement_goal",
+        ]))?;
+
+    let config = Config {
+        algorithm: AlgorithmConfig::Swap {
+            candidate_permutation: PermutationPath::from_raw_clone(
+                input_permutation_path.to_str().unwrap(),
+            ),
+            resume: None,
+            displacement_goal: DisplacementGoalPath::from_raw_clone(
+                input_displacement_goal_path.to_str().unwrap(),
+            ),
+            permutation_output_path_prefix: PermutationPath::from_raw(path_prefix),
+            swap_history_output_path_no_extension: Some(history_path_no_extension),
+            checkpoint: None,
+            parameters: SwapParametersConfig {
+                output_intermediate_permutations: false,
+                intermediate_permutations: Default::default(),
+                ..make_swap_parameters()
+            },
+        },
+        dispatcher: compute::Config {
+            image_dimensions: dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
+    };
+    cli::run(config)?;
+
+    // Safety: The input permutation satisfies permutation constraints.
+    let initial_permutation =
+        unsafe { validation::vector_field_into_validated_permutation_unchecked(input_permutation) };
+    let first_pass_permutation =
+        SwapHistoryReader::open(&history_path)?.replay(initial_permutation.clone(), 0, 0)?;
+    assert_eq!(
+        conversion::to_vec(first_pass_permutation.as_ref()),
+        [
+            VectorFieldEntry(0, 1),
+            VectorFieldEntry(0, -1),
+            VectorFieldEntry(0, 1),
+            VectorFieldEntry(0, -1),
+            VectorFieldEntry(0, 1),
+            VectorFieldEntry(0, -1),
+        ]
+    );
+    let final_permutation =
+        SwapHistoryReader::open(&history_path)?.replay(initial_permutation, 1, 1)?;
+    assert_eq!(
+        *final_permutation.as_ref(),
+        VectorFieldImageBuffer::load(&final_output_path)?
+    );
+
+    std::fs::remove_file(input_permutation_path)?;
+    std::fs::remove_file(input_displacement_goal_path)?;
+    std::fs::remove_file(final_output_path)?;
+    std::fs::remove_file(history_path)?;
+    Ok(())
+}
+
+#[test]
+fn swap_animation() -> Result<(), Box<dyn Error>> {
+    let path_prefix = test_util::make_test_output_path_string(["cli_swap_animation"]);
+    let final_output_path = VectorFieldImageBuffer::make_filename(format!(
+        "{}_round_1_pass_1_offset_vertical",
+        path_prefix
+    ));
+    let intermediate_output_path =
+        VectorFieldImageBuffer::make_filename(format!("{}_round_0_pass_0_vertical", path_prefix));
+    let animation_path_no_extension =
+        test_util::make_test_output_path_string(["cli_swap_animation_frames"]);
+    let animation_path = test_util::make_test_output_path(["cli_swap_animation_frames.png"]);
+    assert!(!final_output_path.is_file());
+    assert!(!animation_path.is_file());
+
+    let dimensions = ImageDimensions::try_new(1, 6)?;
+    let input_permutation = conversion::to_image(
+        &dimensions,
+        &[
+            VectorFieldEntry(0, 1),
+            VectorFieldEntry(0, -1),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+        ],
+    );
+    let input_permutation_path =
+        input_permutation.save_add_extension(test_util::make_test_output_path([
+            "cli_swap_animation_input_permutation",
+        ]))?;
+    let input_displacement_goal = conversion::to_image(
+        &dimensions,
+        &[
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, -3),
+            VectorFieldEntry(0, -3),
+            VectorFieldEntry(0, -3),
+        ],
+    );
+    let input_displacement_goal_path =
+        input_displacement_goal.save_add_extension(test_util::make_test_output_path([
+            "cli_swap_animation_input_displacement_goal",
+        ]))?;
+    let input_image = test_util::image::coordinates_to_colors(&dimensions);
+    let input_image_path = input_image.save_add_extension(test_util::make_test_output_path([
+        "cli_swap_animation_input_image",
+    ]))?;
+
+    let config = Config {
+        algorithm: AlgorithmConfig::Swap {
+            candidate_permutation: PermutationPath::from_raw_clone(
+                input_permutation_path.to_str().unwrap(),
+            ),
+            resume: None,
+            displacement_goal: DisplacementGoalPath::from_raw_clone(
+                input_displacement_goal_path.to_str().unwrap(),
+            ),
+            permutation_output_path_prefix: PermutationPath::from_raw(path_prefix),
+            swap_history_output_path_no_extension: None,
+            checkpoint: None,
+            parameters: SwapParametersConfig {
+                output_intermediate_permutations: false,
+                intermediate_permutations: Default::default(),
+                animation: Some(SwapAnimationConfig {
+                    image: String::from(input_image_path.to_str().unwrap()),
+                    image_conversion: ImageConversionPolicy::AllowNarrowing,
+                    path_no_extension: animation_path_no_extension,
+                    format: AnimationFormat::Apng,
+                    frame_stride: NonZeroUsize::new(2).unwrap(),
+                    parameters: Default::default(),
+                }),
+                ..make_swap_parameters()
+            },
+        },
+        dispatcher: compute::Config {
+            image_dimensions: dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
+    };
+    cli::run(config)?;
+
+    // Intermediate permutations are only used to render frames
+    assert!(!intermediate_output_path.is_file());
+    assert!(final_output_path.is_file());
+
+    // Frames show the initial permutation, the permutation after two passes,
+    // and the final permutation
+    let animation = std::fs::read(&animation_path)?;
+    assert_eq!(
+        animation
+            .windows(4)
+            .filter(|&chunk_type| chunk_type == b"fcTL")
+            .count(),
+        3
+    );
+
+    std::fs::remove_file(input_permutation_path)?;
+    std::fs::remove_file(input_displacement_goal_path)?;
+    std::fs::remove_file(input_image_path)?;
+    std::fs::remove_file(final_output_path)?;
+    std::fs::remove_file(animation_path)?;
+    Ok(())
+}
+
+#[test]
+fn swap_checkpoint_resume() -> Result<(), Box<dyn Error>> {
+    let path_prefix = test_util::make_test_output_path_string(["cli_swap_checkpoint"]);
+    let resumed_path_prefix = test_util::make_test_output_path_string(["cli_swap_resumed"]);
+    let checkpoint_path_prefix =
+        test_util::make_test_output_path_string(["cli_swap_checkpoint_state"]);
+    let [final_output_path, resumed_final_output_path] =
+        [&path_prefix, &resumed_path_prefix].map(|prefix| {
+            VectorFieldImageBuffer::make_filename(format!(
+                "{}_round_2_pass_1_offset_vertical",
+                prefix
+            ))
+        });
+    let checkpoint_paths_no_extension =
+        [0, 1].map(|round_index| format!("{}_round_{}", checkpoint_path_prefix, round_index));
+    assert!(!final_output_path.is_file());
+    assert!(!resumed_final_output_path.is_file());
+    for path_no_extension in checkpoint_paths_no_extension.iter() {
+        assert!(!SwapCheckpointFile::make_filename(path_no_extension).is_file());
+        assert!(!VectorFieldImageBuffer::make_filename(path_no_extension).is_file());
+    }
+
+    let dimensions = ImageDimensions::try_new(1, 6)?;
+    let input_permutation = conversion::to_image(
+        &dimensions,
+        &[
+            VectorFieldEntry(0, 1),
+            VectorFieldEntry(0, -1),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+            VectorFieldEntry(0, 0),
+        ],
+    );
+    let input_permutation_path =
+        input_permutation.save_add_extension(test_util::make_test_output_path([
+            "cli_swap_checkpoint_input_permutation",
+        ]))?;
+    let input_displacement_goal = conversion::to_image(
+        &dimensions,
+        &[
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, 3),
+            VectorFieldEntry(0, -3),
+            VectorFieldEntry(0, -3),
+            VectorFieldEntry(0, -3),
+        ],
+    );
+    let input_displacement_goal_path =
+        input_displacement_goal.save_add_extension(test_util::make_test_output_path([
+            "cli_swap_checkpoint_input_displacement_goal",
+        ]))?;
+
+    let make_unverified_config =
+        |candidate_permutation, resume, permutation_output_path_prefix| UnverifiedConfig::Swap {
+            candidate_permutation,
+            resume,
+            displacement_goal: String::from(input_displacement_goal_path.to_str().unwrap()),
+            permutation_output_path_prefix,
+            swap_history_output_path_no_extension: None,
+            checkpoint: Some(UnverifiedSwapCheckpointConfig {
+                path_prefix: checkpoint_path_prefix.clone(),
+                interval: UnverifiedIterationCount(1),
+            }),
+            parameters: UnverifiedSwapParametersConfig {
+                stop: UnverifiedSwapStopConfig::Bounded {
+                    iteration_count: UnverifiedIterationCount(3),
+                    threshold: None,
+                },
+                swap_acceptance_threshold: Default::default(),
+                swap_pass_sequence: vec![SwapPass::Vertical, SwapPass::OffsetVertical],
+                output_intermediate_permutations: false,
+                intermediate_permutations: None,
+                animation: None,
+                statistics_log: None,
+            },
+            boundary: None,
+        };
+
+    cli::run(Config::try_from(make_unverified_config(
+        Some(String::from(input_permutation_path.to_str().unwrap())),
+        None,
+        path_prefix.clone(),
+    ))?)?;
+    for path_no_extension in checkpoint_paths_no_extension.iter() {
+        assert!(SwapCheckpointFile::make_filename(path_no_extension).is_file());
+    }
+
+    let resumed_config = Config::try_from(make_unverified_config(
+        None,
+        Some(String::from(
+            SwapCheckpointFile::make_filename(&checkpoint_paths_no_extension[0])
+                .to_str()
+                .unwrap(),
+        )),
+        resumed_path_prefix.clone(),
+    ))?;
+    match &resumed_config.algorithm {
+        AlgorithmConfig::Swap { resume, .. } => assert_eq!(
+            resume
+                .as_ref()
+                .map(|resume| (resume.round_index, resume.pass_index)),
+            Some((0, 1))
+        ),
+        _ => unreachable!(),
+    }
+    cli::run(resumed_config)?;
+
+    assert_eq!(
+        VectorFieldImageBuffer::load(&final_output_path)?,
+        VectorFieldImageBuffer::load(&resumed_final_output_path)?
+    );
+
+    std::fs::remove_file(input_permutation_path)?;
+    std::fs::remove_file(input_displacement_goal_path)?;
+    std::fs::remove_file(final_output_path)?;
+    std::fs::remove_file(resumed_final_output_path)?;
+    for path_no_extension in checkpoint_paths_no_extension {
+        std::fs::remove_file(SwapCheckpointFile::make_filename(&path_no_extension))?;
+        std::fs::remove_file(VectorFieldImageBuffer::make_filename(&path_no_extension))?;
+    }
+    Ok(())
+}
+
 #[test]
 fn swap_invalid() -> Result<(), Box<dyn Error>> {
     let (candidate_permutation_path, image_dimensions) =
@@ -152,6 +513,7 @@ fn swap_invalid() -> Result<(), Box<dyn Error>> {
     let config = Config {
         algorithm: AlgorithmConfig::Swap {
             candidate_permutation: candidate_permutation_path,
+            resume: None,
             displacement_goal: DisplacementGoalPath::from_raw(
                 test_util::make_test_data_path_string([
                     "image",
@@ -162,9 +524,15 @@ fn swap_invalid() -> Result<(), Box<dyn Error>> {
             permutation_output_path_prefix: PermutationPath::from_raw(
                 test_util::make_test_output_path_string(["cli_swap_invalid"]),
             ),
+            swap_history_output_path_no_extension: None,
+            checkpoint: None,
             parameters: make_swap_parameters(),
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(cli::run(config), "out of bounds mapping (x, y, delta_x, delta_y) = (3, 10, 257, 511) for an image of dimensions (width, height) = (20, 25)");
     Ok(())
@@ -179,6 +547,7 @@ fn invalid_permutation_format() -> Result<(), Box<dyn Error>> {
     let config = Config {
         algorithm: AlgorithmConfig::Swap {
             candidate_permutation: candidate_permutation_path,
+            resume: None,
             displacement_goal: DisplacementGoalPath::from_raw(
                 test_util::make_test_data_path_string([
                     "image",
@@ -189,9 +558,15 @@ fn invalid_permutation_format() -> Result<(), Box<dyn Error>> {
             permutation_output_path_prefix: PermutationPath::from_raw(
                 test_util::make_test_output_path_string(["cli_swap_invalid_permutation_format"]),
             ),
+            swap_history_output_path_no_extension: None,
+            checkpoint: None,
             parameters: make_swap_parameters(),
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(
         cli::run(config),
@@ -211,6 +586,7 @@ fn invalid_displacement_goal_format() -> Result<(), Box<dyn Error>> {
     let config = Config {
         algorithm: AlgorithmConfig::Swap {
             candidate_permutation: candidate_permutation_path,
+            resume: None,
             displacement_goal: DisplacementGoalPath::from_raw(
                 test_util::make_test_data_path_string(["image", "image", "red.png"]),
             ),
@@ -219,9 +595,15 @@ fn invalid_displacement_goal_format() -> Result<(), Box<dyn Error>> {
                     "cli_swap_invalid_displacement_goal_format",
                 ]),
             ),
+            swap_history_output_path_no_extension: None,
+            checkpoint: None,
             parameters: make_swap_parameters(),
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(
         cli::run(config),
@@ -242,6 +624,7 @@ fn save_missing_directory() -> Result<(), Box<dyn Error>> {
     let config = Config {
         algorithm: AlgorithmConfig::Swap {
             candidate_permutation: candidate_permutation_path,
+            resume: None,
             displacement_goal: DisplacementGoalPath::from_raw(
                 test_util::make_test_data_path_string([
                     "image",
@@ -250,9 +633,15 @@ fn save_missing_directory() -> Result<(), Box<dyn Error>> {
                 ]),
             ),
             permutation_output_path_prefix: PermutationPath::from_raw(path),
+            swap_history_output_path_no_extension: None,
+            checkpoint: None,
             parameters: make_swap_parameters(),
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(cli::run(config), "No such file or directory");
     Ok(())
@@ -16,7 +16,11 @@ fn validate_permutation_valid() -> Result<(), Box<dyn Error>> {
         algorithm: AlgorithmConfig::ValidatePermutation {
             candidate_permutation: candidate_permutation_path,
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     cli::run(config)?;
     Ok(())
@@ -34,7 +38,11 @@ fn validate_permutation_invalid() -> Result<(), Box<dyn Error>> {
         algorithm: AlgorithmConfig::ValidatePermutation {
             candidate_permutation: candidate_permutation_path,
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(cli::run(config), "out of bounds mapping (x, y, delta_x, delta_y) = (3, 10, 257, 511) for an image of dimensions (width, height) = (20, 25)");
     Ok(())
@@ -50,7 +58,11 @@ fn invalid_permutation_format() -> Result<(), Box<dyn Error>> {
         algorithm: AlgorithmConfig::ValidatePermutation {
             candidate_permutation: candidate_permutation_path,
         },
-        dispatcher: compute::Config { image_dimensions },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
     };
     test_util::assert_error_contains(
         cli::run(config),
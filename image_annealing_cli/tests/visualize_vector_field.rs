@@ -0,0 +1,65 @@
+use image_annealing::compute;
+use image_annealing::compute::format::{ImageFileReader, ImageFileWriter, Rgba8Image, RunMetadata};
+use image_annealing::image_utils::visualization::VectorFieldVisualizationParameters;
+use image_annealing::ImageDimensionsHolder;
+use image_annealing_cli::cli;
+use image_annealing_cli::config::{AlgorithmConfig, Config, DisplacementGoalPath, ImagePath};
+use std::error::Error;
+use std::num::NonZeroU32;
+
+#[test]
+fn visualize_vector_field_valid() -> Result<(), Box<dyn Error>> {
+    let path = test_util::make_test_output_path_string(["cli_visualize_vector_field"]);
+    let legend_path =
+        test_util::make_test_output_path_string(["cli_visualize_vector_field_legend"]);
+    let full_output_path = Rgba8Image::make_filename(&path);
+    let full_legend_path = Rgba8Image::make_filename(&legend_path);
+    assert!(!full_output_path.is_file());
+    assert!(!full_legend_path.is_file());
+
+    let (vector_field_path, image_dimensions) =
+        DisplacementGoalPath::from_input_path(test_util::make_test_data_path_string([
+            "image",
+            "permutation",
+            "identity_permutation.png",
+        ]))?;
+    let config = Config {
+        algorithm: AlgorithmConfig::VisualizeVectorField {
+            vector_field: vector_field_path.clone(),
+            visualization_output_path_no_extension: path,
+            legend_output_path_no_extension: Some(legend_path),
+            parameters: VectorFieldVisualizationParameters {
+                max_magnitude: None,
+                arrow_spacing: NonZeroU32::new(4),
+            },
+        },
+        dispatcher: compute::Config {
+            image_dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
+    };
+    cli::run(config)?;
+
+    // The identity permutation has no displacements, so it is rendered black
+    let output_image = Rgba8Image::load(&full_output_path)?;
+    assert_eq!(output_image.dimensions(), &image_dimensions);
+    assert!(output_image
+        .as_ref()
+        .pixels()
+        .all(|px| px.0 == [0, 0, 0, 255]));
+    assert!(full_legend_path.is_file());
+
+    let metadata = RunMetadata::read(&full_output_path)?.unwrap();
+    assert_eq!(metadata.operation, "VisualizeVectorField");
+    assert_eq!(metadata.input_hashes.len(), 1);
+    assert_eq!(metadata.input_hashes[0].path, vector_field_path.to_string());
+    assert_eq!(
+        metadata.input_hashes[0].sha256,
+        image_annealing_cli_util::hash::sha256_file(&metadata.input_hashes[0].path)?
+    );
+
+    std::fs::remove_file(full_output_path)?;
+    std::fs::remove_file(full_legend_path)?;
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use image_annealing::compute;
+use image_annealing::compute::format::{ImageFileReader, ImageFileWriter, Rgba16ImageBuffer};
+use image_annealing_cli::cli;
+use image_annealing_cli::config::{
+    AlgorithmConfig, Config, ImagePath, LosslessImagePath, PermutationPath, WorkflowInput,
+    WorkflowOperationConfig, WorkflowStepConfig,
+};
+use std::error::Error;
+use test_util::permutation::DimensionsAndPermutation;
+
+#[test]
+fn workflow_valid() -> Result<(), Box<dyn Error>> {
+    let path = test_util::make_test_output_path_string(["cli_workflow"]);
+    // The permute step runs once for the output of the validate step
+    let full_output_path = Rgba16ImageBuffer::make_filename(format!("{}_0", path));
+    assert!(!full_output_path.is_file());
+
+    let DimensionsAndPermutation {
+        permutation: input_permutation,
+        dimensions,
+    } = test_util::permutation::bit_interpretation_cases();
+    let input_permutation_path =
+        input_permutation.save_add_extension(test_util::make_test_output_path([
+            "cli_workflow_input_permutation",
+        ]))?;
+
+    let input_image = test_util::image::coordinates_to_colors(&dimensions);
+    let permuted_image =
+        test_util::permutation::bit_interpretation_cases_forward_permute(&input_image);
+    let input_image_path = input_image.save_add_extension(test_util::make_test_output_path([
+        "cli_workflow_input_image",
+    ]))?;
+
+    let config = Config {
+        algorithm: AlgorithmConfig::Workflow {
+            steps: vec![
+                WorkflowStepConfig {
+                    name: String::from("validate"),
+                    for_each: None,
+                    operation: WorkflowOperationConfig::ValidatePermutation {
+                        candidate_permutation: WorkflowInput::File(
+                            PermutationPath::from_raw_clone(
+                                input_permutation_path.to_str().unwrap(),
+                            ),
+                        ),
+                    },
+                },
+                WorkflowStepConfig {
+                    name: String::from("permute"),
+                    for_each: Some(String::from("validate")),
+                    operation: WorkflowOperationConfig::Permute {
+                        candidate_permutation: WorkflowInput::Step(String::from("validate")),
+                        original_image: WorkflowInput::File(LosslessImagePath::Rgba16(
+                            String::from(input_image_path.to_str().unwrap()),
+                        )),
+                        original_image_conversion: Default::default(),
+                        permuted_image_output_path_no_extension: Some(LosslessImagePath::Rgba16(
+                            path,
+                        )),
+                    },
+                },
+            ],
+        },
+        dispatcher: compute::Config {
+            image_dimensions: dimensions,
+            boundary: Default::default(),
+        },
+        output_policy: Default::default(),
+    };
+    cli::run(config)?;
+
+    let output_image = Rgba16ImageBuffer::load(&full_output_path)?;
+    assert_eq!(output_image, permuted_image);
+    std::fs::remove_file(input_permutation_path)?;
+    std::fs::remove_file(input_image_path)?;
+    std::fs::remove_file(full_output_path)?;
+
+    Ok(())
+}
@@ -43,8 +43,11 @@ cargo build -p image_annealing_cli_bin --release --bins
 #
 # This operation generates an identity permutation that will be used as the initial permutation.
 CREATE_PERMUTATION_CONFIG_FILE="${BASE_OUTPUT_DIRECTORY}/create_permutation_config.json"
+# The paths in this file are relative to the working directory, the root directory of this repository,
+# rather than to the directory containing the file.
 cat << _FILE_CONTENTS_ > "${CREATE_PERMUTATION_CONFIG_FILE}"
 {
+  "paths_relative_to_config_file": false,
   "CreatePermutation": {
     "image_dimensions": {
       "width": ${IMAGE_WIDTH},
@@ -82,66 +85,38 @@ INPUT_IMAGE_FILE="${BASE_OUTPUT_DIRECTORY}/image.png"
 # RAW_INPUT_IMAGE_FILE="some_image.jpeg"
 # convert -alpha opaque "${RAW_INPUT_IMAGE_FILE}" "${INPUT_IMAGE_FILE}"
 
-# In order to generate images with sequential file names, we first
-# count the number of permutations so that we know how many characters
-# to use for sequence numbers in file names.
-NUMBER_OF_FILES=$(find "${SWAP_OUTPUT_DIRECTORY}" -maxdepth 1 -type f -name '*.png' -printf x | wc -c)
-FIELD_WIDTH=${#NUMBER_OF_FILES}
-
-# Copy the original input image as the zeroeth permuted image,
-# the first image in the sequence.
-PADDED_NUMBER="$(printf "%0${FIELD_WIDTH}d" "0")"
-cp "${INPUT_IMAGE_FILE}" "${IMAGE_OUTPUT_DIRECTORY}/${PADDED_NUMBER}.png"
+# The swap operation names the permutations with zero-padded sequence numbers,
+# as set by the `name_template` in config/swap.json, so the permutations,
+# and the images named after them, are listed in the order they were created.
+# Copy the original input image as the first image in the sequence.
+cp "${INPUT_IMAGE_FILE}" "${IMAGE_OUTPUT_DIRECTORY}/initial.png"
 
 # This JSON file defines the permute operation, and will be updated to refer
 # to each permutation in turn.
 PERMUTE_CONFIG_FILE="${BASE_OUTPUT_DIRECTORY}/permute_config.json"
 
 # Loop over permutations
-i=1 # Iteration counter
-ROUND=0 # The sequence number of the round of swap passes corresponding to the current permutation
-PASS=0 # The sequence number of the swap pass corresponding to the current permutation
-while [ $i -le "${NUMBER_OF_FILES}" ]; do
-    # Generate the file name of the i-th permutation,
-    # so that the file name can be written into the JSON file.
-    PERMUTATION_FILE="${SWAP_OUTPUT_DIRECTORY}/permutation_round_${ROUND}_pass_${PASS}"
-    case $PASS in
-        0) PERMUTATION_FILE="${PERMUTATION_FILE}_horizontal.png";;
-        1) PERMUTATION_FILE="${PERMUTATION_FILE}_vertical.png";;
-        2) PERMUTATION_FILE="${PERMUTATION_FILE}_offset_horizontal.png";;
-        3) PERMUTATION_FILE="${PERMUTATION_FILE}_offset_vertical.png";;
-        *)
-            echo "invalid swap pass index"
-            exit 1;;
-    esac
-
+for PERMUTATION_FILE in "${SWAP_OUTPUT_DIRECTORY}"/permutation_*.png; do
     echo "Permuting with ${PERMUTATION_FILE}"
 
     # Update the JSON configuration file
-    PADDED_NUMBER="$(printf "%0${FIELD_WIDTH}d" "$i")"
+    PERMUTATION_NAME="$(basename "${PERMUTATION_FILE}" .png)"
     cat << _FILE_CONTENTS_ > "${PERMUTE_CONFIG_FILE}"
 {
+  "paths_relative_to_config_file": false,
   "Permute": {
     "candidate_permutation": "${PERMUTATION_FILE}",
     "original_image": {
       "Rgba8": "${INPUT_IMAGE_FILE}"
     },
     "permuted_image_output_path_no_extension": {
-      "Rgba8": "${IMAGE_OUTPUT_DIRECTORY}/${PADDED_NUMBER}"
+      "Rgba8": "${IMAGE_OUTPUT_DIRECTORY}/${PERMUTATION_NAME}"
     }
   }
 }
 _FILE_CONTENTS_
     # Run the permute operation
     target/release/main --config "${PERMUTE_CONFIG_FILE}"
-
-    # Update loop variables for the next iteration
-    i=$(( i + 1 ))
-    PASS=$(( PASS + 1 ))
-    if [ "${PASS}" -ge 4 ]; then
-        PASS=0
-        ROUND=$(( ROUND + 1 ))
-    fi
 done
 
 # [Optional] Animated GIF generation
@@ -1,5 +1,5 @@
-use image_annealing_cli::args::{self, ParseFailure};
-use image_annealing_cli::cli;
+use image_annealing_cli::args::{self, ParseFailure, ParsedArgs};
+use image_annealing_cli::{cli, config};
 use std::env;
 use std::io::{self, Write};
 
@@ -11,10 +11,33 @@ fn main() {
             }
             ParseFailure::Stderr(message) => {
                 eprintln!("{}", message);
+                std::process::exit(1);
             }
         },
-        Ok(parsed_config) => {
-            if let Err(err) = cli::run(parsed_config) {
+        Ok(ParsedArgs::Schema { output_file }) => {
+            if let Err(err) = config::write_config_file_schema(output_file) {
+                eprintln!("Schema output error: {}", err);
+                std::process::exit(1);
+            }
+        }
+        Ok(ParsedArgs::Operation {
+            config,
+            check: true,
+        }) => {
+            if let Err(err) = cli::check(&config) {
+                eprintln!("Check error: {}", err);
+                std::process::exit(1);
+            }
+        }
+        Ok(ParsedArgs::Operation {
+            config,
+            check: false,
+        }) => {
+            if let Err(err) = cli::run(*config) {
+                if err.is::<cli::InterruptedError>() {
+                    eprintln!("{}", err);
+                    std::process::exit(cli::INTERRUPTED_EXIT_CODE);
+                }
                 eprintln!("Processing error: {}", err);
                 std::process::exit(1);
             }
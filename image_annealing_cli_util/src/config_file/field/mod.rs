@@ -0,0 +1,235 @@
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::error::Error;
+use std::fmt;
+
+/// A component of the path to a value in a configuration file
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigPathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// The path to a value in a configuration file, such as `Swap.parameters.stop`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConfigPath(Vec<ConfigPathSegment>);
+
+impl ConfigPath {
+    pub fn new(segments: Vec<ConfigPathSegment>) -> Self {
+        Self(segments)
+    }
+
+    pub fn segments(&self) -> &[ConfigPathSegment] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<serde_path_to_error::Path> for ConfigPath {
+    fn from(value: serde_path_to_error::Path) -> Self {
+        Self(
+            value
+                .iter()
+                .filter_map(|segment| match segment {
+                    serde_path_to_error::Segment::Seq { index } => {
+                        Some(ConfigPathSegment::Index(*index))
+                    }
+                    serde_path_to_error::Segment::Map { key } => {
+                        Some(ConfigPathSegment::Key(key.clone()))
+                    }
+                    serde_path_to_error::Segment::Enum { variant } => {
+                        Some(ConfigPathSegment::Key(variant.clone()))
+                    }
+                    serde_path_to_error::Segment::Unknown => None,
+                })
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for ConfigPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                ConfigPathSegment::Key(key) if i == 0 => write!(f, "{}", key)?,
+                ConfigPathSegment::Key(key) => write!(f, ".{}", key)?,
+                ConfigPathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error in the value of a field of a configuration file
+#[derive(Debug)]
+pub struct ConfigFieldError {
+    path: ConfigPath,
+    source: Box<dyn Error>,
+}
+
+impl ConfigFieldError {
+    pub fn path(&self) -> &ConfigPath {
+        &self.path
+    }
+
+    pub fn source_error(&self) -> &dyn Error {
+        self.source.as_ref()
+    }
+}
+
+impl fmt::Display for ConfigFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (configuration field `{}`)", self.source, self.path)
+    }
+}
+
+impl Error for ConfigFieldError {}
+
+fn prepend_segment(error: Box<dyn Error>, segment: ConfigPathSegment) -> Box<dyn Error> {
+    match error.downcast::<ConfigFieldError>() {
+        Ok(mut field_error) => {
+            field_error.path.0.insert(0, segment);
+            field_error
+        }
+        Err(source) => Box::new(ConfigFieldError {
+            path: ConfigPath(vec![segment]),
+            source,
+        }),
+    }
+}
+
+/// Records which field of a configuration file caused an error during verification
+///
+/// Calls are made from the innermost field outwards, so that the path of the field
+/// is built from its last component to its first.
+pub trait ConfigFieldContext<T> {
+    fn in_field(self, key: &str) -> Result<T, Box<dyn Error>>;
+
+    fn at_index(self, index: usize) -> Result<T, Box<dyn Error>>;
+}
+
+impl<T, E: Into<Box<dyn Error>>> ConfigFieldContext<T> for Result<T, E> {
+    fn in_field(self, key: &str) -> Result<T, Box<dyn Error>> {
+        self.map_err(|e| prepend_segment(e.into(), ConfigPathSegment::Key(String::from(key))))
+    }
+
+    fn at_index(self, index: usize) -> Result<T, Box<dyn Error>> {
+        self.map_err(|e| prepend_segment(e.into(), ConfigPathSegment::Index(index)))
+    }
+}
+
+/// The message of the error raised by [`Locator`] when it reaches its target value
+pub const LOCATED_MARKER: &str = "configuration value located";
+
+/// Walks through a configuration file to the value at a path, and fails there with an error
+/// whose position, as recorded by the file format's deserializer, is the location of the value
+///
+/// Succeeds if there is no value at the path.
+pub struct Locator<'a> {
+    pub path: &'a [ConfigPathSegment],
+    /// Whether to fail upon reading the key of a value in a map, instead of upon reading the value
+    ///
+    /// The JSON deserializer reports the positions of errors in maps and sequences
+    /// after looking ahead to the next token, which is often on a later line.
+    pub at_key: bool,
+}
+
+impl<'a> Locator<'a> {
+    fn next(&self) -> Self {
+        Self {
+            path: &self.path[1..],
+            at_key: self.at_key,
+        }
+    }
+
+    fn found<E: de::Error>(&self) -> Result<(), E> {
+        if self.path.is_empty() {
+            Err(E::custom(LOCATED_MARKER))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'de, 'a> DeserializeSeed<'de> for Locator<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for Locator<'a> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, _v: bool) -> Result<(), E> {
+        self.found()
+    }
+
+    fn visit_i64<E: de::Error>(self, _v: i64) -> Result<(), E> {
+        self.found()
+    }
+
+    fn visit_u64<E: de::Error>(self, _v: u64) -> Result<(), E> {
+        self.found()
+    }
+
+    fn visit_f64<E: de::Error>(self, _v: f64) -> Result<(), E> {
+        self.found()
+    }
+
+    fn visit_str<E: de::Error>(self, _v: &str) -> Result<(), E> {
+        self.found()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        self.found()
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<(), E> {
+        self.found()
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        self.found()?;
+        if let ConfigPathSegment::Index(index) = self.path[0] {
+            for _ in 0..index {
+                if seq.next_element::<IgnoredAny>()?.is_none() {
+                    return Ok(());
+                }
+            }
+            seq.next_element_seed(self.next())?;
+        }
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        self.found()?;
+        while let Some(key) = map.next_key::<String>()? {
+            match &self.path[0] {
+                ConfigPathSegment::Key(target) if *target == key => {
+                    if self.at_key && self.path.len() == 1 {
+                        return Err(de::Error::custom(LOCATED_MARKER));
+                    }
+                    map.next_value_seed(self.next())?
+                }
+                _ => map.next_value::<IgnoredAny>().map(|_| ())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
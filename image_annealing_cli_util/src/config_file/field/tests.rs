@@ -0,0 +1,55 @@
+mod config_path {
+    use super::super::{ConfigPath, ConfigPathSegment};
+
+    #[test]
+    fn display() {
+        assert_eq!(ConfigPath::default().to_string(), "");
+        assert_eq!(
+            ConfigPath::new(vec![
+                ConfigPathSegment::Key(String::from("Workflow")),
+                ConfigPathSegment::Key(String::from("steps")),
+                ConfigPathSegment::Index(2),
+                ConfigPathSegment::Key(String::from("Swap")),
+            ])
+            .to_string(),
+            "Workflow.steps[2].Swap"
+        );
+    }
+}
+
+mod config_field_context {
+    use super::super::{ConfigFieldContext, ConfigFieldError, ConfigPath, ConfigPathSegment};
+    use std::error::Error;
+
+    fn invalid() -> Result<(), Box<dyn Error>> {
+        Err("invalid value".into())
+    }
+
+    #[test]
+    fn nested_fields() {
+        let error = invalid()
+            .in_field("iteration_count")
+            .at_index(1)
+            .in_field("Any")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid value (configuration field `Any[1].iteration_count`)"
+        );
+        let field_error = error.downcast::<ConfigFieldError>().unwrap();
+        assert_eq!(
+            field_error.path(),
+            &ConfigPath::new(vec![
+                ConfigPathSegment::Key(String::from("Any")),
+                ConfigPathSegment::Index(1),
+                ConfigPathSegment::Key(String::from("iteration_count")),
+            ])
+        );
+        assert_eq!(field_error.source_error().to_string(), "invalid value");
+    }
+
+    #[test]
+    fn ok() {
+        assert!(Ok::<(), Box<dyn Error>>(()).in_field("field").is_ok());
+    }
+}
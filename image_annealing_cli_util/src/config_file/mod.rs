@@ -0,0 +1,292 @@
+use super::io;
+use schemars::JsonSchema;
+use serde::de::{DeserializeOwned, DeserializeSeed};
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+
+mod field;
+
+pub use field::{ConfigFieldContext, ConfigFieldError, ConfigPath, ConfigPathSegment};
+
+/// The format of a configuration file
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConfigFileFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFileFormat {
+    /// The format selected by the extension of the path, or JSON if the extension
+    /// is not recognized
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("toml") => Self::Toml,
+            Some("yaml" | "yml") => Self::Yaml,
+            _ => Self::Json,
+        }
+    }
+
+    pub fn deserialize<T: DeserializeOwned>(
+        self,
+        contents: &str,
+    ) -> Result<T, ConfigFileDeserializationError> {
+        let mut track = serde_path_to_error::Track::new();
+        let result = match self {
+            Self::Json => {
+                let mut deserializer = serde_json::Deserializer::from_str(contents);
+                T::deserialize(serde_path_to_error::Deserializer::new(
+                    &mut deserializer,
+                    &mut track,
+                ))
+                .and_then(|value| deserializer.end().map(|()| value))
+                .map_err(|e| {
+                    let location = (e.line() != 0).then(|| (e.line(), e.column()));
+                    (e.to_string(), location)
+                })
+            }
+            Self::Toml => T::deserialize(serde_path_to_error::Deserializer::new(
+                toml::Deserializer::new(contents),
+                &mut track,
+            ))
+            .map_err(|e| {
+                // Unlike the messages of JSON and YAML errors,
+                // the message of a TOML error does not include its location
+                let location = e.span().map(|span| line_and_column(contents, span.start));
+                let message = match location {
+                    Some((line, column)) => {
+                        format!("{} at line {} column {}", e.message(), line, column)
+                    }
+                    None => String::from(e.message()),
+                };
+                (message, location)
+            }),
+            // Enumerations are written in the same way as in JSON files, as maps with one entry,
+            // rather than as YAML tags.
+            Self::Yaml => serde_yaml::with::singleton_map_recursive::deserialize(
+                serde_path_to_error::Deserializer::new(
+                    serde_yaml::Deserializer::from_str(contents),
+                    &mut track,
+                ),
+            )
+            .map_err(|e| {
+                let location = e
+                    .location()
+                    .map(|location| (location.line(), location.column()));
+                (e.to_string(), location)
+            }),
+        };
+        result.map_err(|(message, location)| ConfigFileDeserializationError {
+            message,
+            location,
+            path: track.path().into(),
+        })
+    }
+
+    /// Returns the line number of the value at a path in a configuration file,
+    /// if the value exists
+    fn locate(self, contents: &str, path: &[ConfigPathSegment]) -> Option<usize> {
+        let locator = field::Locator {
+            path,
+            at_key: self == Self::Json,
+        };
+        match self {
+            Self::Json => {
+                match locator.deserialize(&mut serde_json::Deserializer::from_str(contents)) {
+                    Err(e) if e.to_string().starts_with(field::LOCATED_MARKER) => Some(e.line()),
+                    _ => None,
+                }
+            }
+            Self::Toml => match locator.deserialize(toml::Deserializer::new(contents)) {
+                Err(e) if e.message() == field::LOCATED_MARKER => {
+                    e.span().map(|span| line_and_column(contents, span.start).0)
+                }
+                _ => None,
+            },
+            Self::Yaml => match locator.deserialize(serde_yaml::Deserializer::from_str(contents)) {
+                Err(e) if e.to_string().contains(field::LOCATED_MARKER) => {
+                    e.location().map(|location| location.line())
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Returns the one-based line and column numbers of a byte offset in a string
+fn line_and_column(contents: &str, offset: usize) -> (usize, usize) {
+    let preceding = contents.get(..offset).unwrap_or(contents);
+    let line = preceding.matches('\n').count() + 1;
+    let column = preceding.chars().rev().take_while(|&c| c != '\n').count() + 1;
+    (line, column)
+}
+
+/// Returns the range of bytes of a line, excluding leading and trailing whitespace,
+/// given its one-based line number
+fn line_range(contents: &str, line: usize) -> Option<Range<usize>> {
+    let start: usize = contents
+        .split_inclusive('\n')
+        .take(line.checked_sub(1)?)
+        .map(str::len)
+        .sum();
+    let text = contents.get(start..)?.lines().next()?;
+    let trimmed_start = start + text.len() - text.trim_start().len();
+    Some(trimmed_start..(start + text.trim_end().len()).max(trimmed_start))
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigFileDeserializationError {
+    message: String,
+    location: Option<(usize, usize)>,
+    path: ConfigPath,
+}
+
+impl ConfigFileDeserializationError {
+    pub fn path(&self) -> &ConfigPath {
+        &self.path
+    }
+}
+
+impl fmt::Display for ConfigFileDeserializationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "configuration file deserialization error, \"{}\"",
+            self.message.trim_end()
+        )?;
+        if !self.path.is_empty() {
+            write!(f, " (configuration field `{}`)", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ConfigFileDeserializationError {}
+
+/// An error in a configuration file, followed by a snippet of the file showing its location
+#[derive(Debug, Clone)]
+pub struct AnnotatedConfigFileError {
+    message: String,
+    snippet: Option<String>,
+}
+
+impl fmt::Display for AnnotatedConfigFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.snippet.as_ref() {
+            Some(snippet) => write!(f, "{}", snippet.trim_end()),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for AnnotatedConfigFileError {}
+
+/// The contents of a configuration file, kept in order to report the locations of errors
+#[derive(Debug, Clone)]
+pub struct ConfigFileSource {
+    filename: String,
+    format: ConfigFileFormat,
+    contents: String,
+}
+
+impl ConfigFileSource {
+    pub fn read<P: AsRef<Path>>(filename: P) -> Result<Self, Box<dyn Error>> {
+        io::check_input_file_path(&filename)?;
+        Ok(Self {
+            filename: filename.as_ref().to_string_lossy().into_owned(),
+            format: ConfigFileFormat::from_path(&filename),
+            contents: std::fs::read_to_string(&filename)?,
+        })
+    }
+
+    pub fn new(filename: String, format: ConfigFileFormat, contents: String) -> Self {
+        Self {
+            filename,
+            format,
+            contents,
+        }
+    }
+
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, AnnotatedConfigFileError> {
+        self.format
+            .deserialize(&self.contents)
+            .map_err(|e| self.annotate(e.to_string(), e.location.map(|(line, _)| line)))
+    }
+
+    /// Adds a snippet of the configuration file showing the field that caused an error,
+    /// if the error is a [`ConfigFieldError`]
+    pub fn annotate_error(&self, error: Box<dyn Error>) -> Box<dyn Error> {
+        match error.downcast::<ConfigFieldError>() {
+            Ok(field_error) => {
+                // Fall back to the closest enclosing value when a field is absent from the file,
+                // such as a field with a default value
+                let segments = field_error.path().segments();
+                let line = (0..=segments.len())
+                    .rev()
+                    .find_map(|length| self.format.locate(&self.contents, &segments[..length]));
+                Box::new(self.annotate(field_error.to_string(), line))
+            }
+            Err(error) => error,
+        }
+    }
+
+    fn annotate(&self, message: String, line: Option<usize>) -> AnnotatedConfigFileError {
+        use codespan_reporting::diagnostic::{Diagnostic, Label};
+        use codespan_reporting::term::{self, termcolor::NoColor};
+
+        let snippet = line
+            .and_then(|line| line_range(&self.contents, line))
+            .and_then(|range| {
+                let file = codespan_reporting::files::SimpleFile::new(
+                    self.filename.as_str(),
+                    self.contents.as_str(),
+                );
+                let diagnostic = Diagnostic::error()
+                    .with_message(&message)
+                    .with_labels(vec![Label::primary((), range)]);
+                let mut writer = NoColor::new(Vec::new());
+                term::emit(&mut writer, &term::Config::default(), &file, &diagnostic).ok()?;
+                String::from_utf8(writer.into_inner()).ok()
+            });
+        AnnotatedConfigFileError { message, snippet }
+    }
+}
+
+/// Reads a configuration file in the format selected by the file's extension
+pub fn parse_config_file<T: DeserializeOwned, P: AsRef<Path>>(
+    filename: P,
+) -> Result<T, Box<dyn Error>> {
+    Ok(ConfigFileSource::read(filename)?.deserialize()?)
+}
+
+/// Writes the JSON Schema of configuration files containing values of type `T`
+fn write_schema_to<T: JsonSchema, W: Write>(mut writer: W) -> Result<(), Box<dyn Error>> {
+    serde_json::to_writer_pretty(&mut writer, &schemars::schema_for!(T))?;
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the JSON Schema of configuration files containing values of type `T` to a file,
+/// or to standard output if no file path is given
+pub fn write_schema<T: JsonSchema, P: AsRef<Path>>(
+    output_file: Option<P>,
+) -> Result<(), Box<dyn Error>> {
+    match output_file {
+        Some(path) => write_schema_to::<T, _>(BufWriter::new(File::create(path)?)),
+        None => write_schema_to::<T, _>(std::io::stdout().lock()),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,299 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+struct Dimensions {
+    width: usize,
+    height: usize,
+}
+
+#[derive(Debug, Deserialize, JsonSchema, PartialEq)]
+enum Operation {
+    Create {
+        dimensions: Dimensions,
+        output: Option<String>,
+    },
+}
+
+fn expected_operation() -> Operation {
+    Operation::Create {
+        dimensions: Dimensions {
+            width: 20,
+            height: 25,
+        },
+        output: Some(String::from("out")),
+    }
+}
+
+mod config_file_format {
+    use super::super::ConfigFileFormat;
+
+    #[test]
+    fn from_path() {
+        assert_eq!(
+            ConfigFileFormat::from_path("a.json"),
+            ConfigFileFormat::Json
+        );
+        assert_eq!(
+            ConfigFileFormat::from_path("a.toml"),
+            ConfigFileFormat::Toml
+        );
+        assert_eq!(
+            ConfigFileFormat::from_path("a.yaml"),
+            ConfigFileFormat::Yaml
+        );
+        assert_eq!(ConfigFileFormat::from_path("a.YML"), ConfigFileFormat::Yaml);
+        assert_eq!(ConfigFileFormat::from_path("a.txt"), ConfigFileFormat::Json);
+        assert_eq!(ConfigFileFormat::from_path("a"), ConfigFileFormat::Json);
+    }
+}
+
+mod deserialize {
+    use super::super::ConfigFileFormat;
+    use super::{expected_operation, Operation};
+    use std::error::Error;
+
+    #[test]
+    fn json() -> Result<(), Box<dyn Error>> {
+        let operation: Operation = ConfigFileFormat::Json.deserialize(
+            r#"{ "Create": { "dimensions": { "width": 20, "height": 25 }, "output": "out" } }"#,
+        )?;
+        assert_eq!(operation, expected_operation());
+        Ok(())
+    }
+
+    #[test]
+    fn toml() -> Result<(), Box<dyn Error>> {
+        let operation: Operation = ConfigFileFormat::Toml.deserialize(
+            "# Comment\n[Create]\noutput = \"out\"\n\n[Create.dimensions]\nwidth = 20\nheight = 25\n",
+        )?;
+        assert_eq!(operation, expected_operation());
+        Ok(())
+    }
+
+    #[test]
+    fn yaml() -> Result<(), Box<dyn Error>> {
+        let operation: Operation = ConfigFileFormat::Yaml.deserialize(
+            "# Comment\nCreate:\n  dimensions:\n    width: 20\n    height: 25\n  output: out\n",
+        )?;
+        assert_eq!(operation, expected_operation());
+        Ok(())
+    }
+
+    #[test]
+    fn json_error() {
+        test_util::assert_error_contains(
+            ConfigFileFormat::Json.deserialize::<Operation>("{\n  \"Create\": 5\n}"),
+            "at line 2 column",
+        );
+    }
+
+    #[test]
+    fn toml_error() {
+        test_util::assert_error_contains(
+            ConfigFileFormat::Toml.deserialize::<Operation>("[Create]\noutput = \n"),
+            "at line 2 column",
+        );
+    }
+
+    #[test]
+    fn yaml_error() {
+        test_util::assert_error_contains(
+            ConfigFileFormat::Yaml.deserialize::<Operation>("Create:\n  dimensions: [\n"),
+            "at line",
+        );
+    }
+
+    #[test]
+    fn error_path() {
+        let json = ConfigFileFormat::Json
+            .deserialize::<Operation>(
+                r#"{ "Create": { "dimensions": { "width": -1, "height": 25 } } }"#,
+            )
+            .unwrap_err();
+        assert_eq!(json.path().to_string(), "Create.dimensions.width");
+        let toml = ConfigFileFormat::Toml
+            .deserialize::<Operation>("[Create.dimensions]\nwidth = 20\nheight = \"25\"\n")
+            .unwrap_err();
+        assert_eq!(toml.path().to_string(), "Create.dimensions.height");
+        let yaml = ConfigFileFormat::Yaml
+            .deserialize::<Operation>("Create:\n  dimensions:\n    width: []\n    height: 25\n")
+            .unwrap_err();
+        assert_eq!(yaml.path().to_string(), "Create.dimensions.width");
+        test_util::assert_error_contains(
+            Err::<(), _>(yaml),
+            "(configuration field `Create.dimensions.width`)",
+        );
+    }
+}
+
+mod locate {
+    use super::super::{ConfigFileFormat, ConfigPathSegment};
+
+    fn path() -> Vec<ConfigPathSegment> {
+        vec![
+            ConfigPathSegment::Key(String::from("Create")),
+            ConfigPathSegment::Key(String::from("sizes")),
+            ConfigPathSegment::Index(1),
+            ConfigPathSegment::Key(String::from("width")),
+        ]
+    }
+
+    #[test]
+    fn json() {
+        let contents = "{\n  \"Create\": {\n    \"output\": \"out\",\n    \"sizes\": [\n      { \"width\": 1 },\n      {\n        \"width\": 2\n      }\n    ]\n  }\n}\n";
+        assert_eq!(ConfigFileFormat::Json.locate(contents, &path()), Some(7));
+        assert_eq!(
+            ConfigFileFormat::Json.locate(contents, &path()[..2]),
+            Some(4)
+        );
+        assert_eq!(
+            ConfigFileFormat::Json.locate(contents, &path()[..1]),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn toml() {
+        let contents = "[Create]\noutput = \"out\"\n\n[[Create.sizes]]\nwidth = 1\n\n[[Create.sizes]]\nwidth = 2\n";
+        assert_eq!(ConfigFileFormat::Toml.locate(contents, &path()), Some(8));
+    }
+
+    #[test]
+    fn yaml() {
+        let contents = "Create:\n  output: out\n  sizes:\n    - width: 1\n    - width: 2\n";
+        assert_eq!(ConfigFileFormat::Yaml.locate(contents, &path()), Some(5));
+        assert_eq!(
+            ConfigFileFormat::Yaml.locate(contents, &path()[..2]),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn missing() {
+        let contents = r#"{ "Create": { "output": "out", "sizes": [{ "width": 1 }] } }"#;
+        assert_eq!(ConfigFileFormat::Json.locate(contents, &path()), None);
+        assert_eq!(
+            ConfigFileFormat::Json
+                .locate(contents, &[ConfigPathSegment::Key(String::from("Remove"))]),
+            None
+        );
+    }
+}
+
+mod config_file_source {
+    use super::super::{ConfigFieldContext, ConfigFileFormat, ConfigFileSource};
+    use super::Operation;
+    use std::error::Error;
+
+    fn source() -> ConfigFileSource {
+        ConfigFileSource::new(
+            String::from("config.json"),
+            ConfigFileFormat::Json,
+            String::from(
+                "{\n  \"Create\": {\n    \"dimensions\": { \"width\": 20, \"height\": 25 },\n    \"output\": 5\n  }\n}\n",
+            ),
+        )
+    }
+
+    #[test]
+    fn deserialization_error() {
+        let error = source().deserialize::<Operation>().unwrap_err().to_string();
+        assert!(error.starts_with(
+            "error: configuration file deserialization error, \"invalid type: integer `5`"
+        ));
+        assert!(error.contains("(configuration field `Create.output`)"));
+        assert!(error.contains("┌─ config.json:4:5"));
+        assert!(error.contains("4 │     \"output\": 5"));
+        assert!(error.contains("^^^^^^^^^^^"));
+    }
+
+    #[test]
+    fn field_error() {
+        let error = source().annotate_error(
+            Err::<(), _>("width is too large")
+                .in_field("width")
+                .in_field("dimensions")
+                .in_field("Create")
+                .unwrap_err(),
+        );
+        let message = error.to_string();
+        assert!(message.starts_with(
+            "error: width is too large (configuration field `Create.dimensions.width`)"
+        ));
+        assert!(message.contains("┌─ config.json:3:5"));
+    }
+
+    #[test]
+    fn absent_field_error() {
+        let message = source()
+            .annotate_error(
+                Err::<(), _>("missing")
+                    .in_field("depth")
+                    .in_field("Create")
+                    .unwrap_err(),
+            )
+            .to_string();
+        assert!(message.contains("(configuration field `Create.depth`)"));
+        assert!(message.contains("┌─ config.json:2:3"));
+    }
+
+    #[test]
+    fn other_error() {
+        let error: Box<dyn Error> = "unrelated".into();
+        assert_eq!(source().annotate_error(error).to_string(), "unrelated");
+    }
+}
+
+mod line_and_column {
+    use super::super::line_and_column;
+
+    #[test]
+    fn offsets() {
+        let contents = "ab\ncd\n";
+        assert_eq!(line_and_column(contents, 0), (1, 1));
+        assert_eq!(line_and_column(contents, 1), (1, 2));
+        assert_eq!(line_and_column(contents, 3), (2, 1));
+        assert_eq!(line_and_column(contents, 5), (2, 3));
+        assert_eq!(line_and_column(contents, 100), (3, 1));
+    }
+}
+
+mod line_range {
+    use super::super::line_range;
+
+    #[test]
+    fn lines() {
+        let contents = "ab\n  cd  \n\n";
+        assert_eq!(line_range(contents, 1), Some(0..2));
+        assert_eq!(line_range(contents, 2), Some(5..7));
+        assert_eq!(line_range(contents, 3), Some(10..10));
+        assert_eq!(line_range(contents, 4), None);
+        assert_eq!(line_range(contents, 0), None);
+    }
+}
+
+mod write_schema_to {
+    use super::super::write_schema_to;
+    use super::Operation;
+    use std::error::Error;
+
+    #[test]
+    fn externally_tagged_enum() -> Result<(), Box<dyn Error>> {
+        let mut buffer = Vec::new();
+        write_schema_to::<Operation, _>(&mut buffer)?;
+        let schema: serde_json::Value = serde_json::from_slice(&buffer)?;
+        assert_eq!(schema["title"], "Operation");
+        let variant = &schema["oneOf"][0];
+        assert_eq!(variant["required"][0], "Create");
+        assert_eq!(
+            variant["properties"]["Create"]["properties"]["dimensions"]["$ref"],
+            "#/definitions/Dimensions"
+        );
+        assert_eq!(
+            schema["definitions"]["Dimensions"]["required"],
+            serde_json::json!(["height", "width"])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+mod sha256_file {
+    use std::error::Error;
+
+    #[test]
+    fn empty_file() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            super::super::sha256_file(test_util::make_test_data_path(["empty.txt"]))?,
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn not_found() {
+        assert!(
+            super::super::sha256_file(test_util::make_test_data_path(["not_found.txt"])).is_err()
+        );
+    }
+}
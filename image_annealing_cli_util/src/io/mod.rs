@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt;
 use std::path::{self, Path, PathBuf};
@@ -86,5 +87,117 @@ pub fn convert_and_check_input_file_path<T: AsRef<str>>(filepath: T) -> Result<S
     Ok(new_path)
 }
 
+#[derive(Debug, Clone)]
+pub enum PathVariableError {
+    Undefined { path: String, variable: String },
+    Unterminated(String),
+}
+
+impl fmt::Display for PathVariableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Undefined { path, variable } => write!(
+                f,
+                "path '{}' refers to variable '{}', which is neither a user-defined variable nor an environment variable",
+                path, variable
+            ),
+            Self::Unterminated(path) => {
+                write!(f, "path '{}' contains a '${{' without a matching '}}'", path)
+            }
+        }
+    }
+}
+
+impl Error for PathVariableError {}
+
+/// The directory and variables used to interpret the paths in a configuration file
+#[derive(Clone, Debug, Default)]
+pub struct PathContext {
+    /// The directory that relative paths are resolved against, instead of the working directory
+    base_directory: Option<PathBuf>,
+    /// Values of `${NAME}` references, which take precedence over environment variables
+    variables: HashMap<String, String>,
+}
+
+impl PathContext {
+    pub fn new(base_directory: Option<PathBuf>, variables: HashMap<String, String>) -> Self {
+        Self {
+            base_directory,
+            variables,
+        }
+    }
+
+    pub fn expand_variables<T: AsRef<str>>(
+        &self,
+        filepath: T,
+    ) -> Result<String, PathVariableError> {
+        let filepath = filepath.as_ref();
+        let mut expanded = String::with_capacity(filepath.len());
+        let mut remainder = filepath;
+        while let Some(start) = remainder.find("${") {
+            expanded.push_str(&remainder[..start]);
+            let reference = &remainder[start + 2..];
+            let end = reference
+                .find('}')
+                .ok_or_else(|| PathVariableError::Unterminated(String::from(filepath)))?;
+            let variable = &reference[..end];
+            match self.variables.get(variable) {
+                Some(value) => expanded.push_str(value),
+                None => match std::env::var(variable) {
+                    Ok(value) => expanded.push_str(&value),
+                    Err(_) => {
+                        return Err(PathVariableError::Undefined {
+                            path: String::from(filepath),
+                            variable: String::from(variable),
+                        })
+                    }
+                },
+            }
+            remainder = &reference[end + 1..];
+        }
+        expanded.push_str(remainder);
+        Ok(expanded)
+    }
+
+    /// Expands variables, converts path separators, and then resolves a relative path
+    /// against the base directory, if there is one
+    pub fn resolve<T: AsRef<str>>(&self, filepath: T) -> Result<String, PathVariableError> {
+        let path = convert_path_separators(self.expand_variables(filepath)?);
+        Ok(match self.base_directory.as_ref() {
+            Some(directory) if Path::new(&path).is_relative() => {
+                directory.join(path).to_string_lossy().into_owned()
+            }
+            _ => path,
+        })
+    }
+
+    /// Like [`PathContext::resolve`], but for a glob pattern, so any glob metacharacters
+    /// in the base directory are escaped
+    pub fn resolve_pattern<T: AsRef<str>>(&self, pattern: T) -> Result<String, PathVariableError> {
+        let pattern = convert_path_separators(self.expand_variables(pattern)?);
+        Ok(match self.base_directory.as_ref() {
+            Some(directory) if Path::new(&pattern).is_relative() => {
+                let directory = directory.to_string_lossy();
+                let mut escaped_directory = String::with_capacity(directory.len());
+                for c in directory.chars() {
+                    match c {
+                        '?' | '*' | '[' | ']' => {
+                            escaped_directory.push('[');
+                            escaped_directory.push(c);
+                            escaped_directory.push(']');
+                        }
+                        _ => escaped_directory.push(c),
+                    }
+                }
+                Path::new(&escaped_directory)
+                    .join(pattern)
+                    .to_string_lossy()
+                    .into_owned()
+            }
+            _ => pattern,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests;
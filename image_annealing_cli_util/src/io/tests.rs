@@ -142,3 +142,105 @@ mod convert_and_check_input_file_path {
         Ok(())
     }
 }
+
+mod path_context {
+    use super::super::PathContext;
+    use std::collections::HashMap;
+    use std::error::Error;
+    use std::path::{Path, PathBuf};
+
+    fn make_context(base_directory: Option<&str>) -> PathContext {
+        PathContext::new(
+            base_directory.map(PathBuf::from),
+            HashMap::from([
+                (String::from("DIRECTORY"), String::from("images")),
+                (String::from("NAME"), String::from("stripes")),
+            ]),
+        )
+    }
+
+    #[test]
+    fn no_variables() -> Result<(), Box<dyn Error>> {
+        assert_eq!(make_context(None).expand_variables("a/b.png")?, "a/b.png");
+        Ok(())
+    }
+
+    #[test]
+    fn user_defined_variables() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            make_context(None).expand_variables("${DIRECTORY}/${NAME}_${NAME}.png")?,
+            "images/stripes_stripes.png"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn environment_variable() -> Result<(), Box<dyn Error>> {
+        std::env::set_var("IMAGE_ANNEALING_CLI_UTIL_TEST_DIRECTORY", "environment");
+        assert_eq!(
+            make_context(None)
+                .expand_variables("${IMAGE_ANNEALING_CLI_UTIL_TEST_DIRECTORY}/${NAME}.png")?,
+            "environment/stripes.png"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn undefined_variable() {
+        test_util::assert_error_contains(
+            make_context(None).expand_variables("${IMAGE_ANNEALING_CLI_UTIL_TEST_UNDEFINED}.png"),
+            "path '${IMAGE_ANNEALING_CLI_UTIL_TEST_UNDEFINED}.png' refers to variable 'IMAGE_ANNEALING_CLI_UTIL_TEST_UNDEFINED', which is neither a user-defined variable nor an environment variable",
+        );
+    }
+
+    #[test]
+    fn unterminated_variable() {
+        test_util::assert_error_contains(
+            make_context(None).expand_variables("${NAME.png"),
+            "path '${NAME.png' contains a '${' without a matching '}'",
+        );
+    }
+
+    #[test]
+    fn resolve_without_base_directory() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            make_context(None).resolve("${NAME}.png")?,
+            String::from("stripes.png")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_relative_path() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            make_context(Some("config")).resolve("${DIRECTORY}/${NAME}.png")?,
+            Path::new("config")
+                .join("images")
+                .join("stripes.png")
+                .to_string_lossy()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_absolute_path() -> Result<(), Box<dyn Error>> {
+        let absolute_path = std::env::current_dir()?
+            .join("stripes.png")
+            .to_string_lossy()
+            .into_owned();
+        assert_eq!(
+            make_context(Some("config")).resolve(&absolute_path)?,
+            absolute_path
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_pattern() -> Result<(), Box<dyn Error>> {
+        assert_eq!(
+            make_context(Some("config[1]")).resolve_pattern("*.png")?,
+            Path::new("config[[]1[]]").join("*.png").to_string_lossy()
+        );
+        Ok(())
+    }
+}
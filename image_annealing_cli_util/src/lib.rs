@@ -1 +1,3 @@
+pub mod config_file;
+pub mod hash;
 pub mod io;
@@ -13,6 +13,8 @@ pub const OUTPUT_PERMUTATION_INDEX: u32 = 3;
 
 pub const OUTPUT_COUNT_BUFFER_INDEX: u32 = 4;
 
+pub const OUTPUT_ACCEPTED_SWAPS_BUFFER_INDEX: u32 = 5;
+
 pub(crate) fn bind_group<W: Write>(mut writer: W) -> std::io::Result<()> {
     super::binding_annotation(&mut writer, GROUP_INDEX, PARAMETERS_INDEX)?;
     uniform::swap_parameters(&mut writer)?;
@@ -23,5 +25,7 @@ pub(crate) fn bind_group<W: Write>(mut writer: W) -> std::io::Result<()> {
     super::binding_annotation(&mut writer, GROUP_INDEX, OUTPUT_PERMUTATION_INDEX)?;
     texture::permutation_output(&mut writer)?;
     super::binding_annotation(&mut writer, GROUP_INDEX, OUTPUT_COUNT_BUFFER_INDEX)?;
-    buffer::swap_count_output(&mut writer)
+    buffer::swap_count_output(&mut writer)?;
+    super::binding_annotation(&mut writer, GROUP_INDEX, OUTPUT_ACCEPTED_SWAPS_BUFFER_INDEX)?;
+    buffer::swap_accepted_swaps_output(&mut writer)
 }
@@ -0,0 +1,21 @@
+use crate::compute::WorkgroupDimensions;
+use std::io::Write;
+
+pub const ACCEPTED_SWAPS_WORD_BITS: u32 = u32::BITS;
+
+pub fn accepted_swaps_words_per_workgroup(workgroup_dimensions: WorkgroupDimensions) -> u32 {
+    let invocation_count = workgroup_dimensions.invocation_count();
+    assert_eq!(invocation_count % ACCEPTED_SWAPS_WORD_BITS, 0);
+    invocation_count / ACCEPTED_SWAPS_WORD_BITS
+}
+
+pub fn accepted_swaps_words<W: Write>(
+    mut writer: W,
+    workgroup_dimensions: WorkgroupDimensions,
+) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "let accepted_swaps_words: u32 = {}u;",
+        accepted_swaps_words_per_workgroup(workgroup_dimensions)
+    )
+}
@@ -1,3 +1,4 @@
+use crate::constant;
 use std::io::Write;
 
 mod header;
@@ -40,19 +41,31 @@ pub fn swap<W: Write>(mut writer: W) -> std::io::Result<()> {
     writeln!(
     writer,
     "  var count : f32 = 0.0;
+  if local_id < accepted_swaps_words {{
+    atomicStore(&accepted_swaps[local_id], 0u);
+  }}
+  workgroupBarrier();
+
   let displacement : vec2<i32> = parameters.displacement;
-  let coords1 : vec2<i32> = vec2<i32>(i32(global_id.x) * (displacement.x + 1), i32(global_id.y) * (displacement.y + 1)) + parameters.offset;
+  var coords1 : vec2<i32> = vec2<i32>(i32(global_id.x) * (displacement.x + 1), i32(global_id.y) * (displacement.y + 1)) + parameters.offset;
   let coords2 : vec2<i32> = coords1 + displacement;
   let dimensions : vec2<i32> = textureDimensions(input_permutation);
 
-  var input_permutation_vector1 : vec2<i32> = vec2<i32>(0, 0);
-  var output_permutation_vector1 : vec2<i32> = vec2<i32>(0, 0);
-  let in_bounds1 = coords1.x >= 0 && coords1.y >= 0 && coords1.x < dimensions.x && coords1.y < dimensions.y;
-
   var input_permutation_vector2 : vec2<i32> = vec2<i32>(0, 0);
   var output_permutation_vector2 : vec2<i32> = vec2<i32>(0, 0);
   let in_bounds2 = coords2.x >= 0 && coords2.y >= 0 && coords2.x < dimensions.x && coords2.y < dimensions.y;
 
+  // A pair can wrap around the edge of the image only if there is an even number of texels in the direction of the swap,
+  // as otherwise its first texel is also part of another pair.
+  if is_toroidal() && in_bounds2 && all(((dimensions * displacement) % vec2<i32>(2)) == vec2<i32>(0)) {{
+    coords1 = wrap_coordinates(coords1, dimensions);
+  }}
+
+  var input_permutation_vector1 : vec2<i32> = vec2<i32>(0, 0);
+  var output_permutation_vector1 : vec2<i32> = vec2<i32>(0, 0);
+  let in_bounds1 = coords1.x >= 0 && coords1.y >= 0 && coords1.x < dimensions.x && coords1.y < dimensions.y;
+  let pair_displacement : vec2<i32> = coords2 - coords1;
+
   if in_bounds1 {{
     input_permutation_vector1 = load_permutation_vector(coords1);
     output_permutation_vector1 = input_permutation_vector1;
@@ -64,9 +77,9 @@ pub fn swap<W: Write>(mut writer: W) -> std::io::Result<()> {
   }}
 
   if in_bounds1 && in_bounds2 {{
-    if swap_cost(coords1, displacement, input_permutation_vector1, input_permutation_vector2) < parameters.acceptance_threshold {{
-      output_permutation_vector1 = input_permutation_vector2 + displacement;
-      output_permutation_vector2 = input_permutation_vector1 - displacement;
+    if swap_cost(coords1, coords2, input_permutation_vector1, input_permutation_vector2) < parameters.acceptance_threshold {{
+      output_permutation_vector1 = input_permutation_vector2 + pair_displacement;
+      output_permutation_vector2 = input_permutation_vector1 - pair_displacement;
       count = 1.0;
     }}
   }}
@@ -79,16 +92,27 @@ pub fn swap<W: Write>(mut writer: W) -> std::io::Result<()> {
     store_permutation_vector(coords2, output_permutation_vector2);
   }}
 
+  // Record accepted swaps as one bit per invocation, in order of local invocation index
+  if count > 0.0 {{
+    atomicOr(&accepted_swaps[local_id / {word_bits}u], 1u << (local_id % {word_bits}u));
+  }}
+
   partial_sum[local_id] = count;
   workgroupBarrier();
 
   reduce_partial_sum(local_id);
 
+  let workgroup_index : u32 = workgroup_id.x + (workgroup_id.y * num_workgroups.x) + (workgroup_id.z * num_workgroups.x * num_workgroups.y);
+  let output_workgroup_index : u32 = parameters.count_output_offset + workgroup_index;
   if local_id == 0u {{
-    let workgroup_index : u32 = workgroup_id.x + (workgroup_id.y * num_workgroups.x) + (workgroup_id.z * num_workgroups.x * num_workgroups.y);
-    count_output[parameters.count_output_offset + workgroup_index] = partial_sum[local_id];
+    count_output[output_workgroup_index] = partial_sum[local_id];
   }}
-}}"
+
+  if local_id < accepted_swaps_words {{
+    accepted_swaps_output[output_workgroup_index * accepted_swaps_words + local_id] = atomicLoad(&accepted_swaps[local_id]);
+  }}
+}}",
+    word_bits = constant::swap::ACCEPTED_SWAPS_WORD_BITS
   )
 }
 
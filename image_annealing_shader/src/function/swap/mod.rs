@@ -9,14 +9,47 @@ pub fn potential_energy<W: Write>(mut writer: W) -> std::io::Result<()> {
     )
 }
 
+pub fn wrap_coordinates<W: Write>(mut writer: W) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "fn wrap_coordinates(coords : vec2<i32>, dimensions : vec2<i32>) -> vec2<i32> {{
+  return ((coords % dimensions) + dimensions) % dimensions;
+}}"
+    )
+}
+
+pub fn is_toroidal<W: Write>(mut writer: W) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "fn is_toroidal() -> bool {{
+  return parameters.boundary != 0u;
+}}"
+    )
+}
+
+pub fn separation<W: Write>(mut writer: W) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "fn separation(from_coords : vec2<i32>, to_coords : vec2<i32>) -> f32 {{
+  var difference : vec2<i32> = to_coords - from_coords;
+  if is_toroidal() {{
+    let dimensions : vec2<i32> = textureDimensions(input_permutation);
+    let wrapped_difference : vec2<i32> = wrap_coordinates(difference, dimensions);
+    difference = min(wrapped_difference, dimensions - wrapped_difference);
+  }}
+  return length(vec2<f32>(difference));
+}}"
+    )
+}
+
 pub fn displacement_cost<W: Write>(mut writer: W) -> std::io::Result<()> {
     writeln!(
         writer,
-        "fn displacement_cost(coords : vec2<i32>, displacement : vec2<i32>, permutation_vector : vec2<i32>) -> f32 {{
+        "fn displacement_cost(coords : vec2<i32>, displaced_coords : vec2<i32>, permutation_vector : vec2<i32>) -> f32 {{
   let origin : vec2<i32> = coords + permutation_vector;
   let desired_position : vec2<i32> = origin + load_displacement_goal_vector(origin);
-  let current_distance : f32 = length(vec2<f32>(desired_position - coords));
-  let displaced_distance : f32 = length(vec2<f32>(desired_position - (coords + displacement)));
+  let current_distance : f32 = separation(coords, desired_position);
+  let displaced_distance : f32 = separation(displaced_coords, desired_position);
   return potential_energy(displaced_distance) - potential_energy(current_distance);
 }}"
     )
@@ -27,12 +60,11 @@ pub fn swap_cost<W: Write>(mut writer: W) -> std::io::Result<()> {
         writer,
         "fn swap_cost(
   coords1 : vec2<i32>,
-  displacement : vec2<i32>,
+  coords2 : vec2<i32>,
   permutation_vector1 : vec2<i32>,
   permutation_vector2 : vec2<i32>
 ) -> f32 {{
-  let coords2 : vec2<i32> = coords1 + displacement;
-  return displacement_cost(coords1, displacement, permutation_vector1) + displacement_cost(coords2, -displacement, permutation_vector2);
+  return displacement_cost(coords1, coords2, permutation_vector1) + displacement_cost(coords2, coords1, permutation_vector2);
 }}"
     )
 }
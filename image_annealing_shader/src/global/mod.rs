@@ -7,6 +7,13 @@ pub fn partial_scalar_sum<W: Write>(mut writer: W) -> std::io::Result<()> {
     )
 }
 
+pub fn accepted_swaps<W: Write>(mut writer: W) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "var<workgroup> accepted_swaps : array<atomic<u32>, accepted_swaps_words>;"
+    )
+}
+
 pub fn partial_vector_sum<W: Write>(mut writer: W) -> std::io::Result<()> {
     writeln!(
         writer,
@@ -11,6 +11,13 @@ pub fn count_swap_output<W: Write>(mut writer: W) -> std::io::Result<()> {
     )
 }
 
+pub fn swap_accepted_swaps_output<W: Write>(mut writer: W) -> std::io::Result<()> {
+    writeln!(
+        writer,
+        "var<storage, read_write> accepted_swaps_output : array<u32>;"
+    )
+}
+
 pub fn swap_count_output<W: Write>(mut writer: W) -> std::io::Result<()> {
     writeln!(
         writer,
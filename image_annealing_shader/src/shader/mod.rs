@@ -38,13 +38,18 @@ pub fn swap<W: Write>(mut writer: W) -> std::io::Result<()> {
     type_definitions::swap(&mut writer)?;
     swap::bind_group(&mut writer)?;
     constant::workgroup_invocations(&mut writer, workgroup_dimensions)?;
+    constant::swap::accepted_swaps_words(&mut writer, workgroup_dimensions)?;
     global::partial_scalar_sum(&mut writer)?;
+    global::accepted_swaps(&mut writer)?;
     function::workgroup::reduce_partial_sum(&mut writer, workgroup_dimensions.invocation_count())?;
     conversion::u16_to_i32(&mut writer)?;
     conversion::i32_to_u16(&mut writer)?;
     io::load_permutation_vector(&mut writer)?;
     io::store_permutation_vector(&mut writer)?;
     io::load_displacement_goal_vector(&mut writer)?;
+    function::swap::wrap_coordinates(&mut writer)?;
+    function::swap::is_toroidal(&mut writer)?;
+    function::swap::separation(&mut writer)?;
     function::swap::potential_energy(&mut writer)?;
     function::swap::displacement_cost(&mut writer)?;
     function::swap::swap_cost(&mut writer)?;
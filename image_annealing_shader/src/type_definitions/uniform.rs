@@ -19,6 +19,7 @@ pub fn swap_parameters<W: Write>(mut writer: W) -> std::io::Result<()> {
   offset: vec2<i32>,
   count_output_offset : u32,
   acceptance_threshold: f32,
+  boundary: u32,
 }}"
     )
 }
@@ -7,7 +7,7 @@ use std::path::PathBuf;
 /// Assemble and validate a shader
 pub struct AssembleShaderOptions {
     /// Path of the configuration file for creating the shader
-    #[bpaf(long, short, argument::<String>("CONFIG_FILE"), complete_shell(ShellComp::File { mask: Some("*.json") }), parse(config::parse_config_file))]
+    #[bpaf(long, short, argument::<String>("CONFIG_FILE"), complete_shell(ShellComp::File { mask: None }), parse(config::parse_config_file))]
     pub config: Config<'static>,
     /// Path of the shader file to output
     #[bpaf(long, short('f'), argument("OUTPUT_FILE"), complete_shell(ShellComp::File { mask: Some("*.wgsl") }))]
@@ -24,12 +24,22 @@ pub struct DefaultShaderOutputOptions {
     pub output_directory: Option<PathBuf>,
 }
 
+#[derive(Debug, Bpaf, Eq, PartialEq)]
+#[bpaf(command("schema"))]
+/// Output the JSON Schema of configuration files
+pub struct SchemaOutputOptions {
+    /// Path of the file to output (defaults to standard output)
+    #[bpaf(long, short('f'), argument("OUTPUT_FILE"), complete_shell(ShellComp::File { mask: Some("*.json") }))]
+    pub output_file: Option<PathBuf>,
+}
+
 #[derive(Debug, Bpaf, Eq, PartialEq)]
 #[bpaf(generate(make_option_parser), options, version)]
 /// Generate shader files
 pub enum Options {
     Assemble(#[bpaf(external(assemble_shader_options))] AssembleShaderOptions),
     Default(#[bpaf(external(default_shader_output_options))] DefaultShaderOutputOptions),
+    Schema(#[bpaf(external(schema_output_options))] SchemaOutputOptions),
 }
 
 #[cfg(test)]
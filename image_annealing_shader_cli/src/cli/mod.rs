@@ -1,5 +1,7 @@
-use crate::args::{AssembleShaderOptions, DefaultShaderOutputOptions, Options};
-use crate::config::Config;
+use crate::args::{
+    AssembleShaderOptions, DefaultShaderOutputOptions, Options, SchemaOutputOptions,
+};
+use crate::config::{self, Config};
 use crate::output;
 use image_annealing_shader::{shader, validate};
 use std::error::Error;
@@ -30,6 +32,9 @@ pub fn run(options: &Options) -> Result<(), Box<dyn Error>> {
         Options::Default(default_shader_output_options) => {
             output_default_shaders(default_shader_output_options)
         }
+        Options::Schema(SchemaOutputOptions { output_file }) => {
+            config::write_config_file_schema(output_file.as_ref())
+        }
     }
 }
 
@@ -1,11 +1,13 @@
+use image_annealing_cli_util::config_file::ConfigFieldContext;
 use image_annealing_cli_util::io;
 use image_annealing_shader::shader::CreateDisplacementGoalShaderContent;
+use schemars::JsonSchema;
 use serde::Deserialize;
 use std::borrow::Cow;
 use std::error::Error;
 use std::fs;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, JsonSchema)]
 pub enum UnverifiedConfig {
     CreateDisplacementGoal { body: String },
 }
@@ -23,9 +25,15 @@ impl<'a> TryFrom<UnverifiedConfig> for Config<'a> {
             UnverifiedConfig::CreateDisplacementGoal {
                 body: unverified_body_path,
             } => {
-                let path = io::convert_and_check_input_file_path(unverified_body_path)?;
+                let path = io::convert_and_check_input_file_path(unverified_body_path)
+                    .in_field("body")
+                    .in_field("CreateDisplacementGoal")?;
                 Self::CreateDisplacementGoal(CreateDisplacementGoalShaderContent {
-                    body: Cow::Owned(fs::read_to_string(path)?),
+                    body: Cow::Owned(
+                        fs::read_to_string(path)
+                            .in_field("body")
+                            .in_field("CreateDisplacementGoal")?,
+                    ),
                 })
             }
         })
@@ -1,19 +1,22 @@
 use super::data::{Config, UnverifiedConfig};
+use image_annealing_cli_util::config_file::ConfigFileSource;
 use std::error::Error;
-use std::fs::File;
-use std::io::BufReader;
 use std::path::Path;
 
 pub fn parse_config_file<P: AsRef<Path>>(filename: P) -> Result<Config<'static>, Box<dyn Error>> {
-    image_annealing_cli_util::io::check_input_file_path(&filename)?;
-    let file = File::open(filename)?;
-    let reader = BufReader::new(file);
-    let unverified_config: UnverifiedConfig = serde_json::from_reader(reader)
-        .map_err(|e| format!("configuration file deserialization error, \"{}\"", e))?;
-
-    let config = Config::try_from(unverified_config)?;
+    let source = ConfigFileSource::read(filename)?;
+    let unverified_config: UnverifiedConfig = source.deserialize()?;
+    let config = Config::try_from(unverified_config).map_err(|e| source.annotate_error(e))?;
     Ok(config)
 }
 
+/// Writes the JSON Schema of configuration files to a file,
+/// or to standard output if no file path is given
+pub fn write_config_file_schema<P: AsRef<Path>>(
+    output_file: Option<P>,
+) -> Result<(), Box<dyn Error>> {
+    image_annealing_cli_util::config_file::write_schema::<UnverifiedConfig, P>(output_file)
+}
+
 #[cfg(test)]
 mod tests;
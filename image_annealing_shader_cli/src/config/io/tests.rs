@@ -59,3 +59,25 @@ mod parse_config_file {
         test_util::assert_error_contains(parse_config_file(path), "does not exist");
     }
 }
+
+mod write_config_file_schema {
+    use super::super::write_config_file_schema;
+    use std::error::Error;
+
+    #[test]
+    fn write_to_file() -> Result<(), Box<dyn Error>> {
+        let path = test_util::make_test_output_path(["shader_config_file_schema.json"]);
+        write_config_file_schema(Some(&path))?;
+        let contents = std::fs::read_to_string(&path);
+        std::fs::remove_file(&path)?;
+        let schema: serde_json::Value = serde_json::from_str(&contents?)?;
+        assert_eq!(schema["title"], "UnverifiedConfig");
+        let variant = &schema["oneOf"][0];
+        assert_eq!(variant["required"][0], "CreateDisplacementGoal");
+        assert_eq!(
+            variant["properties"]["CreateDisplacementGoal"]["properties"]["body"]["type"],
+            "string"
+        );
+        Ok(())
+    }
+}
@@ -2,4 +2,4 @@ mod data;
 mod io;
 
 pub use data::{Config, UnverifiedConfig};
-pub use io::parse_config_file;
+pub use io::{parse_config_file, write_config_file_schema};
@@ -104,6 +104,8 @@ pub fn default_swap_parameters() -> SwapParameters {
         sequence: SwapPass::Horizontal.into(),
         swap_acceptance_threshold: Default::default(),
         count_swap: true,
+        record_accepted_swaps: false,
+        permuted_image_format: None,
     }
 }
 
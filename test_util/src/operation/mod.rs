@@ -1,7 +1,7 @@
 use image_annealing::compute::conversion::VectorFieldEntry;
 use image_annealing::compute::format::VectorFieldImageBuffer;
 use image_annealing::compute::{SwapAlgorithm, SwapParameters, SwapPartialOutput};
-use image_annealing::ImageDimensions;
+use image_annealing::{BoundaryMode, ImageDimensions};
 
 pub fn swap(vector_field: &VectorFieldImageBuffer) -> VectorFieldImageBuffer {
     let width = vector_field.width();
@@ -39,8 +39,11 @@ fn assert_correct_swap_count_output_inner(
     image_dimensions: &ImageDimensions,
     swaps_accepted: SwapAcceptedCount,
 ) {
-    assert_eq!(output.is_some(), parameters.count_swap);
-    if let Some(SwapPartialOutput { counts }) = output {
+    assert_eq!(
+        output.is_some(),
+        parameters.count_swap || parameters.record_accepted_swaps
+    );
+    if let Some(SwapPartialOutput { counts, .. }) = output {
         let is_none_accepted = match swaps_accepted {
             SwapAcceptedCount::None => true,
             SwapAcceptedCount::Some(ref v) => v.iter().sum::<usize>() == 0,
@@ -60,7 +63,7 @@ fn assert_correct_swap_count_output_inner(
             .enumerate()
             .fold((0_usize, 0_usize), |mut acc, (i, (&pass, pass_data))| {
                 assert_eq!(pass, pass_data.pass());
-                let total_i = pass.total_swaps(image_dimensions);
+                let total_i = pass.total_swaps(image_dimensions, BoundaryMode::Bounded);
                 assert_eq!(pass_data.total(), total_i);
                 let accepted_i = match &swaps_accepted {
                     SwapAcceptedCount::None => 0,